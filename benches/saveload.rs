@@ -0,0 +1,47 @@
+use bevy_ecs::world::World;
+use bevy_salo::{bench, Marker, SaveLoadExtension, SaveTarget, LoadSource};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+
+const COUNT: usize = 1000;
+
+type Generator = fn(&mut World, usize);
+
+const WORLDS: [(&str, Generator); 4] = [
+    ("wide_flat", bench::wide_flat),
+    ("deep_hierarchy", bench::deep_hierarchy),
+    ("many_types", bench::many_types),
+    ("few_types", bench::few_types),
+];
+
+fn bench_method<M: Marker>(c: &mut Criterion, method: &str) {
+    let mut group = c.benchmark_group(method);
+    group.throughput(Throughput::Elements(COUNT as u64));
+    for (name, generate) in WORLDS {
+        let mut world = bench::world::<M>();
+        generate(&mut world, COUNT);
+        group.bench_function(BenchmarkId::new("save", name), |b| {
+            b.iter(|| world.save::<M>(SaveTarget::Bytes).unwrap())
+        });
+        let bytes = world.save::<M>(SaveTarget::Bytes).unwrap().into_bytes().unwrap();
+        group.bench_function(BenchmarkId::new("load", name), |b| {
+            b.iter_batched(
+                bench::world::<M>,
+                |mut world| world.load::<M>(LoadSource::Bytes(&bytes)).unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn saveload(c: &mut Criterion) {
+    use bevy_salo::methods::*;
+    bench_method::<bevy_salo::All<SerdeJson<false>>>(c, "json");
+    #[cfg(feature="ron")]
+    bench_method::<bevy_salo::All<Ron<false>>>(c, "ron");
+    #[cfg(feature="postcard")]
+    bench_method::<bevy_salo::All<Postcard>>(c, "postcard");
+}
+
+criterion_group!(benches, saveload);
+criterion_main!(benches);
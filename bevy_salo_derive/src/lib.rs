@@ -0,0 +1,323 @@
+//! Derive macros for `bevy_salo`, see the main crate for documentation.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Data, Expr, Fields, Ident, Lit, LitStr, Type, Error};
+
+/// Parsed `#[salo(..)]` attributes on the container.
+#[derive(Default)]
+struct Attrs {
+    name: Option<LitStr>,
+    path: Option<Expr>,
+    via: Option<Type>,
+    inline: bool,
+    version: Option<syn::LitInt>,
+    migrate: Option<syn::Path>,
+    /// `Some(None)` for `Default::default`.
+    on_missing: Option<Option<syn::Path>>,
+    fills: Vec<LitStr>,
+    fields: Vec<FieldAttrs>,
+}
+
+/// Parsed `#[salo(..)]` attributes on a field.
+#[derive(Default)]
+struct FieldAttrs {
+    skip: bool,
+    rename: Option<LitStr>,
+    default: bool,
+}
+
+impl FieldAttrs {
+    fn is_schema(&self) -> bool {
+        self.skip || self.rename.is_some() || self.default
+    }
+}
+
+fn check_name(lit: &LitStr) -> syn::Result<()> {
+    if lit.value().contains("::") {
+        Err(Error::new(lit.span(), "`::` is reserved for path separation."))
+    } else {
+        Ok(())
+    }
+}
+
+fn parse_attrs(input: &DeriveInput) -> syn::Result<Attrs> {
+    let mut attrs = Attrs::default();
+    for attr in &input.attrs {
+        if !attr.path().is_ident("salo") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                let lit: LitStr = meta.value()?.parse()?;
+                check_name(&lit)?;
+                attrs.name = Some(lit);
+            } else if meta.path.is_ident("path") {
+                let lit: LitStr = meta.value()?.parse()?;
+                let expr: Expr = lit.parse()?;
+                if let Expr::Lit(syn::ExprLit { lit: Lit::Str(s), .. }) = &expr {
+                    check_name(s)?;
+                }
+                attrs.path = Some(expr);
+            } else if meta.path.is_ident("via") {
+                let lit: LitStr = meta.value()?.parse()?;
+                attrs.via = Some(lit.parse()?);
+            } else if meta.path.is_ident("inline") {
+                attrs.inline = true;
+            } else if meta.path.is_ident("version") {
+                attrs.version = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("migrate") {
+                let lit: LitStr = meta.value()?.parse()?;
+                attrs.migrate = Some(lit.parse()?);
+            } else if meta.path.is_ident("on_missing") {
+                attrs.on_missing = Some(if meta.input.peek(syn::Token![=]) {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    Some(lit.parse()?)
+                } else {
+                    None
+                });
+            } else if meta.path.is_ident("fills") {
+                attrs.fills.push(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("expected `name`, `path`, `via`, `inline`, `version`, `migrate`, `on_missing` or `fills`."));
+            }
+            Ok(())
+        })?;
+    }
+    // Field level attributes.
+    if let Data::Struct(data) = &input.data {
+        for (index, field) in data.fields.iter().enumerate() {
+            let mut field_attrs = FieldAttrs::default();
+            for attr in &field.attrs {
+                if !attr.path().is_ident("salo") {
+                    continue;
+                }
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("path") {
+                        if attrs.path.is_some() {
+                            return Err(meta.error("duplicated `path`."));
+                        }
+                        let member = match &field.ident {
+                            Some(ident) => quote!(#ident),
+                            None => {
+                                let index = syn::Index::from(index);
+                                quote!(#index)
+                            },
+                        };
+                        attrs.path = Some(syn::parse2(quote!(self.#member))?);
+                    } else if meta.path.is_ident("skip") {
+                        field_attrs.skip = true;
+                    } else if meta.path.is_ident("rename") {
+                        field_attrs.rename = Some(meta.value()?.parse()?);
+                    } else if meta.path.is_ident("default") {
+                        field_attrs.default = true;
+                    } else {
+                        return Err(meta.error("expected `path`, `skip`, `rename` or `default`."));
+                    }
+                    Ok(())
+                })?;
+            }
+            if field_attrs.skip && (field_attrs.rename.is_some() || field_attrs.default) {
+                return Err(Error::new_spanned(field, "`skip` cannot be used with `rename` or `default`."));
+            }
+            attrs.fields.push(field_attrs);
+        }
+    }
+    if attrs.fields.iter().any(FieldAttrs::is_schema) {
+        if attrs.via.is_some() {
+            return Err(Error::new_spanned(input, "`skip`, `rename` and `default` cannot be used with `via`."));
+        }
+        if !input.generics.params.is_empty() {
+            return Err(Error::new_spanned(&input.generics, "`skip`, `rename` and `default` are not supported on generic types."));
+        }
+        match &input.data {
+            Data::Struct(data) if matches!(data.fields, Fields::Named(_)) => (),
+            _ => return Err(Error::new_spanned(input, "`skip`, `rename` and `default` require a struct with named fields.")),
+        }
+    }
+    Ok(attrs)
+}
+
+/// Derive `SaveLoadCore`, or `SaveLoadMapped` if `via` is specified.
+///
+/// # Container Attributes
+///
+/// * `#[salo(name = "weapon")]`: Set `type_name`.
+/// * `#[salo(path = "self.name")]`: Set `path_name` to an expression implementing `ToString`.
+/// * `#[salo(via = "WeaponData")]`: Implement `SaveLoadMapped` through an intermediate type,
+///   requires `WeaponData: From<&Self>` and `Self: From<WeaponData>`.
+/// * `#[salo(inline)]`: Set `inline`, fields are written next to the record's path.
+/// * `#[salo(version = 2)]`: Set `version`.
+/// * `#[salo(migrate = "migrate_unit")]`: Set `migrate` to a function with the same signature.
+/// * `#[salo(on_missing)]`: Set `on_missing` to `Default::default()`,
+///   or `#[salo(on_missing = "new_unit")]` to a function returning `Self`.
+/// * `#[salo(fills = "unit")]`: Add a type name to `fills`, can be repeated.
+///
+/// # Field Attributes
+///
+/// * `#[salo(path)]`: Use this field as `path_name`.
+/// * `#[salo(skip)]`: Do not save this field, loaded as `Default::default()`.
+/// * `#[salo(rename = "hp")]`: Save this field under a different name.
+/// * `#[salo(default)]`: Use `Default::default()` if missing from the save.
+///
+/// If any of `skip`, `rename` or `default` is used, `SaveLoadMapped` is implemented
+/// through generated intermediate structs instead, so the save format is independent
+/// of the serde implementation of the type.
+#[proc_macro_derive(SaveLoad, attributes(salo))]
+pub fn derive_save_load(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match save_load(&input) {
+        Ok(stream) => stream.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+fn save_load(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let attrs = parse_attrs(input)?;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let cow = quote!(::std::borrow::Cow);
+    let type_name = attrs.name.map(|name| quote! {
+        fn type_name() -> #cow<'static, str> {
+            #cow::Borrowed(#name)
+        }
+    });
+    let path_name = attrs.path.map(|expr| quote! {
+        fn path_name(&self) -> ::std::option::Option<#cow<'static, str>> {
+            ::std::option::Option::Some(#cow::Owned(::std::string::ToString::to_string(&#expr)))
+        }
+    });
+    let inline = attrs.inline.then(|| quote! {
+        fn inline() -> bool {
+            true
+        }
+    });
+    let version = attrs.version.map(|lit| quote! {
+        fn version() -> u32 {
+            #lit
+        }
+    });
+    let migrate = attrs.migrate.map(|path| quote! {
+        fn migrate<S: ::bevy_salo::methods::SerializationMethod>(version: u32, value: S::Value)
+            -> ::bevy_salo::__private::anyhow::Result<S::Value> {
+            #path::<S>(version, value)
+        }
+    });
+    let on_missing = attrs.on_missing.map(|path| {
+        let value = match path {
+            Some(path) => quote!(#path()),
+            None => quote!(::std::default::Default::default()),
+        };
+        quote! {
+            fn on_missing() -> ::std::option::Option<Self> {
+                ::std::option::Option::Some(#value)
+            }
+        }
+    });
+    let fills = (!attrs.fills.is_empty()).then(|| {
+        let fills = &attrs.fills;
+        quote! {
+            fn fills() -> &'static [&'static str] {
+                &[#(#fills),*]
+            }
+        }
+    });
+    let items = quote!(#type_name #path_name #inline #version #migrate #on_missing #fills);
+    if attrs.fields.iter().any(FieldAttrs::is_schema) {
+        let Data::Struct(data) = &input.data else { unreachable!() };
+        return Ok(mapped_fields(ident, &data.fields, &attrs.fields, items));
+    }
+    Ok(match attrs.via {
+        None => quote! {
+            impl #impl_generics ::bevy_salo::SaveLoadCore for #ident #ty_generics #where_clause {
+                #items
+            }
+        },
+        Some(via) => {
+            let lifetime = syn::Lifetime::new("'ser", Span::call_site());
+            quote! {
+                impl #impl_generics ::bevy_salo::SaveLoadMapped for #ident #ty_generics #where_clause {
+                    type Ser<#lifetime> = #via;
+                    type De = #via;
+
+                    fn to_serializable(&self) -> Self::Ser<'_> {
+                        <#via as ::std::convert::From<&Self>>::from(self)
+                    }
+
+                    fn from_deserialize(de: Self::De) -> Self {
+                        <Self as ::std::convert::From<#via>>::from(de)
+                    }
+
+                    #items
+                }
+            }
+        }
+    })
+}
+
+/// Implement `SaveLoadMapped` through generated intermediate structs.
+fn mapped_fields(
+    ident: &Ident,
+    fields: &Fields,
+    attrs: &[FieldAttrs],
+    items: TokenStream2,
+) -> TokenStream2 {
+    let serde = quote!(::bevy_salo::__private::serde);
+    let serde_crate = LitStr::new("::bevy_salo::__private::serde", Span::call_site());
+    let mut ser_fields = Vec::new();
+    let mut de_fields = Vec::new();
+    let mut to_ser = Vec::new();
+    let mut from_de = Vec::new();
+    for (field, attrs) in fields.iter().zip(attrs) {
+        let name = field.ident.as_ref().expect("named fields");
+        let ty = &field.ty;
+        if attrs.skip {
+            from_de.push(quote!(#name: ::std::default::Default::default()));
+            continue;
+        }
+        let rename = attrs.rename.as_ref().map(|lit| quote!(#[serde(rename = #lit)]));
+        let default = attrs.default.then(|| quote!(#[serde(default)]));
+        ser_fields.push(quote!(#rename #name: &'ser #ty));
+        de_fields.push(quote!(#rename #default #name: #ty));
+        to_ser.push(quote!(#name: &self.#name));
+        from_de.push(quote!(#name: de.#name));
+    }
+    quote! {
+        const _: () = {
+            #[derive(#serde::Serialize)]
+            #[serde(crate = #serde_crate)]
+            pub struct __SaloSer<'ser> {
+                #(#ser_fields,)*
+                #[serde(skip)]
+                __p: ::std::marker::PhantomData<&'ser ()>,
+            }
+
+            #[derive(#serde::Deserialize)]
+            #[serde(crate = #serde_crate)]
+            pub struct __SaloDe {
+                #(#de_fields,)*
+            }
+
+            impl ::bevy_salo::SaveLoadMapped for #ident {
+                type Ser<'ser> = __SaloSer<'ser>;
+                type De = __SaloDe;
+
+                fn to_serializable(&self) -> Self::Ser<'_> {
+                    __SaloSer {
+                        #(#to_ser,)*
+                        __p: ::std::marker::PhantomData,
+                    }
+                }
+
+                fn from_deserialize(de: Self::De) -> Self {
+                    Self {
+                        #(#from_de,)*
+                    }
+                }
+
+                #items
+            }
+        };
+    }
+}
@@ -0,0 +1,34 @@
+//! Convert a save between formats by file extension, i.e. to inspect a ron save as json.
+//!
+//! ```bash
+//! cargo run --example convert --features ron -- save.ron save.json
+//! ```
+use bevy_salo::methods::{Ron, SerdeJson, SerializationMethod};
+use bevy_salo::{convert, SaloError};
+
+fn convert_to<F: SerializationMethod>(bytes: &[u8], to: &str) -> Result<Vec<u8>, SaloError> {
+    match to {
+        "json" => convert::<F, SerdeJson<true>>(bytes),
+        _ => convert::<F, Ron<true>>(bytes),
+    }
+}
+
+fn extension(path: &str) -> &str {
+    path.rsplit_once('.').map(|(_, ext)| ext).unwrap_or_default()
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [input, output] = args.as_slice() else {
+        eprintln!("usage: convert <input.{{json,ron}}> <output.{{json,ron}}>");
+        std::process::exit(1);
+    };
+    let bytes = std::fs::read(input)?;
+    let to = extension(output);
+    let result = match extension(input) {
+        "json" => convert_to::<SerdeJson>(&bytes, to),
+        _ => convert_to::<Ron>(&bytes, to),
+    }?;
+    std::fs::write(output, result)?;
+    Ok(())
+}
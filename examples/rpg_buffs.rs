@@ -1,7 +1,7 @@
 use bevy_app::{App, Update, Startup};
 use bevy_ecs::{system::{Commands, Res, ResMut, Resource}, component::Component, world::World};
 use bevy_hierarchy::BuildChildren;
-use bevy_salo::{SaveLoadPlugin, SaveLoadCore, SaveLoadExtension, methods::Ron, interned_enum, SaveLoad, EntityPath, SaveLoadResCore};
+use bevy_salo::{SaveLoadPlugin, SaveLoadCore, SaveLoadExtension, methods::Ron, interned_enum, SaveLoad, EntityPath, SaveLoadResCore, SaveTarget, LoadSource};
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Component, Clone, Serialize, Deserialize, Default)]
@@ -159,12 +159,12 @@ pub fn spawn(mut commands: Commands) {
 }
 
 pub fn serialize(world: &mut World) {
-    world.save_to_file::<All>("test.ron");
-    world.load_from_file::<All>("test.ron");
-    world.save_to_file::<All>("duplicated.ron");
+    world.save::<All>(SaveTarget::File("test.ron")).unwrap();
+    world.load::<All>(LoadSource::File("test.ron")).unwrap();
+    world.save::<All>(SaveTarget::File("duplicated.ron")).unwrap();
     world.remove_serialized_components::<All>();
-    world.save_to_file::<All>("cleared.ron");
-    world.load_from_file::<All>("test.ron");
-    world.save_to_file::<All>("roundtrip.ron");
+    world.save::<All>(SaveTarget::File("cleared.ron")).unwrap();
+    world.load::<All>(LoadSource::File("test.ron")).unwrap();
+    world.save::<All>(SaveTarget::File("roundtrip.ron")).unwrap();
 
 }
\ No newline at end of file
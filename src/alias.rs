@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+use bevy_ecs::system::{Res, ResMut, Resource};
+use smol_str::SmolStr;
+
+use crate::saveload::{join_path, EntityParent};
+use crate::transfer::{remap, rewrite_paths, segments};
+use crate::{DeserializeContext, EntityPath, Marker, SerializeContext};
+
+/// Alias table of a save header, new path by old path.
+pub(crate) type Aliases = BTreeMap<String, String>;
+
+/// Redirects renamed entity paths when loading, unique per marker,
+/// i.e. `town::Blacksmith` renamed to `town::Armorer` in the live world.
+///
+/// Descendants of an aliased path are redirected too, the longest matching path is used,
+/// and chains like `a -> b -> c` are followed to the end.
+/// Entries used by paths of a save are written to its header and applied when it is loaded,
+/// entries of this resource replace entries of the header with the same path.
+#[derive(Debug, Clone, Resource)]
+pub struct PathAliases<M: Marker>(Aliases, PhantomData<M>);
+
+impl<M: Marker> Default for PathAliases<M> {
+    fn default() -> Self {
+        PathAliases(Aliases::new(), PhantomData)
+    }
+}
+
+impl<M: Marker> PathAliases<M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Redirect `old` to `new`, both are `::` delimited paths.
+    pub fn with(mut self, old: impl Into<String>, new: impl Into<String>) -> Self {
+        self.insert(old, new);
+        self
+    }
+
+    pub fn insert(&mut self, old: impl Into<String>, new: impl Into<String>) {
+        self.0.insert(old.into(), new.into());
+    }
+
+    pub fn remove(&mut self, old: &str) -> Option<String> {
+        self.0.remove(old)
+    }
+
+    /// Path `old` is redirected to, without matching ancestors.
+    pub fn get(&self, old: &str) -> Option<&str> {
+        self.0.get(old).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(old, new)| (old.as_str(), new.as_str()))
+    }
+}
+
+/// Prefix rules of an alias table with chains resolved, longest path first.
+fn resolve(table: &Aliases) -> Vec<(Vec<SmolStr>, Vec<SmolStr>)> {
+    let mut rules: Vec<_> = table.iter()
+        .filter(|(old, _)| !old.is_empty())
+        .map(|(old, new)| (segments(old), segments(new)))
+        .collect();
+    rules.sort_by_key(|(old, _)| std::cmp::Reverse(old.len()));
+    let resolved = rules.iter().map(|(old, new)| {
+        let mut new = new.clone();
+        // Each step applies a different rule, stops on cycles.
+        for _ in 0..rules.len() {
+            match remap(&rules, &new) {
+                Some(next) if next != new => new = next,
+                _ => break,
+            }
+        }
+        (old.clone(), new)
+    }).collect();
+    resolved
+}
+
+/// Write the aliases used by paths of this save to the header.
+pub(crate) fn write_aliases<M: Marker>(aliases: Option<Res<PathAliases<M>>>, mut ctx: ResMut<SerializeContext<M>>) {
+    let Some(aliases) = aliases else { return };
+    let rules = resolve(&aliases.0);
+    let ctx = &mut *ctx;
+    let records = ctx.components.values().flatten().flat_map(|r| {
+        let path = match &r.path {
+            EntityPath::Path(path) => Some(path),
+            _ => None,
+        };
+        let parent = match &r.parent {
+            EntityParent::Path(path) => Some(path),
+            _ => None,
+        };
+        path.into_iter().chain(parent)
+    });
+    let removed = ctx.delta.iter().flat_map(|d| d.removed.values().flatten()).chain(&ctx.tombstones)
+        .filter_map(|path| match path {
+            EntityPath::Path(path) => Some(path),
+            _ => None,
+        });
+    let mut used = Aliases::new();
+    for path in ctx.paths.values().chain(records).chain(removed) {
+        if let Some((old, new)) = rules.iter().find(|(old, _)| path.starts_with(old)) {
+            used.insert(join_path(old), join_path(new));
+        }
+    }
+    ctx.aliases = used;
+}
+
+/// Redirect paths of loaded records and store the rules for entity references.
+pub(crate) fn apply_aliases<M: Marker>(aliases: Option<Res<PathAliases<M>>>, mut ctx: ResMut<DeserializeContext<M>>) {
+    let mut table = std::mem::take(&mut ctx.saved_aliases);
+    if let Some(aliases) = aliases {
+        table.extend(aliases.0.iter().map(|(old, new)| (old.clone(), new.clone())));
+    }
+    let rules = resolve(&table);
+    rewrite_paths(&mut ctx, &rules);
+    ctx.aliases = rules;
+}
@@ -0,0 +1,167 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::marker::PhantomData;
+
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_utils::Duration;
+
+use crate::{DeserializeContext, Marker, SaloHistory};
+
+/// Statistics of a load recorded by [`SaloAnalytics`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadSample {
+    /// Number of records by type name, including unregistered types.
+    pub counts: BTreeMap<String, usize>,
+    pub duration: Duration,
+    /// Size of the input in bytes, if known.
+    pub size: Option<usize>,
+    /// Returns true if the load failed.
+    pub failed: bool,
+}
+
+impl LoadSample {
+    /// Total number of records.
+    pub fn records(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+/// Distribution of record counts of a type over recorded loads.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TypeStats {
+    /// Number of loads containing the type.
+    pub loads: usize,
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+}
+
+/// Receives each [`LoadSample`], i.e. to send them to a telemetry backend.
+///
+/// Implemented for `FnMut(&LoadSample)`.
+pub trait AnalyticsExporter: Send + Sync + 'static {
+    fn export(&mut self, sample: &LoadSample);
+}
+
+impl<F: FnMut(&LoadSample) + Send + Sync + 'static> AnalyticsExporter for F {
+    fn export(&mut self, sample: &LoadSample) {
+        self(sample)
+    }
+}
+
+/// Statistics of recent loads of a marker, i.e. to detect save bloat, unique per marker.
+///
+/// Not inserted by the plugin, loads are recorded once inserted.
+/// Holds the last 256 samples by default, oldest first.
+#[derive(Resource)]
+pub struct SaloAnalytics<M: Marker> {
+    samples: VecDeque<LoadSample>,
+    capacity: usize,
+    exporter: Option<Box<dyn AnalyticsExporter>>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> std::fmt::Debug for SaloAnalytics<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SaloAnalytics")
+            .field("samples", &self.samples)
+            .field("capacity", &self.capacity)
+            .field("exporter", &self.exporter.is_some())
+            .finish()
+    }
+}
+
+impl<M: Marker> Default for SaloAnalytics<M> {
+    fn default() -> Self {
+        Self::with_capacity(256)
+    }
+}
+
+impl<M: Marker> SaloAnalytics<M> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::new(),
+            capacity,
+            exporter: None,
+            p: PhantomData,
+        }
+    }
+
+    /// Send each sample to an exporter as it is recorded.
+    pub fn with_exporter(mut self, exporter: impl AnalyticsExporter) -> Self {
+        self.exporter = Some(Box::new(exporter));
+        self
+    }
+
+    pub fn set_exporter(&mut self, exporter: impl AnalyticsExporter) {
+        self.exporter = Some(Box::new(exporter));
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Iterate samples, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &LoadSample> {
+        self.samples.iter()
+    }
+
+    /// Names of all types in recorded samples, sorted.
+    pub fn type_names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.samples.iter().flat_map(|s| s.counts.keys()).map(|k| k.as_str()).collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Distribution of record counts of a type, over samples containing it.
+    pub fn type_stats(&self, type_name: &str) -> Option<TypeStats> {
+        let counts: Vec<_> = self.samples.iter().filter_map(|s| s.counts.get(type_name).copied()).collect();
+        Some(TypeStats {
+            loads: counts.len(),
+            min: *counts.iter().min()?,
+            max: *counts.iter().max()?,
+            mean: counts.iter().sum::<usize>() as f64 / counts.len() as f64,
+        })
+    }
+
+    /// Average duration of recorded loads.
+    pub fn mean_duration(&self) -> Duration {
+        match self.samples.len() {
+            0 => Duration::ZERO,
+            len => self.samples.iter().map(|s| s.duration).sum::<Duration>() / len as u32,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear()
+    }
+
+    fn push(&mut self, sample: LoadSample) {
+        if let Some(exporter) = &mut self.exporter {
+            exporter.export(&sample);
+        }
+        self.samples.push_back(sample);
+        while self.samples.len() > self.capacity {
+            self.samples.pop_front();
+        }
+    }
+}
+
+/// Record a sample of the finished load if analytics are enabled.
+pub(crate) fn record_analytics<M: Marker>(
+    analytics: Option<ResMut<SaloAnalytics<M>>>,
+    history: Res<SaloHistory<M>>,
+    ctx: Res<DeserializeContext<M>>,
+) {
+    let (Some(mut analytics), Some(entry)) = (analytics, history.last()) else { return };
+    analytics.push(LoadSample {
+        counts: ctx.counts.iter().cloned().collect(),
+        duration: entry.duration,
+        size: entry.size,
+        failed: !entry.is_ok(),
+    });
+}
@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Res, ResMut, Resource};
+
+use crate::{DeserializeContext, Marker};
+
+/// Labels a well-known root of the application, i.e. `"UI"`, `"Level"` or `"Persistent"`,
+/// that loads can spawn their entities under with [`LoadOptions::spawn_under_label`].
+///
+/// Usually spawned at startup, labels should be unique. Anchors are not named, name them with
+/// [`PathName`](crate::PathName) or set an [`OrphanPolicy`](crate::OrphanPolicy) to save the loaded entities again.
+#[derive(Debug, Clone, PartialEq, Eq, Component)]
+pub struct SpawnAnchor(Cow<'static, str>);
+
+impl SpawnAnchor {
+    pub fn new(s: &'static str) -> Self {
+        Self(Cow::Borrowed(s))
+    }
+
+    pub fn new_owned(s: String) -> Self {
+        Self(Cow::Owned(s))
+    }
+
+    pub fn get(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Options of [`load_with`](crate::SaveLoadExtension::load_with).
+///
+/// ```
+/// # use bevy_salo::*;
+/// # use bevy_ecs::world::World;
+/// # let mut world = World::new();
+/// # SaveLoadPlugin::new::<All>().build_world(&mut world);
+/// # let save = world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+/// world.spawn(SpawnAnchor::new("Level"));
+/// world.load_with::<All>(LoadSource::String(&save), LoadOptions::new()
+///     .spawn_under_label("Level")
+/// ).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LoadOptions {
+    pub(crate) label: Option<Cow<'static, str>>,
+}
+
+impl LoadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add entities at the root of the save, named or unnamed, as children of the entity
+    /// with this [`SpawnAnchor`] label, requires the `hierarchy` feature.
+    ///
+    /// The load fails with [`SaloError::MissingAnchor`](crate::SaloError::MissingAnchor) if no entity has the label.
+    #[cfg(feature="hierarchy")]
+    pub fn spawn_under_label(mut self, label: impl Into<Cow<'static, str>>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+/// Parent of root records of a load, inserted by [`load_with`](crate::SaveLoadExtension::load_with).
+#[derive(Debug, Resource)]
+pub(crate) struct LoadAnchor<M: Marker> {
+    parent: Entity,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> LoadAnchor<M> {
+    pub(crate) fn new(parent: Entity) -> Self {
+        Self { parent, p: PhantomData }
+    }
+}
+
+/// Set the parent of root records, named or not, before they are loaded.
+pub(crate) fn apply_load_anchor<M: Marker>(
+    anchor: Option<Res<LoadAnchor<M>>>,
+    mut ctx: ResMut<DeserializeContext<M>>,
+) {
+    ctx.anchor = anchor.map(|anchor| anchor.parent);
+}
@@ -0,0 +1,257 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use bevy_ecs::system::{Res, ResMut, Resource};
+use smol_str::SmolStr;
+
+use crate::methods::SerializationMethod;
+use crate::saveload::{join_path, split_path, EntityParent, ValueOf};
+use crate::{EntityPath, Marker, SaloError, SaloErrors, SaveLoadCore, SerializeContext};
+
+/// How values of a type are written by [`save_anonymized`](crate::SaveLoadExtension::save_anonymized).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Redaction {
+    /// Write the value unchanged.
+    Keep,
+    /// Replace the value with a hash of its encoding, equal values have equal hashes.
+    #[default]
+    Hash,
+    /// Replace the value with an empty value, the record is kept.
+    Clear,
+    /// Remove all records of the type.
+    Remove,
+}
+
+/// Replacement of a type's values.
+enum Rule<M: Marker> {
+    Redact(Redaction),
+    Default(fn() -> anyhow::Result<ValueOf<M>>),
+}
+
+impl<M: Marker> Clone for Rule<M> {
+    fn clone(&self) -> Self {
+        match self {
+            Rule::Redact(r) => Rule::Redact(*r),
+            Rule::Default(f) => Rule::Default(*f),
+        }
+    }
+}
+
+impl<M: Marker> std::fmt::Debug for Rule<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rule::Redact(r) => r.fmt(f),
+            Rule::Default(_) => f.write_str("Default"),
+        }
+    }
+}
+
+fn default_value<M: Marker, T: SaveLoadCore + Default>() -> anyhow::Result<ValueOf<M>> {
+    M::Method::serialize_value(&T::default())
+}
+
+/// Per-type [`Redaction`] of an anonymized save, unique per marker.
+///
+/// Types, counts and hierarchy are kept, so the save can be shared to reproduce an issue
+/// without exposing personal content. Types are identified by their `type_name`,
+/// unlisted types use the fallback, which is [`Redaction::Hash`] by default.
+/// Names in paths are hashed and metadata is removed by default.
+///
+/// Anonymized saves are not throttled by [`SaloConfig::with_min_save_interval`](crate::SaloConfig::with_min_save_interval).
+///
+/// Saves with hashed or cleared values might not load, use
+/// [`with_default`](Self::with_default) for types required to load.
+#[derive(Debug, Resource)]
+pub struct AnonymizePolicy<M: Marker> {
+    rules: HashMap<Cow<'static, str>, Rule<M>>,
+    fallback: Redaction,
+    metadata: Redaction,
+    hash_names: bool,
+}
+
+impl<M: Marker> Default for AnonymizePolicy<M> {
+    fn default() -> Self {
+        Self {
+            rules: HashMap::new(),
+            fallback: Redaction::Hash,
+            metadata: Redaction::Remove,
+            hash_names: true,
+        }
+    }
+}
+
+impl<M: Marker> Clone for AnonymizePolicy<M> {
+    fn clone(&self) -> Self {
+        Self {
+            rules: self.rules.clone(),
+            fallback: self.fallback,
+            metadata: self.metadata,
+            hash_names: self.hash_names,
+        }
+    }
+}
+
+impl<M: Marker> AnonymizePolicy<M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, type_name: impl Into<Cow<'static, str>>, redaction: Redaction) -> Self {
+        self.set(type_name, redaction);
+        self
+    }
+
+    pub fn set(&mut self, type_name: impl Into<Cow<'static, str>>, redaction: Redaction) {
+        self.rules.insert(type_name.into(), Rule::Redact(redaction));
+    }
+
+    /// Replace values of `T` with `T::default()`.
+    pub fn with_default<T: SaveLoadCore + Default>(mut self) -> Self {
+        self.rules.insert(T::type_name(), Rule::Default(default_value::<M, T>));
+        self
+    }
+
+    /// Set the [`Redaction`] of unlisted types.
+    pub fn with_fallback(mut self, redaction: Redaction) -> Self {
+        self.fallback = redaction;
+        self
+    }
+
+    /// Set the [`Redaction`] of [`SaveMetadata`](crate::SaveMetadata), default is [`Redaction::Remove`].
+    pub fn with_metadata(mut self, redaction: Redaction) -> Self {
+        self.metadata = redaction;
+        self
+    }
+
+    /// Replace each segment of paths with its hash, default is `true`.
+    ///
+    /// Segments with the same name have the same hash, so the hierarchy is preserved.
+    /// Applies to paths of records, parents, removed items, tombstones, attributes and aliases.
+    pub fn with_hash_names(mut self, hash_names: bool) -> Self {
+        self.hash_names = hash_names;
+        self
+    }
+
+    fn rule(&self, type_name: &str) -> Rule<M> {
+        self.rules.get(type_name).cloned().unwrap_or(Rule::Redact(self.fallback))
+    }
+}
+
+/// 64-bit FNV-1a, stable across platforms and versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn hash_name(name: &str) -> SmolStr {
+    SmolStr::new(format!("{:016x}", fnv1a(name.as_bytes())))
+}
+
+fn hash_value<M: Marker>(value: &ValueOf<M>) -> anyhow::Result<ValueOf<M>> {
+    let bytes = M::Method::serialize_bytes(value)?;
+    M::Method::serialize_value(&format!("{:016x}", fnv1a(&bytes)))
+}
+
+fn hash_segments(segments: &mut [SmolStr]) {
+    segments.iter_mut().for_each(|s| *s = hash_name(s));
+}
+
+fn hash_path(path: &mut EntityPath) {
+    if let EntityPath::Path(segments) = path {
+        hash_segments(segments);
+    }
+}
+
+fn hash_joined(path: &str) -> String {
+    let mut segments = split_path(path);
+    hash_segments(&mut segments);
+    join_path(&segments)
+}
+
+/// Apply a redaction, returns false if the value should be removed.
+fn redact<M: Marker>(redaction: Redaction, value: &mut ValueOf<M>) -> anyhow::Result<bool> {
+    match redaction {
+        Redaction::Keep => (),
+        Redaction::Hash => *value = hash_value::<M>(value)?,
+        Redaction::Clear => *value = Default::default(),
+        Redaction::Remove => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Rewrite serialized records with [`AnonymizePolicy`] if present.
+pub(crate) fn anonymize<M: Marker>(
+    policy: Option<Res<AnonymizePolicy<M>>>,
+    mut ctx: ResMut<SerializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    let Some(policy) = policy else { return };
+    let ctx = ctx.as_mut();
+    let mut result = Ok(());
+    ctx.components.retain(|name, records| {
+        let replacement = match policy.rule(name) {
+            Rule::Redact(Redaction::Remove) => return false,
+            Rule::Redact(redaction) => {
+                for record in records.iter_mut() {
+                    if let Err(e) = redact::<M>(redaction, &mut record.value) {
+                        result = Err(e);
+                    }
+                }
+                None
+            },
+            Rule::Default(f) => match f() {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    result = Err(e);
+                    None
+                },
+            },
+        };
+        for record in records.iter_mut() {
+            if let Some(value) = &replacement {
+                record.value = value.clone();
+            }
+            if policy.hash_names {
+                hash_path(&mut record.path);
+                if let EntityParent::Path(segments) = &mut record.parent {
+                    hash_segments(segments);
+                }
+            }
+        }
+        true
+    });
+    let components = &ctx.components;
+    ctx.attributes.retain(|name, _| components.contains_key(name.as_str()));
+    if policy.hash_names {
+        ctx.tombstones.iter_mut().for_each(hash_path);
+        ctx.tombstones.sort_by_cached_key(|path| path.to_string());
+        for entries in ctx.attributes.values_mut() {
+            entries.iter_mut().for_each(|entry| hash_path(&mut entry.path));
+            entries.sort_by_cached_key(|entry| entry.path.to_string());
+        }
+        ctx.aliases = std::mem::take(&mut ctx.aliases).into_iter()
+            .map(|(old, new)| (hash_joined(&old), hash_joined(&new)))
+            .collect();
+    }
+    if let Some(mut metadata) = ctx.metadata.take() {
+        match redact::<M>(policy.metadata, &mut metadata) {
+            Ok(true) => ctx.metadata = Some(metadata),
+            Ok(false) => (),
+            Err(e) => result = Err(e),
+        }
+    }
+    if let Some(delta) = ctx.delta.as_mut() {
+        for (name, paths) in delta.removed.iter_mut() {
+            if matches!(policy.rule(name), Rule::Redact(Redaction::Remove)) {
+                paths.clear();
+            } else if policy.hash_names {
+                paths.iter_mut().for_each(hash_path);
+            }
+        }
+        delta.removed.retain(|_, paths| !paths.is_empty());
+    }
+    if let Err(e) = result {
+        errors.push(SaloError::Serialization(e));
+    }
+}
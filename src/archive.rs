@@ -0,0 +1,264 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::system::Resource;
+
+use crate::Marker;
+#[cfg(feature="fs")]
+use std::collections::{BTreeMap, HashSet};
+#[cfg(feature="zip")]
+use std::io::{Read, Write};
+#[cfg(feature="fs")]
+use bevy_ecs::system::{Res, ResMut};
+#[cfg(feature="fs")]
+use crate::methods::{SerializationMethod, SerializeValue};
+#[cfg(feature="fs")]
+use crate::serde_impls::InlineRecords;
+#[cfg(feature="fs")]
+use crate::fingerprint::Fingerprint;
+#[cfg(feature="fs")]
+use crate::version::Versions;
+#[cfg(feature="fs")]
+use crate::alias::Aliases;
+#[cfg(feature="fs")]
+use crate::tombstone::Tombstones;
+#[cfg(feature="fs")]
+use crate::attributes::AttributeTable;
+#[cfg(feature="fs")]
+use crate::saveload::{DeltaHeader, Document, PathedValue, Signatures, ValueOf};
+#[cfg(feature="fs")]
+use crate::wire::{encoding_of, upgrade_document, DecodingScope, PathEncoding};
+#[cfg(feature="fs")]
+use crate::{SaloError, SaloErrors, SaloHistory, SaveEncryptionKey, SerializeContext};
+
+/// Container of an archive, see [`ArchiveOutput`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArchiveFormat {
+    /// A directory of files.
+    #[default]
+    Directory,
+    /// A zip file.
+    #[cfg(feature="zip")]
+    Zip,
+}
+
+/// Resource that contains the path of archive output, unique per marker.
+///
+/// Each type is written to its own file, i.e. `weapon.json`, with a `manifest` file
+/// mapping type names to files, unique ignoring case. Files are written with
+/// [`SerializationMethod::serialize_bytes_keyed`] so wrapping methods still apply.
+#[derive(Debug, Clone, Resource)]
+pub struct ArchiveOutput<M: Marker>(String, ArchiveFormat, PhantomData<M>);
+
+impl<M: Marker> ArchiveOutput<M> {
+    pub fn new(path: impl Into<String>, format: ArchiveFormat) -> Self {
+        ArchiveOutput(path.into(), format, PhantomData)
+    }
+
+    pub fn get(&self) -> &str {
+        &self.0
+    }
+
+    pub fn format(&self) -> ArchiveFormat {
+        self.1
+    }
+}
+
+/// Resource that contains the path of archive input, unique per marker.
+#[derive(Debug, Clone, Resource)]
+pub struct ArchiveInput<M: Marker>(String, ArchiveFormat, PhantomData<M>);
+
+impl<M: Marker> ArchiveInput<M> {
+    pub fn new(path: impl Into<String>, format: ArchiveFormat) -> Self {
+        ArchiveInput(path.into(), format, PhantomData)
+    }
+
+    pub fn get(&self) -> &str {
+        &self.0
+    }
+
+    pub fn format(&self) -> ArchiveFormat {
+        self.1
+    }
+}
+
+/// Index of an archive, contains everything in a save except records.
+#[cfg(feature="fs")]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(bound = "V: SerializeValue")]
+struct Manifest<V> {
+    types: BTreeMap<String, String>,
+    metadata: Option<V>,
+    delta: Option<DeltaHeader>,
+    signatures: Option<Signatures>,
+    #[serde(default)]
+    fingerprint: Option<Fingerprint>,
+    #[serde(default)]
+    versions: Option<Versions>,
+    #[serde(default)]
+    aliases: Option<Aliases>,
+    #[serde(default)]
+    tombstones: Option<Tombstones>,
+    #[serde(default)]
+    attributes: Option<AttributeTable>,
+    /// [`WIRE_VERSION`](crate::wire::WIRE_VERSION) of the writer, see [`wire`](crate::wire).
+    #[serde(default)]
+    wire: Option<u32>,
+}
+
+#[cfg(feature="fs")]
+fn manifest_name<M: Marker>() -> String {
+    format!("manifest.{}", M::Method::EXTENSION)
+}
+
+/// A file name for a type name, unique in `used` ignoring case.
+#[cfg(feature="fs")]
+fn file_name<M: Marker>(type_name: &str, used: &mut HashSet<String>) -> String {
+    let stem: String = type_name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let mut name = format!("{}.{}", stem, M::Method::EXTENSION);
+    let mut i = 2;
+    while !used.insert(name.to_ascii_lowercase()) {
+        name = format!("{}_{}.{}", stem, i, M::Method::EXTENSION);
+        i += 1;
+    }
+    name
+}
+
+/// Check a file name of the manifest is a plain name inside the archive.
+#[cfg(feature="fs")]
+fn check_file_name(name: &str) -> anyhow::Result<()> {
+    let mut components = std::path::Path::new(name).components();
+    match (components.next(), components.next()) {
+        (Some(std::path::Component::Normal(_)), None) if !name.contains(['/', '\\', ':']) => Ok(()),
+        _ => anyhow::bail!("Invalid file name {:?} in manifest.", name),
+    }
+}
+
+#[cfg(feature="fs")]
+fn write_files(output: &ArchiveOutput<impl Marker>, files: Vec<(String, Vec<u8>)>) -> anyhow::Result<()> {
+    match output.format() {
+        ArchiveFormat::Directory => {
+            let dir = std::path::Path::new(output.get());
+            std::fs::create_dir_all(dir)?;
+            for (name, bytes) in files {
+                std::fs::write(dir.join(name), bytes)?;
+            }
+        },
+        #[cfg(feature="zip")]
+        ArchiveFormat::Zip => {
+            let mut zip = zip::ZipWriter::new(std::fs::File::create(output.get())?);
+            for (name, bytes) in files {
+                zip.start_file(name, zip::write::SimpleFileOptions::default())?;
+                zip.write_all(&bytes)?;
+            }
+            zip.finish()?.flush()?;
+        },
+    }
+    Ok(())
+}
+
+/// Write each type of the save to its own file.
+#[cfg(feature="fs")]
+pub(crate) fn write_to_archive<M: Marker>(
+    output: Option<Res<ArchiveOutput<M>>>,
+    key: Option<Res<SaveEncryptionKey<M>>>,
+    data: Res<SerializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+    mut history: ResMut<SaloHistory<M>>,
+) {
+    let Some(output) = output else { return };
+    let key = key.as_ref().map(|k| k.get());
+    let result = (|| {
+        let mut used = HashSet::from([manifest_name::<M>()]);
+        let mut types = BTreeMap::new();
+        let mut files = Vec::new();
+        for (type_name, records) in data.components.iter() {
+            let name = file_name::<M>(type_name, &mut used);
+            let bytes = if data.inline.contains(type_name) {
+                M::Method::serialize_bytes_keyed(&InlineRecords(records), key)?
+            } else {
+                M::Method::serialize_bytes_keyed(records, key)?
+            };
+            files.push((name.clone(), bytes));
+            types.insert(type_name.to_string(), name);
+        }
+        let manifest = Manifest {
+            types,
+            metadata: data.metadata.clone(),
+            delta: data.delta.clone(),
+            signatures: data.signatures.clone(),
+            fingerprint: data.fingerprint.clone(),
+            versions: (!data.versions.is_empty()).then(|| data.versions.clone()),
+            aliases: (!data.aliases.is_empty()).then(|| data.aliases.clone()),
+            tombstones: (!data.tombstones.is_empty()).then(|| data.tombstones.clone()),
+            attributes: (!data.attributes.is_empty()).then(|| data.attributes.clone()),
+            wire: Some(PathEncoding::current().version()),
+        };
+        files.push((manifest_name::<M>(), M::Method::serialize_bytes_keyed(&manifest, key)?));
+        let size = files.iter().map(|(_, bytes)| bytes.len()).sum();
+        write_files(&output, files)?;
+        anyhow::Ok(size)
+    })();
+    match result {
+        Ok(size) => history.size = Some(size),
+        Err(e) => errors.push(SaloError::serialization(e)),
+    }
+}
+
+/// Reads a file in an archive by name.
+#[cfg(feature="fs")]
+type ReadFile = dyn FnMut(&str) -> anyhow::Result<Vec<u8>>;
+
+/// Read a save from an archive, returns the document and its size in bytes.
+#[cfg(feature="fs")]
+pub(crate) fn read_archive<M: Marker>(
+    input: &ArchiveInput<M>,
+    key: Option<&[u8; 32]>,
+) -> anyhow::Result<(Document<ValueOf<M>>, usize)> {
+    let mut size = 0;
+    let mut read: Box<ReadFile> = match input.format() {
+        ArchiveFormat::Directory => {
+            let dir = std::path::PathBuf::from(input.get());
+            Box::new(move |name| Ok(std::fs::read(dir.join(name))?))
+        },
+        #[cfg(feature="zip")]
+        ArchiveFormat::Zip => {
+            let mut zip = zip::ZipArchive::new(std::fs::File::open(input.get())?)?;
+            Box::new(move |name| {
+                let mut bytes = Vec::new();
+                zip.by_name(name)?.read_to_end(&mut bytes)?;
+                Ok(bytes)
+            })
+        },
+    };
+    let bytes = read(&manifest_name::<M>())?;
+    size += bytes.len();
+    let mut manifest: Manifest<ValueOf<M>> = M::Method::deserialize_keyed(&bytes, key)?;
+    let encoding = manifest.wire.map_or(Ok(PathEncoding::current()), encoding_of)?;
+    let _scope = DecodingScope::enter(encoding);
+    if encoding != PathEncoding::current() {
+        // Paths of the manifest were read with the current encoding.
+        manifest = M::Method::deserialize_keyed(&bytes, key)?;
+    }
+    let mut document = Document {
+        metadata: manifest.metadata,
+        delta: manifest.delta,
+        signatures: manifest.signatures,
+        fingerprint: manifest.fingerprint,
+        versions: manifest.versions,
+        aliases: manifest.aliases,
+        tombstones: manifest.tombstones,
+        attributes: manifest.attributes,
+        ..Default::default()
+    };
+    for (type_name, file) in manifest.types {
+        check_file_name(&file)?;
+        let bytes = read(&file)?;
+        size += bytes.len();
+        let records: Vec<PathedValue<ValueOf<M>>> = M::Method::deserialize_keyed(&bytes, key)?;
+        document.components.insert(type_name, records);
+    }
+    upgrade_document(&mut document, encoding);
+    Ok((document, size))
+}
@@ -0,0 +1,110 @@
+use std::borrow::Cow;
+use std::ops::{Deref, DerefMut};
+
+use bevy_asset::{Asset, AssetId, AssetServer, Handle};
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Commands, Res, Resource, SystemParamItem};
+use bevy_utils::{get_short_name, Uuid};
+use serde::{Deserialize, Serialize};
+
+use crate::{EntityPath, SaveLoad};
+
+/// Saved form of a `Handle`, see [`SaloHandle`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SavedHandle {
+    /// An asset loaded from a path, loaded again with the `AssetServer`.
+    Path(String),
+    /// A handle with a stable id, i.e. created with `Handle::weak_from_u128`.
+    Uuid(u128),
+    /// A procedural or otherwise untracked asset, loaded as its [`AssetFallback`].
+    Untracked,
+}
+
+impl SavedHandle {
+    pub fn new<A: Asset>(handle: &Handle<A>) -> Self {
+        match (handle.path(), handle.id()) {
+            (Some(path), _) => SavedHandle::Path(path.to_string()),
+            (None, AssetId::Uuid { uuid }) => SavedHandle::Uuid(uuid.as_u128()),
+            (None, AssetId::Index { .. }) => SavedHandle::Untracked,
+        }
+    }
+
+    /// Convert back to a handle, paths are loaded with `server` if present.
+    ///
+    /// Untracked assets, and paths without a `server`, use the fallback or `Handle::default()`.
+    pub fn resolve<A: Asset>(self, server: Option<&AssetServer>, fallback: Option<&AssetFallback<A>>) -> Handle<A> {
+        match (self, server) {
+            (SavedHandle::Path(path), Some(server)) => server.load(path),
+            (SavedHandle::Uuid(uuid), _) => Handle::Weak(AssetId::Uuid { uuid: Uuid::from_u128(uuid) }),
+            _ => fallback.map(|f| f.0.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Handle used for untracked assets of a type when loading [`SaloHandle`]s, i.e. a placeholder.
+#[derive(Debug, Clone, Resource)]
+pub struct AssetFallback<A: Asset>(pub Handle<A>);
+
+/// A `Handle<A>` that can be saved, requires the `asset` feature.
+///
+/// Handles are saved as their asset path and loaded with the `AssetServer`, see [`SavedHandle`].
+/// Can be registered directly as a component, or used as a field with [`SavedHandle`]
+/// in a custom [`SaveLoad`] implementation with `Res<AssetServer>` as its context.
+#[derive(Debug, Clone, PartialEq, Eq, Component)]
+pub struct SaloHandle<A: Asset>(pub Handle<A>);
+
+impl<A: Asset> Default for SaloHandle<A> {
+    fn default() -> Self {
+        SaloHandle(Handle::default())
+    }
+}
+
+impl<A: Asset> Deref for SaloHandle<A> {
+    type Target = Handle<A>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<A: Asset> DerefMut for SaloHandle<A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<A: Asset> From<Handle<A>> for SaloHandle<A> {
+    fn from(value: Handle<A>) -> Self {
+        SaloHandle(value)
+    }
+}
+
+impl<A: Asset> SaveLoad for SaloHandle<A> {
+    type Ser<'ser> = SavedHandle;
+    type De = SavedHandle;
+    type Context<'w, 's> = ();
+    type ContextMut<'w, 's> = (Option<Res<'w, AssetServer>>, Option<Res<'w, AssetFallback<A>>>);
+
+    fn to_serializable<'t>(&'t self,
+        _: Entity,
+        _: impl Fn(Entity) -> EntityPath,
+        _: &'t SystemParamItem<Self::Context<'_, '_>>
+    ) -> Self::Ser<'t> {
+        SavedHandle::new(&self.0)
+    }
+
+    fn from_deserialize(
+        de: Self::De,
+        _: &mut Commands,
+        _: Entity,
+        _: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+        (server, fallback): &mut SystemParamItem<Self::ContextMut<'_, '_>>
+    ) -> Self {
+        SaloHandle(de.resolve(server.as_deref(), fallback.as_deref()))
+    }
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("SaloHandle<{}>", get_short_name(std::any::type_name::<A>())))
+    }
+}
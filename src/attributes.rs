@@ -0,0 +1,126 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Commands, Query, ResMut};
+use serde::{Deserialize, Serialize};
+
+use crate::{DeserializeContext, EntityPath, Marker, SerializeContext};
+
+/// Key value attributes of a record, preserved but ignored by loads,
+/// i.e. `"icon": "sword.png"` or `"editor_color": "#ff0000"` written by an editor.
+pub type Attributes = BTreeMap<String, String>;
+
+/// Attributes of a named record in the header of a save.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AttributeEntry {
+    pub(crate) path: EntityPath,
+    pub(crate) attributes: Attributes,
+}
+
+/// Attributes of records by type name.
+pub(crate) type AttributeTable = BTreeMap<String, Vec<AttributeEntry>>;
+
+/// [`Attributes`] of records of an entity by type name, inserted by loads and written back by saves.
+///
+/// Attributes returned by [`SaveLoad::attributes`](crate::SaveLoad::attributes) take precedence.
+/// Only records with a path keep their attributes.
+#[derive(Debug, Clone, Default, PartialEq, Component)]
+pub struct RecordAttributes(HashMap<String, Attributes>);
+
+impl RecordAttributes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attributes of the record of a type.
+    pub fn get(&self, type_name: &str) -> Option<&Attributes> {
+        self.0.get(type_name)
+    }
+
+    /// Get an attribute of the record of a type.
+    pub fn attribute(&self, type_name: &str, key: &str) -> Option<&str> {
+        self.0.get(type_name)?.get(key).map(|v| v.as_str())
+    }
+
+    /// Set an attribute of the record of a type.
+    pub fn set(&mut self, type_name: impl Into<String>, key: impl Into<String>, value: impl Into<String>) {
+        self.0.entry(type_name.into()).or_default().insert(key.into(), value.into());
+    }
+
+    /// Remove all attributes of the record of a type.
+    pub fn remove(&mut self, type_name: &str) -> Option<Attributes> {
+        self.0.remove(type_name)
+    }
+
+    /// Iterate over type names and attributes.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Attributes)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.values().all(|a| a.is_empty())
+    }
+}
+
+/// Add preserved attributes to the header, drop attributes of records not written.
+pub(crate) fn write_attributes<M: Marker>(
+    mut ctx: ResMut<SerializeContext<M>>,
+    query: Query<(Entity, &RecordAttributes)>,
+) {
+    let ctx = ctx.as_mut();
+    if ctx.attributes.is_empty() && query.is_empty() {
+        return;
+    }
+    let written: HashMap<&str, HashSet<&EntityPath>> = ctx.components.iter()
+        .map(|(name, records)| (name.as_ref(), records.iter()
+            .filter(|r| !r.path.is_unique())
+            .map(|r| &r.path)
+            .collect()))
+        .collect();
+    let mut table: HashMap<(String, EntityPath), Attributes> = HashMap::new();
+    for (entity, attributes) in query.iter() {
+        let path = ctx.path_of(entity);
+        for (name, attributes) in attributes.iter() {
+            if written.get(name).is_some_and(|paths| paths.contains(&path)) && !attributes.is_empty() {
+                table.insert((name.to_owned(), path.clone()), attributes.clone());
+            }
+        }
+    }
+    for (name, entries) in std::mem::take(&mut ctx.attributes) {
+        for AttributeEntry { path, attributes } in entries {
+            if written.get(name.as_str()).is_some_and(|paths| paths.contains(&path)) {
+                table.entry((name.clone(), path)).or_default().extend(attributes);
+            }
+        }
+    }
+    let mut result = AttributeTable::new();
+    for ((name, path), attributes) in table {
+        result.entry(name).or_default().push(AttributeEntry { path, attributes });
+    }
+    for entries in result.values_mut() {
+        entries.sort_by_cached_key(|e| e.path.to_string());
+    }
+    ctx.attributes = result;
+}
+
+/// Insert [`RecordAttributes`] of the loaded save into matched entities.
+pub(crate) fn apply_attributes<M: Marker>(
+    mut commands: Commands,
+    mut ctx: ResMut<DeserializeContext<M>>,
+    existing: Query<&RecordAttributes>,
+) {
+    let mut result: HashMap<Entity, RecordAttributes> = HashMap::new();
+    for (name, entries) in std::mem::take(&mut ctx.attributes) {
+        for AttributeEntry { path, attributes } in entries {
+            let path = ctx.remapped(&path);
+            let Some(entity) = ctx.path_map.get(&path) else { continue };
+            result.entry(*entity)
+                .or_insert_with(|| existing.get(*entity).cloned().unwrap_or_default())
+                .0.insert(name.clone(), attributes);
+        }
+    }
+    for (entity, attributes) in result {
+        commands.entity(entity).insert(attributes);
+    }
+}
@@ -0,0 +1,275 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use bevy_app::{App, Last, Plugin};
+use bevy_ecs::event::{Event, EventReader};
+use bevy_ecs::schedule::{Condition, IntoSystemConfigs, SystemConfigs};
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_ecs::world::World;
+use bevy_utils::{get_short_name, Duration, Instant};
+
+use crate::{Marker, SaloError, SaloHistory, SaloStorage, SaveLoadExtension, SaveTarget};
+
+/// Configuration of [`AutoSavePlugin`], unique per marker.
+///
+/// Can be replaced or modified at runtime.
+#[derive(Debug, Resource)]
+pub struct AutoSaveConfig<M: Marker> {
+    interval: Option<Duration>,
+    max_rotations: usize,
+    prefix: String,
+    paused: bool,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Default for AutoSaveConfig<M> {
+    fn default() -> Self {
+        Self {
+            interval: Some(Duration::from_secs(300)),
+            max_rotations: 3,
+            prefix: "autosave".to_owned(),
+            paused: false,
+            p: PhantomData,
+        }
+    }
+}
+
+impl<M: Marker> Clone for AutoSaveConfig<M> {
+    fn clone(&self) -> Self {
+        Self {
+            interval: self.interval,
+            max_rotations: self.max_rotations,
+            prefix: self.prefix.clone(),
+            paused: self.paused,
+            p: PhantomData,
+        }
+    }
+}
+
+impl<M: Marker> AutoSaveConfig<M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Save every `interval`, `None` to only save on events. Defaults to 5 minutes.
+    pub fn with_interval(mut self, interval: Option<Duration>) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn set_interval(&mut self, interval: Option<Duration>) {
+        self.interval = interval;
+    }
+
+    pub fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    /// Number of slots to rotate through, i.e. `autosave_1` to `autosave_3`. Defaults to 3.
+    pub fn with_max_rotations(mut self, max: usize) -> Self {
+        self.max_rotations = max.max(1);
+        self
+    }
+
+    pub fn set_max_rotations(&mut self, max: usize) {
+        self.max_rotations = max.max(1);
+    }
+
+    pub fn max_rotations(&self) -> usize {
+        self.max_rotations
+    }
+
+    /// Prefix of slot names. Defaults to `autosave`.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    pub fn set_prefix(&mut self, prefix: impl Into<String>) {
+        self.prefix = prefix.into();
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Defer autosaves until resumed.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Name of the nth slot, starting from 1.
+    pub fn slot(&self, n: usize) -> String {
+        format!("{}_{}", self.prefix, n)
+    }
+}
+
+/// Rotation and pending trigger of [`AutoSavePlugin`], unique per marker.
+#[derive(Debug, Resource)]
+pub struct AutoSaveState<M: Marker> {
+    last: Instant,
+    next: usize,
+    pending: Option<Cow<'static, str>>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Default for AutoSaveState<M> {
+    fn default() -> Self {
+        Self {
+            last: Instant::now(),
+            next: 1,
+            pending: None,
+            p: PhantomData,
+        }
+    }
+}
+
+impl<M: Marker> AutoSaveState<M> {
+    /// Request an autosave at the end of this frame.
+    pub fn request(&mut self, reason: impl Into<Cow<'static, str>>) {
+        self.pending = Some(reason.into());
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Index of the next slot, starting from 1.
+    pub fn next_slot(&self) -> usize {
+        self.next
+    }
+
+    /// Time of the last autosave, or when the plugin is built.
+    pub fn last(&self) -> Instant {
+        self.last
+    }
+}
+
+type PauseCondition = Box<dyn Fn(SystemConfigs) -> SystemConfigs + Send + Sync>;
+
+/// Saves periodically or on events, rotating through slots `autosave_1..N`.
+///
+/// Writes to [`SaloStorage`] if present, otherwise to files in
+/// [`SaveDirectory`](crate::SaveDirectory) if present, otherwise to the working directory.
+/// The [`SaveLoadPlugin`](crate::SaveLoadPlugin) of the marker must be added separately.
+///
+/// Triggers while paused are deferred until resumed.
+/// Rotation restarts from the first slot each run.
+pub struct AutoSavePlugin<M: Marker> {
+    config: AutoSaveConfig<M>,
+    triggers: Vec<fn(&mut App)>,
+    pause: Vec<PauseCondition>,
+}
+
+impl<M: Marker> Default for AutoSavePlugin<M> {
+    fn default() -> Self {
+        Self {
+            config: AutoSaveConfig::default(),
+            triggers: Vec::new(),
+            pause: Vec::new(),
+        }
+    }
+}
+
+impl<M: Marker> AutoSavePlugin<M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_config(mut self, config: AutoSaveConfig<M>) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Also save when event `E` is sent.
+    pub fn on_event<E: Event>(mut self) -> Self {
+        self.triggers.push(|app| {
+            app.add_systems(Last, trigger_on_event::<M, E>.before(autosave::<M>));
+        });
+        self
+    }
+
+    /// Defer autosaves while the condition is true, i.e. during cutscenes.
+    pub fn pause_if<P>(mut self, condition: impl Condition<P> + Clone + Send + Sync + 'static) -> Self {
+        self.pause.push(Box::new(move |system| {
+            system.run_if(bevy_ecs::schedule::common_conditions::not(condition.clone()))
+        }));
+        self
+    }
+}
+
+fn trigger_on_interval<M: Marker>(config: Res<AutoSaveConfig<M>>, mut state: ResMut<AutoSaveState<M>>) {
+    if let Some(interval) = config.interval {
+        if state.pending.is_none() && state.last.elapsed() >= interval {
+            state.pending = Some(Cow::Borrowed("interval"));
+        }
+    }
+}
+
+fn trigger_on_event<M: Marker, E: Event>(mut events: EventReader<E>, mut state: ResMut<AutoSaveState<M>>) {
+    if !events.is_empty() {
+        events.clear();
+        state.pending = Some(Cow::Owned(get_short_name(std::any::type_name::<E>())));
+    }
+}
+
+fn autosave<M: Marker>(world: &mut World) {
+    let config = world.resource::<AutoSaveConfig<M>>();
+    if config.paused {
+        return;
+    }
+    let max = config.max_rotations;
+    let mut state = world.resource_mut::<AutoSaveState<M>>();
+    let Some(reason) = state.pending.take() else { return };
+    state.last = Instant::now();
+    let n = state.next;
+    let slot = world.resource::<AutoSaveConfig<M>>().slot(n);
+    if let Some(mut history) = world.get_resource_mut::<SaloHistory<M>>() {
+        history.request("autosave", reason);
+    }
+    let result = if world.contains_resource::<SaloStorage<M>>() {
+        world.save::<M>(SaveTarget::Storage(&slot)).map(|_| ())
+    } else {
+        save_to_directory::<M>(world, &slot)
+    };
+    if result.is_ok() {
+        world.resource_mut::<AutoSaveState<M>>().next = n % max + 1;
+    }
+}
+
+#[cfg(feature="fs")]
+fn save_to_directory<M: Marker>(world: &mut World, slot: &str) -> Result<(), SaloError> {
+    let directory = world.get_resource::<crate::SaveDirectory>().cloned()
+        .unwrap_or_else(|| crate::SaveDirectory::new("."));
+    let file = directory.file(slot).map_err(|e| SaloError::Serialization(e.into()))?;
+    world.save::<M>(SaveTarget::File(&file)).map(|_| ())
+}
+
+#[cfg(not(feature="fs"))]
+#[allow(clippy::extra_unused_type_parameters)]
+fn save_to_directory<M: Marker>(_: &mut World, _: &str) -> Result<(), SaloError> {
+    Err(SaloError::UnsupportedPlatform)
+}
+
+impl<M: Marker> Plugin for AutoSavePlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.config.clone());
+        app.init_resource::<AutoSaveState<M>>();
+        let mut system = autosave::<M>.into_configs();
+        for condition in &self.pause {
+            system = condition(system);
+        }
+        app.add_systems(Last, (trigger_on_interval::<M>.before(autosave::<M>), system));
+        for trigger in &self.triggers {
+            trigger(app);
+        }
+    }
+}
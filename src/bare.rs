@@ -0,0 +1,71 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Commands, Query, Res, ResMut};
+
+use crate::saveload::{EntityParent, PathedValue};
+use crate::{DeserializeContext, EntityPath, Marked, Marker, Parents, PathName, SaloConfig, SaveSelection, SerializeContext};
+
+/// Type name of bare entity records, see [`SaloConfig::with_bare_entities`].
+pub const BARE_ENTITY: &str = "(entity)";
+
+/// Write named entities without records as bare entity records.
+pub(crate) fn emit_bare_entities<M: Marker>(
+    config: Res<SaloConfig<M>>,
+    mut ctx: ResMut<SerializeContext<M>>,
+    names: Query<(Entity, &PathName)>,
+    parents: Parents,
+    marked: Marked<M>,
+    selection: SaveSelection<M>,
+) {
+    if !config.bare_entities() || selection.is_delta() {
+        return;
+    }
+    let recorded: HashSet<_> = ctx.components.values().flatten().map(|r| r.path.clone()).collect();
+    let mut records = Vec::new();
+    for (entity, _) in names.iter() {
+        if !marked.contains(entity) || !selection.contains(entity) {
+            continue;
+        }
+        let path = ctx.path_of(entity);
+        if !matches!(path, EntityPath::Path(_)) || recorded.contains(&path) {
+            continue;
+        }
+        let parent = match parents.get(entity) {
+            Some(parent) => match ctx.path_of(parent) {
+                EntityPath::Path(path) => EntityParent::Path(path),
+                EntityPath::Entity(id) if marked.contains(parent) => EntityParent::Entity(id),
+                _ => EntityParent::Root,
+            },
+            None => EntityParent::Root,
+        };
+        records.push(PathedValue { parent, path, value: Default::default() });
+    }
+    if !records.is_empty() {
+        records.sort_by_cached_key(|r| r.path.to_string());
+        ctx.components.insert(Cow::Borrowed(BARE_ENTITY), records);
+    }
+}
+
+/// Spawn entities of bare entity records with a [`PathName`], matching existing entities by path.
+pub(crate) fn load_bare_entities<M: Marker>(
+    mut commands: Commands,
+    mut ctx: ResMut<DeserializeContext<M>>,
+) {
+    let Some(records) = ctx.components.remove(BARE_ENTITY) else { return };
+    for PathedValue { parent, path, .. } in records {
+        let EntityPath::Path(segments) = &path else { continue };
+        let Some(name) = segments.last() else { continue };
+        if ctx.over_budget(&path) {
+            ctx.deferred.entry(BARE_ENTITY.to_owned()).or_default().push(PathedValue { parent, path, value: Default::default() });
+            continue;
+        }
+        let existed = ctx.path_map.contains_key(&path);
+        let entity = ctx.get_or_new(&mut commands, &path);
+        if !existed {
+            commands.entity(entity).insert(PathName::new_owned(name.to_string()));
+            ctx.set_parent(&mut commands, entity, parent);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Representative worlds for benchmarks, enabled by the `bench` feature.
+//!
+//! Used by the crate's own benches, and for sizing save budgets on your own hardware.
+//!
+//! ```
+//! # use bevy_salo::{bench, methods::SerdeJson, SaveLoadExtension, SaveTarget, LoadSource};
+//! type All = bevy_salo::All<SerdeJson<false>>;
+//! let mut world = bench::world::<All>();
+//! bench::deep_hierarchy(&mut world, 100);
+//! let bytes = world.save::<All>(SaveTarget::Bytes).unwrap().into_bytes().unwrap();
+//! bench::world::<All>().load::<All>(LoadSource::Bytes(&bytes)).unwrap();
+//! ```
+use std::borrow::Cow;
+
+use bevy_ecs::component::Component;
+use bevy_ecs::world::World;
+use serde::{Deserialize, Serialize};
+
+use crate::{Marker, SaveLoadCore, SaveLoadPlugin};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Component, Serialize, Deserialize)]
+pub struct Position {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl SaveLoadCore for Position {}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Component, Serialize, Deserialize)]
+pub struct Health(pub u32);
+
+impl SaveLoadCore for Health {}
+
+/// A named entity, builds paths.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Component, Serialize, Deserialize)]
+pub struct Label(pub String);
+
+impl SaveLoadCore for Label {
+    fn path_name(&self) -> Option<Cow<'static, str>> {
+        Some(Cow::Owned(self.0.clone()))
+    }
+}
+
+/// One of [`TAGS`] distinct component types.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Component, Serialize, Deserialize)]
+pub struct Tag<const N: usize>(pub u32);
+
+impl<const N: usize> SaveLoadCore for Tag<N> {}
+
+/// Number of [`Tag`] types registered.
+pub const TAGS: usize = 16;
+
+macro_rules! tags {
+    ($plugin: expr, $($n: literal),*) => {
+        $plugin$(.register::<Tag<$n>>())*
+    };
+}
+
+macro_rules! spawn_tags {
+    ($world: expr, $i: expr, $($n: literal),*) => {
+        $($world.spawn(Tag::<$n>($i));)*
+    };
+}
+
+/// Create a world with all benchmark types registered with marker `M`.
+pub fn world<M: Marker>() -> World {
+    let mut world = World::new();
+    let plugin = SaveLoadPlugin::new::<M>()
+        .register::<Position>()
+        .register::<Health>()
+        .register::<Label>();
+    tags!(plugin, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15).build_world(&mut world);
+    world
+}
+
+fn position(i: usize) -> Position {
+    Position { x: i as f32, y: (i * 2) as f32, z: (i * 3) as f32 }
+}
+
+/// Spawn `count` unnamed root entities with two components each.
+pub fn wide_flat(world: &mut World, count: usize) {
+    for i in 0..count {
+        world.spawn((position(i), Health(i as u32)));
+    }
+}
+
+/// Spawn a chain of `depth` named entities, each a child of the previous.
+///
+/// Entities are flat without the `hierarchy` feature.
+pub fn deep_hierarchy(world: &mut World, depth: usize) {
+    #[cfg(feature="hierarchy")]
+    let mut parent: Option<bevy_ecs::entity::Entity> = None;
+    for i in 0..depth {
+        let _entity = world.spawn((Label(format!("node{}", i)), position(i))).id();
+        #[cfg(feature="hierarchy")]
+        {
+            use bevy_hierarchy::BuildWorldChildren;
+            if let Some(parent) = parent {
+                world.entity_mut(parent).add_child(_entity);
+            }
+            parent = Some(_entity);
+        }
+    }
+}
+
+/// Spawn `count` entities spread over [`TAGS`] component types.
+pub fn many_types(world: &mut World, count: usize) {
+    for i in 0..count.div_ceil(TAGS) {
+        spawn_tags!(world, i as u32, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+    }
+}
+
+/// Spawn `count` unnamed entities of a single component type.
+pub fn few_types(world: &mut World, count: usize) {
+    for i in 0..count {
+        world.spawn(Health(i as u32));
+    }
+}
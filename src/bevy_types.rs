@@ -0,0 +1,159 @@
+//! `SaveLoad` implementations of common bevy components, requires the `bevy_types` feature.
+//!
+//! * `Transform` is saved as `"Transform"`.
+//! * `Name` is saved as `"Name"` and used as the path name of its entity.
+//! * `Visibility` is saved as `"Visibility"`, requires the `visibility` feature.
+//!
+//! `GlobalTransform` is computed from `Transform` by bevy and is not saved,
+//! it is inserted alongside a loaded `Transform` so the entity is picked up by transform propagation.
+//! Likewise `InheritedVisibility` and `ViewVisibility` are inserted alongside a loaded `Visibility`.
+//! Register all of them with [`register_bevy_core_types`](crate::SaveLoadPlugin::register_bevy_core_types).
+use std::borrow::Cow;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Commands, SystemParamItem};
+use bevy_math::Quat;
+use serde::{Deserialize, Serialize};
+
+use crate::{EntityPath, SaveLoad};
+
+pub use bevy_core::Name;
+pub use bevy_transform::components::{GlobalTransform, Transform};
+#[cfg(feature="visibility")]
+pub use bevy_render::view::{InheritedVisibility, ViewVisibility, Visibility};
+
+/// Types registered by [`register_bevy_core_types`](crate::SaveLoadPlugin::register_bevy_core_types).
+#[cfg(not(feature="visibility"))]
+pub type BevyCoreTypes = (Transform, Name);
+
+/// Types registered by [`register_bevy_core_types`](crate::SaveLoadPlugin::register_bevy_core_types).
+#[cfg(feature="visibility")]
+pub type BevyCoreTypes = (Transform, Name, Visibility);
+
+/// Saved form of a `Transform`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SavedTransform {
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+}
+
+impl SaveLoad for Transform {
+    type Ser<'ser> = SavedTransform;
+    type De = SavedTransform;
+    type Context<'w, 's> = ();
+    type ContextMut<'w, 's> = ();
+
+    fn to_serializable<'t>(&'t self,
+        _: Entity,
+        _: impl Fn(Entity) -> EntityPath,
+        _: &'t SystemParamItem<Self::Context<'_, '_>>
+    ) -> Self::Ser<'t> {
+        SavedTransform {
+            translation: self.translation.to_array(),
+            rotation: self.rotation.to_array(),
+            scale: self.scale.to_array(),
+        }
+    }
+
+    fn from_deserialize(
+        de: Self::De,
+        commands: &mut Commands,
+        self_entity: Entity,
+        _: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+        _: &mut SystemParamItem<Self::ContextMut<'_, '_>>
+    ) -> Self {
+        let transform = Transform {
+            translation: de.translation.into(),
+            rotation: Quat::from_array(de.rotation),
+            scale: de.scale.into(),
+        };
+        commands.entity(self_entity).insert(GlobalTransform::from(transform));
+        transform
+    }
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("Transform")
+    }
+}
+
+impl SaveLoad for Name {
+    type Ser<'ser> = &'ser str;
+    type De = String;
+    type Context<'w, 's> = ();
+    type ContextMut<'w, 's> = ();
+
+    fn to_serializable<'t>(&'t self,
+        _: Entity,
+        _: impl Fn(Entity) -> EntityPath,
+        _: &'t SystemParamItem<Self::Context<'_, '_>>
+    ) -> Self::Ser<'t> {
+        self.as_str()
+    }
+
+    fn from_deserialize(
+        de: Self::De,
+        _: &mut Commands,
+        _: Entity,
+        _: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+        _: &mut SystemParamItem<Self::ContextMut<'_, '_>>
+    ) -> Self {
+        Name::new(de)
+    }
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("Name")
+    }
+
+    fn path_name(&self) -> Option<Cow<'static, str>> {
+        Some(Cow::Owned(self.as_str().to_owned()))
+    }
+}
+
+/// Saved form of a `Visibility`.
+#[cfg(feature="visibility")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SavedVisibility {
+    Inherited,
+    Hidden,
+    Visible,
+}
+
+#[cfg(feature="visibility")]
+impl SaveLoad for Visibility {
+    type Ser<'ser> = SavedVisibility;
+    type De = SavedVisibility;
+    type Context<'w, 's> = ();
+    type ContextMut<'w, 's> = ();
+
+    fn to_serializable<'t>(&'t self,
+        _: Entity,
+        _: impl Fn(Entity) -> EntityPath,
+        _: &'t SystemParamItem<Self::Context<'_, '_>>
+    ) -> Self::Ser<'t> {
+        match self {
+            Visibility::Inherited => SavedVisibility::Inherited,
+            Visibility::Hidden => SavedVisibility::Hidden,
+            Visibility::Visible => SavedVisibility::Visible,
+        }
+    }
+
+    fn from_deserialize(
+        de: Self::De,
+        commands: &mut Commands,
+        self_entity: Entity,
+        _: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+        _: &mut SystemParamItem<Self::ContextMut<'_, '_>>
+    ) -> Self {
+        commands.entity(self_entity).insert((InheritedVisibility::default(), ViewVisibility::default()));
+        match de {
+            SavedVisibility::Inherited => Visibility::Inherited,
+            SavedVisibility::Hidden => Visibility::Hidden,
+            SavedVisibility::Visible => Visibility::Visible,
+        }
+    }
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("Visibility")
+    }
+}
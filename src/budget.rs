@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Commands, Res, ResMut, Resource};
+use bevy_ecs::world::World;
+
+use crate::methods::SerializationMethod;
+use crate::saveload::{DeltaHeader, DocumentRef, PathedValueOf};
+use crate::attributes::{AttributeEntry, AttributeTable};
+use crate::import::TypeRecords;
+use crate::version::Versions;
+use crate::{DeserializeContext, EntityPath, FormatLayout, Marker, SaloError, SaloErrors};
+
+/// Records not loaded because of [`SaloConfig::with_entity_budget`](crate::SaloConfig::with_entity_budget),
+/// unique per marker.
+///
+/// Replaced by each load, removed when nothing is pending.
+#[derive(Debug, Resource)]
+pub struct PendingLoad<M: Marker> {
+    components: HashMap<String, Vec<PathedValueOf<M>>>,
+    /// Unnamed entities spawned by previous chunks, so later records can refer to them.
+    entities: HashMap<EntityPath, Entity>,
+    versions: Versions,
+    /// Attributes of pending records, with paths already remapped.
+    attributes: AttributeTable,
+    /// Filter of [`load_type_records`](crate::SaveLoadExtension::load_type_records), reapplied to each chunk.
+    type_records: Option<TypeRecords<M>>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> PendingLoad<M> {
+    /// Number of pending records.
+    pub fn len(&self) -> usize {
+        self.components.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.values().all(Vec::is_empty)
+    }
+}
+
+/// Entities of previous chunks available to the current load.
+#[derive(Debug, Resource)]
+pub(crate) struct PendingEntities<M: Marker>(HashMap<EntityPath, Entity>, PhantomData<M>);
+
+/// Returns false when loading a chunk of [`PendingLoad`], i.e. to run hooks once per load.
+///
+/// Records of a chunk are already verified, renamed and remapped by the first chunk.
+pub(crate) fn is_first_chunk<M: Marker>(pending: Option<Res<PendingEntities<M>>>) -> bool {
+    pending.is_none()
+}
+
+/// Seed the path map with entities spawned by previous chunks.
+pub(crate) fn apply_pending_entities<M: Marker>(
+    pending: Option<Res<PendingEntities<M>>>,
+    mut ctx: ResMut<DeserializeContext<M>>,
+) {
+    if let Some(pending) = pending {
+        ctx.path_map.extend(pending.0.iter().map(|(path, entity)| (path.clone(), *entity)));
+    }
+}
+
+/// Move records over the entity budget to [`PendingLoad`].
+pub(crate) fn collect_pending<M: Marker>(
+    mut commands: Commands,
+    mut ctx: ResMut<DeserializeContext<M>>,
+    errors: Res<SaloErrors<M>>,
+    type_records: Option<Res<TypeRecords<M>>>,
+) {
+    let components = std::mem::take(&mut ctx.deferred);
+    ctx.pending = errors.is_empty() && !components.values().all(Vec::is_empty);
+    if !ctx.pending {
+        commands.remove_resource::<PendingLoad<M>>();
+        return;
+    }
+    let entities = ctx.path_map.iter()
+        .filter(|(path, _)| matches!(path, EntityPath::Entity(_)))
+        .map(|(path, entity)| (path.clone(), *entity))
+        .collect();
+    let versions = ctx.versions.clone();
+    let attributes = ctx.attributes.iter()
+        .filter(|(name, _)| components.contains_key(*name))
+        .map(|(name, entries)| (name.clone(), entries.iter().map(|entry| AttributeEntry {
+            path: ctx.remapped(&entry.path),
+            attributes: entry.attributes.clone(),
+        }).collect()))
+        .collect();
+    let type_records = type_records.map(|r| r.for_chunk());
+    commands.insert_resource(PendingLoad::<M> { components, entities, versions, attributes, type_records, p: PhantomData });
+}
+
+/// Load the next chunk of [`PendingLoad`], returns false if nothing was pending.
+pub(crate) fn load_pending<M: Marker>(world: &mut World) -> Result<bool, SaloError> {
+    let Some(pending) = world.remove_resource::<PendingLoad<M>>() else { return Ok(false) };
+    if pending.is_empty() {
+        return Ok(false);
+    }
+    // Loaded as a delta so the chunk does not reset `SaveState` or fail `TypePolicy::Required`,
+    // verification, remapping and hooks are skipped by `is_first_chunk`.
+    let delta = DeltaHeader::default();
+    let value = M::Method::serialize_value(&DocumentRef {
+        metadata: None,
+        delta: Some(&delta),
+        signatures: None,
+        fingerprint: None,
+        versions: (!pending.versions.is_empty()).then_some(&pending.versions),
+        aliases: None,
+        tombstones: None,
+        attributes: (!pending.attributes.is_empty()).then_some(&pending.attributes),
+        layout: FormatLayout::TypeMajor,
+        components: &pending.components,
+        inline: None,
+    }).map_err(SaloError::Serialization)?;
+    world.insert_resource(PendingEntities::<M>(pending.entities, PhantomData));
+    if let Some(type_records) = pending.type_records {
+        world.insert_resource(type_records);
+    }
+    let result = crate::document::load_value::<M>(world, value);
+    world.remove_resource::<PendingEntities<M>>();
+    world.remove_resource::<TypeRecords<M>>();
+    result.map(|_| true)
+}
@@ -0,0 +1,161 @@
+use std::borrow::Cow;
+
+use bevy_ecs::bundle::Bundle;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::{QueryItem, ReadOnlyWorldQuery};
+use bevy_ecs::system::{Commands, Query, ResMut};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::methods::SerializationMethod;
+use crate::propagation::Marked;
+use crate::saveload::{EntityPath, Parents, PathedValue, SaveSelection};
+use crate::{DeserializeContext, Marker, SaloError, SaloErrors, SerializeContext};
+
+/// Saves multiple tightly coupled components as a single record,
+/// i.e. `Transform`, `Velocity` and `Collider` of a physics body.
+///
+/// Entities matching all of `Query` are saved, `Bundle` is inserted as a whole when loading.
+/// Register with [`SaveLoadPlugin::register_bundle`](crate::SaveLoadPlugin::register_bundle).
+///
+/// ```
+/// # use bevy_ecs::component::Component;
+/// # use bevy_ecs::query::QueryItem;
+/// # use bevy_salo::SaveLoadBundle;
+/// # use std::borrow::Cow;
+/// # #[derive(Component, Clone, Copy, serde::Serialize, serde::Deserialize)]
+/// # struct Position(f32, f32);
+/// # #[derive(Component, Clone, Copy, serde::Serialize, serde::Deserialize)]
+/// # struct Velocity(f32, f32);
+/// struct Body;
+///
+/// impl SaveLoadBundle for Body {
+///     type Query = (&'static Position, &'static Velocity);
+///     type Bundle = (Position, Velocity);
+///     type Ser<'ser> = (&'ser Position, &'ser Velocity);
+///     type De = (Position, Velocity);
+///
+///     fn to_serializable(item: QueryItem<'_, Self::Query>) -> Self::Ser<'_> {
+///         item
+///     }
+///
+///     fn from_deserialize(de: Self::De) -> Self::Bundle {
+///         de
+///     }
+///
+///     fn type_name() -> Cow<'static, str> {
+///         Cow::Borrowed("body")
+///     }
+/// }
+/// ```
+pub trait SaveLoadBundle: Send + Sync + 'static {
+    /// Components read when saving.
+    type Query: ReadOnlyWorldQuery;
+    /// Components inserted when loading.
+    type Bundle: Bundle;
+    type Ser<'ser>: Serialize;
+    type De: DeserializeOwned;
+
+    fn to_serializable(item: QueryItem<'_, Self::Query>) -> Self::Ser<'_>;
+
+    fn from_deserialize(de: Self::De) -> Self::Bundle;
+
+    /// Name associated with this type, see [`SaveLoad::type_name`](crate::SaveLoad::type_name).
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed(std::any::type_name::<Self>())
+    }
+}
+
+/// System for serialization, delta saves always write all selected bundles.
+pub(crate) fn serialize_bundle<M: Marker, T: SaveLoadBundle>(
+    mut paths: ResMut<SerializeContext<M>>,
+    query: Query<(Entity, T::Query), M::Query>,
+    all: Query<(Entity, T::Query)>,
+    parents: Parents,
+    marked: Marked<M>,
+    selection: SaveSelection<M>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    if !selection.contains_type(&T::type_name()) {
+        return;
+    }
+    let propagated = marked.descendants().filter_map(|e| all.get(e).ok());
+    for (entity, item) in query.iter().chain(propagated) {
+        if !selection.contains(entity) {
+            continue;
+        }
+        if selection.is_delta() {
+            if let Some(path) = paths.paths.get(&entity) {
+                let path = EntityPath::Path(path.clone());
+                paths.present.entry(T::type_name().into_owned()).or_default().insert(path);
+            }
+        }
+        let Some(parent) = paths.parent_of(T::type_name(), entity, &parents, &marked, &selection) else {
+            continue;
+        };
+        let value = match M::Method::serialize_value(&T::to_serializable(item)) {
+            Ok(value) => value,
+            Err(e) => {
+                errors.push(SaloError::serialization(e));
+                continue;
+            }
+        };
+        let record = PathedValue {
+            parent,
+            path: paths.path_of(entity),
+            value,
+        };
+        paths.components.entry(T::type_name()).or_default().push(record);
+    }
+}
+
+/// System for deserialization.
+pub(crate) fn deserialize_bundle<M: Marker, T: SaveLoadBundle>(
+    mut commands: Commands,
+    mut context: ResMut<DeserializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    let removed = context.delta.as_mut().and_then(|d| d.removed.remove(T::type_name().as_ref()));
+    for path in removed.into_iter().flatten() {
+        if let Some(entity) = context.path_map.get(&path) {
+            commands.entity(*entity).remove::<T::Bundle>();
+        }
+    }
+    let Some(items) = context.components.remove(T::type_name().as_ref()) else { return };
+    let mut batch = Vec::with_capacity(items.len());
+    for PathedValue { parent, path, value } in items {
+        if context.over_budget(&path) {
+            let type_name = T::type_name().into_owned();
+            context.deferred.entry(type_name).or_default().push(PathedValue { parent, path, value });
+            continue;
+        }
+        let de = match M::Method::deserialize_value::<T::De>(value) {
+            Ok(de) => de,
+            Err(e) => {
+                context.reject(&T::type_name(), e, &mut errors);
+                continue;
+            }
+        };
+        let entity = match context.path_map.get(&path) {
+            Some(entity) => *entity,
+            None => {
+                let e = context.spawn(&mut commands, &path);
+                context.path_map.insert(path, e);
+                e
+            }
+        };
+        batch.push((entity, T::from_deserialize(de)));
+        context.set_parent(&mut commands, entity, parent);
+    }
+    commands.insert_or_spawn_batch(batch);
+}
+
+/// Remove all copies of the bundle.
+pub(crate) fn remove_bundle<M: Marker, T: SaveLoadBundle>(
+    mut commands: Commands,
+    entities: Query<Entity, (T::Query, M::Query)>,
+) {
+    entities.iter().for_each(|e| {
+        commands.entity(e).remove::<T::Bundle>();
+    })
+}
@@ -0,0 +1,177 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::system::{Command, Commands};
+use bevy_ecs::world::World;
+use bevy_utils::tracing::error;
+
+use crate::reentrant;
+use crate::{LoadSource, Marker, SaloError, SaveLoadExtension, SaveTarget};
+
+type BytesCallback = Box<dyn FnOnce(&mut World, Vec<u8>) + Send + Sync>;
+
+/// Log a failed command, queued calls are reported when they run.
+pub(crate) fn report<T>(name: &str, result: Result<T, SaloError>) {
+    match result {
+        Ok(_) | Err(SaloError::Queued) => (),
+        Err(e) => error!("{} failed: {}", name, e),
+    }
+}
+
+/// [`Command`] that saves to a file, errors are logged.
+pub struct SaveToFile<M: Marker> {
+    file: String,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> SaveToFile<M> {
+    pub fn new(file: impl Into<String>) -> Self {
+        Self { file: file.into(), p: PhantomData }
+    }
+}
+
+impl<M: Marker> Command for SaveToFile<M> {
+    fn apply(self, world: &mut World) {
+        report("SaveToFile", world.save::<M>(SaveTarget::File(&self.file)));
+    }
+}
+
+/// [`Command`] that saves to a key in [`SaloStorage`](crate::SaloStorage), errors are logged.
+pub struct SaveToStorage<M: Marker> {
+    key: String,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> SaveToStorage<M> {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self { key: key.into(), p: PhantomData }
+    }
+}
+
+impl<M: Marker> Command for SaveToStorage<M> {
+    fn apply(self, world: &mut World) {
+        report("SaveToStorage", world.save::<M>(SaveTarget::Storage(&self.key)));
+    }
+}
+
+/// [`Command`] that saves to bytes and passes them to a callback, errors are logged.
+pub struct SaveToBytes<M: Marker> {
+    callback: BytesCallback,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> SaveToBytes<M> {
+    pub fn new(callback: impl FnOnce(&mut World, Vec<u8>) + Send + Sync + 'static) -> Self {
+        Self { callback: Box::new(callback), p: PhantomData }
+    }
+}
+
+impl<M: Marker> Command for SaveToBytes<M> {
+    fn apply(self, world: &mut World) {
+        let callback = self.callback;
+        // Queue the command instead of the save, so the callback receives its output.
+        if reentrant::is_running::<M>(world) {
+            return reentrant::defer::<M, _>(world, "SaveToBytes", move |w| Self { callback, p: PhantomData }.apply(w), ());
+        }
+        match world.save::<M>(SaveTarget::Bytes) {
+            Ok(saved) => callback(world, saved.into_bytes().unwrap_or_default()),
+            Err(e) => report::<()>("SaveToBytes", Err(e)),
+        }
+    }
+}
+
+/// [`Command`] that loads from a file, errors are logged.
+pub struct LoadFromFile<M: Marker> {
+    file: String,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> LoadFromFile<M> {
+    pub fn new(file: impl Into<String>) -> Self {
+        Self { file: file.into(), p: PhantomData }
+    }
+}
+
+impl<M: Marker> Command for LoadFromFile<M> {
+    fn apply(self, world: &mut World) {
+        report("LoadFromFile", world.load::<M>(LoadSource::File(&self.file)));
+    }
+}
+
+/// [`Command`] that loads from a key in [`SaloStorage`](crate::SaloStorage), errors are logged.
+pub struct LoadFromStorage<M: Marker> {
+    key: String,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> LoadFromStorage<M> {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self { key: key.into(), p: PhantomData }
+    }
+}
+
+impl<M: Marker> Command for LoadFromStorage<M> {
+    fn apply(self, world: &mut World) {
+        report("LoadFromStorage", world.load::<M>(LoadSource::Storage(&self.key)));
+    }
+}
+
+/// [`Command`] that loads from bytes, errors are logged.
+pub struct LoadFromBytes<M: Marker> {
+    bytes: Vec<u8>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> LoadFromBytes<M> {
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Self { bytes: bytes.into(), p: PhantomData }
+    }
+}
+
+impl<M: Marker> Command for LoadFromBytes<M> {
+    fn apply(self, world: &mut World) {
+        report("LoadFromBytes", world.load::<M>(LoadSource::Bytes(&self.bytes)));
+    }
+}
+
+/// Queue saves and loads from systems without exclusive world access,
+/// they run when commands are applied.
+pub trait SaveLoadCommandsExtension {
+    /// Queue [`SaveToFile`].
+    fn salo_save<M: Marker>(&mut self, file: impl Into<String>);
+    /// Queue [`LoadFromFile`].
+    fn salo_load<M: Marker>(&mut self, file: impl Into<String>);
+    /// Queue [`SaveToStorage`].
+    fn salo_save_to_storage<M: Marker>(&mut self, key: impl Into<String>);
+    /// Queue [`LoadFromStorage`].
+    fn salo_load_from_storage<M: Marker>(&mut self, key: impl Into<String>);
+    /// Queue [`SaveToBytes`].
+    fn salo_save_to_bytes<M: Marker>(&mut self, callback: impl FnOnce(&mut World, Vec<u8>) + Send + Sync + 'static);
+    /// Queue [`LoadFromBytes`].
+    fn salo_load_from_bytes<M: Marker>(&mut self, bytes: impl Into<Vec<u8>>);
+}
+
+impl SaveLoadCommandsExtension for Commands<'_, '_> {
+    fn salo_save<M: Marker>(&mut self, file: impl Into<String>) {
+        self.add(SaveToFile::<M>::new(file))
+    }
+
+    fn salo_load<M: Marker>(&mut self, file: impl Into<String>) {
+        self.add(LoadFromFile::<M>::new(file))
+    }
+
+    fn salo_save_to_storage<M: Marker>(&mut self, key: impl Into<String>) {
+        self.add(SaveToStorage::<M>::new(key))
+    }
+
+    fn salo_load_from_storage<M: Marker>(&mut self, key: impl Into<String>) {
+        self.add(LoadFromStorage::<M>::new(key))
+    }
+
+    fn salo_save_to_bytes<M: Marker>(&mut self, callback: impl FnOnce(&mut World, Vec<u8>) + Send + Sync + 'static) {
+        self.add(SaveToBytes::<M>::new(callback))
+    }
+
+    fn salo_load_from_bytes<M: Marker>(&mut self, bytes: impl Into<Vec<u8>>) {
+        self.add(LoadFromBytes::<M>::new(bytes))
+    }
+}
@@ -0,0 +1,532 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::event::Event;
+use bevy_ecs::system::Resource;
+use bevy_ecs::world::World;
+use bevy_utils::{Duration, Instant};
+
+use smol_str::SmolStr;
+
+use crate::{AnonymizePolicy, Marker, PathConflict};
+use crate::saveload::split_path;
+
+/// How conflicting names and paths are handled, see [`PathConflict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConflictPolicy {
+    /// Panic on conflict.
+    #[default]
+    Panic,
+    /// Fail the save or load with [`SaloError::Conflict`](crate::SaloError::Conflict).
+    Error,
+    /// Keep the first name or entity, sends [`SaloEventKind::Conflict`].
+    FirstWins,
+    /// Keep the last name or entity, sends [`SaloEventKind::Conflict`].
+    LastWins,
+}
+
+/// Which entities are considered marked by a [`MarkerComponent`](crate::MarkerComponent).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarkerPropagation {
+    /// Only entities with the marker.
+    #[default]
+    None,
+    /// Entities with the marker and all their descendants,
+    /// i.e. to only mark the root of a prefab.
+    Descendants,
+}
+
+/// How a serialized entity with a parent that is neither named nor serialized is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrphanPolicy {
+    /// Panic when serializing the entity.
+    #[default]
+    Panic,
+    /// Save the entity without a parent, sends [`SaloEventKind::Orphan`].
+    TreatAsRoot,
+    /// Do not save records of the entity, sends [`SaloEventKind::Orphan`].
+    Skip,
+}
+
+/// How records are grouped in a save, both layouts load regardless of this setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum FormatLayout {
+    /// A list of records by type name, the most compact layout.
+    #[default]
+    TypeMajor,
+    /// A list of entities under `$entities`, each with a map of type names to values,
+    /// i.e. for hand-edited scene files. Tagged by `"$layout": "EntityMajor"`.
+    ///
+    /// Records with the same path and parent are grouped, inline records are written as usual values.
+    EntityMajor,
+}
+
+/// Runtime configuration of a marker, inserted by the plugin.
+///
+/// Can be replaced or modified between save and load calls.
+#[derive(Debug, Resource)]
+pub struct SaloConfig<M: Marker> {
+    root: Option<Cow<'static, str>>,
+    min_save_interval: Option<Duration>,
+    preserve_unknown: bool,
+    conflict_policy: ConflictPolicy,
+    marker_propagation: MarkerPropagation,
+    orphan_policy: OrphanPolicy,
+    bare_entities: bool,
+    fingerprint: bool,
+    fill_defaults: bool,
+    entity_budget: Option<usize>,
+    spawn_disabled: bool,
+    persist_marker: bool,
+    retain_buffers: bool,
+    format_layout: FormatLayout,
+    deterministic: bool,
+    save_stats: bool,
+    debug_dump: Option<Cow<'static, str>>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Default for SaloConfig<M> {
+    fn default() -> Self {
+        Self {
+            root: None,
+            min_save_interval: None,
+            preserve_unknown: false,
+            conflict_policy: ConflictPolicy::Panic,
+            marker_propagation: MarkerPropagation::None,
+            orphan_policy: OrphanPolicy::Panic,
+            bare_entities: false,
+            fingerprint: false,
+            fill_defaults: false,
+            entity_budget: None,
+            spawn_disabled: false,
+            persist_marker: false,
+            retain_buffers: false,
+            format_layout: FormatLayout::TypeMajor,
+            deterministic: false,
+            save_stats: false,
+            debug_dump: None,
+            p: PhantomData,
+        }
+    }
+}
+
+impl<M: Marker> SaloConfig<M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Place all paths under a logical root, i.e. `Players::John` becomes `level1::Players::John`.
+    ///
+    /// The root is prepended to the paths of both the world and the save file,
+    /// so records saved under a different root will never match entities in this one.
+    pub fn with_root(mut self, root: impl Into<Cow<'static, str>>) -> Self {
+        self.root = Some(root.into());
+        self
+    }
+
+    pub fn set_root(&mut self, root: impl Into<Cow<'static, str>>) {
+        self.root = Some(root.into());
+    }
+
+    pub fn clear_root(&mut self) {
+        self.root = None;
+    }
+
+    pub fn root(&self) -> Option<&str> {
+        self.root.as_deref()
+    }
+
+    /// Drop saves requested sooner than this interval after the last successful save,
+    /// a [`SaveThrottled`] event is sent instead.
+    ///
+    /// Only applies to the [`SaveLoadExtension`](crate::SaveLoadExtension) methods.
+    pub fn with_min_save_interval(mut self, interval: Duration) -> Self {
+        self.min_save_interval = Some(interval);
+        self
+    }
+
+    pub fn set_min_save_interval(&mut self, interval: Option<Duration>) {
+        self.min_save_interval = interval;
+    }
+
+    pub fn min_save_interval(&self) -> Option<Duration> {
+        self.min_save_interval
+    }
+
+    /// Keep records of unregistered types in [`UnknownComponents`](crate::UnknownComponents)
+    /// when loading, and write them back on the next save.
+    ///
+    /// Useful for round-tripping data from newer versions or mods.
+    pub fn with_preserve_unknown(mut self, preserve: bool) -> Self {
+        self.preserve_unknown = preserve;
+        self
+    }
+
+    pub fn set_preserve_unknown(&mut self, preserve: bool) {
+        self.preserve_unknown = preserve;
+    }
+
+    pub fn preserve_unknown(&self) -> bool {
+        self.preserve_unknown
+    }
+
+    /// How an entity with two names or two entities with the same path are handled.
+    /// Defaults to [`ConflictPolicy::Panic`].
+    pub fn with_conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.conflict_policy = policy;
+        self
+    }
+
+    pub fn set_conflict_policy(&mut self, policy: ConflictPolicy) {
+        self.conflict_policy = policy;
+    }
+
+    pub fn conflict_policy(&self) -> ConflictPolicy {
+        self.conflict_policy
+    }
+
+    /// Save descendants of marked entities with [`MarkerPropagation::Descendants`].
+    /// Defaults to [`MarkerPropagation::None`], has no effect on [`All`](crate::All).
+    ///
+    /// Descendants are collected at the start of each save and load,
+    /// change tracking in [`SaveState`](crate::SaveState) only covers marked entities.
+    pub fn with_marker_propagation(mut self, propagation: MarkerPropagation) -> Self {
+        self.marker_propagation = propagation;
+        self
+    }
+
+    pub fn set_marker_propagation(&mut self, propagation: MarkerPropagation) {
+        self.marker_propagation = propagation;
+    }
+
+    pub fn marker_propagation(&self) -> MarkerPropagation {
+        self.marker_propagation
+    }
+
+    /// How a serialized entity whose parent is neither named nor serialized is handled,
+    /// i.e. a marked child of an unmarked parent. Defaults to [`OrphanPolicy::Panic`].
+    pub fn with_orphan_policy(mut self, policy: OrphanPolicy) -> Self {
+        self.orphan_policy = policy;
+        self
+    }
+
+    pub fn set_orphan_policy(&mut self, policy: OrphanPolicy) {
+        self.orphan_policy = policy;
+    }
+
+    pub fn orphan_policy(&self) -> OrphanPolicy {
+        self.orphan_policy
+    }
+
+    /// Write entities with a [`PathName`](crate::PathName) but no serialized components
+    /// as records of type [`BARE_ENTITY`](crate::BARE_ENTITY) with a path, parent and no value,
+    /// so structural entities round trip. Not written in delta saves.
+    ///
+    /// Bare entity records are always accepted when loading.
+    pub fn with_bare_entities(mut self, bare: bool) -> Self {
+        self.bare_entities = bare;
+        self
+    }
+
+    pub fn set_bare_entities(&mut self, bare: bool) {
+        self.bare_entities = bare;
+    }
+
+    pub fn bare_entities(&self) -> bool {
+        self.bare_entities
+    }
+
+    /// Write the names and versions of registered types in the save, when loading the save
+    /// changes since are listed in [`RegistryChanges`](crate::RegistryChanges).
+    pub fn with_fingerprint(mut self, fingerprint: bool) -> Self {
+        self.fingerprint = fingerprint;
+        self
+    }
+
+    pub fn set_fingerprint(&mut self, fingerprint: bool) {
+        self.fingerprint = fingerprint;
+    }
+
+    pub fn fingerprint(&self) -> bool {
+        self.fingerprint
+    }
+
+    /// Measure the serialized size of records of each type after each save,
+    /// for [`save_stats`](crate::SaveLoadExtension::save_stats) and more precise
+    /// [`estimate_save_size`](crate::SaveLoadExtension::estimate_save_size).
+    ///
+    /// Serializes every record a second time, disabled by default.
+    pub fn with_save_stats(mut self, save_stats: bool) -> Self {
+        self.save_stats = save_stats;
+        self
+    }
+
+    pub fn set_save_stats(&mut self, save_stats: bool) {
+        self.save_stats = save_stats;
+    }
+
+    pub fn save_stats(&self) -> bool {
+        self.save_stats
+    }
+
+    /// On full loads, insert [`SaveLoad::on_missing`](crate::SaveLoad::on_missing) values
+    /// of types with no records in the save, i.e. types added since the save was written.
+    ///
+    /// Components are inserted on loaded entities with a path that do not have them,
+    /// if the entities have records of a type in [`SaveLoad::fills`](crate::SaveLoad::fills).
+    pub fn with_fill_defaults(mut self, fill: bool) -> Self {
+        self.fill_defaults = fill;
+        self
+    }
+
+    pub fn set_fill_defaults(&mut self, fill: bool) {
+        self.fill_defaults = fill;
+    }
+
+    pub fn fill_defaults(&self) -> bool {
+        self.fill_defaults
+    }
+
+    /// Spawn at most this many entities per load, records of entities over the budget
+    /// are kept in [`PendingLoad`](crate::PendingLoad) and loaded by
+    /// [`load_pending`](crate::SaveLoadExtension::load_pending),
+    /// which the plugin runs once per frame with `bevy_app`.
+    ///
+    /// Entities referenced by loaded records, i.e. parents, are always spawned.
+    pub fn with_entity_budget(mut self, budget: usize) -> Self {
+        self.entity_budget = Some(budget);
+        self
+    }
+
+    pub fn set_entity_budget(&mut self, budget: Option<usize>) {
+        self.entity_budget = budget;
+    }
+
+    pub fn entity_budget(&self) -> Option<usize> {
+        self.entity_budget
+    }
+
+    /// Spawn entities of a load with [`SaloDisabled`](crate::SaloDisabled), removed after
+    /// post load systems once nothing is left in [`PendingLoad`](crate::PendingLoad),
+    /// so partially loaded content is never shown.
+    ///
+    /// Entities saved as disabled stay disabled.
+    pub fn with_spawn_disabled(mut self, disabled: bool) -> Self {
+        self.spawn_disabled = disabled;
+        self
+    }
+
+    pub fn set_spawn_disabled(&mut self, disabled: bool) {
+        self.spawn_disabled = disabled;
+    }
+
+    pub fn spawn_disabled(&self) -> bool {
+        self.spawn_disabled
+    }
+
+    /// Save the [`MarkerComponent`](crate::MarkerComponent) as a record of each marked entity,
+    /// and insert it on loaded entities, default is `false`.
+    ///
+    /// Without this the marker is structural only, loaded entities are not marked
+    /// unless the marker is registered as a type, and marker records of a save are dropped.
+    /// The marker is always in [`SaloRegistry`](crate::SaloRegistry) under its
+    /// [`type_name`](crate::MarkerComponent::type_name), so it is part of the fingerprint
+    /// and [`LoadPolicy`](crate::LoadPolicy) applies to it. Has no effect on [`All`](crate::All).
+    pub fn with_persist_marker(mut self, persist: bool) -> Self {
+        self.persist_marker = persist;
+        self
+    }
+
+    pub fn set_persist_marker(&mut self, persist: bool) {
+        self.persist_marker = persist;
+    }
+
+    pub fn persist_marker(&self) -> bool {
+        self.persist_marker
+    }
+
+    /// Keep `PathNames`, the serialization contexts and inputs in the world after each run,
+    /// i.e. for inspecting them while debugging, default is `false`.
+    ///
+    /// Otherwise they are removed by the [`Teardown`](crate::schedules::Teardown) set.
+    pub fn with_retain_buffers(mut self, retain: bool) -> Self {
+        self.retain_buffers = retain;
+        self
+    }
+
+    pub fn set_retain_buffers(&mut self, retain: bool) {
+        self.retain_buffers = retain;
+    }
+
+    pub fn retain_buffers(&self) -> bool {
+        self.retain_buffers
+    }
+
+    /// How records are grouped in the save, see [`FormatLayout`].
+    /// Defaults to [`FormatLayout::TypeMajor`], has no effect on archives.
+    pub fn with_format_layout(mut self, layout: FormatLayout) -> Self {
+        self.format_layout = layout;
+        self
+    }
+
+    pub fn set_format_layout(&mut self, layout: FormatLayout) {
+        self.format_layout = layout;
+    }
+
+    pub fn format_layout(&self) -> FormatLayout {
+        self.format_layout
+    }
+
+    /// Sort records of each type by path before writing, default is `false`.
+    ///
+    /// Types are always written in order of their names, with this saving the same world
+    /// twice writes the same output, i.e. for content hashing or keeping saves under version control.
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+    }
+
+    pub fn deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// In debug builds, write a pretty json copy of each file save to `template`,
+    /// where `{path}` is replaced by the path of the save, i.e. `"{path}.debug.json"`.
+    ///
+    /// Intended for binary saves, skipped with a [`SaveEncryptionKey`](crate::SaveEncryptionKey).
+    /// Values of formats that are not self-describing, like postcard, are written as hex strings.
+    /// Failures to write the copy are logged and do not fail the save.
+    pub fn with_mirror_debug_dump(mut self, template: impl Into<Cow<'static, str>>) -> Self {
+        self.debug_dump = Some(template.into());
+        self
+    }
+
+    pub fn set_mirror_debug_dump(&mut self, template: Option<Cow<'static, str>>) {
+        self.debug_dump = template;
+    }
+
+    pub fn mirror_debug_dump(&self) -> Option<&str> {
+        self.debug_dump.as_deref()
+    }
+
+    /// Path of the debug dump of a save, always `None` in release builds.
+    #[cfg(feature="fs")]
+    pub(crate) fn debug_dump_path(&self, file: &str) -> Option<String> {
+        match &self.debug_dump {
+            Some(template) if cfg!(debug_assertions) => Some(template.replace("{path}", file)),
+            _ => None,
+        }
+    }
+
+    /// Apply the root to a path.
+    pub(crate) fn rooted(&self, mut path: Vec<SmolStr>) -> Vec<SmolStr> {
+        if let Some(root) = &self.root {
+            path.splice(0..0, split_path(root));
+        }
+        path
+    }
+}
+
+/// Event sent when a save is dropped due to [`SaloConfig::with_min_save_interval`].
+///
+/// Registered automatically with `bevy_app`, otherwise `Events<SaveThrottled<M>>`
+/// needs to be updated manually.
+#[derive(Debug)]
+pub struct SaveThrottled<M: Marker> {
+    /// Time since the last save.
+    pub elapsed: Duration,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Event for SaveThrottled<M> {}
+
+/// Time of the last save.
+#[derive(Debug, Resource)]
+pub(crate) struct SaveThrottle<M: Marker>(Option<Instant>, PhantomData<M>);
+
+impl<M: Marker> Default for SaveThrottle<M> {
+    fn default() -> Self {
+        SaveThrottle(None, PhantomData)
+    }
+}
+
+/// Returns false and sends [`SaveThrottled`] if the last successful save is too recent.
+pub(crate) fn check_throttle<M: Marker>(world: &mut World) -> bool {
+    // Anonymized exports are not throttled and do not delay the next save.
+    if world.contains_resource::<AnonymizePolicy<M>>() {
+        return true;
+    }
+    let now = Instant::now();
+    let interval = world.get_resource::<SaloConfig<M>>().and_then(|c| c.min_save_interval);
+    let last = world.get_resource::<SaveThrottle<M>>().and_then(|t| t.0);
+    if let (Some(interval), Some(last)) = (interval, last) {
+        let elapsed = now.duration_since(last);
+        if elapsed < interval {
+            world.send_event(SaveThrottled::<M> { elapsed, p: PhantomData });
+            return false;
+        }
+    }
+    true
+}
+
+/// Record the time of a successful save, failed saves do not delay the next one.
+pub(crate) fn stamp_throttle<M: Marker>(world: &mut World) {
+    if world.contains_resource::<AnonymizePolicy<M>>() {
+        return;
+    }
+    world.insert_resource(SaveThrottle::<M>(Some(Instant::now()), PhantomData));
+}
+
+/// Progress of a save or load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaloEventKind {
+    SaveStarted,
+    /// Records of a type are serialized.
+    TypeSerialized {
+        name: Cow<'static, str>,
+        count: usize,
+    },
+    SaveFinished,
+    /// The save failed, errors are in [`SaloErrors`](crate::SaloErrors).
+    SaveFailed,
+    LoadStarted,
+    /// Records of a type are deserialized.
+    TypeDeserialized {
+        name: Cow<'static, str>,
+        count: usize,
+    },
+    LoadFinished,
+    /// The load failed, errors are in [`SaloErrors`](crate::SaloErrors).
+    LoadFailed,
+    /// A conflict resolved by [`ConflictPolicy::FirstWins`] or [`ConflictPolicy::LastWins`].
+    Conflict(PathConflict),
+    /// A record of an orphaned entity handled by [`OrphanPolicy::TreatAsRoot`] or [`OrphanPolicy::Skip`].
+    Orphan {
+        name: Cow<'static, str>,
+        entity: Entity,
+    },
+}
+
+/// Event sent during save and load, unique per marker, i.e. for loading screens.
+///
+/// Registered automatically with `bevy_app`, otherwise `Events<SaloEvent<M>>`
+/// needs to be updated manually.
+#[derive(Debug)]
+pub struct SaloEvent<M: Marker> {
+    pub kind: SaloEventKind,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> SaloEvent<M> {
+    pub fn new(kind: SaloEventKind) -> Self {
+        Self { kind, p: PhantomData }
+    }
+}
+
+impl<M: Marker> Event for SaloEvent<M> {}
@@ -0,0 +1,56 @@
+use std::any::type_name;
+use std::collections::HashMap;
+
+use crate::methods::SerializationMethod;
+use crate::saveload::{Document, DocumentRef, PathedValue};
+use crate::SaloError;
+
+/// Re-encode a save from method `F` to method `T` without a `World`,
+/// i.e. to pretty print a save, or to convert between json and ron in migration scripts.
+///
+/// Records, metadata, delta headers, fingerprints, versions, aliases, tombstones,
+/// attributes and the layout are kept.
+/// Section signatures are dropped since they depend on the encoding.
+///
+/// Values are converted with `T::serialize_value`, which is lossless between self-describing
+/// formats like json and ron. Formats that are not self-describing, like postcard,
+/// cannot be decoded without their types and fail with an error.
+///
+/// ```
+/// # use bevy_salo::{convert, methods::SerdeJson};
+/// let save = br#"{"unit":[{"path":"John","value":{"hp":1}}]}"#;
+/// let pretty = convert::<SerdeJson<false>, SerdeJson<true>>(save).unwrap();
+/// assert!(pretty.starts_with(b"{\n"));
+/// ```
+pub fn convert<F: SerializationMethod, T: SerializationMethod>(bytes: &[u8]) -> Result<Vec<u8>, SaloError> {
+    if !F::SELF_DESCRIBING {
+        return Err(SaloError::Deserialization(anyhow::anyhow!("Format {} is not self-describing.", type_name::<F>())));
+    }
+    if !T::SELF_DESCRIBING {
+        return Err(SaloError::Serialization(anyhow::anyhow!("Format {} is not self-describing.", type_name::<T>())));
+    }
+    let document: Document<F::Value> = F::deserialize(bytes).map_err(SaloError::Deserialization)?;
+    let metadata = document.metadata.as_ref().map(T::serialize_value).transpose()
+        .map_err(SaloError::Serialization)?;
+    let components = document.components.into_iter().map(|(name, records)| {
+        let records = records.into_iter().map(|PathedValue { parent, path, value }| Ok(PathedValue {
+            parent,
+            path,
+            value: T::serialize_value(&value)?,
+        })).collect::<anyhow::Result<Vec<_>>>()?;
+        Ok((name, records))
+    }).collect::<anyhow::Result<HashMap<_, _>>>().map_err(SaloError::Serialization)?;
+    T::serialize_bytes(&DocumentRef {
+        metadata: metadata.as_ref(),
+        delta: document.delta.as_ref(),
+        signatures: None,
+        fingerprint: document.fingerprint.as_ref(),
+        versions: document.versions.as_ref(),
+        aliases: document.aliases.as_ref(),
+        tombstones: document.tombstones.as_ref(),
+        attributes: document.attributes.as_ref(),
+        layout: document.layout,
+        components: &components,
+        inline: None,
+    }).map_err(SaloError::Serialization)
+}
@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::With;
+use bevy_ecs::system::{Commands, Query, Res};
+
+use crate::{DeserializeContext, Marker, SaloConfig, SaveLoad, SaveLoadRes};
+
+/// Returns true if defaults are filled and the save has no records of a type.
+fn is_missing<M: Marker>(config: &SaloConfig<M>, ctx: &DeserializeContext<M>, name: &str) -> bool {
+    config.fill_defaults() && ctx.delta.is_none() && !ctx.counts.iter().any(|(n, _)| n == name)
+}
+
+/// Insert [`SaveLoad::on_missing`] on loaded entities with a path and records of a type in
+/// [`SaveLoad::fills`] if the save has no records of `T`.
+pub(crate) fn fill_component<M: Marker, T: SaveLoad>(
+    mut commands: Commands,
+    config: Res<SaloConfig<M>>,
+    ctx: Res<DeserializeContext<M>>,
+    existing: Query<(), With<T>>,
+) {
+    if !is_missing(&config, &ctx, &T::type_name()) {
+        return;
+    }
+    let Some(first) = T::on_missing() else { return };
+    let entities: HashSet<Entity> = T::fills().iter()
+        .filter_map(|name| ctx.loaded.get(*name))
+        .flatten()
+        .filter_map(|path| ctx.path_map.get(&ctx.remapped(path)).copied())
+        .filter(|entity| !existing.contains(*entity))
+        .collect();
+    // `T` is not `Clone`, later entities get a value of their own.
+    let mut first = Some(first);
+    for entity in entities {
+        if let Some(value) = first.take().or_else(T::on_missing) {
+            commands.entity(entity).insert(value);
+        }
+    }
+}
+
+/// Insert [`SaveLoadRes::on_missing`] if the save has no record of `T`.
+pub(crate) fn fill_resource<M: Marker, T: SaveLoadRes>(
+    mut commands: Commands,
+    config: Res<SaloConfig<M>>,
+    ctx: Res<DeserializeContext<M>>,
+) {
+    if !is_missing(&config, &ctx, &T::type_name()) {
+        return;
+    }
+    if let Some(value) = T::on_missing() {
+        commands.insert_resource(value);
+    }
+}
@@ -0,0 +1,146 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::event::Events;
+use bevy_ecs::system::Resource;
+use bevy_ecs::world::World;
+use bevy_utils::get_short_name;
+use smol_str::SmolStr;
+
+use crate::saveload::{join_path, split_path};
+use crate::{ConflictPolicy, DeserializeContext, EntityPath, Marker, PathConflict, PathNames, SaloConfig, SaloError, SaloErrors, SaloEvent, SaloEventKind};
+
+/// An entity involved in a [`PathCollision`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityDiagnostic {
+    pub entity: Entity,
+    /// Short type names of components on the entity.
+    pub components: Vec<String>,
+    /// Ancestors from the root to the entity itself, with their names if any.
+    pub hierarchy: Vec<(Entity, Option<String>)>,
+}
+
+impl EntityDiagnostic {
+    fn new<M: Marker>(world: &World, names: &PathNames<M>, entity: Entity) -> Self {
+        let mut components: Vec<_> = match world.get_entity(entity) {
+            Some(_) => world.inspect_entity(entity).iter().map(|c| get_short_name(c.name())).collect(),
+            None => Vec::new(),
+        };
+        components.sort();
+        let mut hierarchy = vec![(entity, names.get(entity).map(|n| n.to_owned()))];
+        #[cfg(feature="hierarchy")]
+        {
+            let mut current = entity;
+            while let Some(parent) = world.get::<bevy_hierarchy::Parent>(current) {
+                current = parent.get();
+                hierarchy.push((current, names.get(current).map(|n| n.to_owned())));
+            }
+        }
+        hierarchy.reverse();
+        Self { entity, components, hierarchy }
+    }
+}
+
+impl Display for EntityDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("(root)")?;
+        for (_, name) in &self.hierarchy {
+            match name {
+                Some(name) => write!(f, "::{}", name)?,
+                None => f.write_str("::(unnamed)")?,
+            }
+        }
+        write!(f, " {:?} [{}]", self.entity, self.components.join(", "))
+    }
+}
+
+/// A [`PathConflict`] with details of the entities involved, for tooling and error messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathCollision {
+    pub conflict: PathConflict,
+    /// The entity, or both entities of a duplicate path.
+    pub entities: Vec<EntityDiagnostic>,
+    /// An unused path for the second entity of a duplicate path.
+    pub suggestion: Option<String>,
+}
+
+impl Display for PathCollision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.conflict)?;
+        for entity in &self.entities {
+            write!(f, "\n  {}", entity)?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "\n  help: rename one of them, i.e. to `{}`.", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Collisions found in the last save or load, unique per marker, inserted by the plugin.
+#[derive(Debug, Resource)]
+pub struct PathCollisions<M: Marker>(Vec<PathCollision>, PhantomData<M>);
+
+impl<M: Marker> Default for PathCollisions<M> {
+    fn default() -> Self {
+        PathCollisions(Vec::new(), PhantomData)
+    }
+}
+
+impl<M: Marker> PathCollisions<M> {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PathCollision> {
+        self.0.iter()
+    }
+}
+
+/// Find the first unused path by appending a number to the name.
+fn suggest(path: &str, ctx: Option<&DeserializeContext<impl Marker>>) -> Option<String> {
+    let mut segments = split_path(path);
+    let name = segments.pop()?;
+    (2..).map(|i| {
+        let mut path = segments.clone();
+        path.push(SmolStr::new(format!("{}_{}", name, i)));
+        path
+    }).find(|path| ctx.map_or(true, |ctx| !ctx.path_map.contains_key(&EntityPath::Path(path.clone()))))
+        .map(|path| join_path(&path))
+}
+
+/// Report conflicts resolved while building paths.
+pub(crate) fn report_conflicts<M: Marker>(world: &mut World) {
+    let mut conflicts = std::mem::take(&mut world.resource_mut::<PathNames<M>>().conflicts);
+    if let Some(mut ctx) = world.get_resource_mut::<DeserializeContext<M>>() {
+        conflicts.append(&mut ctx.conflicts);
+    }
+    let names = world.resource::<PathNames<M>>();
+    let ctx = world.get_resource::<DeserializeContext<M>>();
+    let collisions: Vec<_> = conflicts.into_iter().map(|conflict| {
+        let (entities, suggestion) = match &conflict {
+            PathConflict::Name { entity, .. } => (vec![*entity], None),
+            PathConflict::Path { path, first, second } => (vec![*first, *second], suggest(path, ctx)),
+        };
+        PathCollision {
+            entities: entities.into_iter().map(|e| EntityDiagnostic::new(world, names, e)).collect(),
+            conflict,
+            suggestion,
+        }
+    }).collect();
+    let policy = world.resource::<SaloConfig<M>>().conflict_policy();
+    if policy == ConflictPolicy::Panic && !collisions.is_empty() {
+        let messages: Vec<_> = collisions.iter().map(|c| c.to_string()).collect();
+        panic!("{}", messages.join("\n"));
+    }
+    for collision in &collisions {
+        match policy {
+            ConflictPolicy::Error => world.resource_mut::<SaloErrors<M>>()
+                .push(SaloError::Conflict(collision.conflict.clone())),
+            _ => world.resource_mut::<Events<SaloEvent<M>>>()
+                .send(SaloEvent::new(SaloEventKind::Conflict(collision.conflict.clone()))),
+        }
+    }
+    world.insert_resource(PathCollisions::<M>(collisions, PhantomData));
+}
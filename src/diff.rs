@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::methods::SerializationMethod;
+use crate::saveload::{Document, EntityParent, ValueOf};
+use crate::{EntityPath, Marker, SaloError};
+
+/// Kind of a difference in a [`SaveDiff`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiffKind {
+    /// Only in the second save.
+    Added,
+    /// Only in the first save.
+    Removed,
+    /// In both saves with a different value or parent.
+    Changed,
+}
+
+impl Display for DiffKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DiffKind::Added => "+",
+            DiffKind::Removed => "-",
+            DiffKind::Changed => "~",
+        })
+    }
+}
+
+/// A component that differs between two saves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentDiff {
+    pub path: EntityPath,
+    pub type_name: String,
+    pub kind: DiffKind,
+}
+
+/// Differences between two saves keyed by path, created by [`diff`].
+///
+/// Entries are sorted by path then type name. Records without a path,
+/// which only appear in handwritten saves, are not compared.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SaveDiff {
+    /// Entities only in the second save.
+    pub added: Vec<EntityPath>,
+    /// Entities only in the first save.
+    pub removed: Vec<EntityPath>,
+    /// Components added, removed or changed, including those of added and removed entities.
+    pub components: Vec<ComponentDiff>,
+}
+
+impl SaveDiff {
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Differences of an entity.
+    pub fn get<'t>(&'t self, path: &'t EntityPath) -> impl Iterator<Item = &'t ComponentDiff> {
+        self.components.iter().filter(move |c| &c.path == path)
+    }
+}
+
+impl Display for SaveDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for path in &self.added {
+            writeln!(f, "{} {}", DiffKind::Added, path)?;
+        }
+        for path in &self.removed {
+            writeln!(f, "{} {}", DiffKind::Removed, path)?;
+        }
+        for component in &self.components {
+            writeln!(f, "{} {} [{}]", component.kind, component.path, component.type_name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Records of a save by path then type name, values are compared as serialized bytes.
+type Index<'t> = HashMap<&'t EntityPath, HashMap<&'t str, (&'t EntityParent, Vec<u8>)>>;
+
+fn index<M: Marker>(document: &Document<ValueOf<M>>) -> anyhow::Result<Index<'_>> {
+    let mut index = Index::new();
+    for (type_name, records) in &document.components {
+        for record in records.iter().filter(|r| !r.path.is_unique()) {
+            let value = M::Method::serialize_bytes(&record.value)?;
+            index.entry(&record.path).or_default().insert(type_name.as_str(), (&record.parent, value));
+        }
+    }
+    Ok(index)
+}
+
+fn sorted(mut paths: Vec<EntityPath>) -> Vec<EntityPath> {
+    paths.sort_by_cached_key(|p| p.to_string());
+    paths
+}
+
+/// Compare two saves of marker `M`, i.e. to find out why a roundtrip changed the save.
+///
+/// Saves are parsed with `M`'s serialization method, headers such as metadata are ignored.
+/// ```
+/// # use bevy_salo::{diff, methods::SerdeJson};
+/// # type All = bevy_salo::All<SerdeJson<false>>;
+/// let a = br#"{"unit":[{"path":"John","value":{"hp":1}}]}"#;
+/// let b = br#"{"unit":[{"path":"John","value":{"hp":2}}]}"#;
+/// assert_eq!(diff::<All>(a, b).unwrap().to_string(), "~ John [unit]\n");
+/// ```
+pub fn diff<M: Marker>(a: &[u8], b: &[u8]) -> Result<SaveDiff, SaloError> {
+    let a: Document<ValueOf<M>> = M::Method::deserialize(a).map_err(SaloError::Deserialization)?;
+    let b: Document<ValueOf<M>> = M::Method::deserialize(b).map_err(SaloError::Deserialization)?;
+    let a = index::<M>(&a).map_err(SaloError::Deserialization)?;
+    let b = index::<M>(&b).map_err(SaloError::Deserialization)?;
+    let empty = HashMap::new();
+    let mut result = SaveDiff {
+        added: sorted(b.keys().filter(|p| !a.contains_key(*p)).map(|p| (*p).clone()).collect()),
+        removed: sorted(a.keys().filter(|p| !b.contains_key(*p)).map(|p| (*p).clone()).collect()),
+        components: Vec::new(),
+    };
+    for path in a.keys().chain(b.keys().filter(|p| !a.contains_key(*p))) {
+        let before = a.get(path).unwrap_or(&empty);
+        let after = b.get(path).unwrap_or(&empty);
+        let mut push = |type_name: &str, kind| result.components.push(ComponentDiff {
+            path: (*path).clone(),
+            type_name: type_name.to_owned(),
+            kind,
+        });
+        for (type_name, record) in before {
+            match after.get(type_name) {
+                None => push(type_name, DiffKind::Removed),
+                Some(other) if other != record => push(type_name, DiffKind::Changed),
+                Some(_) => (),
+            }
+        }
+        for type_name in after.keys().filter(|t| !before.contains_key(*t)) {
+            push(type_name, DiffKind::Added);
+        }
+    }
+    result.components.sort_by_cached_key(|c| (c.path.to_string(), c.type_name.clone()));
+    Ok(result)
+}
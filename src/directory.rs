@@ -0,0 +1,115 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use bevy_ecs::system::Resource;
+
+/// A directory of save slots on disk.
+///
+/// Slot names are sanitized, so user input can be used directly.
+#[derive(Debug, Clone, Resource)]
+pub struct SaveDirectory {
+    root: PathBuf,
+    extension: String,
+}
+
+impl SaveDirectory {
+    /// Use a directory with the default extension `sav`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            extension: "sav".to_owned(),
+        }
+    }
+
+    /// Use the platform data directory, i.e. `~/.local/share/{app}/saves` on linux
+    /// or `%APPDATA%\{app}\saves` on windows.
+    #[cfg(feature="dirs")]
+    pub fn platform_default(app: &str) -> Option<Self> {
+        Some(Self::new(dirs::data_dir()?.join(sanitize(app)?).join("saves")))
+    }
+
+    pub fn with_extension(mut self, extension: impl Into<String>) -> Self {
+        self.extension = extension.into();
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn extension(&self) -> &str {
+        &self.extension
+    }
+
+    /// Path of a slot, fails if the name is empty after sanitization.
+    pub fn path(&self, slot: &str) -> io::Result<PathBuf> {
+        match sanitize(slot) {
+            Some(name) => Ok(self.root.join(name).with_extension(&self.extension)),
+            None => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid slot name {:?}.", slot))),
+        }
+    }
+
+    /// Path of a slot as a string, for use in [`SaveTarget::File`](crate::SaveTarget::File) and [`LoadSource::File`](crate::LoadSource::File).
+    ///
+    /// This also creates the directory if missing.
+    pub fn file(&self, slot: &str) -> io::Result<String> {
+        std::fs::create_dir_all(&self.root)?;
+        let path = self.path(slot)?;
+        path.to_str().map(|s| s.to_owned()).ok_or_else(||
+            io::Error::new(io::ErrorKind::InvalidInput, format!("Non utf-8 path {:?}.", path))
+        )
+    }
+
+    pub fn exists(&self, slot: &str) -> bool {
+        self.path(slot).map(|p| p.is_file()).unwrap_or(false)
+    }
+
+    /// List slot names, sorted. Returns an empty list if the directory does not exist.
+    pub fn list(&self) -> io::Result<Vec<String>> {
+        let entries = match std::fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut result = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some(&self.extension) {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                result.push(name.to_owned());
+            }
+        }
+        result.sort();
+        Ok(result)
+    }
+
+    pub fn delete(&self, slot: &str) -> io::Result<()> {
+        std::fs::remove_file(self.path(slot)?)
+    }
+
+    /// Rename a slot, fails if the target exists.
+    pub fn rename(&self, from: &str, to: &str) -> io::Result<()> {
+        let target = self.path(to)?;
+        if target.exists() {
+            return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("Slot {:?} already exists.", to)));
+        }
+        std::fs::rename(self.path(from)?, target)
+    }
+}
+
+/// Replace characters unsafe in file names with `_`,
+/// returns `None` if no alphanumeric characters remain.
+pub(crate) fn sanitize(name: &str) -> Option<String> {
+    let name: String = name.trim().chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '-' | '_' | ' ') { c } else { '_' })
+        .take(64)
+        .collect();
+    let name = name.trim();
+    if !name.chars().any(|c| c.is_alphanumeric()) {
+        None
+    } else {
+        Some(name.to_owned())
+    }
+}
@@ -0,0 +1,67 @@
+use std::borrow::Cow;
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::With;
+use bevy_ecs::system::{Commands, Local, Query, ResMut, SystemParamItem};
+
+use crate::{DeserializeContext, EntityPath, Marker, SaveLoad};
+
+/// Marks an entity as disabled, systems that render or simulate
+/// loaded content should skip entities with this component.
+///
+/// Saved as a record of type `"(disabled)"` if registered,
+/// inserted on entities spawned by a load with [`SaloConfig::with_spawn_disabled`](crate::SaloConfig::with_spawn_disabled).
+#[derive(Debug, Clone, Copy, Default, Component)]
+pub struct SaloDisabled;
+
+/// Entities disabled only until the load finishes.
+#[derive(Debug, Component)]
+pub(crate) struct SpawnedDisabled;
+
+impl SaveLoad for SaloDisabled {
+    type Ser<'ser> = ();
+    type De = ();
+    type Context<'w, 's> = ();
+    type ContextMut<'w, 's> = ();
+
+    fn to_serializable<'t>(&'t self,
+        _: Entity,
+        _: impl Fn(Entity) -> EntityPath,
+        _: &'t SystemParamItem<Self::Context<'_, '_>>
+    ) -> Self::Ser<'t> {}
+
+    fn from_deserialize(
+        _: Self::De,
+        commands: &mut Commands,
+        self_entity: Entity,
+        _: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+        _: &mut SystemParamItem<Self::ContextMut<'_, '_>>
+    ) -> Self {
+        // Saved as disabled, stays disabled after the load.
+        commands.entity(self_entity).remove::<SpawnedDisabled>();
+        SaloDisabled
+    }
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("(disabled)")
+    }
+}
+
+/// Enable entities spawned disabled once nothing is pending, after post load systems.
+pub(crate) fn enable_loaded<M: Marker>(
+    mut commands: Commands,
+    mut ctx: ResMut<DeserializeContext<M>>,
+    mut disabled: Local<Vec<Entity>>,
+    spawned: Query<(), With<SpawnedDisabled>>,
+) {
+    disabled.append(&mut ctx.disabled);
+    if ctx.pending {
+        return;
+    }
+    for entity in disabled.drain(..) {
+        if spawned.contains(entity) {
+            commands.entity(entity).remove::<(SaloDisabled, SpawnedDisabled)>();
+        }
+    }
+}
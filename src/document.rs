@@ -0,0 +1,206 @@
+use std::collections::{BTreeMap, HashSet};
+use std::marker::PhantomData;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::Resource;
+use bevy_ecs::world::World;
+use bevy_utils::tracing::error;
+
+use crate::schedules::{SaveSchedule, LoadSchedule, ResetSchedule};
+use crate::saveload::ValueOf;
+use crate::{first_error, LoadPolicy, Marker, PathRemap, SaloError, SaveFilter, TypeFilter};
+
+/// Resource that contains the serialized value output, used by in-memory saves.
+#[derive(Debug, Resource, Default)]
+pub(crate) struct ValueOutput<M: Marker>(pub(crate) Option<ValueOf<M>>, PhantomData<M>);
+
+/// Resource that contains the serialized value input, used by in-memory loads.
+#[derive(Debug, Resource)]
+pub(crate) struct ValueInput<M: Marker>(pub(crate) ValueOf<M>, PhantomData<M>);
+
+/// Run the save schedule and return the document as a value.
+pub(crate) fn save_value<M: Marker>(world: &mut World) -> Result<ValueOf<M>, SaloError> {
+    #[cfg(feature="fs")]
+    world.remove_resource::<crate::FileOutput<M>>();
+    world.remove_resource::<crate::BytesOutput<M>>();
+    world.remove_resource::<crate::StringOutput<M>>();
+    world.init_resource::<ValueOutput<M>>();
+    world.run_schedule(SaveSchedule::with_marker::<M>());
+    let result = world.remove_resource::<ValueOutput<M>>().and_then(|v| v.0);
+    first_error::<M>(world)?;
+    result.ok_or_else(|| SaloError::Serialization(anyhow::anyhow!("Output not found.")))
+}
+
+/// Run the load schedule with a document as a value.
+pub(crate) fn load_value<M: Marker>(world: &mut World, value: ValueOf<M>) -> Result<(), SaloError> {
+    #[cfg(feature="fs")]
+    world.remove_resource::<crate::FileInput<M>>();
+    world.remove_resource::<crate::BytesInput<M>>();
+    world.remove_resource::<crate::StringInput<M>>();
+    world.insert_resource(ValueInput::<M>(value, PhantomData));
+    world.run_schedule(LoadSchedule::with_marker::<M>());
+    world.remove_resource::<ValueInput<M>>();
+    first_error::<M>(world)
+}
+
+/// An in-memory save of a marker, not encoded to bytes.
+///
+/// Created by [`snapshot_then_reset`](crate::SaveLoadExtension::snapshot_then_reset)
+/// and applied with [`load_document`](crate::SaveLoadExtension::load_document),
+/// possibly in a different world.
+#[derive(Debug)]
+pub struct SaloDocument<M: Marker>(ValueOf<M>, PhantomData<M>);
+
+impl<M: Marker> Clone for SaloDocument<M> {
+    fn clone(&self) -> Self {
+        SaloDocument(self.0.clone(), PhantomData)
+    }
+}
+
+impl<M: Marker> SaloDocument<M> {
+    pub fn from_value(value: ValueOf<M>) -> Self {
+        SaloDocument(value, PhantomData)
+    }
+
+    pub fn value(&self) -> &ValueOf<M> {
+        &self.0
+    }
+
+    pub fn into_value(self) -> ValueOf<M> {
+        self.0
+    }
+}
+
+pub(crate) fn snapshot_then_reset<M: Marker>(world: &mut World) -> Result<SaloDocument<M>, SaloError> {
+    let value = save_value::<M>(world)?;
+    world.run_schedule(ResetSchedule::with_marker::<M>());
+    Ok(SaloDocument(value, PhantomData))
+}
+
+/// Named in-memory snapshots of a marker, i.e. restore points for practice modes, inserted by the plugin.
+///
+/// Stored by [`store_snapshot`](crate::SaveLoadExtension::store_snapshot)
+/// and applied by [`restore_snapshot`](crate::SaveLoadExtension::restore_snapshot).
+#[derive(Debug, Resource)]
+pub struct SnapshotRegistry<M: Marker>(BTreeMap<String, SaloDocument<M>>);
+
+impl<M: Marker> Default for SnapshotRegistry<M> {
+    fn default() -> Self {
+        SnapshotRegistry(BTreeMap::new())
+    }
+}
+
+impl<M: Marker> SnapshotRegistry<M> {
+    /// Names of all snapshots, sorted.
+    pub fn list(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(|k| k.as_str())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SaloDocument<M>> {
+        self.0.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    /// Add a snapshot, replacing one with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, document: SaloDocument<M>) {
+        self.0.insert(name.into(), document);
+    }
+
+    /// Remove a snapshot, returns false if not found.
+    pub fn drop(&mut self, name: &str) -> bool {
+        self.0.remove(name).is_some()
+    }
+}
+
+/// Present while storing or restoring a snapshot, which does not change [`SaveState`](crate::SaveState).
+#[derive(Debug, Resource)]
+pub(crate) struct SnapshotScope<M: Marker>(PhantomData<M>);
+
+/// Run `f` with [`SnapshotScope`].
+fn snapshot_scope<M: Marker, T>(world: &mut World, f: impl FnOnce(&mut World) -> T) -> T {
+    world.insert_resource(SnapshotScope::<M>(PhantomData));
+    let result = f(world);
+    world.remove_resource::<SnapshotScope<M>>();
+    result
+}
+
+pub(crate) fn store_snapshot<M: Marker>(world: &mut World, name: &str) -> Result<(), SaloError> {
+    let value = snapshot_scope::<M, _>(world, save_value::<M>)?;
+    world.get_resource_or_insert_with(SnapshotRegistry::<M>::default)
+        .insert(name, SaloDocument(value, PhantomData));
+    Ok(())
+}
+
+/// Reset the world and load a snapshot, rolled back if the snapshot fails to load.
+pub(crate) fn restore_snapshot<M: Marker>(world: &mut World, name: &str) -> Result<(), SaloError> {
+    let value = world.get_resource::<SnapshotRegistry<M>>()
+        .and_then(|registry| registry.get(name))
+        .map(|document| document.value().clone())
+        .ok_or_else(|| SaloError::Deserialization(anyhow::anyhow!("Snapshot {:?} not found.", name)))?;
+    load_or_rollback::<M>(world, |world| snapshot_scope::<M, _>(world, |world| {
+        world.run_schedule(ResetSchedule::with_marker::<M>());
+        load_value::<M>(world, value)
+    }))
+}
+
+/// Entities that existed before a rolled back load, unnamed records of the
+/// rollback are loaded into them instead of spawning new entities.
+#[derive(Debug, Resource)]
+pub(crate) struct RollbackEntities<M: Marker>(pub(crate) Vec<Entity>, PhantomData<M>);
+
+/// Run `f` with a resource replaced, or removed if `resource` is `None`.
+fn replaced<R: Resource, T>(world: &mut World, resource: Option<R>, f: impl FnOnce(&mut World) -> T) -> T {
+    let prev = world.remove_resource::<R>();
+    if let Some(resource) = resource {
+        world.insert_resource(resource);
+    }
+    let result = f(world);
+    world.remove_resource::<R>();
+    if let Some(prev) = prev {
+        world.insert_resource(prev);
+    }
+    result
+}
+
+/// Detach and despawn entities spawned by a failed load.
+fn despawn_spawned(world: &mut World, existing: &HashSet<Entity>) {
+    let spawned: Vec<Entity> = world.iter_entities()
+        .map(|entity| entity.id())
+        .filter(|entity| !existing.contains(entity))
+        .collect();
+    #[cfg(feature="hierarchy")]
+    for entity in spawned.iter() {
+        use bevy_hierarchy::BuildWorldChildren;
+        world.entity_mut(*entity).remove_parent().clear_children();
+    }
+    for entity in spawned {
+        world.despawn(entity);
+    }
+}
+
+/// Save data of the marker in memory and run a load, if it fails despawn entities it spawned,
+/// reset the marker and load the saved data again.
+pub(crate) fn load_or_rollback<M: Marker>(world: &mut World, load: impl FnOnce(&mut World) -> Result<(), SaloError>) -> Result<(), SaloError> {
+    let existing: Vec<Entity> = world.iter_entities().map(|entity| entity.id()).collect();
+    // Filters and load settings are ignored, so all data is saved and loaded back as is.
+    let value = replaced::<SaveFilter<M>, _>(world, None, |world| replaced::<TypeFilter<M>, _>(world, None, |world| {
+        snapshot_scope::<M, _>(world, save_value::<M>)
+    }))?;
+    let Err(err) = load(world) else { return Ok(()) };
+    despawn_spawned(world, &existing.iter().copied().collect());
+    world.insert_resource(RollbackEntities::<M>(existing, PhantomData));
+    let restored = replaced(world, Some(LoadPolicy::<M>::new()), |world| replaced::<PathRemap<M>, _>(world, None, |world| {
+        snapshot_scope::<M, _>(world, |world| {
+            world.run_schedule(ResetSchedule::with_marker::<M>());
+            load_value::<M>(world, value)
+        })
+    }));
+    world.remove_resource::<RollbackEntities<M>>();
+    if let Err(e) = restored {
+        error!("Failed to roll back a load: {}", e);
+    }
+    Err(err)
+}
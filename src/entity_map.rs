@@ -0,0 +1,177 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Commands, Resource, SystemParamItem};
+use bevy_utils::get_short_name;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::{EntityPath, SaveLoad, SaveLoadRes};
+
+/// A `HashMap<Entity, T>` that can be saved, keys are converted to [`EntityPath`]s and back.
+///
+/// Can be registered directly as a component or a resource,
+/// or used as a field with [`SaloEntityMap::to_serializable`] and [`SaloEntityMap::from_deserialize`]
+/// in a custom [`SaveLoad`] implementation.
+///
+/// Keys not saved with the same marker are saved as entity IDs and spawned as new entities when loaded,
+/// same as any other entity reference.
+#[derive(Debug, Clone, PartialEq, Eq, Component, Resource)]
+pub struct SaloEntityMap<T: Send + Sync + 'static>(pub HashMap<Entity, T>);
+
+impl<T: Send + Sync + 'static> Default for SaloEntityMap<T> {
+    fn default() -> Self {
+        SaloEntityMap(HashMap::new())
+    }
+}
+
+impl<T: Send + Sync + 'static> Deref for SaloEntityMap<T> {
+    type Target = HashMap<Entity, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Send + Sync + 'static> DerefMut for SaloEntityMap<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Send + Sync + 'static> From<HashMap<Entity, T>> for SaloEntityMap<T> {
+    fn from(value: HashMap<Entity, T>) -> Self {
+        SaloEntityMap(value)
+    }
+}
+
+impl<T: Send + Sync + 'static> FromIterator<(Entity, T)> for SaloEntityMap<T> {
+    fn from_iter<I: IntoIterator<Item = (Entity, T)>>(iter: I) -> Self {
+        SaloEntityMap(iter.into_iter().collect())
+    }
+}
+
+impl<T: Send + Sync + 'static> SaloEntityMap<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convert keys to paths with the `path_fetcher` of `to_serializable`, sorted by path.
+    pub fn to_serializable(&self, path_fetcher: impl Fn(Entity) -> EntityPath) -> Vec<(EntityPath, &T)> {
+        let mut entries: Vec<_> = self.0.iter().map(|(entity, value)| (path_fetcher(*entity), value)).collect();
+        entries.sort_by_cached_key(|(path, _)| path.to_string());
+        entries
+    }
+
+    /// Convert paths back to entities with the `entity_fetcher` of `from_deserialize`.
+    ///
+    /// If multiple paths resolve to the same entity, the last one wins,
+    /// see [`try_from_deserialize`](Self::try_from_deserialize).
+    pub fn from_deserialize(
+        de: Vec<(EntityPath, T)>,
+        commands: &mut Commands,
+        mut entity_fetcher: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+    ) -> Self {
+        SaloEntityMap(de.into_iter().map(|(path, value)| (entity_fetcher(commands, &path), value)).collect())
+    }
+
+    /// Convert paths back to entities with the `entity_fetcher` of `from_deserialize`.
+    ///
+    /// Returns an error if multiple paths resolve to the same entity,
+    /// i.e. entities with duplicated paths when saved.
+    pub fn try_from_deserialize(
+        de: Vec<(EntityPath, T)>,
+        commands: &mut Commands,
+        mut entity_fetcher: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+    ) -> anyhow::Result<Self> {
+        let mut map = HashMap::with_capacity(de.len());
+        for (path, value) in de {
+            if map.insert(entity_fetcher(commands, &path), value).is_some() {
+                anyhow::bail!("Duplicate key {} in {}.", path, Self::type_name());
+            }
+        }
+        Ok(SaloEntityMap(map))
+    }
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Owned(format!("SaloEntityMap<{}>", get_short_name(std::any::type_name::<T>())))
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + Send + Sync + 'static> SaveLoad for SaloEntityMap<T> {
+    type Ser<'ser> = Vec<(EntityPath, &'ser T)>;
+    type De = Vec<(EntityPath, T)>;
+    type Context<'w, 's> = ();
+    type ContextMut<'w, 's> = ();
+
+    fn to_serializable<'t>(&'t self,
+        _: Entity,
+        path_fetcher: impl Fn(Entity) -> EntityPath,
+        _: &'t SystemParamItem<Self::Context<'_, '_>>
+    ) -> Self::Ser<'t> {
+        SaloEntityMap::to_serializable(self, path_fetcher)
+    }
+
+    fn from_deserialize(
+        de: Self::De,
+        commands: &mut Commands,
+        _: Entity,
+        entity_fetcher: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+        _: &mut SystemParamItem<Self::ContextMut<'_, '_>>
+    ) -> Self {
+        SaloEntityMap::from_deserialize(de, commands, entity_fetcher)
+    }
+
+    fn try_from_deserialize(
+        de: Self::De,
+        commands: &mut Commands,
+        _: Entity,
+        entity_fetcher: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+        _: &mut SystemParamItem<Self::ContextMut<'_, '_>>
+    ) -> anyhow::Result<Self> {
+        SaloEntityMap::try_from_deserialize(de, commands, entity_fetcher)
+    }
+
+    fn type_name() -> Cow<'static, str> {
+        SaloEntityMap::<T>::type_name()
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + Send + Sync + 'static> SaveLoadRes for SaloEntityMap<T> {
+    type Ser<'ser> = Vec<(EntityPath, &'ser T)>;
+    type De = Vec<(EntityPath, T)>;
+    type Context<'w, 's> = ();
+    type ContextMut<'w, 's> = ();
+
+    fn to_serializable<'t>(&'t self,
+        path_fetcher: impl Fn(Entity) -> EntityPath,
+        _: &'t SystemParamItem<Self::Context<'_, '_>>
+    ) -> Self::Ser<'t> {
+        SaloEntityMap::to_serializable(self, path_fetcher)
+    }
+
+    fn from_deserialize(
+        de: Self::De,
+        commands: &mut Commands,
+        entity_fetcher: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+        _: &mut SystemParamItem<Self::ContextMut<'_, '_>>
+    ) -> Self {
+        SaloEntityMap::from_deserialize(de, commands, entity_fetcher)
+    }
+
+    fn try_from_deserialize(
+        de: Self::De,
+        commands: &mut Commands,
+        entity_fetcher: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+        _: &mut SystemParamItem<Self::ContextMut<'_, '_>>
+    ) -> anyhow::Result<Self> {
+        SaloEntityMap::try_from_deserialize(de, commands, entity_fetcher)
+    }
+
+    fn type_name() -> Cow<'static, str> {
+        SaloEntityMap::<T>::type_name()
+    }
+}
@@ -0,0 +1,164 @@
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::Resource;
+use bevy_utils::tracing::error;
+
+use crate::Marker;
+
+/// A naming conflict found while building paths, handled by [`ConflictPolicy`](crate::ConflictPolicy).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathConflict {
+    /// Two components on one entity provide different names.
+    Name {
+        entity: Entity,
+        first: Cow<'static, str>,
+        second: Cow<'static, str>,
+    },
+    /// Two entities in the world have the same path.
+    Path {
+        path: String,
+        first: Entity,
+        second: Entity,
+    },
+}
+
+impl Display for PathConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathConflict::Name { entity, first, second } => write!(f, "Trying to rename entity {:?} from {} to {}.", entity, first, second),
+            PathConflict::Path { path, first, second } => write!(f, "Duplicate path {} for entity {:?} and {:?}.", path, first, second),
+        }
+    }
+}
+
+/// Errors produced during saving and loading.
+#[derive(Debug)]
+pub enum SaloError {
+    /// Checksum of the input does not match its content.
+    CorruptSave,
+    /// More than one of `FileInput`, `BytesInput`, `StringInput` and `ReaderInput` exist.
+    ConflictingInput,
+    /// No input found in deserialization.
+    NoInput,
+    /// File operations are not supported on this platform or without the `fs` feature.
+    UnsupportedPlatform,
+    /// A type marked as required in `LoadPolicy` is missing.
+    MissingRequired(Cow<'static, str>),
+    /// Save dropped due to `min_save_interval`.
+    Throttled,
+    /// Save aborted by inserting `AbortSave`, or by an error in `DocumentTransform`.
+    Aborted(Cow<'static, str>),
+    /// `SaloStorage` is not inserted.
+    NoStorage,
+    /// Called inside a running schedule of the same marker, i.e. from a hook,
+    /// the call is queued after the current run and its result is discarded.
+    Queued,
+    /// Called inside a running schedule of the same marker with an in-memory output,
+    /// which cannot be returned from a queued call.
+    Reentrant,
+    /// No entity has the `SpawnAnchor` label of `LoadOptions`.
+    MissingAnchor(Cow<'static, str>),
+    /// The input is not a delta of the base in `SaveDelta`.
+    BaseMismatch {
+        expected: String,
+        found: Option<String>,
+    },
+    /// A signed section is unsigned or modified, see `SectionSigning`.
+    InvalidSignature(String),
+    /// A naming conflict with [`ConflictPolicy::Error`](crate::ConflictPolicy::Error).
+    Conflict(PathConflict),
+    /// Failed to serialize the output.
+    Serialization(anyhow::Error),
+    /// Failed to deserialize the input.
+    Deserialization(anyhow::Error),
+}
+
+impl SaloError {
+    /// Recover a [`SaloError`] returned by a method, otherwise wrap as serialization error.
+    pub fn serialization(error: anyhow::Error) -> Self {
+        match error.downcast::<SaloError>() {
+            Ok(e) => e,
+            Err(e) => SaloError::Serialization(e),
+        }
+    }
+
+    /// Recover a [`SaloError`] returned by a method, otherwise wrap as deserialization error.
+    pub fn deserialization(error: anyhow::Error) -> Self {
+        match error.downcast::<SaloError>() {
+            Ok(e) => e,
+            Err(e) => SaloError::Deserialization(e),
+        }
+    }
+}
+
+impl Display for SaloError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaloError::CorruptSave => write!(f, "Checksum mismatch, the save is corrupted."),
+            SaloError::ConflictingInput => write!(f, "Multiple inputs exist, pick only one."),
+            SaloError::NoInput => write!(f, "No input found in deserialization."),
+            SaloError::MissingRequired(name) => write!(f, "Required type {} is missing.", name),
+            SaloError::Throttled => write!(f, "Save dropped, the last save is too recent."),
+            SaloError::Aborted(reason) => write!(f, "Save aborted: {}", reason),
+            SaloError::NoStorage => write!(f, "No SaloStorage found."),
+            SaloError::Queued => write!(f, "Called inside a running schedule, queued after the current run."),
+            SaloError::Reentrant => write!(f, "Called inside a running schedule, in-memory saves cannot be queued."),
+            SaloError::MissingAnchor(label) => write!(f, "No SpawnAnchor labeled {} found.", label),
+            SaloError::BaseMismatch { expected, found: Some(found) } => write!(f, "Expected a delta of {}, found a delta of {}.", expected, found),
+            SaloError::BaseMismatch { expected, found: None } => write!(f, "Expected a delta of {}, found a full save.", expected),
+            SaloError::InvalidSignature(section) => write!(f, "Signature of section \"{}\" is missing or invalid.", section),
+            SaloError::Conflict(conflict) => write!(f, "Conflict: {}", conflict),
+            SaloError::UnsupportedPlatform => write!(f, "File operations are not supported on this platform."),
+            SaloError::Serialization(e) => write!(f, "Serialization failed: {}", e),
+            SaloError::Deserialization(e) => write!(f, "Deserialization failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SaloError {}
+
+/// Errors emitted during the last run of a schedule, unique per marker.
+#[derive(Debug, Resource)]
+pub struct SaloErrors<M: Marker>(Vec<SaloError>, PhantomData<M>);
+
+impl<M: Marker> Default for SaloErrors<M> {
+    fn default() -> Self {
+        SaloErrors(Vec::new(), PhantomData)
+    }
+}
+
+impl<M: Marker> SaloErrors<M> {
+    /// Record and log an error.
+    pub fn push(&mut self, error: SaloError) {
+        error!("{}", error);
+        self.0.push(error);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SaloError> {
+        self.0.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Remove and return the first error.
+    pub fn take_first(&mut self) -> Option<SaloError> {
+        if self.0.is_empty() {
+            None
+        } else {
+            Some(self.0.remove(0))
+        }
+    }
+
+    pub fn take(&mut self) -> Vec<SaloError> {
+        std::mem::take(&mut self.0)
+    }
+}
@@ -0,0 +1,216 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::Write;
+use std::marker::PhantomData;
+
+use bevy_ecs::query::With;
+use serde::{Deserialize, Serialize};
+use bevy_ecs::system::{Query, Res, ResMut, Resource};
+
+use crate::methods::SerializationMethod;
+use crate::{Marker, SaloConfig, SaloErrors, SaloHistory, SaveLoad, SaveLoadBundle, SaveLoadRes, SerializeContext};
+
+/// Average serialized size of records by type from previous saves, unique per marker, inserted by the plugin.
+///
+/// Updated after each successful save that is not a delta, from the size of the output
+/// or from the size of each type with [`SaloConfig::with_save_stats`].
+/// Used by [`estimate_save_size`](crate::SaveLoadExtension::estimate_save_size).
+#[derive(Debug, Resource)]
+pub struct SaveSizeCache<M: Marker> {
+    averages: HashMap<String, f64>,
+    /// Bytes not attributed to records, i.e. headers.
+    overhead: usize,
+    counts: HashMap<Cow<'static, str>, usize>,
+    stats: SaveStats,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Default for SaveSizeCache<M> {
+    fn default() -> Self {
+        Self {
+            averages: HashMap::new(),
+            overhead: 0,
+            counts: HashMap::new(),
+            stats: SaveStats::default(),
+            p: PhantomData,
+        }
+    }
+}
+
+impl<M: Marker> SaveSizeCache<M> {
+    /// Returns true if nothing has been saved yet.
+    pub fn is_empty(&self) -> bool {
+        self.averages.is_empty()
+    }
+
+    /// Average size of a record of a type in bytes.
+    pub fn average(&self, type_name: &str) -> Option<f64> {
+        self.averages.get(type_name).copied()
+    }
+
+    /// Statistics of the last successful save, see [`SaveStats`].
+    pub fn stats(&self) -> &SaveStats {
+        &self.stats
+    }
+
+    /// Estimated size in bytes of a save with the last counted items.
+    ///
+    /// Types without an average use the average of all records.
+    pub(crate) fn estimate(&self) -> usize {
+        let fallback = match self.averages.len() {
+            0 => 0.0,
+            len => self.averages.values().sum::<f64>() / len as f64,
+        };
+        let records: f64 = self.counts.iter()
+            .map(|(name, count)| self.average(name).unwrap_or(fallback) * *count as f64)
+            .sum();
+        self.overhead + records.round() as usize
+    }
+}
+
+/// Entries and size of a type in a save.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeSize {
+    pub type_name: String,
+    /// Number of records.
+    pub entries: usize,
+    /// Size of the records in bytes, measured without the rest of the document.
+    pub bytes: usize,
+}
+
+/// Entries and sizes by type of the last save of a marker,
+/// returned by [`save_stats`](crate::SaveLoadExtension::save_stats).
+///
+/// `Display` prints a table, largest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveStats {
+    /// Sorted by size, largest first.
+    pub types: Vec<TypeSize>,
+    /// Size of the output in bytes, `None` for in-memory saves.
+    pub total: Option<usize>,
+}
+
+impl SaveStats {
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Statistics of a type.
+    pub fn get(&self, type_name: &str) -> Option<&TypeSize> {
+        self.types.iter().find(|t| t.type_name == type_name)
+    }
+
+    /// Number of records of all types.
+    pub fn entries(&self) -> usize {
+        self.types.iter().map(|t| t.entries).sum()
+    }
+
+    /// Size of records of all types in bytes.
+    pub fn bytes(&self) -> usize {
+        self.types.iter().map(|t| t.bytes).sum()
+    }
+}
+
+impl std::fmt::Display for SaveStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self.types.iter().map(|t| t.type_name.len()).max().unwrap_or(0).max(4);
+        let bytes = self.bytes().max(1) as f64;
+        writeln!(f, "{:width$} {:>10} {:>12} {:>6}", "type", "entries", "bytes", "%")?;
+        for t in &self.types {
+            writeln!(f, "{:width$} {:>10} {:>12} {:>6.1}", t.type_name, t.entries, t.bytes, t.bytes as f64 * 100.0 / bytes)?;
+        }
+        write!(f, "{:width$} {:>10} {:>12}", "total", self.entries(), self.bytes())?;
+        if let Some(total) = self.total {
+            write!(f, " ({} written)", total)?;
+        }
+        writeln!(f)
+    }
+}
+
+/// Counts bytes without storing them.
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Update averages from the size of the output, and measure records of each type
+/// for [`SaveStats`] if enabled by [`SaloConfig::with_save_stats`].
+///
+/// Without stats all records are assumed to be of the same size.
+pub(crate) fn record_sizes<M: Marker>(
+    config: Res<SaloConfig<M>>,
+    ctx: Res<SerializeContext<M>>,
+    errors: Res<SaloErrors<M>>,
+    history: Res<SaloHistory<M>>,
+    mut cache: ResMut<SaveSizeCache<M>>,
+) {
+    if !errors.is_empty() {
+        return;
+    }
+    let records = ctx.components.iter().filter(|(_, records)| !records.is_empty());
+    let sizes: Vec<_> = if config.save_stats() {
+        let mut sizes = Vec::new();
+        for (name, records) in records {
+            let mut counter = Counter(0);
+            if M::Method::serialize_writer(&mut counter, records).is_err() {
+                return;
+            }
+            sizes.push((name.to_string(), counter.0 as f64, records.len()));
+        }
+        let mut types: Vec<_> = sizes.iter()
+            .map(|(name, size, count)| TypeSize { type_name: name.clone(), entries: *count, bytes: *size as usize })
+            .collect();
+        types.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.type_name.cmp(&b.type_name)));
+        cache.stats = SaveStats { types, total: history.size };
+        sizes
+    } else {
+        records.map(|(name, records)| (name.to_string(), records.len() as f64, records.len())).collect()
+    };
+    if ctx.delta.is_some() {
+        return;
+    }
+    let measured: f64 = sizes.iter().map(|(_, size, _)| size).sum();
+    let (scale, overhead) = match history.size {
+        Some(total) if !config.save_stats() => (total as f64 / measured.max(1.0), 0),
+        Some(total) if total as f64 >= measured => (1.0, total - measured as usize),
+        Some(total) if measured > 0.0 => (total as f64 / measured, 0),
+        _ if !config.save_stats() => return,
+        _ => (1.0, cache.overhead),
+    };
+    cache.overhead = overhead;
+    cache.averages = sizes.into_iter()
+        .map(|(name, size, count)| (name, size * scale / count as f64))
+        .collect();
+}
+
+/// Count items of a type for an estimate.
+pub(crate) fn count_items<M: Marker, T: SaveLoad>(
+    mut cache: ResMut<SaveSizeCache<M>>,
+    query: Query<(), (With<T>, M::Query)>,
+) {
+    cache.counts.insert(T::type_name(), query.iter().count());
+}
+
+/// Count items of a bundle for an estimate.
+pub(crate) fn count_bundle<M: Marker, T: SaveLoadBundle>(
+    mut cache: ResMut<SaveSizeCache<M>>,
+    query: Query<(), (T::Query, M::Query)>,
+) {
+    cache.counts.insert(T::type_name(), query.iter().count());
+}
+
+/// Count a resource for an estimate.
+pub(crate) fn count_resource<M: Marker, T: SaveLoadRes>(
+    mut cache: ResMut<SaveSizeCache<M>>,
+    res: Option<Res<T>>,
+) {
+    cache.counts.insert(T::type_name(), res.is_some() as usize);
+}
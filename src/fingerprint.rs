@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+use bevy_ecs::system::{Commands, Res, ResMut, Resource};
+
+use crate::{DeserializeContext, Marker, SaloConfig, SaloRegistry, SerializeContext};
+
+/// Registered types of a save, type name to version.
+pub(crate) type Fingerprint = BTreeMap<String, u32>;
+
+fn fingerprint<M: Marker>(registry: &SaloRegistry<M>) -> Fingerprint {
+    registry.registered_types().map(|t| (t.name.to_string(), t.version)).collect()
+}
+
+/// Changes to registered types since a save was created, unique per marker.
+///
+/// Inserted when loading a save written with [`SaloConfig::with_fingerprint`],
+/// removed when loading a save without one. All lists are sorted by type name.
+#[derive(Debug, Clone, PartialEq, Eq, Resource)]
+pub struct RegistryChanges<M: Marker> {
+    /// Type names registered now but not when saved.
+    pub added: Vec<String>,
+    /// Type names registered when saved but not now.
+    pub removed: Vec<String>,
+    /// Old and new type names, see [`SaloRegistry::set_renamed`].
+    pub renamed: Vec<(String, String)>,
+    /// Type names with their saved and current [`version`](crate::SaveLoad::version).
+    pub versions: Vec<(String, u32, u32)>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> RegistryChanges<M> {
+    /// Compare the fingerprint of a save to the current registry.
+    fn new(saved: &Fingerprint, registry: &SaloRegistry<M>) -> Self {
+        let current = fingerprint(registry);
+        let mut renamed = Vec::new();
+        let mut removed = Vec::new();
+        let mut versions = Vec::new();
+        for (name, version) in saved.iter() {
+            let new = match registry.renamed(name) {
+                Some(new) if !current.contains_key(name) && !saved.contains_key(new) => new,
+                _ => name,
+            };
+            match current.get(new) {
+                Some(current) => {
+                    if new != name {
+                        renamed.push((name.clone(), new.to_owned()));
+                    }
+                    if current != version {
+                        versions.push((new.to_owned(), *version, *current));
+                    }
+                },
+                None => removed.push(name.clone()),
+            }
+        }
+        let added = current.keys()
+            .filter(|name| !saved.contains_key(*name) && !renamed.iter().any(|(_, new)| new == *name))
+            .cloned()
+            .collect();
+        versions.sort();
+        Self { added, removed, renamed, versions, p: PhantomData }
+    }
+
+    /// Returns true if the registry is unchanged since the save.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.renamed.is_empty() && self.versions.is_empty()
+    }
+}
+
+/// Write the fingerprint of the registry if enabled.
+pub(crate) fn write_fingerprint<M: Marker>(
+    config: Res<SaloConfig<M>>,
+    registry: Res<SaloRegistry<M>>,
+    mut ctx: ResMut<SerializeContext<M>>,
+) {
+    if config.fingerprint() {
+        ctx.fingerprint = Some(fingerprint(&registry));
+    }
+}
+
+/// Compare the fingerprint of the loaded save to the registry.
+pub(crate) fn check_fingerprint<M: Marker>(
+    mut commands: Commands,
+    registry: Res<SaloRegistry<M>>,
+    mut ctx: ResMut<DeserializeContext<M>>,
+) {
+    match ctx.fingerprint.take() {
+        Some(saved) => commands.insert_resource(RegistryChanges::new(&saved, &registry)),
+        None => commands.remove_resource::<RegistryChanges<M>>(),
+    }
+}
+
+/// Load records of renamed types under their current names, see [`SaloRegistry::set_renamed`].
+pub(crate) fn apply_renames<M: Marker>(
+    registry: Res<SaloRegistry<M>>,
+    mut ctx: ResMut<DeserializeContext<M>>,
+) {
+    let ctx = &mut *ctx;
+    let renamed: Vec<_> = ctx.components.keys()
+        .filter_map(|old| Some((old.clone(), registry.renamed(old)?.to_owned())))
+        .filter(|(_, new)| !ctx.components.contains_key(new))
+        .collect();
+    for (old, new) in renamed {
+        if let Some(records) = ctx.components.remove(&old) {
+            ctx.components.insert(new.clone(), records);
+        }
+        if let Some(version) = ctx.versions.remove(&old) {
+            ctx.versions.insert(new.clone(), version);
+        }
+        if let Some(paths) = ctx.loaded.remove(&old) {
+            ctx.loaded.insert(new.clone(), paths);
+        }
+        if let Some(attributes) = ctx.attributes.remove(&old) {
+            ctx.attributes.insert(new.clone(), attributes);
+        }
+        for (name, _) in ctx.counts.iter_mut().filter(|(name, _)| *name == old) {
+            *name = new.clone();
+        }
+    }
+}
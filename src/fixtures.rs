@@ -0,0 +1,338 @@
+//! Procedural worlds with known contents for tests, enabled by the `fixtures` feature.
+//!
+//! Used by the crate's own tests, and to stress-test registrations of downstream crates
+//! by registering the fixture types next to their own.
+//!
+//! ```
+//! # use bevy_salo::{fixtures, methods::SerdeJson, SaveLoadExtension, SaveTarget, LoadSource};
+//! type All = bevy_salo::All<SerdeJson<false>>;
+//! let shape = fixtures::Shape::new().with_depth(3).with_links(10);
+//! let mut world = fixtures::world::<All>();
+//! let expected = fixtures::generate(&mut world, &shape);
+//! let bytes = world.save::<All>(SaveTarget::Bytes).unwrap().into_bytes().unwrap();
+//!
+//! let mut world = fixtures::world::<All>();
+//! world.load::<All>(LoadSource::Bytes(&bytes)).unwrap();
+//! expected.check(&mut world).unwrap();
+//! ```
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::ensure;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Commands, Resource, SystemParamItem};
+use bevy_ecs::world::World;
+use serde::{Deserialize, Serialize};
+
+use crate::saveload::join_path;
+use crate::schedules::BuildRes;
+use crate::{EntityPath, Marker, SaveLoad, SaveLoadCore, SaveLoadPlugin, SaveLoadResCore, SaveReport, SmolStr};
+
+/// A named entity, builds paths.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Component, Serialize, Deserialize)]
+pub struct Node(pub String);
+
+impl SaveLoadCore for Node {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("fixture_node")
+    }
+
+    fn path_name(&self) -> Option<Cow<'static, str>> {
+        Some(Cow::Owned(self.0.clone()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Component, Serialize, Deserialize)]
+pub struct Stats {
+    pub hp: u32,
+    pub level: u32,
+}
+
+impl SaveLoadCore for Stats {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("fixture_stats")
+    }
+}
+
+/// A reference to another entity, saved as its path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct Link(pub Entity);
+
+impl SaveLoad for Link {
+    type Ser<'ser> = EntityPath;
+    type De = EntityPath;
+    type Context<'w, 's> = ();
+    type ContextMut<'w, 's> = ();
+
+    fn to_serializable<'t>(&'t self,
+        _: Entity,
+        path_fetcher: impl Fn(Entity) -> EntityPath,
+        _: &'t SystemParamItem<Self::Context<'_, '_>>
+    ) -> Self::Ser<'t> {
+        path_fetcher(self.0)
+    }
+
+    fn from_deserialize(
+        de: Self::De,
+        commands: &mut Commands,
+        _: Entity,
+        mut entity_fetcher: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+        _: &mut SystemParamItem<Self::ContextMut<'_, '_>>
+    ) -> Self {
+        Link(entity_fetcher(commands, &de))
+    }
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("fixture_link")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Resource, Serialize, Deserialize)]
+pub struct Score(pub u64);
+
+impl SaveLoadResCore for Score {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("fixture_score")
+    }
+}
+
+/// All fixture types, registered with [`register_tuple`](SaveLoadPlugin::register_tuple).
+pub type FixtureTypes = (Node, Stats, Link, BuildRes<Score>);
+
+/// Create a world with the fixture types registered with marker `M`.
+pub fn world<M: Marker>() -> World {
+    let mut world = World::new();
+    SaveLoadPlugin::new::<M>()
+        .register_tuple::<FixtureTypes>()
+        .build_world(&mut world);
+    world
+}
+
+/// Size and shape of a generated world.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape {
+    roots: usize,
+    depth: usize,
+    children: usize,
+    unnamed: usize,
+    links: usize,
+    resources: bool,
+    seed: u64,
+}
+
+impl Default for Shape {
+    fn default() -> Self {
+        Shape { roots: 2, depth: 2, children: 2, unnamed: 2, links: 4, resources: true, seed: 1 }
+    }
+}
+
+impl Shape {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of named trees, default is 2.
+    pub fn with_roots(mut self, roots: usize) -> Self {
+        self.roots = roots;
+        self
+    }
+
+    /// Levels of each tree including the root, default is 2.
+    ///
+    /// Trees are flat without the `hierarchy` feature.
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Children of each named entity above the last level, default is 2.
+    pub fn with_children(mut self, children: usize) -> Self {
+        self.children = children;
+        self
+    }
+
+    /// Number of unnamed entities with only [`Stats`], default is 2.
+    pub fn with_unnamed(mut self, unnamed: usize) -> Self {
+        self.unnamed = unnamed;
+        self
+    }
+
+    /// Number of [`Link`]s between named entities, at most one per entity, default is 4.
+    pub fn with_links(mut self, links: usize) -> Self {
+        self.links = links;
+        self
+    }
+
+    /// Insert the [`Score`] resource, default is `true`.
+    pub fn with_resources(mut self, resources: bool) -> Self {
+        self.resources = resources;
+        self
+    }
+
+    /// Seed of link targets and stats, default is 1.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// Xorshift, stable across versions and platforms.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Rng(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// Contents of a generated world, as paths and record counts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expected {
+    /// Number of records by type name.
+    pub records: BTreeMap<String, usize>,
+    /// Paths of named entities.
+    pub paths: BTreeSet<String>,
+    /// Paths of the source and target of links.
+    pub links: BTreeSet<(String, String)>,
+    pub score: Option<u64>,
+}
+
+#[cfg(feature="hierarchy")]
+fn attach(world: &mut World, parent: Option<Entity>, child: Entity) {
+    use bevy_hierarchy::BuildWorldChildren;
+    if let Some(parent) = parent {
+        world.entity_mut(parent).add_child(child);
+    }
+}
+
+#[cfg(not(feature="hierarchy"))]
+fn attach(_: &mut World, _: Option<Entity>, _: Entity) {}
+
+#[cfg(feature="hierarchy")]
+fn parent_of(world: &World, entity: Entity) -> Option<Entity> {
+    world.get::<bevy_hierarchy::Parent>(entity).map(|p| p.get())
+}
+
+#[cfg(not(feature="hierarchy"))]
+fn parent_of(_: &World, _: Entity) -> Option<Entity> {
+    None
+}
+
+/// Path of an entity from the [`Node`]s of itself and its ancestors, `None` if unnamed.
+fn path_of(world: &World, entity: Entity) -> Option<String> {
+    let mut segments = Vec::new();
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        if let Some(node) = world.get::<Node>(entity) {
+            segments.push(SmolStr::new(&node.0));
+        }
+        current = parent_of(world, entity);
+    }
+    segments.reverse();
+    (!segments.is_empty()).then(|| join_path(&segments))
+}
+
+/// Spawn entities of a shape and return what a save of them contains.
+///
+/// Names are unique across trees, i.e. `r0`, `r0_1`, `r0_1_0`, so paths are unique with or without the `hierarchy` feature.
+pub fn generate(world: &mut World, shape: &Shape) -> Expected {
+    let mut rng = Rng::new(shape.seed);
+    let mut expected = Expected::default();
+    let mut named = Vec::new();
+    let mut level: Vec<(Option<Entity>, String)> = (0..shape.roots).map(|i| (None, format!("r{}", i))).collect();
+    for depth in 0..shape.depth {
+        let mut next = Vec::new();
+        for (parent, name) in level {
+            let stats = Stats { hp: rng.below(100) as u32, level: depth as u32 };
+            let entity = world.spawn((Node(name.clone()), stats)).id();
+            attach(world, parent, entity);
+            named.push(entity);
+            if depth + 1 < shape.depth {
+                next.extend((0..shape.children).map(|i| (Some(entity), format!("{}_{}", name, i))));
+            }
+        }
+        level = next;
+    }
+    for i in 0..shape.unnamed {
+        world.spawn(Stats { hp: i as u32, level: 0 });
+    }
+    let mut sources = named.clone();
+    let links = shape.links.min(named.len());
+    for i in 0..links {
+        let j = i + rng.below(sources.len() - i);
+        sources.swap(i, j);
+        let target = named[rng.below(named.len())];
+        world.entity_mut(sources[i]).insert(Link(target));
+    }
+    if shape.resources {
+        let score = rng.next() % 1000;
+        world.insert_resource(Score(score));
+        expected.score = Some(score);
+        expected.records.insert(<Score as SaveLoadResCore>::type_name().into_owned(), 1);
+    }
+    expected.records.insert(<Node as SaveLoadCore>::type_name().into_owned(), named.len());
+    expected.records.insert(<Stats as SaveLoadCore>::type_name().into_owned(), named.len() + shape.unnamed);
+    if links > 0 {
+        expected.records.insert(<Link as SaveLoad>::type_name().into_owned(), links);
+    }
+    expected.paths = named.iter().filter_map(|e| path_of(world, *e)).collect();
+    expected.links = sources[..links].iter().filter_map(|e| {
+        let target = world.get::<Link>(*e)?.0;
+        Some((path_of(world, *e)?, path_of(world, target)?))
+    }).collect();
+    expected
+}
+
+impl Expected {
+    /// Check a world contains the generated entities, i.e. after a load.
+    ///
+    /// Entities other than the fixture types are ignored.
+    pub fn check(&self, world: &mut World) -> anyhow::Result<()> {
+        let nodes: Vec<_> = world.query::<(Entity, &Node)>().iter(world).map(|(e, _)| e).collect();
+        self.check_count(<Node as SaveLoadCore>::type_name(), nodes.len())?;
+        let stats = world.query::<&Stats>().iter(world).count();
+        self.check_count(<Stats as SaveLoadCore>::type_name(), stats)?;
+
+        let paths: BTreeSet<_> = nodes.iter().filter_map(|e| path_of(world, *e)).collect();
+        if let Some(path) = self.paths.symmetric_difference(&paths).next() {
+            let found = if paths.contains(path) { "unexpected" } else { "missing" };
+            anyhow::bail!("Path {} is {}.", path, found);
+        }
+        let links: Vec<_> = world.query::<(Entity, &Link)>().iter(world).map(|(e, l)| (e, l.0)).collect();
+        let links: BTreeSet<_> = links.into_iter()
+            .map(|(from, to)| (path_of(world, from).unwrap_or_default(), path_of(world, to).unwrap_or_default()))
+            .collect();
+        ensure!(links == self.links, "Links {:?}, expected {:?}.", links, self.links);
+        let score = world.get_resource::<Score>().map(|s| s.0);
+        ensure!(self.score.is_none() || score == self.score, "Score {:?}, expected {:?}.", score, self.score);
+        Ok(())
+    }
+
+    fn check_count(&self, name: Cow<'static, str>, found: usize) -> anyhow::Result<()> {
+        let expected = self.records.get(name.as_ref()).copied().unwrap_or(0);
+        ensure!(found == expected, "{} records of {}, expected {}.", found, name, expected);
+        Ok(())
+    }
+
+    /// Check a [`SaveReport`] of [`validate_save`](crate::SaveLoadExtension::validate_save)
+    /// has the expected record counts and no errors.
+    pub fn check_report(&self, report: &SaveReport) -> anyhow::Result<()> {
+        ensure!(report.is_compatible(), "Incompatible save: {:?} {:?}.", report.unknown_types, report.errors);
+        ensure!(report.unresolved_parents.is_empty(), "Unresolved parents: {:?}.", report.unresolved_parents);
+        self.records.keys().try_for_each(|name| {
+            self.check_count(Cow::Owned(name.clone()), report.records.get(name).copied().unwrap_or(0))
+        })
+    }
+}
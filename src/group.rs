@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+
+use bevy_ecs::world::World;
+
+use crate::methods::SerializationMethod;
+use crate::saveload::ValueOf;
+use crate::document::{save_value, load_value};
+use crate::reentrant::{self, QueuedSource, QueuedTarget};
+use crate::schedules::keyed_string_error;
+use crate::{sealed, Marker, SaloError, SaloStorage, SaveEncryptionKey, LoadSource, SaveTarget, Saved};
+
+/// Contents of a group file, documents keyed by marker name.
+type GroupDocument<V> = BTreeMap<String, V>;
+
+/// Name of a marker in a group file, the full type name so markers
+/// with the same name in different modules do not collide.
+fn group_key<M: Marker>() -> String {
+    std::any::type_name::<M>().to_owned()
+}
+
+/// A tuple of markers saved to and loaded from a single file,
+/// see [`save_group`](crate::SaveLoadExtension::save_group).
+///
+/// All markers must share the same [`SerializationMethod`].
+/// The file is a map of the full type name of each marker to its document,
+/// written with the method and [`SaveEncryptionKey`] of the first marker.
+pub trait MarkerGroup: sealed::Sealed + 'static {
+    type First: Marker;
+
+    /// Names of markers in the group.
+    fn keys() -> Vec<String>;
+    #[doc(hidden)]
+    fn is_running(world: &World) -> bool;
+    #[doc(hidden)]
+    fn defer(world: &mut World, name: &str, f: Box<dyn FnOnce(&mut World) + Send + Sync>);
+    #[doc(hidden)]
+    fn save_all(world: &mut World, document: &mut GroupDocument<ValueOf<Self::First>>) -> Result<(), SaloError>;
+    #[doc(hidden)]
+    fn load_all(world: &mut World, document: &mut GroupDocument<ValueOf<Self::First>>) -> Result<(), SaloError>;
+}
+
+macro_rules! group_tuple {
+    ($first: ident $(,$rest: ident)*) => {
+        impl<$first: Marker $(,$rest: Marker<Method = $first::Method>)*> sealed::Sealed for ($first, $($rest),*) {}
+
+        impl<$first: Marker $(,$rest: Marker<Method = $first::Method>)*> MarkerGroup for ($first, $($rest),*) {
+            type First = $first;
+
+            fn keys() -> Vec<String> {
+                vec![group_key::<$first>() $(,group_key::<$rest>())*]
+            }
+
+            fn is_running(world: &World) -> bool {
+                reentrant::is_running::<$first>(world) $(|| reentrant::is_running::<$rest>(world))*
+            }
+
+            fn defer(world: &mut World, name: &str, f: Box<dyn FnOnce(&mut World) + Send + Sync>) {
+                $(if reentrant::is_running::<$rest>(world) {
+                    return reentrant::defer::<$rest, _>(world, name, f, ());
+                })*
+                reentrant::defer::<$first, _>(world, name, f, ())
+            }
+
+            fn save_all(world: &mut World, document: &mut GroupDocument<ValueOf<$first>>) -> Result<(), SaloError> {
+                let keys = Self::keys();
+                if let Some(key) = keys.iter().enumerate().find_map(|(i, key)| keys[..i].contains(key).then_some(key)) {
+                    return Err(SaloError::Serialization(anyhow::anyhow!("Marker {} is in the group more than once.", key)));
+                }
+                document.insert(group_key::<$first>(), reentrant::scope::<$first, _>(world, save_value::<$first>)?);
+                $(document.insert(group_key::<$rest>(), reentrant::scope::<$rest, _>(world, save_value::<$rest>)?);)*
+                Ok(())
+            }
+
+            fn load_all(world: &mut World, document: &mut GroupDocument<ValueOf<$first>>) -> Result<(), SaloError> {
+                for key in Self::keys() {
+                    if !document.contains_key(&key) {
+                        return Err(SaloError::Deserialization(anyhow::anyhow!("Marker {} not found in group.", key)));
+                    }
+                }
+                let value = document.remove(&group_key::<$first>()).unwrap_or_default();
+                reentrant::scope::<$first, _>(world, |world| load_value::<$first>(world, value))?;
+                $(
+                    let value = document.remove(&group_key::<$rest>()).unwrap_or_default();
+                    reentrant::scope::<$rest, _>(world, |world| load_value::<$rest>(world, value))?;
+                )*
+                Ok(())
+            }
+        }
+    };
+}
+
+group_tuple!(A, B);
+group_tuple!(A, B, C);
+group_tuple!(A, B, C, D);
+group_tuple!(A, B, C, D, E);
+group_tuple!(A, B, C, D, E, F);
+
+type Method<G> = <<G as MarkerGroup>::First as Marker>::Method;
+
+fn encryption_key<G: MarkerGroup>(world: &World) -> Option<[u8; 32]> {
+    world.get_resource::<SaveEncryptionKey<G::First>>().map(|k| *k.get())
+}
+
+pub(crate) fn save_group<G: MarkerGroup>(world: &mut World, target: SaveTarget) -> Result<Saved, SaloError> {
+    if G::is_running(world) {
+        if QueuedTarget::in_memory(&target) {
+            reentrant::reject::<G::First>("save_group");
+            return Err(SaloError::Reentrant);
+        }
+        let target = QueuedTarget::new(target);
+        G::defer(world, "save_group", Box::new(move |w| { let _ = save_group::<G>(w, target.get()); }));
+        return Err(SaloError::Queued);
+    }
+    let mut document = GroupDocument::new();
+    G::save_all(world, &mut document)?;
+    let key = encryption_key::<G>(world);
+    let bytes = || Method::<G>::serialize_bytes_keyed(&document, key.as_ref()).map_err(SaloError::Serialization);
+    match target {
+        SaveTarget::File(file) => write_file(file, &bytes()?).map(|_| Saved::File),
+        SaveTarget::Bytes => bytes().map(Saved::Bytes),
+        SaveTarget::String if key.is_some() => Err(SaloError::Serialization(keyed_string_error())),
+        SaveTarget::String => Method::<G>::serialize_string(&document)
+            .map(Saved::String)
+            .map_err(SaloError::Serialization),
+        SaveTarget::Storage(name) => {
+            let bytes = bytes()?;
+            match world.get_resource_mut::<SaloStorage<G::First>>() {
+                Some(mut storage) => storage.get_mut().write(name, &bytes).map_err(SaloError::Serialization)?,
+                None => return Err(SaloError::NoStorage),
+            }
+            Ok(Saved::Storage)
+        },
+    }
+}
+
+pub(crate) fn load_group<G: MarkerGroup>(world: &mut World, source: LoadSource) -> Result<(), SaloError> {
+    if G::is_running(world) {
+        let source = QueuedSource::new(source);
+        G::defer(world, "load_group", Box::new(move |w| { let _ = load_group::<G>(w, source.get()); }));
+        return Err(SaloError::Queued);
+    }
+    let key = encryption_key::<G>(world);
+    let mut document: GroupDocument<ValueOf<G::First>> = match source {
+        LoadSource::File(file) => Method::<G>::deserialize_keyed(&read_file(file)?, key.as_ref()),
+        LoadSource::Bytes(bytes) => Method::<G>::deserialize_keyed(bytes, key.as_ref()),
+        LoadSource::String(_) if key.is_some() => Err(keyed_string_error()),
+        LoadSource::String(string) => Method::<G>::deserialize_str(string),
+        LoadSource::Storage(name) => match world.get_resource::<SaloStorage<G::First>>() {
+            Some(storage) => {
+                let bytes = storage.get().read(name).map_err(SaloError::Deserialization)?;
+                Method::<G>::deserialize_keyed(&bytes, key.as_ref())
+            },
+            None => return Err(SaloError::NoStorage),
+        },
+    }.map_err(SaloError::Deserialization)?;
+    G::load_all(world, &mut document)
+}
+
+#[cfg(feature="fs")]
+fn write_file(file: &str, bytes: &[u8]) -> Result<(), SaloError> {
+    if !crate::HAS_FILE_SYSTEM {
+        return Err(SaloError::UnsupportedPlatform);
+    }
+    crate::FileWriteOptions::default()
+        .write(file, |file| Ok(std::fs::write(file, bytes)?))
+        .map_err(SaloError::Serialization)
+}
+
+#[cfg(not(feature="fs"))]
+fn write_file(_: &str, _: &[u8]) -> Result<(), SaloError> {
+    Err(SaloError::UnsupportedPlatform)
+}
+
+#[cfg(feature="fs")]
+fn read_file(file: &str) -> Result<Vec<u8>, SaloError> {
+    if !crate::HAS_FILE_SYSTEM {
+        return Err(SaloError::UnsupportedPlatform);
+    }
+    #[cfg(feature="fs-lock")]
+    let _lock = crate::lock_file(file, false).map_err(|e| SaloError::Deserialization(e.into()))?;
+    std::fs::read(file).map_err(|e| SaloError::Deserialization(e.into()))
+}
+
+#[cfg(not(feature="fs"))]
+fn read_file(_: &str) -> Result<Vec<u8>, SaloError> {
+    Err(SaloError::UnsupportedPlatform)
+}
@@ -0,0 +1,176 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::marker::PhantomData;
+
+use bevy_ecs::system::{Res, ResMut, Resource};
+use bevy_utils::{Duration, Instant};
+
+use crate::{Marker, SaloErrors};
+
+/// Kind of a recorded operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaloOperation {
+    Save,
+    Load,
+}
+
+/// A completed save or load in [`SaloHistory`].
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub operation: SaloOperation,
+    /// Who requested the operation, set by [`SaloHistory::request`].
+    pub requester: Option<Cow<'static, str>>,
+    /// Why the operation was requested, set by [`SaloHistory::request`].
+    pub reason: Option<Cow<'static, str>>,
+    /// The first error emitted, if any.
+    pub error: Option<String>,
+    pub duration: Duration,
+    /// Size of the output or input in bytes, if known.
+    pub size: Option<usize>,
+}
+
+impl HistoryEntry {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Bounded log of recent save and load operations, unique per marker, inserted by the plugin.
+///
+/// Holds the last 32 operations by default, oldest first.
+#[derive(Debug, Resource)]
+pub struct SaloHistory<M: Marker> {
+    entries: VecDeque<HistoryEntry>,
+    capacity: usize,
+    next: Option<(Cow<'static, str>, Cow<'static, str>)>,
+    current: Option<(Cow<'static, str>, Cow<'static, str>)>,
+    started: Option<Instant>,
+    pub(crate) size: Option<usize>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Default for SaloHistory<M> {
+    fn default() -> Self {
+        Self::with_capacity(32)
+    }
+}
+
+impl<M: Marker> SaloHistory<M> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity,
+            next: None,
+            current: None,
+            started: None,
+            size: None,
+            p: PhantomData,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Set the capacity, dropping the oldest entries if over.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.truncate();
+    }
+
+    /// Annotate the next save or load with a requester and a reason.
+    ///
+    /// ```
+    /// # use bevy_salo::*;
+    /// # let mut history = SaloHistory::<All>::default();
+    /// history.request("autosave", "entered checkpoint");
+    /// ```
+    pub fn request(&mut self, requester: impl Into<Cow<'static, str>>, reason: impl Into<Cow<'static, str>>) {
+        self.next = Some((requester.into(), reason.into()));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate entries, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> {
+        self.entries.iter()
+    }
+
+    /// The most recent entry.
+    pub fn last(&self) -> Option<&HistoryEntry> {
+        self.entries.back()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear()
+    }
+
+    /// Format all entries, one per line, oldest first.
+    pub fn report(&self) -> String {
+        let mut result = String::new();
+        for entry in self.entries.iter() {
+            let operation = match entry.operation {
+                SaloOperation::Save => "save",
+                SaloOperation::Load => "load",
+            };
+            let _ = write!(result, "{} {:?}", operation, entry.duration);
+            if let Some(size) = entry.size {
+                let _ = write!(result, " {} bytes", size);
+            }
+            if let Some(requester) = &entry.requester {
+                let _ = write!(result, " by {}", requester);
+            }
+            if let Some(reason) = &entry.reason {
+                let _ = write!(result, " ({})", reason);
+            }
+            match &entry.error {
+                Some(error) => { let _ = writeln!(result, ": {}", error); },
+                None => result.push_str(": ok\n"),
+            }
+        }
+        result
+    }
+
+    pub(crate) fn begin(&mut self) {
+        self.current = self.next.take();
+        self.started = Some(Instant::now());
+        self.size = None;
+    }
+
+    fn finish(&mut self, operation: SaloOperation, error: Option<String>) {
+        let (requester, reason) = match self.current.take() {
+            Some((requester, reason)) => (Some(requester), Some(reason)),
+            None => (None, None),
+        };
+        self.entries.push_back(HistoryEntry {
+            operation,
+            requester,
+            reason,
+            error,
+            duration: self.started.take().map(|t| t.elapsed()).unwrap_or_default(),
+            size: self.size.take(),
+        });
+        self.truncate();
+    }
+
+    fn truncate(&mut self) {
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+}
+
+pub(crate) fn record_save<M: Marker>(mut history: ResMut<SaloHistory<M>>, errors: Res<SaloErrors<M>>) {
+    history.finish(SaloOperation::Save, errors.iter().next().map(|e| e.to_string()))
+}
+
+pub(crate) fn record_load<M: Marker>(mut history: ResMut<SaloHistory<M>>, errors: Res<SaloErrors<M>>) {
+    history.finish(SaloOperation::Load, errors.iter().next().map(|e| e.to_string()))
+}
@@ -0,0 +1,64 @@
+//! Schedule-free helpers for testing [`SaveLoad`] implementations in isolation.
+//!
+//! These bypass markers, schedules and paths, and work on a plain [`World`]
+//! without building an `App` or registering plugins.
+//! Entities are referenced by `Entity`, references to other entities
+//! in [`apply_record`] always spawn new entities.
+//!
+//! ```
+//! # use bevy_salo::{SaveLoadCore, immediate, methods::SerdeJson};
+//! # use bevy_ecs::{world::World, component::Component};
+//! #[derive(Debug, PartialEq, Component, serde::Serialize, serde::Deserialize)]
+//! struct Hp(u32);
+//! impl SaveLoadCore for Hp {}
+//!
+//! let mut world = World::new();
+//! let entity = world.spawn(Hp(10)).id();
+//! let value = immediate::save_component::<Hp, SerdeJson>(&mut world, entity).unwrap();
+//! assert_eq!(value, serde_json::json!(10));
+//!
+//! let entity = world.spawn_empty().id();
+//! immediate::apply_record::<Hp, SerdeJson>(&mut world, entity, value).unwrap();
+//! assert_eq!(world.get::<Hp>(entity), Some(&Hp(10)));
+//! ```
+use std::collections::HashMap;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Commands, Query, StaticSystemParam, SystemState};
+use bevy_ecs::world::World;
+
+use crate::methods::SerializationMethod;
+use crate::{EntityPath, SaloError, SaveLoad};
+
+/// Serialize component `T` of an entity with method `S`.
+///
+/// Entity references are written as [`EntityPath::Entity`].
+/// Returns a serialization error if the entity does not have `T`.
+pub fn save_component<T: SaveLoad, S: SerializationMethod>(world: &mut World, entity: Entity) -> Result<S::Value, SaloError> {
+    let mut state = SystemState::<(Query<&T>, StaticSystemParam<T::Context<'_, '_>>)>::new(world);
+    let (query, ctx) = state.get_mut(world);
+    let item = query.get(entity).map_err(|e| SaloError::Serialization(e.into()))?;
+    let path_fetcher = |e: Entity| EntityPath::Entity(e.to_bits());
+    let value = S::serialize_value(&item.to_serializable(entity, path_fetcher, &ctx));
+    value.map_err(SaloError::serialization)
+}
+
+/// Deserialize a value of component `T` with method `S` and insert it into an entity.
+///
+/// Commands issued by [`SaveLoad::try_from_deserialize`] are applied before returning.
+pub fn apply_record<T: SaveLoad, S: SerializationMethod>(world: &mut World, entity: Entity, value: S::Value) -> Result<(), SaloError> {
+    let de = S::deserialize_value::<T::De>(value).map_err(SaloError::deserialization)?;
+    let mut state = SystemState::<(Commands, StaticSystemParam<T::ContextMut<'_, '_>>)>::new(world);
+    let result = {
+        let (mut commands, mut ctx) = state.get_mut(world);
+        let mut spawned = HashMap::new();
+        let entity_fetcher = |commands: &mut Commands, path: &EntityPath| match path {
+            EntityPath::Unique => commands.spawn_empty().id(),
+            path => *spawned.entry(path.clone()).or_insert_with(|| commands.spawn_empty().id()),
+        };
+        let item = T::try_from_deserialize(de, &mut commands, entity, entity_fetcher, &mut ctx);
+        item.map(|item| { commands.entity(entity).insert(item); })
+    };
+    state.apply(world);
+    result.map_err(SaloError::deserialization)
+}
@@ -0,0 +1,71 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Commands, Res, ResMut, Resource};
+#[cfg(feature="hierarchy")]
+use bevy_hierarchy::BuildChildren;
+
+use crate::saveload::EntityParent;
+use crate::{DeserializeContext, EntityPath, Marker};
+
+/// Loads records of one type only, inserted by
+/// [`load_type_records`](crate::SaveLoadExtension::load_type_records).
+#[derive(Debug, Resource)]
+pub(crate) struct TypeRecords<M: Marker> {
+    pub(crate) type_name: Cow<'static, str>,
+    parent: Option<Entity>,
+    /// Paths of records at the root, attached to `parent`.
+    roots: Vec<EntityPath>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> TypeRecords<M> {
+    pub(crate) fn new(type_name: Cow<'static, str>, parent: Option<Entity>) -> Self {
+        Self { type_name, parent, roots: Vec::new(), p: PhantomData }
+    }
+
+    /// The same filter without collected roots, for later chunks of [`PendingLoad`](crate::PendingLoad).
+    pub(crate) fn for_chunk(&self) -> Self {
+        Self::new(self.type_name.clone(), self.parent)
+    }
+}
+
+/// Record paths of root records before they are loaded.
+pub(crate) fn collect_type_roots<M: Marker>(
+    records: Option<ResMut<TypeRecords<M>>>,
+    ctx: Res<DeserializeContext<M>>,
+) {
+    let Some(mut records) = records else { return };
+    if records.parent.is_none() {
+        return;
+    }
+    let roots = ctx.components.get(records.type_name.as_ref()).into_iter().flatten()
+        .filter(|r| matches!(r.parent, EntityParent::Root) && !r.path.is_unique())
+        .map(|r| r.path.clone())
+        .collect();
+    records.roots = roots;
+}
+
+/// Attach entities of root records to the parent, ignored without the `hierarchy` feature.
+pub(crate) fn attach_type_roots<M: Marker>(
+    mut commands: Commands,
+    records: Option<Res<TypeRecords<M>>>,
+    ctx: Res<DeserializeContext<M>>,
+) {
+    let Some(records) = records else { return };
+    let Some(parent) = records.parent else { return };
+    for path in records.roots.iter() {
+        if let Some(entity) = ctx.path_map.get(path) {
+            attach(&mut commands, parent, *entity);
+        }
+    }
+}
+
+#[cfg(feature="hierarchy")]
+pub(crate) fn attach(commands: &mut Commands, parent: Entity, child: Entity) {
+    commands.entity(parent).add_child(child);
+}
+
+#[cfg(not(feature="hierarchy"))]
+pub(crate) fn attach(_: &mut Commands, _: Entity, _: Entity) {}
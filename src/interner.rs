@@ -1,7 +1,17 @@
 
 /// Create an integer based enum and a resource that manages its associated strings.
+///
+/// With `#[save]`, i.e. `interned_enum!(#[save] ElementsServer, Elements: u64 { .. })`,
+/// the resource implements [`SaveLoadResCore`](crate::SaveLoadResCore) as its list of strings,
+/// so interned values stay valid across sessions.
+/// The loaded server replaces the resource at the end of the load,
+/// strings interned by `from_deserialize` of the same load are not kept.
 #[macro_export]
 macro_rules! interned_enum {
+    (#[save] $res: ident, $($rest: tt)*) => {
+        $crate::interned_enum!($res, $($rest)*);
+        $crate::__interned_save!($res);
+    };
     ($res: ident, $name: ident : $repr: ident {$($fields: ident),* $(,)*}) => {
         $crate::interned_enum!($res, $name : $repr {} (0) $($fields),*);
     };
@@ -16,6 +26,7 @@ macro_rules! interned_enum {
         pub struct $res {
             flags: std::collections::HashMap<String, $repr>,
             names: Vec<String>,
+            frozen: bool,
         }
 
         impl ::std::default::Default for $res {
@@ -24,38 +35,87 @@ macro_rules! interned_enum {
             }
         }
 
+        // Not every helper is used by every server.
+        #[allow(dead_code)]
         impl $res {
             pub fn new() -> Self {
                 Self {
                     flags: std::collections::HashMap::from([
                         $((stringify!($fields).to_owned(), $value)),*
                     ]),
-                    names: vec![$(stringify!($fields).to_owned()),*]
+                    names: vec![$(stringify!($fields).to_owned()),*],
+                    frozen: false,
                 }
             }
 
-            pub fn len(&self) -> $repr {
-                self.names.len() as $repr
+            /// Maximum number of variants.
+            pub fn capacity(&self) -> usize {
+                usize::try_from(<$repr>::MAX).map_or(usize::MAX, |x| x.saturating_add(1))
+            }
+
+            /// Disallow interning new strings, useful for validating loaded data.
+            pub fn freeze(&mut self) {
+                self.frozen = true
+            }
+
+            pub fn unfreeze(&mut self) {
+                self.frozen = false
+            }
+
+            pub fn is_frozen(&self) -> bool {
+                self.frozen
             }
 
+            pub fn len(&self) -> usize {
+                self.names.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.names.is_empty()
+            }
+
+            /// Reset to the initial variants, does not unfreeze.
             pub fn clear(&mut self) {
-                *self = Self::new()
+                *self = Self {
+                    frozen: self.frozen,
+                    ..Self::new()
+                }
             }
 
             pub fn try_get(&self, s: &str) -> Option<$name> {
                 self.flags.get(s).map(|v| $name(*v))
             }
 
+            /// Get or intern a string.
+            /// 
+            /// # Panics
+            /// 
+            /// If frozen or out of capacity, use `try_intern` to handle these cases.
             pub fn get(&mut self, s: &str) -> $name {
-                let len = self.len();
-                match self.flags.get(s) {
-                    Some(v) => $name(*v),
-                    None => {
-                        self.flags.insert(s.to_owned(), len);
-                        self.names.push(s.to_owned());
-                        $name(len)
-                    }
+                match self.try_intern(s) {
+                    Ok(v) => v,
+                    Err(e) => panic!("{}", e),
+                }
+            }
+
+            /// Get or intern a string, fails if frozen or out of capacity.
+            pub fn try_intern(&mut self, s: &str) -> Result<$name, $crate::InternError> {
+                if let Some(v) = self.flags.get(s) {
+                    return Ok($name(*v));
                 }
+                if self.frozen {
+                    return Err($crate::InternError::Frozen(stringify!($res), s.to_owned()));
+                }
+                let Ok(len) = <$repr>::try_from(self.names.len()) else {
+                    return Err($crate::InternError::Overflow(stringify!($res), s.to_owned()));
+                };
+                self.push_name(s);
+                Ok($name(len))
+            }
+
+            fn push_name(&mut self, s: &str) {
+                self.flags.insert(s.to_owned(), self.names.len() as $repr);
+                self.names.push(s.to_owned());
             }
 
             pub fn as_str(&self, value: $name) -> &str {
@@ -69,6 +129,7 @@ macro_rules! interned_enum {
         #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ::bevy_ecs::component::Component)]
         pub struct $name($repr);
 
+        #[allow(dead_code)]
         impl $name {
             $(
                 #[allow(non_upper_case_globals)]
@@ -83,135 +144,177 @@ macro_rules! interned_enum {
     };
 }
 
-#[cfg(test)]
-mod test {
-    use bevy_ecs::{system::{Res, ResMut}, entity::Entity};
-
-    use crate::EntityPath;
-
-    interned_enum!(ElementsServer, Elements: u64 {
-        Water, Earth, Fire, Air
-    });
-
-    impl crate::SaveLoad for Elements {
-        type Ser<'ser> = &'ser str;
-        type De = String;
-        type Context<'w, 's> = Res<'w, ElementsServer>;
-        type ContextMut<'w, 's> = ResMut<'s, ElementsServer>;
-
-        fn to_serializable<'t>(&'t self, 
-            _: Entity,
-            _: impl Fn(Entity) -> EntityPath, 
-            res: &'t Res<ElementsServer>
-        ) -> Self::Ser<'t> {
-            res.as_str(*self)
-        }
-
-        fn from_deserialize<'w>(
-            de: Self::De, 
-            _: &mut bevy_ecs::system::Commands,
-            _: bevy_ecs::entity::Entity,
-            _: impl FnMut(&mut bevy_ecs::system::Commands, &crate::EntityPath) -> bevy_ecs::entity::Entity, 
-            res: &mut ResMut<ElementsServer>
-        ) -> Self {
-            res.get(&de)
-        }
-    }
-}
-
 /// Create an integer based flags and a resource that manages its associated strings.
+/// 
+/// The flags implement [`SaveLoad`](crate::SaveLoad) and are serialized as `"A|B"` strings.
+/// The resource can be saved with `#[save]`, see [`interned_enum!`].
+///
+/// Flags are stored in an unsigned integer or `[u64; N]` for more than 128 flags,
+/// see [`FlagBits`](crate::FlagBits).
 #[macro_export]
 macro_rules! interned_flags {
-    ($res: ident, $name: ident : $repr: ident {$($fields: ident),* $(,)*}) => {
+    (#[save] $res: ident, $($rest: tt)*) => {
+        $crate::interned_flags!($res, $($rest)*);
+        $crate::__interned_save!($res);
+    };
+    ($res: ident, $name: ident : $repr: ty {$($fields: ident),* $(,)*}) => {
         $crate::interned_flags!($res, $name : $repr {} (0) $($fields),*);
     };
-    ($res: ident, $name: ident : $repr: ident {$($fields: ident= $value: expr),*} ($index: expr) $first: ident $(, $rest: ident)*) => {
+    ($res: ident, $name: ident : $repr: ty {$($fields: ident= $value: expr),*} ($index: expr) $first: ident $(, $rest: ident)*) => {
         $crate::interned_flags!($res, $name : $repr {$($fields= $value,)* $first = $index} ($index + 1) $($rest),*);
     };
-    ($res: ident, $name: ident : $repr: ident {$($fields: ident= $value: expr),*} ($index: expr)) => {
+    ($res: ident, $name: ident : $repr: ty {$($fields: ident= $value: expr),*} ($index: expr)) => {
         $crate::interned_flags!($res, $name : $repr {$($fields=$value),*});
     };
-    ($res: ident, $name: ident : $repr: ident {$($fields: ident= $value: expr),* $(,)*}) => {
+    ($res: ident, $name: ident : $repr: ty {$($fields: ident= $value: expr),* $(,)*}) => {
         #[derive(Debug, Clone, ::bevy_ecs::system::Resource)]
         pub struct $res {
-            flags: std::collections::HashMap<String, $repr>,
+            flags: std::collections::HashMap<String, <$repr as $crate::FlagBits>::Index>,
             names: Vec<String>,
+            frozen: bool,
+        }
+
+        impl ::std::default::Default for $res {
+            fn default() -> Self{
+                Self::new()
+            }
         }
 
+        // Not every helper is used by every server.
+        #[allow(dead_code)]
         impl $res {
             pub fn new() -> Self {
                 Self {
                     flags: std::collections::HashMap::from([
                         $((stringify!($fields).to_owned(), $value)),*
                     ]),
-                    names: vec![$(stringify!($fields).to_owned()),*]
+                    names: vec![$(stringify!($fields).to_owned()),*],
+                    frozen: false,
                 }
             }
 
-            pub fn len(&self) -> $repr {
-                self.names.len() as $repr
+            /// Maximum number of flags.
+            pub fn capacity(&self) -> usize {
+                <$repr as $crate::FlagBits>::BITS
+            }
+
+            /// Disallow interning new strings, useful for validating loaded data.
+            pub fn freeze(&mut self) {
+                self.frozen = true
+            }
+
+            pub fn unfreeze(&mut self) {
+                self.frozen = false
+            }
+
+            pub fn is_frozen(&self) -> bool {
+                self.frozen
+            }
+
+            pub fn len(&self) -> <$repr as $crate::FlagBits>::Index {
+                <$repr as $crate::FlagBits>::index(self.names.len())
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.names.is_empty()
             }
 
+            /// Reset to the initial flags, does not unfreeze.
             pub fn clear(&mut self) {
-                *self = Self::new()
+                *self = Self {
+                    frozen: self.frozen,
+                    ..Self::new()
+                }
             }
 
+            /// Get or intern a single flag.
+            /// 
+            /// # Panics
+            /// 
+            /// If frozen or out of capacity, use `try_intern_single` to handle these cases.
             pub fn get_single(&mut self, s: &str) -> $name {
-                let len = self.len();
-                match self.flags.get(s) {
-                    Some(v) => $name(1 << *v),
-                    None => {
-                        self.flags.insert(s.to_owned(), len);
-                        self.names.push(s.to_owned());
-                        $name(1 << len)
-                    }
+                match self.try_intern_single(s) {
+                    Ok(v) => v,
+                    Err(e) => panic!("{}", e),
+                }
+            }
+
+            /// Get or intern a single flag, fails if frozen or out of capacity.
+            pub fn try_intern_single(&mut self, s: &str) -> Result<$name, $crate::InternError> {
+                if let Some(v) = self.try_get_single(s) {
+                    return Ok(v);
+                }
+                if self.frozen {
+                    return Err($crate::InternError::Frozen(stringify!($res), s.to_owned()));
+                }
+                if self.names.len() >= self.capacity() {
+                    return Err($crate::InternError::Overflow(stringify!($res), s.to_owned()));
                 }
+                let len = self.names.len();
+                self.push_name(s);
+                Ok($name(<$repr as $crate::FlagBits>::bit(len)))
+            }
+
+            fn push_name(&mut self, s: &str) {
+                self.flags.insert(s.to_owned(), <$repr as $crate::FlagBits>::index(self.names.len()));
+                self.names.push(s.to_owned());
+            }
+
+            /// Get or intern `|` delimited flags, fails if frozen or out of capacity.
+            pub fn try_intern(&mut self, s: &str) -> Result<$name, $crate::InternError> {
+                Self::split(s).try_fold($name::None, |a, b| Ok(a | self.try_intern_single(b)?))
+            }
+
+            /// Split `|` delimited flags, an empty string has no flags.
+            fn split(s: &str) -> impl Iterator<Item = &str> {
+                s.split('|').filter(|x| !x.is_empty())
             }
 
             pub fn try_get_single(&self, s: &str) -> Option<$name> {
-                self.flags.get(s).map(|v| $name(1 << *v))
+                self.flags.get(s).map(|v| $name(<$repr as $crate::FlagBits>::bit(<$repr as $crate::FlagBits>::position(*v))))
             }
 
             pub fn get(&mut self, s: &str) -> $name {
-                s.split('|').map(|x| self.get_single(x)).fold($name::None, |a, b| a|b)
+                Self::split(s).map(|x| self.get_single(x)).fold($name::None, |a, b| a|b)
             }
 
             pub fn try_get(&self, s: &str) -> Option<$name> {
-                s.split('|').map(|x| self.try_get_single(x)).fold(Some($name::None), |a, b| Some(a?|b?))
+                Self::split(s).map(|x| self.try_get_single(x)).fold(Some($name::None), |a, b| Some(a?|b?))
             }
 
+            /// Names of the flags joined by `|`, empty if no flags are set.
             pub fn as_str(&self, value: $name) -> String {
-                if value == $name::None {
-                    return "None".to_owned()
-                }
-                let mut v = value.0;
-                let mut index = 0;
                 let mut result = Vec::new();
-                while v > 0 {
-                    if v & 1 == 1 {
-                        let s = match self.names.get(index) {
-                            Some(v) => v.as_str(),
-                            None => panic!("Invalid enum variant {:?}.", value),
-                        };
-                        result.push(s);
+                for index in 0..<$repr as $crate::FlagBits>::BITS {
+                    if !<$repr as $crate::FlagBits>::has(&value.0, index) {
+                        continue;
+                    }
+                    match self.names.get(index) {
+                        Some(v) => result.push(v.as_str()),
+                        None => panic!("Invalid enum variant {:?}.", value),
                     }
-                    v >>= 1;
-                    index += 1;
                 }
                 result.join("|")
             }
         }
 
-        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ::bevy_ecs::component::Component)]
         pub struct $name($repr);
 
+        impl ::std::default::Default for $name {
+            fn default() -> Self {
+                Self::None
+            }
+        }
+
+        #[allow(dead_code)]
         impl $name {
             #[allow(non_upper_case_globals)]
-            pub const None: Self = Self(0);
+            pub const None: Self = Self(<$repr as $crate::FlagBits>::EMPTY);
             
             $(
                 #[allow(non_upper_case_globals)]
-                pub const $fields: Self = Self(1 << ($value));
+                pub const $fields: Self = Self($crate::FlagBit::<$repr>::at($value));
             )*
 
             pub fn value(&self) -> $repr {
@@ -219,64 +322,460 @@ macro_rules! interned_flags {
             }
 
             pub fn contains(&self, other: Self) -> bool {
-                self.0 & other.0 == other.0
+                <$repr as $crate::FlagBits>::and(self.0, other.0) == other.0
             }
 
             pub fn intersects(&self, other: Self) -> bool {
-                self.0 & other.0 > 0
+                <$repr as $crate::FlagBits>::and(self.0, other.0) != <$repr as $crate::FlagBits>::EMPTY
+            }
+
+            /// Iterate through set bits as single flags.
+            pub fn iter(&self) -> impl Iterator<Item = Self> {
+                let v = self.0;
+                (0..<$repr as $crate::FlagBits>::BITS)
+                    .filter(move |i| <$repr as $crate::FlagBits>::has(&v, *i))
+                    .map(|i| Self(<$repr as $crate::FlagBits>::bit(i)))
+            }
+        }
+
+        /// Serialized as names joined by `|`, i.e. `"Fire|Water"`, or `""` if empty.
+        impl $crate::SaveLoad for $name {
+            type Ser<'ser> = String;
+            type De = String;
+            type Context<'w, 's> = ::bevy_ecs::system::Res<'w, $res>;
+            type ContextMut<'w, 's> = ::bevy_ecs::system::ResMut<'s, $res>;
+
+            fn type_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(stringify!($name))
+            }
+
+            fn to_serializable<'t>(&'t self, 
+                _: ::bevy_ecs::entity::Entity,
+                _: impl Fn(::bevy_ecs::entity::Entity) -> $crate::EntityPath, 
+                res: &'t ::bevy_ecs::system::Res<$res>
+            ) -> Self::Ser<'t> {
+                res.as_str(*self)
+            }
+
+            fn from_deserialize(
+                de: Self::De, 
+                _: &mut ::bevy_ecs::system::Commands,
+                _: ::bevy_ecs::entity::Entity,
+                _: impl FnMut(&mut ::bevy_ecs::system::Commands, &$crate::EntityPath) -> ::bevy_ecs::entity::Entity, 
+                res: &mut ::bevy_ecs::system::ResMut<$res>
+            ) -> Self {
+                res.get(&de)
+            }
+
+            /// Fails on unknown strings if the server is frozen or out of capacity.
+            fn try_from_deserialize(
+                de: Self::De, 
+                _: &mut ::bevy_ecs::system::Commands,
+                _: ::bevy_ecs::entity::Entity,
+                _: impl FnMut(&mut ::bevy_ecs::system::Commands, &$crate::EntityPath) -> ::bevy_ecs::entity::Entity, 
+                res: &mut ::bevy_ecs::system::ResMut<$res>
+            ) -> $crate::__private::anyhow::Result<Self> {
+                Ok(res.try_intern(&de)?)
             }
         }
 
         impl std::ops::Sub for $name {
             type Output = Self;
             fn sub(self, rhs: Self) -> Self{
-                Self(self.0 - (self.0 & rhs.0))
+                Self(<$repr as $crate::FlagBits>::and_not(self.0, rhs.0))
             }
         }
 
         impl std::ops::BitOr for $name {
             type Output = Self;
             fn bitor(self, rhs: Self) -> Self{
-                Self(self.0 | rhs.0)
+                Self(<$repr as $crate::FlagBits>::or(self.0, rhs.0))
             }
         }
 
         impl std::ops::BitAnd for $name {
             type Output = Self;
             fn bitand(self, rhs: Self) -> Self{
-                Self(self.0 & rhs.0)
+                Self(<$repr as $crate::FlagBits>::and(self.0, rhs.0))
             }
         }
 
         impl std::ops::BitXor for $name {
             type Output = Self;
             fn bitxor(self, rhs: Self) -> Self{
-                Self(self.0 ^ rhs.0)
+                Self(<$repr as $crate::FlagBits>::xor(self.0, rhs.0))
             }
         }
 
         impl std::ops::SubAssign for $name {
             fn sub_assign(&mut self, rhs: Self){
-                self.0 -= self.0 & rhs.0
+                *self = *self - rhs
             }
         }
 
         impl std::ops::BitOrAssign for $name {
             fn bitor_assign(&mut self, rhs: Self){
-                self.0 |= rhs.0
+                *self = *self | rhs
             }
         }
 
         impl std::ops::BitAndAssign for $name {
             fn bitand_assign(&mut self, rhs: Self){
-                self.0 &= rhs.0
+                *self = *self & rhs
             }
         }
 
         impl std::ops::BitXorAssign for $name {
             fn bitxor_assign(&mut self, rhs: Self){
-                self.0 ^= rhs.0
+                *self = *self ^ rhs
             }
         }
     };
-}
\ No newline at end of file
+}
+
+/// Storage of `interned_flags!`, implemented for unsigned integers and `[u64; N]`.
+pub trait FlagBits: Copy + Eq + std::hash::Hash + std::fmt::Debug {
+    /// Index of a flag in the server, `Self` for integers and `usize` for arrays.
+    type Index: Copy + Eq + std::fmt::Debug;
+    /// Number of flags.
+    const BITS: usize;
+    /// No flags set.
+    const EMPTY: Self;
+
+    fn index(position: usize) -> Self::Index;
+    fn position(index: Self::Index) -> usize;
+    /// The flag at `position`.
+    fn bit(position: usize) -> Self;
+    /// Returns true if the flag at `position` is set.
+    fn has(&self, position: usize) -> bool;
+    fn or(self, other: Self) -> Self;
+    fn and(self, other: Self) -> Self;
+    fn xor(self, other: Self) -> Self;
+    /// Flags of `self` not in `other`.
+    fn and_not(self, other: Self) -> Self;
+}
+
+/// Const constructor of single flags for `interned_flags!`.
+#[doc(hidden)]
+pub struct FlagBit<T>(std::marker::PhantomData<T>);
+
+macro_rules! flag_bits {
+    ($($ty: ident),*) => {$(
+        impl FlagBits for $ty {
+            type Index = $ty;
+            const BITS: usize = $ty::BITS as usize;
+            const EMPTY: Self = 0;
+
+            fn index(position: usize) -> Self::Index {
+                position as $ty
+            }
+            fn position(index: Self::Index) -> usize {
+                index as usize
+            }
+            fn bit(position: usize) -> Self {
+                1 << position
+            }
+            fn has(&self, position: usize) -> bool {
+                (self >> position) & 1 == 1
+            }
+            fn or(self, other: Self) -> Self {
+                self | other
+            }
+            fn and(self, other: Self) -> Self {
+                self & other
+            }
+            fn xor(self, other: Self) -> Self {
+                self ^ other
+            }
+            fn and_not(self, other: Self) -> Self {
+                self & !other
+            }
+        }
+
+        impl FlagBit<$ty> {
+            pub const fn at(position: usize) -> $ty {
+                1 << position
+            }
+        }
+    )*};
+}
+
+flag_bits!(u8, u16, u32, u64, u128, usize);
+
+impl<const N: usize> FlagBits for [u64; N] {
+    type Index = usize;
+    const BITS: usize = N * 64;
+    const EMPTY: Self = [0; N];
+
+    fn index(position: usize) -> Self::Index {
+        position
+    }
+    fn position(index: Self::Index) -> usize {
+        index
+    }
+    fn bit(position: usize) -> Self {
+        FlagBit::<Self>::at(position)
+    }
+    fn has(&self, position: usize) -> bool {
+        (self[position / 64] >> (position % 64)) & 1 == 1
+    }
+    fn or(self, other: Self) -> Self {
+        std::array::from_fn(|i| self[i] | other[i])
+    }
+    fn and(self, other: Self) -> Self {
+        std::array::from_fn(|i| self[i] & other[i])
+    }
+    fn xor(self, other: Self) -> Self {
+        std::array::from_fn(|i| self[i] ^ other[i])
+    }
+    fn and_not(self, other: Self) -> Self {
+        std::array::from_fn(|i| self[i] & !other[i])
+    }
+}
+
+impl<const N: usize> FlagBit<[u64; N]> {
+    pub const fn at(position: usize) -> [u64; N] {
+        let mut bits = [0; N];
+        bits[position / 64] = 1 << (position % 64);
+        bits
+    }
+}
+
+/// Serialize a server as its strings in order, loading replaces runtime interned strings
+/// and fails if the initial variants changed since the save.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __interned_save {
+    ($res: ident) => {
+        impl $crate::__private::serde::Serialize for $res {
+            fn serialize<S: $crate::__private::serde::Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                $crate::__private::serde::Serialize::serialize(&self.names, serializer)
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $res {
+            fn deserialize<D: $crate::__private::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+                use $crate::__private::serde::de::Error;
+                let names: Vec<String> = $crate::__private::serde::Deserialize::deserialize(deserializer)?;
+                let mut server = Self::new();
+                if !names.starts_with(&server.names) {
+                    return Err(D::Error::custom(format!("Initial variants of {} do not match the save.", stringify!($res))));
+                }
+                for name in &names[server.names.len()..] {
+                    if server.flags.contains_key(name) {
+                        return Err(D::Error::custom(format!("Duplicated string {:?} in {}.", name, stringify!($res))));
+                    }
+                    if server.names.len() >= server.capacity() {
+                        return Err(D::Error::custom($crate::InternError::Overflow(stringify!($res), name.clone())));
+                    }
+                    server.push_name(name);
+                }
+                Ok(server)
+            }
+        }
+
+        impl $crate::SaveLoadResCore for $res {
+            fn type_name() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(stringify!($res))
+            }
+        }
+    };
+}
+
+/// Error produced by `interned_enum!` and `interned_flags!` servers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InternError {
+    /// The server is frozen and the string is unknown.
+    Frozen(&'static str, String),
+    /// The server has no more room for the string.
+    Overflow(&'static str, String),
+}
+
+impl std::fmt::Display for InternError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InternError::Frozen(server, s) => write!(f, "Unknown string {:?} in frozen {}.", s, server),
+            InternError::Overflow(server, s) => write!(f, "Cannot intern {:?}, {} is out of capacity.", s, server),
+        }
+    }
+}
+
+impl std::error::Error for InternError {}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::{system::{Res, ResMut}, entity::Entity};
+
+    use crate::EntityPath;
+
+    interned_enum!(ElementsServer, Elements: u64 {
+        Water, Earth, Fire, Air
+    });
+
+    impl crate::SaveLoad for Elements {
+        type Ser<'ser> = &'ser str;
+        type De = String;
+        type Context<'w, 's> = Res<'w, ElementsServer>;
+        type ContextMut<'w, 's> = ResMut<'s, ElementsServer>;
+
+        fn to_serializable<'t>(&'t self, 
+            _: Entity,
+            _: impl Fn(Entity) -> EntityPath, 
+            res: &'t Res<ElementsServer>
+        ) -> Self::Ser<'t> {
+            res.as_str(*self)
+        }
+
+        fn from_deserialize<'w>(
+            de: Self::De, 
+            _: &mut bevy_ecs::system::Commands,
+            _: bevy_ecs::entity::Entity,
+            _: impl FnMut(&mut bevy_ecs::system::Commands, &crate::EntityPath) -> bevy_ecs::entity::Entity, 
+            res: &mut ResMut<ElementsServer>
+        ) -> Self {
+            res.get(&de)
+        }
+    }
+    #[test]
+    fn interned_enum() {
+        let mut server = ElementsServer::new();
+        assert_eq!(server.len(), 4);
+        assert_eq!(server.get("Fire"), Elements::Fire);
+        assert_eq!(server.as_str(Elements::Air), "Air");
+        let lightning = server.get("Lightning");
+        assert_eq!(lightning.value(), 4);
+        assert_eq!(server.try_get("Lightning"), Some(lightning));
+        assert_eq!(server.try_get("Ice"), None);
+        assert_eq!(Elements::Water.value(), 0);
+        assert_eq!(Elements::Earth.value(), 1);
+        server.clear();
+        assert!(!server.is_empty());
+        assert_eq!(server.try_get("Lightning"), None);
+    }
+    interned_flags!(TagsServer, Tags: u32 {
+        Fire, Water, Poison
+    });
+
+    #[test]
+    fn interned_flags() {
+        let mut server = TagsServer::default();
+        let tags = Tags::Fire | Tags::Poison;
+        assert_eq!(server.as_str(tags), "Fire|Poison");
+        assert_eq!(server.as_str(Tags::default()), "");
+        assert_eq!(server.get(""), Tags::None);
+        assert_eq!(server.try_get(""), Some(Tags::None));
+        assert_eq!(server.len(), 3);
+        assert_eq!(server.get("Poison|Fire"), tags);
+        assert_eq!(tags.iter().collect::<Vec<_>>(), [Tags::Fire, Tags::Poison]);
+        let holy = server.get_single("Holy");
+        assert_eq!(server.try_get("Holy|Water"), Some(holy | Tags::Water));
+        assert_eq!(server.try_get("Holy|Ice"), None);
+        assert_eq!(holy.value(), 1 << 3);
+        assert!(tags.contains(Tags::Fire));
+        assert!(!tags.contains(Tags::Fire | Tags::Water));
+        assert!(tags.intersects(Tags::Fire | Tags::Water));
+        server.clear();
+        assert!(!server.is_empty());
+        assert_eq!(server.try_get_single("Holy"), None);
+        server.freeze();
+        assert!(server.is_frozen());
+        assert!(matches!(server.try_intern("Fire|Holy"), Err(crate::InternError::Frozen(..))));
+        server.unfreeze();
+        for i in 3..32 {
+            server.get_single(&i.to_string());
+        }
+        assert_eq!(server.capacity(), 32);
+        assert!(matches!(server.try_intern_single("Overflow"), Err(crate::InternError::Overflow(..))));
+    }
+
+    #[test]
+    fn interned_flags_roundtrip() {
+        use crate::immediate::{apply_record, save_component};
+        use crate::methods::SerdeJson;
+        let mut world = bevy_ecs::world::World::new();
+        world.init_resource::<TagsServer>();
+        for tags in [Tags::None, Tags::Fire | Tags::Water] {
+            let entity = world.spawn(tags).id();
+            let value = save_component::<Tags, SerdeJson>(&mut world, entity).unwrap();
+            apply_record::<Tags, SerdeJson>(&mut world, entity, value).unwrap();
+            assert_eq!(world.get::<Tags>(entity), Some(&tags));
+        }
+        assert_eq!(world.resource::<TagsServer>().len(), 3);
+
+        world.resource_mut::<TagsServer>().freeze();
+        let entity = world.spawn_empty().id();
+        let value = serde_json::Value::String("Fire|Holy".to_owned());
+        assert!(apply_record::<Tags, SerdeJson>(&mut world, entity, value).is_err());
+        assert!(world.get::<Tags>(entity).is_none());
+    }
+
+    interned_enum!(SmallServer, Small: u8 { Zero });
+
+    #[test]
+    fn interned_enum_capacity() {
+        let mut server = SmallServer::new();
+        assert_eq!(server.capacity(), 256);
+        for i in 1..256 {
+            assert_eq!(server.get(&i.to_string()).value() as usize, i);
+        }
+        assert_eq!(server.as_str(Small::Zero), "Zero");
+        assert!(matches!(server.try_intern("256"), Err(crate::InternError::Overflow(..))));
+        assert_eq!(server.try_intern("255"), Ok(Small(255)));
+    }
+
+    interned_enum!(#[save] SavedServer, Saved: u8 { A, B });
+    interned_flags!(#[save] SavedTagsServer, SavedTags: u8 { A });
+
+    #[test]
+    fn interned_save() {
+        use crate::SaveLoadResCore;
+        let mut server = SavedServer::new();
+        let c = server.get("C");
+        let json = serde_json::to_string(&server).unwrap();
+        assert_eq!(json, r#"["A","B","C"]"#);
+        let mut loaded: SavedServer = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.try_get("C"), Some(c));
+        assert_eq!(loaded.get("D").value(), 3);
+        assert_eq!(<SavedServer as SaveLoadResCore>::type_name(), "SavedServer");
+
+        assert!(serde_json::from_str::<SavedServer>(r#"["B","A"]"#).is_err());
+        assert!(serde_json::from_str::<SavedServer>(r#"["A","B","C","C"]"#).is_err());
+
+        let mut tags = SavedTagsServer::new();
+        let b = tags.get_single("B");
+        let loaded: SavedTagsServer = serde_json::from_str(&serde_json::to_string(&tags).unwrap()).unwrap();
+        assert_eq!(loaded.try_get("A|B"), Some(SavedTags::A | b));
+        let full = serde_json::to_string(&std::iter::once("A".to_owned()).chain((1..9).map(|i| i.to_string())).collect::<Vec<_>>()).unwrap();
+        assert!(matches!(serde_json::from_str::<SavedTagsServer>(&full), Err(e) if e.to_string().contains("out of capacity")));
+    }
+
+    interned_flags!(WideServer, Wide: u128 { A });
+    interned_flags!(LargeServer, Large: [u64; 3] { A, B });
+
+    #[test]
+    fn interned_flags_width() {
+        let mut wide = WideServer::new();
+        for i in 1..128 {
+            assert_eq!(wide.get_single(&i.to_string()).value(), 1 << i);
+        }
+        assert_eq!(wide.len(), 128);
+        assert!(matches!(wide.try_intern_single("128"), Err(crate::InternError::Overflow(..))));
+        assert_eq!(wide.try_get_single("128"), None);
+
+        let mut large = LargeServer::new();
+        assert_eq!(large.capacity(), 192);
+        assert_eq!(Large::B.value(), [2, 0, 0]);
+        for i in 2..192 {
+            large.get_single(&i.to_string());
+        }
+        let flags = large.get("A|100|191");
+        assert_eq!(flags.value(), [1, 1 << 36, 1 << 63]);
+        assert_eq!(large.as_str(flags), "A|100|191");
+        assert_eq!(flags.iter().count(), 3);
+        assert!(flags.contains(Large::A));
+        assert!(!flags.intersects(Large::B));
+        assert_eq!(flags - Large::A, large.get("100|191"));
+        assert_eq!(Large::default(), Large::None);
+        assert!(matches!(large.try_intern("A|192"), Err(crate::InternError::Overflow(..))));
+    }
+}
@@ -77,8 +77,8 @@
 //! 
 //! ```
 //! # /*
-//! world.load_from_file::<All>("test.ron");
-//! world.save_to_file::<All>("test.json");
+//! world.load::<All>(LoadSource::File("test.ron"));
+//! world.save::<All>(SaveTarget::File("test.json"));
 //! world.deserialize_from::<All>(bytes);
 //! let bytes = world.serialize_to::<All>();
 //! # */
@@ -144,6 +144,41 @@
 //! }
 //! ```
 //! 
+//! With the `derive` feature, this can be written as:
+//! 
+//! ```
+//! # /*
+//! #[derive(Serialize, Deserialize, Component, SaveLoad)]
+//! #[salo(name = "weapon", path = "self.name")]
+//! struct Weapon  { name: String }
+//! # */
+//! ```
+//! 
+//! `::` in literal names is a compile error.
+//! 
+//! Fields can be skipped, renamed or defaulted in the save without changing the serde implementation:
+//! 
+//! ```
+//! # /*
+//! #[derive(Serialize, Deserialize, Component, SaveLoad)]
+//! struct Stats {
+//!     #[salo(rename = "hp")]
+//!     health: u32,
+//!     #[salo(default)]
+//!     mana: u32,
+//!     #[salo(skip)]
+//!     cache: Vec<u32>,
+//! }
+//! # */
+//! ```
+//! 
+//! In JSON and RON, `#[salo(inline)]` (or `fn inline() -> bool { true }`) writes fields of a struct
+//! next to the record's path instead of nested in `value`:
+//! 
+//! ```json
+//! {"path":"Origin","x":1,"y":2}
+//! ```
+//! 
 //! ## SaveLoadMapped
 //! 
 //! [`SaveLoadMapped`] is just like `SaveLoadCore` but you can map non-serializable struct into 
@@ -191,11 +226,33 @@
 //! }
 //! ```
 //! 
+//! Maps keyed by `Entity` can use [`SaloEntityMap`], which saves keys as paths
+//! and can be registered as either a component or a resource.
+//! 
+//! ```
+//! # use bevy_app::App;
+//! # use bevy_salo::{*, methods::SerdeJson};
+//! # let mut app = App::new();
+//! # type All = bevy_salo::All<SerdeJson>;
+//! app.add_plugins(SaveLoadPlugin::new::<All>()
+//!     .register::<SaloEntityMap<u32>>()
+//!     .register_resource::<SaloEntityMap<String>>()
+//! );
+//! ```
+//! 
+//! With the `asset` feature, `SaloHandle` saves a `Handle` as its asset path
+//! and loads it with the `AssetServer`.
+//! 
+//! Tightly coupled components can be saved as a single record with [`SaveLoadBundle`],
+//! registered with [`register_bundle`](SaveLoadPlugin::register_bundle).
+//! 
 //! # Paths
 //! 
 //! `bevy_salo` records each entity as either its Entity ID or its path. 
 //! Entity ID is only used for disambiguation, 
 //! while path allow matching with existing entity.
+//! Unnamed entities with a [`PersistentId`] are recorded by their id instead,
+//! which can also be matched across sessions.
 //! 
 //! Each component can optionally provide a name with the `path_name` function
 //! defined in the aforementioned traits for their associated entity. 
@@ -215,7 +272,9 @@
 //! ```
 //! 
 //! 
-//! This panics for conflicting names.
+//! This panics for conflicting names by default, see [`ConflictPolicy`] in [`SaloConfig`].
+//! Conflicts of the last save or load are listed in [`PathCollisions`],
+//! with components and ancestors of the entities involved and a suggested rename.
 //! 
 //! ```
 //! # /*
@@ -239,6 +298,10 @@
 //! into an existing entity `"characters"`.
 //! 
 //! 
+//! Names containing `::` are escaped as `\::` in paths, enable the `legacy-paths`
+//! feature to read saves written before escaping was added, or parse their paths
+//! with [`wire::PathEncoding`].
+//!
 //! Pathed entities must have unique paths, but duplicated names are allowed.
 //! 
 //! ```
@@ -253,6 +316,57 @@
 //! # */
 //! ```
 //! 
+//! A logical root can be set per marker with [`SaloConfig`], which is prepended
+//! to all paths. Records saved under a different root will not match existing entities.
+//! 
+//! ```
+//! # /*
+//! world.resource_mut::<SaloConfig<All>>().set_root("level1");
+//! // Players::John is saved as level1::Players::John
+//! world.save::<All>(SaveTarget::File("level1.json"));
+//! # */
+//! ```
+//! 
+//! # Metadata
+//! 
+//! Insert a [`SaveMetadata`] resource to write a header before all records,
+//! which can be read back cheaply with [`peek_metadata`], i.e. for a save selection screen.
+//! 
+//! ```
+//! # /*
+//! world.insert_resource(SaveMetadata::<All>::new(&(timestamp, playtime))?);
+//! world.save::<All>(SaveTarget::File("save1.json"));
+//! let (timestamp, playtime) = peek_metadata::<All>(&bytes)?.unwrap().get()?;
+//! # */
+//! ```
+//! 
+//! # Deltas
+//! 
+//! [`save_delta`](SaveLoadExtension::save_delta) writes only items changed since the last complete save or load,
+//! and named items removed since. Apply it over the base with [`load_delta`](SaveLoadExtension::load_delta).
+//! 
+//! ```
+//! # /*
+//! world.save::<All>(SaveTarget::File("save1.json"));
+//! let delta = world.save_delta::<All, Vec<u8>>("save1.json")?;
+//! // later
+//! world.load::<All>(LoadSource::File("save1.json"));
+//! world.load_delta::<All>(&delta, "save1.json")?;
+//! # */
+//! ```
+//! 
+//! # Groups
+//! 
+//! Markers sharing a serialization method can be saved to one file as a [`MarkerGroup`],
+//! each marker runs its own schedules and is keyed by its name in the file.
+//! 
+//! ```
+//! # /*
+//! world.save_group_to_file::<(Terrain, Units)>("world.json")?;
+//! world.load_group_from_file::<(Terrain, Units)>("world.json")?;
+//! # */
+//! ```
+//! 
 //! # Warnings
 //! 
 //! When serializing, non-serializing parents of 
@@ -277,18 +391,136 @@
 pub mod methods;
 mod saveload;
 mod res;
+mod config;
+mod error;
+mod registry;
+mod state;
+mod policy;
+mod history;
+mod target;
+mod group;
+mod document;
+mod transfer;
+mod alias;
+mod tombstone;
+mod root;
+mod entity_map;
+mod anonymize;
+mod persistent;
+mod propagation;
+mod diagnostics;
+mod archive;
+mod bare;
+mod fingerprint;
+mod version;
+mod estimate;
+mod budget;
+mod disabled;
+mod analytics;
+mod bundle;
+mod marker;
+mod diff;
+mod convert;
+#[cfg(feature="sha2")]
+mod signing;
+#[cfg(feature="bevy_app")]
+mod autosave;
+#[cfg(feature="bevy_app")]
+mod request;
+#[cfg(feature="fs")]
+mod directory;
+#[cfg(feature="fs")]
+mod split;
+#[cfg(feature="fs")]
+pub(crate) use split::SplitOutput;
+#[cfg(feature="asset")]
+mod asset;
+mod import;
+mod anchor;
+mod tree;
+mod reentrant;
+mod validate;
+mod attributes;
+mod defaults;
+mod command;
+use reentrant::{QueuedSource, QueuedTarget};
+mod transform;
 
 use bevy_ecs::bundle::Bundle;
 use bevy_ecs::query::{ReadOnlyWorldQuery, With};
 use bevy_ecs::world::World;
+use bevy_ecs::schedule::Schedule;
 use methods::{SerializationMethod, SerdeJson};
 pub use saveload::*;
 pub use res::*;
+pub use config::*;
+use config::{check_throttle, stamp_throttle};
+use import::TypeRecords;
+use anchor::LoadAnchor;
+use tombstone::TombstoneBase;
+pub use error::*;
+pub use registry::*;
+pub use state::SaveState;
+pub use policy::{LoadPolicy, TypePolicy, LoadResolver, Resolution};
+pub use history::{SaloHistory, HistoryEntry, SaloOperation};
+#[cfg(feature="bevy_app")]
+pub use autosave::{AutoSavePlugin, AutoSaveConfig, AutoSaveState};
+#[cfg(feature="bevy_app")]
+pub use request::{SaveRequest, LoadRequest};
+pub use entity_map::SaloEntityMap;
+pub use anonymize::{AnonymizePolicy, Redaction};
+pub use persistent::{PersistentId, PersistentIdAllocator};
+pub use propagation::Marked;
+pub use archive::{ArchiveFormat, ArchiveOutput, ArchiveInput};
+pub use bare::BARE_ENTITY;
+pub use fingerprint::RegistryChanges;
+pub use estimate::{SaveSizeCache, SaveStats, TypeSize};
+pub use budget::PendingLoad;
+pub use disabled::SaloDisabled;
+pub use analytics::{SaloAnalytics, LoadSample, TypeStats, AnalyticsExporter};
+pub use bundle::SaveLoadBundle;
+pub use diagnostics::{PathCollisions, PathCollision, EntityDiagnostic};
+pub use diff::{diff, SaveDiff, ComponentDiff, DiffKind};
+pub use convert::convert;
+#[cfg(feature="sha2")]
+pub use signing::SectionSigning;
+pub use group::MarkerGroup;
+pub use document::{SaloDocument, SnapshotRegistry};
+pub use transfer::{transfer, TransferOptions, PathRemap};
+pub use alias::PathAliases;
+pub use root::PathRoot;
+pub use anchor::{SpawnAnchor, LoadOptions};
+pub use tree::{PathTree, PathNode};
+pub use validate::SaveReport;
+pub use attributes::{Attributes, RecordAttributes};
+pub use transform::{DocumentTransform, DocumentParts};
+pub use command::{SaveLoadCommandsExtension, SaveToFile, SaveToStorage, SaveToBytes, LoadFromFile, LoadFromStorage, LoadFromBytes};
+pub use target::{SaveTarget, LoadSource, Saved, OutputSpec, SavedOutputs, SaveStorage, MemoryStorage, SaloStorage};
+#[cfg(feature="fs")]
+pub use directory::SaveDirectory;
+#[cfg(feature="asset")]
+pub use asset::{SaloHandle, SavedHandle, AssetFallback};
+pub use interner::{InternError, FlagBits};
+#[doc(hidden)]
+pub use interner::FlagBit;
+pub use smol_str::SmolStr;
+#[cfg(feature="derive")]
+pub use bevy_salo_derive::SaveLoad;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+    pub use anyhow;
+}
 use schedules::{SaveSchedule, ResetSchedule};
-use sealed::SerializationResult;
 use std::borrow::Cow;
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::rc::Rc;
+use std::io::Cursor;
+use std::collections::HashSet;
+use bevy_ecs::entity::Entity;
 
 use bevy_ecs::component::Component;
 use bevy_ecs::system::{Resource, RunSystemOnce, Query};
@@ -296,6 +528,14 @@ use bevy_ecs::system::{Resource, RunSystemOnce, Query};
 pub(crate) mod sealed;
 
 pub mod schedules;
+pub mod immediate;
+pub mod wire;
+#[cfg(feature="bevy_types")]
+pub mod bevy_types;
+#[cfg(feature="bench")]
+pub mod bench;
+#[cfg(feature="fixtures")]
+pub mod fixtures;
 
 mod serde_impls;
 mod interner;
@@ -329,6 +569,13 @@ pub struct All<S: SerializationMethod=SerdeJson, const FORK: char='\0'>(PhantomD
 /// Implement this on your marker types.
 pub trait MarkerComponent: Component + Debug + Default + Send + Sync + 'static {
     type Method: SerializationMethod;
+
+    /// Name of records of the marker, see [`SaloConfig::with_persist_marker`].
+    ///
+    /// Defaults to the type name without its module path, i.e. `SaLo`.
+    fn type_name() -> Cow<'static, str> {
+        Cow::Owned(bevy_utils::get_short_name(std::any::type_name::<Self>()))
+    }
 }
 
 /// Provides path names for non-serialized entities.
@@ -358,22 +605,70 @@ impl PathName {
     }
 }
 
+type ScheduleHook = Box<dyn Fn(&mut Schedule) + Send + Sync>;
+
 /// Plugin for saving and loading.
-pub struct SaveLoadPlugin<Marker=All, Children = ()> (PhantomData<(Marker, Children)>);
+pub struct SaveLoadPlugin<Marker=All, Children = ()> (
+    PhantomData<(Marker, Children)>, 
+    Vec<fn(&mut Schedule)>,
+    Vec<(schedules::HookTarget, ScheduleHook)>,
+    /// Type names registered with `register_if` but inactive.
+    Vec<Cow<'static, str>>,
+);
 
 impl SaveLoadPlugin {
     /// Create a new save load plugin with the given marker.
     pub fn new<M: Marker>() -> SaveLoadPlugin::<M> {
-        SaveLoadPlugin(PhantomData)
+        SaveLoadPlugin(PhantomData, Vec::new(), Vec::new(), Vec::new())
+    }
+}
+
+/// Types registered on multiple plugins with [`register_shared`](SaveLoadPlugin::register_shared),
+/// i.e. types saved by every marker.
+///
+/// ```
+/// # use bevy_salo::*;
+/// # use bevy_ecs::world::World;
+/// # #[derive(Debug, Default, bevy_ecs::component::Component)] struct Hp;
+/// # impl MarkerComponent for Hp { type Method = methods::SerdeJson; }
+/// # #[derive(bevy_ecs::component::Component, serde::Serialize, serde::Deserialize)] struct Unit;
+/// # impl SaveLoadCore for Unit {}
+/// let shared = SharedTypes::new().register::<Unit>();
+/// let mut world = World::new();
+/// SaveLoadPlugin::new::<All>().register_shared(shared).build_world(&mut world);
+/// SaveLoadPlugin::new::<Hp>().register_shared(shared).build_world(&mut world);
+/// ```
+pub struct SharedTypes<Children = ()>(PhantomData<Children>);
+
+impl SharedTypes {
+    pub fn new() -> Self {
+        SharedTypes(PhantomData)
     }
 }
 
+impl Default for SharedTypes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Clone for SharedTypes<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for SharedTypes<C> {}
+
 /// A marker component with a serialization method.
 pub trait Marker: sealed::MarkerSeal + std::fmt::Debug + Default + Send + Sync + 'static {
     type Method: SerializationMethod;
     type Query: ReadOnlyWorldQuery;
     type Bundle: Bundle + Default;
     const IS_ALL: bool;
+
+    /// Name of records of the marker, see [`SaloConfig::with_persist_marker`].
+    fn type_name() -> Cow<'static, str>;
 }
 
 impl<T> sealed::MarkerSeal for T where T: MarkerComponent {}
@@ -383,6 +678,10 @@ impl<T> Marker for T where T: MarkerComponent {
     type Query = With<T>;
     type Bundle = T;
     const IS_ALL: bool = false;
+
+    fn type_name() -> Cow<'static, str> {
+        <T as MarkerComponent>::type_name()
+    }
 }
 
 impl<S: SerializationMethod, const FORK: char> sealed::MarkerSeal for All<S, FORK> {}
@@ -392,27 +691,241 @@ impl<S: SerializationMethod, const FORK: char> Marker for All<S, FORK> {
     type Query = ();
     type Bundle = ();
     const IS_ALL: bool = true;
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("All")
+    }
 }
 
 
 /// Extension methods for [`World`].
 pub trait SaveLoadExtension: sealed::Sealed {
+    /// Serialize all data with a marker to a [`SaveTarget`].
+    ///
+    /// Returns the output matching the target, or the first error recorded in [`SaloErrors`].
+    ///
+    /// ```
+    /// # use bevy_salo::*;
+    /// # use bevy_ecs::world::World;
+    /// # let mut world = World::new();
+    /// # SaveLoadPlugin::new::<All>().build_world(&mut world);
+    /// let save = world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+    /// world.load::<All>(LoadSource::String(&save)).unwrap();
+    /// ```
+    fn save<M: Marker>(&mut self, target: SaveTarget) -> Result<Saved, SaloError>;
+    /// Serialize all data with a marker once and write it to every output of an [`OutputSpec`],
+    /// i.e. a file and bytes for a cloud upload.
+    ///
+    /// Storage keys are written only if the save succeeds.
+    fn save_outputs<M: Marker>(&mut self, spec: OutputSpec) -> Result<SavedOutputs, SaloError>;
+    /// Deserialize all data with a marker from a [`LoadSource`].
+    ///
+    /// Returns the first error recorded in [`SaloErrors`].
+    fn load<M: Marker>(&mut self, source: LoadSource) -> Result<(), SaloError>;
+    /// Deserialize all data with a marker from a [`LoadSource`] with [`LoadOptions`],
+    /// i.e. to spawn entities under a [`SpawnAnchor`].
+    ///
+    /// Returns [`SaloError::MissingAnchor`] if the label is not found, or the first error recorded in [`SaloErrors`].
+    fn load_with<M: Marker>(&mut self, source: LoadSource, options: LoadOptions) -> Result<(), SaloError>;
+    /// Load and roll back if the load fails, `self` is only changed if it succeeds.
+    ///
+    /// Data of the marker is saved in memory first, so the load fails without changes if that save fails.
+    /// On failure, entities spawned by the load are despawned and the saved data is loaded again,
+    /// which runs post load systems.
+    fn load_transactional<M: Marker>(&mut self, bytes: &[u8]) -> Result<(), SaloError>;
     /// Serialize all data with a marker to a file.
-    #[cfg(feature="fs")]
-    fn save_to_file<M: Marker>(&mut self, file: &str);
+    /// 
+    /// Returns the first error recorded in [`SaloErrors`], or [`SaloError::UnsupportedPlatform`]
+    /// if the `fs` feature is disabled or the target has no file system.
+    #[deprecated(note = "use `save` with `SaveTarget::File`")]
+    fn save_to_file<M: Marker>(&mut self, file: &str) -> Result<(), SaloError>;
+    /// Serialize records of types allowed by a [`TypeFilter`] with a marker to a file.
+    ///
+    /// Does not count as a complete save in [`SaveState`].
+    fn save_to_file_filtered<M: Marker>(&mut self, file: &str, filter: TypeFilter<M>) -> Result<(), SaloError>;
+    /// Serialize all data with a marker to a file with options.
+    fn save_to_file_opts<M: Marker>(&mut self, file: &str, options: FileWriteOptions) -> Result<(), SaloError>;
     /// Serialize all data with a marker to a `String` or a `Vec<u8>`.
+    /// 
+    /// Returns `None` on failure or if throttled by [`SaloConfig::with_min_save_interval`].
+    #[deprecated(note = "use `save` with `SaveTarget::Bytes` or `SaveTarget::String`")]
     fn save_to<M: Marker, S: SerializationResult>(&mut self) -> Option<S>;
+    /// Serialize data with a marker that also matches the query filter `F` to a `String` or a `Vec<u8>`.
+    /// 
+    /// See [`SaveFilter`].
+    fn save_filtered<M: Marker, F: ReadOnlyWorldQuery + 'static, S: SerializationResult>(&mut self) -> Option<S>;
     /// Deserialize all data with a marker from a file.
-    #[cfg(feature="fs")]
-    fn load_from_file<M: Marker>(&mut self, file: &str);
+    /// 
+    /// Returns the first error recorded in [`SaloErrors`], or [`SaloError::UnsupportedPlatform`]
+    /// if the `fs` feature is disabled or the target has no file system.
+    #[deprecated(note = "use `load` with `LoadSource::File`")]
+    fn load_from_file<M: Marker>(&mut self, file: &str) -> Result<(), SaloError>;
+    /// Serialize only registered resources with a marker, without scanning entities,
+    /// i.e. for settings or profile files.
+    ///
+    /// Does not count as a complete save in [`SaveState`].
+    fn save_resources<M: Marker>(&mut self, target: SaveTarget) -> Result<Saved, SaloError>;
+    /// Deserialize only registered resources with a marker, records of components are ignored.
+    ///
+    /// Does not count as a complete load in [`SaveState`], [`UnknownComponents`] and [`PendingLoad`] are kept.
+    fn load_resources<M: Marker>(&mut self, source: LoadSource) -> Result<(), SaloError>;
+    /// Deserialize only records of `T` with a marker, i.e. to import item definitions from another save.
+    ///
+    /// Entities at the root of the save are added as children of `parent` if specified,
+    /// requires the `hierarchy` feature.
+    /// Existing entities with the same path are updated instead of spawned.
+    ///
+    /// Does not count as a complete load in [`SaveState`], [`UnknownComponents`] are kept.
+    fn load_type_records<M: Marker, T: SaveLoad>(&mut self, source: LoadSource, parent: Option<Entity>) -> Result<(), SaloError>;
+    /// Serialize only registered resources with a marker to a file, see [`save_resources`](Self::save_resources).
+    fn save_resources_to_file<M: Marker>(&mut self, file: &str) -> Result<(), SaloError>;
+    /// Deserialize only registered resources with a marker from a file, see [`load_resources`](Self::load_resources).
+    fn load_resources_from_file<M: Marker>(&mut self, file: &str) -> Result<(), SaloError>;
+    /// Serialize all data with a marker to an archive with one file per type, see [`ArchiveOutput`].
+    ///
+    /// Returns the first error recorded in [`SaloErrors`], or [`SaloError::UnsupportedPlatform`]
+    /// if the `fs` feature is disabled or the target has no file system.
+    fn save_to_archive<M: Marker>(&mut self, path: &str, format: ArchiveFormat) -> Result<(), SaloError>;
+    /// Deserialize all data with a marker from an archive written by [`save_to_archive`](Self::save_to_archive).
+    ///
+    /// Returns the first error recorded in [`SaloErrors`], or [`SaloError::UnsupportedPlatform`]
+    /// if the `fs` feature is disabled or the target has no file system.
+    fn load_from_archive<M: Marker>(&mut self, path: &str, format: ArchiveFormat) -> Result<(), SaloError>;
+    /// Serialize all data with a marker to one file per entity path, named by `file`.
+    ///
+    /// `file` receives the `::` joined path of each named entity, or of the nearest named
+    /// ancestor of an unnamed entity, and an empty string for unnamed roots and resources.
+    /// Records with the same file name are written together, i.e.
+    /// `|path| format!("saves/{}.ron", path.split("::").take(2).collect::<Vec<_>>().join("-"))`
+    /// writes each `Players::<name>` subtree to its own file. Signatures are not written.
+    ///
+    /// Returns the written files in order, or [`SaloError::UnsupportedPlatform`]
+    /// if the `fs` feature is disabled or the target has no file system.
+    fn save_split<M: Marker>(&mut self, file: impl Fn(&str) -> String + Send + Sync + 'static) -> Result<Vec<String>, SaloError>;
+    /// Serialize all data with a marker into a writer, i.e. a socket or an archive entry.
+    ///
+    /// Returns the writer, or the first error recorded in [`SaloErrors`].
+    fn save_to_writer<M: Marker, W: std::io::Write + Send + Sync + 'static>(&mut self, writer: W) -> Result<W, SaloError>;
+    /// Deserialize all data with a marker from a reader.
+    ///
+    /// Returns the first error recorded in [`SaloErrors`].
+    fn load_from_reader<M: Marker>(&mut self, reader: impl std::io::Read + Send + Sync + 'static) -> Result<(), SaloError>;
     /// Deserialize all data with a marker from a `&[u8]`.
+    #[deprecated(note = "use `load` with `LoadSource::Bytes`")]
     fn load_from_bytes<M: Marker>(&mut self, value: &[u8]);
     /// Deserialize all data with a marker from a `String` or a `Vec<u8>`.
+    #[deprecated(note = "use `load` with `LoadSource::Bytes` or `LoadSource::String`")]
     fn load_from<M: Marker, S: SerializationResult>(&mut self, value: &S);
+    /// Deserialize all data with a marker from shared bytes, does not copy if given an `Arc<[u8]>`.
+    fn load_from_shared<M: Marker>(&mut self, value: impl Into<Arc<[u8]>>);
+    /// Serialize items changed since the last complete save or load as a delta over `base`,
+    /// along with named items removed since.
+    ///
+    /// `base` identifies the base save, i.e. its file name, and is checked by [`load_delta`](Self::load_delta).
+    /// Deltas do not update [`SaveState`], so each delta contains all changes since the base.
+    fn save_delta<M: Marker, S: SerializationResult>(&mut self, base: &str) -> Option<S>;
+    /// Apply a delta created by [`save_delta`](Self::save_delta) over the current world,
+    /// which should be loaded from `base`.
+    ///
+    /// Named entities are matched by path, unnamed entities are spawned.
+    /// Returns [`SaloError::BaseMismatch`] if the input is not a delta over `base`.
+    fn load_delta<M: Marker>(&mut self, value: &[u8], base: &str) -> Result<(), SaloError>;
+    /// Serialize all data with a marker, and record named entities of `prev` that were despawned
+    /// as tombstones, i.e. for state synchronization.
+    ///
+    /// Entities matching a tombstone are despawned when the output is loaded.
+    /// Paths only saved with types excluded by [`TypeFilter`] are not recorded,
+    /// and nothing is recorded if a [`SaveFilter`] has an include list.
+    /// Returns `None` if `prev` cannot be parsed.
+    fn save_with_tombstones<M: Marker, S: SerializationResult>(&mut self, prev: &S) -> Option<S>;
+    /// Deserialize all data with a marker from a `&str`, i.e. a scene literal in `ron`.
+    ///
+    /// Only works with human-readable formats.
+    #[deprecated(note = "use `load` with `LoadSource::String`")]
+    fn load_from_str<M: Marker>(&mut self, value: &str);
+    /// Serialize all data of a group of markers into one output, i.e. `(Terrain, Units)`.
+    ///
+    /// Each marker runs its own save schedule, outputs are keyed by the full type name of each marker.
+    /// Returns the first error of any marker, in which case nothing is written.
+    ///
+    /// See [`MarkerGroup`].
+    fn save_group<G: MarkerGroup>(&mut self, target: SaveTarget) -> Result<Saved, SaloError>;
+    /// Deserialize all data of a group of markers saved by [`save_group`](Self::save_group).
+    ///
+    /// Markers are loaded in order, stops at the first error.
+    fn load_group<G: MarkerGroup>(&mut self, source: LoadSource) -> Result<(), SaloError>;
+    /// Serialize all data of a group of markers to a file.
+    fn save_group_to_file<G: MarkerGroup>(&mut self, file: &str) -> Result<(), SaloError>;
+    /// Deserialize all data of a group of markers from a file.
+    fn load_group_from_file<G: MarkerGroup>(&mut self, file: &str) -> Result<(), SaloError>;
+    /// Save all data with a marker to an in-memory [`SaloDocument`],
+    /// then remove the saved components, i.e. to move entities to another world or level.
+    ///
+    /// Nothing is removed if the save fails.
+    fn snapshot_then_reset<M: Marker>(&mut self) -> Result<SaloDocument<M>, SaloError>;
+    /// Deserialize all data with a marker from a [`SaloDocument`].
+    fn load_document<M: Marker>(&mut self, document: SaloDocument<M>) -> Result<(), SaloError>;
+    /// Serialize all data with a marker as a named in-memory snapshot in [`SnapshotRegistry`],
+    /// replacing a snapshot with the same name.
+    ///
+    /// Storing or restoring a snapshot does not count as a complete save or load in [`SaveState`].
+    fn store_snapshot<M: Marker>(&mut self, name: &str) -> Result<(), SaloError>;
+    /// Remove all serialized components with a marker, then load a snapshot
+    /// stored by [`store_snapshot`](Self::store_snapshot). The snapshot is kept.
+    ///
+    /// Rolled back if the snapshot fails to load, see [`load_transactional`](Self::load_transactional).
+    fn restore_snapshot<M: Marker>(&mut self, name: &str) -> Result<(), SaloError>;
+    /// Serialize all data with a marker with values redacted by an [`AnonymizePolicy`],
+    /// i.e. a save players can attach to a bug report.
+    ///
+    /// Does not count as a complete save in [`SaveState`].
+    ///
+    /// ```
+    /// # use bevy_salo::*;
+    /// # use bevy_ecs::world::World;
+    /// # let mut world = World::new();
+    /// # SaveLoadPlugin::new::<All>().build_world(&mut world);
+    /// let report = world.save_anonymized::<All>(SaveTarget::String, AnonymizePolicy::new()
+    ///     .with("player::Profile", Redaction::Remove)
+    ///     .with_fallback(Redaction::Hash)
+    ///     .with_hash_names(true)
+    /// ).unwrap();
+    /// ```
+    fn save_anonymized<M: Marker>(&mut self, target: SaveTarget, policy: AnonymizePolicy<M>) -> Result<Saved, SaloError>;
     /// Remove all components marked with `SaveLoad` and marker. Maybe useful when reloading a save.
     /// 
     /// Note this does not remove entities.
     fn remove_serialized_components<M: Marker>(&mut self);
+    /// Returns true if registered types changed since the last complete save or load.
+    /// 
+    /// See [`SaveState`].
+    fn has_unsaved_changes<M: Marker>(&mut self) -> bool;
+    /// Estimate the size in bytes of the next save, i.e. to pre-allocate a buffer
+    /// or check available space before saving.
+    ///
+    /// Uses average record sizes of each type from previous saves, see [`SaveSizeCache`],
+    /// returns 0 if nothing has been saved.
+    fn estimate_save_size<M: Marker>(&mut self) -> usize;
+    /// Entries and serialized sizes by type of the last successful save, i.e. to find what bloats a save.
+    ///
+    /// Empty unless enabled by [`SaloConfig::with_save_stats`] and something has been saved, see [`SaveStats`].
+    fn save_stats<M: Marker>(&self) -> SaveStats;
+    /// Check a save can be loaded without loading it, i.e. to show "save incompatible" in a launcher.
+    ///
+    /// Parses the input and checks all types are registered, all records can be migrated and deserialized,
+    /// and parents are found. Nothing is spawned and no hooks or events run.
+    /// Returns an error if the input cannot be parsed, see [`SaveReport`].
+    fn validate_save<M: Marker>(&mut self, bytes: &[u8]) -> Result<SaveReport, SaloError>;
+    /// Named entities of a marker and their ancestors with the paths the next save would write,
+    /// i.e. for editors or to catch naming mistakes before saving. Does not run hooks or send events.
+    ///
+    /// See [`PathTree`].
+    fn export_path_tree<M: Marker>(&mut self) -> PathTree;
+    /// Load the next chunk of records over the entity budget,
+    /// see [`SaloConfig::with_entity_budget`].
+    ///
+    /// Runs once per frame with `bevy_app`, returns false if nothing was pending.
+    fn load_pending<M: Marker>(&mut self) -> Result<bool, SaloError>;
     /// Despawn all entities with a marker.
     ///
     /// `All` cannot be used here and is hardcoded to fail.
@@ -421,52 +934,675 @@ pub trait SaveLoadExtension: sealed::Sealed {
 
 impl sealed::Sealed for World {}
 
+/// `std::fs` is unsupported on `wasm32` targets other than `wasi`.
+#[cfg(feature="fs")]
+const HAS_FILE_SYSTEM: bool = !cfg!(all(target_arch = "wasm32", not(target_os = "wasi")));
+
+/// Take the first error of the last run.
+fn first_error<M: Marker>(world: &mut World) -> Result<(), SaloError> {
+    match world.get_resource_mut::<SaloErrors<M>>().and_then(|mut e| e.take_first()) {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Remove output resources of previous saves.
+fn remove_outputs<M: Marker>(world: &mut World) {
+    #[cfg(feature="fs")]
+    world.remove_resource::<FileOutput<M>>();
+    world.remove_resource::<BytesOutput<M>>();
+    world.remove_resource::<StringOutput<M>>();
+    world.remove_resource::<WriterOutput<M>>();
+    #[cfg(feature="fs")]
+    world.remove_resource::<SplitOutput<M>>();
+}
+
+/// Run the save schedule, then record the time for throttling if it succeeded.
+fn run_save_schedule<M: Marker>(world: &mut World) {
+    world.run_schedule(SaveSchedule::with_marker::<M>());
+    if world.get_resource::<SaloErrors<M>>().map_or(true, |e| e.is_empty()) {
+        stamp_throttle::<M>(world);
+    }
+}
+
+/// Run the save schedule with output `S`, without checking throttling.
+fn run_save<M: Marker, S: SerializationResult>(world: &mut World) -> Option<S> {
+    remove_outputs::<M>(world);
+    S::setup::<M>(world);
+    run_save_schedule::<M>(world);
+    let result = S::get::<M>(world);
+    match world.get_resource::<SaloErrors<M>>() {
+        Some(errors) if errors.iter().any(|e| matches!(e, SaloError::Aborted(_) | SaloError::Conflict(_))) => None,
+        _ => result,
+    }
+}
+
+/// Save to output `S`, returns the first error if any.
+fn save_output<M: Marker, S: SerializationResult>(world: &mut World) -> Result<S, SaloError> {
+    if !check_throttle::<M>(world) {
+        return Err(SaloError::Throttled);
+    }
+    let result = run_save::<M, S>(world);
+    first_error::<M>(world)?;
+    result.ok_or_else(|| SaloError::Serialization(anyhow::anyhow!("Output not found.")))
+}
+
+// Deprecated methods are kept as the implementation of their replacements.
+#[allow(deprecated)]
 impl SaveLoadExtension for World {
+    fn save<M: Marker>(&mut self, target: SaveTarget) -> Result<Saved, SaloError> {
+        if reentrant::is_running::<M>(self) {
+            if QueuedTarget::in_memory(&target) {
+                reentrant::reject::<M>("save");
+                return Err(SaloError::Reentrant);
+            }
+            let target = QueuedTarget::new(target);
+            return reentrant::defer::<M, _>(self, "save", move |w| { let _ = w.save::<M>(target.get()); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| match target {
+            SaveTarget::File(file) => world.save_to_file::<M>(file).map(|_| Saved::File),
+            SaveTarget::Bytes => save_output::<M, Vec<u8>>(world).map(Saved::Bytes),
+            SaveTarget::String => save_output::<M, String>(world).map(Saved::String),
+            SaveTarget::Storage(key) => {
+                if !world.contains_resource::<SaloStorage<M>>() {
+                    return Err(SaloError::NoStorage);
+                }
+                let bytes = save_output::<M, Vec<u8>>(world)?;
+                world.resource_mut::<SaloStorage<M>>().get_mut().write(key, &bytes)
+                    .map_err(SaloError::Serialization)?;
+                Ok(Saved::Storage)
+            },
+        })
+    }
+
+    fn save_outputs<M: Marker>(&mut self, spec: OutputSpec) -> Result<SavedOutputs, SaloError> {
+        if reentrant::is_running::<M>(self) {
+            if spec.bytes || spec.string {
+                reentrant::reject::<M>("save_outputs");
+                return Err(SaloError::Reentrant);
+            }
+            return reentrant::defer::<M, _>(self, "save_outputs", move |w| { let _ = w.save_outputs::<M>(spec); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            #[cfg(feature="fs")]
+            if spec.file.is_some() && !HAS_FILE_SYSTEM {
+                return Err(SaloError::UnsupportedPlatform);
+            }
+            #[cfg(not(feature="fs"))]
+            if spec.file.is_some() {
+                return Err(SaloError::UnsupportedPlatform);
+            }
+            if !spec.storage.is_empty() && !world.contains_resource::<SaloStorage<M>>() {
+                return Err(SaloError::NoStorage);
+            }
+            if !check_throttle::<M>(world) {
+                return Err(SaloError::Throttled);
+            }
+            remove_outputs::<M>(world);
+            #[cfg(feature="fs")]
+            if let Some(file) = &spec.file {
+                world.insert_resource(FileOutput::<M>::new(file.as_str()));
+            }
+            if spec.bytes || !spec.storage.is_empty() {
+                world.init_resource::<BytesOutput<M>>();
+            }
+            if spec.string {
+                world.init_resource::<StringOutput<M>>();
+            }
+            run_save_schedule::<M>(world);
+            let bytes = world.remove_resource::<BytesOutput<M>>().map(|b| b.take());
+            let string = world.remove_resource::<StringOutput<M>>().map(|s| s.take());
+            remove_outputs::<M>(world);
+            first_error::<M>(world)?;
+            if let Some(bytes) = &bytes {
+                for key in &spec.storage {
+                    world.resource_mut::<SaloStorage<M>>().get_mut().write(key, bytes)
+                        .map_err(SaloError::Serialization)?;
+                }
+            }
+            Ok(SavedOutputs {
+                bytes: bytes.filter(|_| spec.bytes),
+                string,
+            })
+        })
+    }
+
+    fn load<M: Marker>(&mut self, source: LoadSource) -> Result<(), SaloError> {
+        if reentrant::is_running::<M>(self) {
+            let source = QueuedSource::new(source);
+            return reentrant::defer::<M, _>(self, "load", move |w| { let _ = w.load::<M>(source.get()); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            match source {
+                LoadSource::File(file) => return world.load_from_file::<M>(file),
+                LoadSource::Bytes(bytes) => world.load_from_bytes::<M>(bytes),
+                LoadSource::String(string) => world.load_from_str::<M>(string),
+                LoadSource::Storage(key) => {
+                    let bytes = match world.get_resource::<SaloStorage<M>>() {
+                        Some(storage) => storage.get().read(key).map_err(SaloError::Deserialization)?,
+                        None => return Err(SaloError::NoStorage),
+                    };
+                    world.load_from_bytes::<M>(&bytes)
+                },
+            }
+            first_error::<M>(world)
+        })
+    }
+
+    fn load_with<M: Marker>(&mut self, source: LoadSource, options: LoadOptions) -> Result<(), SaloError> {
+        if reentrant::is_running::<M>(self) {
+            let source = QueuedSource::new(source);
+            return reentrant::defer::<M, _>(self, "load_with", move |w| { let _ = w.load_with::<M>(source.get(), options); }, Err(SaloError::Queued));
+        }
+        let Some(label) = options.label else { return self.load::<M>(source) };
+        let anchor = self.query::<(Entity, &SpawnAnchor)>().iter(self)
+            .find(|(_, anchor)| anchor.get() == label.as_ref())
+            .map(|(entity, _)| entity);
+        let Some(anchor) = anchor else { return Err(SaloError::MissingAnchor(label)) };
+        reentrant::scope::<M, _>(self, |world| {
+            world.insert_resource(LoadAnchor::<M>::new(anchor));
+            let result = world.load::<M>(source);
+            world.remove_resource::<LoadAnchor<M>>();
+            result
+        })
+    }
+
+    fn load_transactional<M: Marker>(&mut self, bytes: &[u8]) -> Result<(), SaloError> {
+        if reentrant::is_running::<M>(self) {
+            let bytes = bytes.to_vec();
+            return reentrant::defer::<M, _>(self, "load_transactional", move |w| { let _ = w.load_transactional::<M>(&bytes); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| document::load_or_rollback::<M>(world, |world| world.load::<M>(LoadSource::Bytes(bytes))))
+    }
+
+    fn save_to_file<M: Marker>(&mut self, file: &str) -> Result<(), SaloError> {
+        self.save_to_file_opts::<M>(file, FileWriteOptions::default())
+    }
+
+    fn save_to_file_filtered<M: Marker>(&mut self, file: &str, filter: TypeFilter<M>) -> Result<(), SaloError> {
+        if reentrant::is_running::<M>(self) {
+            let file = file.to_owned();
+            return reentrant::defer::<M, _>(self, "save_to_file_filtered", move |w| { let _ = w.save_to_file_filtered::<M>(&file, filter); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            let prev = world.remove_resource::<TypeFilter<M>>();
+            world.insert_resource(filter);
+            let result = world.save_to_file::<M>(file);
+            world.remove_resource::<TypeFilter<M>>();
+            if let Some(prev) = prev {
+                world.insert_resource(prev);
+            }
+            result
+        })
+    }
+
+    fn save_resources<M: Marker>(&mut self, target: SaveTarget) -> Result<Saved, SaloError> {
+        if reentrant::is_running::<M>(self) {
+            if QueuedTarget::in_memory(&target) {
+                reentrant::reject::<M>("save_resources");
+                return Err(SaloError::Reentrant);
+            }
+            let target = QueuedTarget::new(target);
+            return reentrant::defer::<M, _>(self, "save_resources", move |w| { let _ = w.save_resources::<M>(target.get()); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            world.insert_resource(ResourcesOnly::<M>(PhantomData));
+            let result = world.save::<M>(target);
+            world.remove_resource::<ResourcesOnly<M>>();
+            result
+        })
+    }
+
+    fn load_resources<M: Marker>(&mut self, source: LoadSource) -> Result<(), SaloError> {
+        if reentrant::is_running::<M>(self) {
+            let source = QueuedSource::new(source);
+            return reentrant::defer::<M, _>(self, "load_resources", move |w| { let _ = w.load_resources::<M>(source.get()); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            world.insert_resource(ResourcesOnly::<M>(PhantomData));
+            let result = world.load::<M>(source);
+            world.remove_resource::<ResourcesOnly<M>>();
+            result
+        })
+    }
+
+    fn load_type_records<M: Marker, T: SaveLoad>(&mut self, source: LoadSource, parent: Option<Entity>) -> Result<(), SaloError> {
+        if reentrant::is_running::<M>(self) {
+            let source = QueuedSource::new(source);
+            return reentrant::defer::<M, _>(self, "load_type_records", move |w| { let _ = w.load_type_records::<M, T>(source.get(), parent); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            world.insert_resource(TypeRecords::<M>::new(T::type_name(), parent));
+            let result = world.load::<M>(source);
+            world.remove_resource::<TypeRecords<M>>();
+            result
+        })
+    }
+
+    fn save_resources_to_file<M: Marker>(&mut self, file: &str) -> Result<(), SaloError> {
+        self.save_resources::<M>(SaveTarget::File(file)).map(|_| ())
+    }
+
+    fn load_resources_from_file<M: Marker>(&mut self, file: &str) -> Result<(), SaloError> {
+        self.load_resources::<M>(LoadSource::File(file))
+    }
+
+    #[cfg(feature="fs")]
+    fn save_to_file_opts<M: Marker>(&mut self, file: &str, options: FileWriteOptions) -> Result<(), SaloError> {
+        if !HAS_FILE_SYSTEM {
+            return Err(SaloError::UnsupportedPlatform);
+        }
+        if reentrant::is_running::<M>(self) {
+            let file = file.to_owned();
+            return reentrant::defer::<M, _>(self, "save_to_file", move |w| { let _ = w.save_to_file_opts::<M>(&file, options); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            if !check_throttle::<M>(world) {
+                return Err(SaloError::Throttled);
+            }
+            world.remove_resource::<BytesOutput<M>>();
+            world.remove_resource::<StringOutput<M>>();
+            world.remove_resource::<WriterOutput<M>>();
+            world.insert_resource(FileOutput::<M>::with_options(file, options));
+            run_save_schedule::<M>(world);
+            first_error::<M>(world)
+        })
+    }
+
+    #[cfg(not(feature="fs"))]
+    fn save_to_file_opts<M: Marker>(&mut self, _: &str, _: FileWriteOptions) -> Result<(), SaloError> {
+        Err(SaloError::UnsupportedPlatform)
+    }
+
     #[cfg(feature="fs")]
-    fn save_to_file<M: Marker>(&mut self, file: &str) {
-        self.remove_resource::<BytesOutput<M>>();
-        self.remove_resource::<StringOutput<M>>();
-        self.insert_resource(FileOutput::<M>::new(file));
-        self.run_schedule(SaveSchedule::with_marker::<M>())
+    fn save_to_archive<M: Marker>(&mut self, path: &str, format: ArchiveFormat) -> Result<(), SaloError> {
+        if !HAS_FILE_SYSTEM {
+            return Err(SaloError::UnsupportedPlatform);
+        }
+        if reentrant::is_running::<M>(self) {
+            let path = path.to_owned();
+            return reentrant::defer::<M, _>(self, "save_to_archive", move |w| { let _ = w.save_to_archive::<M>(&path, format); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            if !check_throttle::<M>(world) {
+                return Err(SaloError::Throttled);
+            }
+            world.remove_resource::<FileOutput<M>>();
+            world.remove_resource::<BytesOutput<M>>();
+            world.remove_resource::<StringOutput<M>>();
+            world.remove_resource::<WriterOutput<M>>();
+            world.insert_resource(ArchiveOutput::<M>::new(path, format));
+            run_save_schedule::<M>(world);
+            world.remove_resource::<ArchiveOutput<M>>();
+            first_error::<M>(world)
+        })
+    }
+
+    #[cfg(feature="fs")]
+    fn load_from_archive<M: Marker>(&mut self, path: &str, format: ArchiveFormat) -> Result<(), SaloError> {
+        use crate::schedules::LoadSchedule;
+        if !HAS_FILE_SYSTEM {
+            return Err(SaloError::UnsupportedPlatform);
+        }
+        if reentrant::is_running::<M>(self) {
+            let path = path.to_owned();
+            return reentrant::defer::<M, _>(self, "load_from_archive", move |w| { let _ = w.load_from_archive::<M>(&path, format); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            world.remove_resource::<FileInput<M>>();
+            world.remove_resource::<BytesInput<M>>();
+            world.remove_resource::<StringInput<M>>();
+            world.insert_resource(ArchiveInput::<M>::new(path, format));
+            world.run_schedule(LoadSchedule::with_marker::<M>());
+            world.remove_resource::<ArchiveInput<M>>();
+            first_error::<M>(world)
+        })
+    }
+
+    #[cfg(not(feature="fs"))]
+    fn save_to_archive<M: Marker>(&mut self, _: &str, _: ArchiveFormat) -> Result<(), SaloError> {
+        Err(SaloError::UnsupportedPlatform)
+    }
+
+    #[cfg(not(feature="fs"))]
+    fn load_from_archive<M: Marker>(&mut self, _: &str, _: ArchiveFormat) -> Result<(), SaloError> {
+        Err(SaloError::UnsupportedPlatform)
+    }
+
+    #[cfg(feature="fs")]
+    fn save_split<M: Marker>(&mut self, file: impl Fn(&str) -> String + Send + Sync + 'static) -> Result<Vec<String>, SaloError> {
+        if reentrant::is_running::<M>(self) {
+            return reentrant::defer::<M, _>(self, "save_split", move |w| { let _ = w.save_split::<M>(file); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            if !HAS_FILE_SYSTEM {
+                return Err(SaloError::UnsupportedPlatform);
+            }
+            if !check_throttle::<M>(world) {
+                return Err(SaloError::Throttled);
+            }
+            remove_outputs::<M>(world);
+            world.insert_resource(SplitOutput::<M>::new(file));
+            run_save_schedule::<M>(world);
+            let written = world.remove_resource::<SplitOutput<M>>().map(|x| x.written).unwrap_or_default();
+            first_error::<M>(world).map(|_| written)
+        })
+    }
+
+    #[cfg(not(feature="fs"))]
+    fn save_split<M: Marker>(&mut self, _: impl Fn(&str) -> String + Send + Sync + 'static) -> Result<Vec<String>, SaloError> {
+        Err(SaloError::UnsupportedPlatform)
+    }
+
+    fn save_to_writer<M: Marker, W: std::io::Write + Send + Sync + 'static>(&mut self, writer: W) -> Result<W, SaloError> {
+        if reentrant::is_running::<M>(self) {
+            reentrant::reject::<M>("save_to_writer");
+            return Err(SaloError::Reentrant);
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            if !check_throttle::<M>(world) {
+                return Err(SaloError::Throttled);
+            }
+            #[cfg(feature="fs")]
+            world.remove_resource::<FileOutput<M>>();
+            world.remove_resource::<BytesOutput<M>>();
+            world.remove_resource::<StringOutput<M>>();
+            world.insert_resource(WriterOutput::<M>::new(writer));
+            run_save_schedule::<M>(world);
+            let writer = world.remove_resource::<WriterOutput<M>>().and_then(|w| w.take());
+            first_error::<M>(world)?;
+            writer.ok_or_else(|| SaloError::Serialization(anyhow::anyhow!("Output not found.")))
+        })
+    }
+
+    fn load_from_reader<M: Marker>(&mut self, reader: impl std::io::Read + Send + Sync + 'static) -> Result<(), SaloError> {
+        use crate::schedules::LoadSchedule;
+        if reentrant::is_running::<M>(self) {
+            return reentrant::defer::<M, _>(self, "load_from_reader", move |w| { let _ = w.load_from_reader::<M>(reader); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            #[cfg(feature="fs")]
+            world.remove_resource::<FileInput<M>>();
+            world.remove_resource::<BytesInput<M>>();
+            world.remove_resource::<StringInput<M>>();
+            world.insert_resource(ReaderInput::<M>::new(reader));
+            world.run_schedule(LoadSchedule::with_marker::<M>());
+            world.remove_resource::<ReaderInput<M>>();
+            first_error::<M>(world)
+        })
     }
 
     fn save_to<M: Marker, S: SerializationResult>(&mut self) -> Option<S> {
-        #[cfg(feature="fs")]
-        self.remove_resource::<FileOutput<M>>();
-        self.remove_resource::<BytesOutput<M>>();
-        self.remove_resource::<StringOutput<M>>();
-        S::setup::<M>(self);
-        self.run_schedule(SaveSchedule::with_marker::<M>());
-        S::get::<M>(self)
+        if reentrant::is_running::<M>(self) {
+            reentrant::reject::<M>("save_to");
+            return None;
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            if !check_throttle::<M>(world) {
+                return None;
+            }
+            run_save::<M, S>(world)
+        })
+    }
+
+    fn save_filtered<M: Marker, F: ReadOnlyWorldQuery + 'static, S: SerializationResult>(&mut self) -> Option<S> {
+        if reentrant::is_running::<M>(self) {
+            reentrant::reject::<M>("save_filtered");
+            return None;
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            let filter = SaveFilter::<M>::new(world.run_system_once(|query: Query<Entity, F>| {
+                query.iter().collect::<Vec<_>>()
+            }));
+            let prev = world.remove_resource::<SaveFilter<M>>();
+            world.insert_resource(filter);
+            let result = world.save_to::<M, S>();
+            world.remove_resource::<SaveFilter<M>>();
+            if let Some(prev) = prev {
+                world.insert_resource(prev);
+            }
+            result
+        })
+    }
+
+    fn save_delta<M: Marker, S: SerializationResult>(&mut self, base: &str) -> Option<S> {
+        if reentrant::is_running::<M>(self) {
+            reentrant::reject::<M>("save_delta");
+            return None;
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            let prev = world.remove_resource::<SaveDelta<M>>();
+            world.insert_resource(SaveDelta::<M>::new(base));
+            let result = world.save_to::<M, S>();
+            world.remove_resource::<SaveDelta<M>>();
+            if let Some(prev) = prev {
+                world.insert_resource(prev);
+            }
+            result
+        })
+    }
+
+    fn load_delta<M: Marker>(&mut self, value: &[u8], base: &str) -> Result<(), SaloError> {
+        if reentrant::is_running::<M>(self) {
+            let (value, base) = (value.to_vec(), base.to_owned());
+            return reentrant::defer::<M, _>(self, "load_delta", move |w| { let _ = w.load_delta::<M>(&value, &base); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            let prev = world.remove_resource::<SaveDelta<M>>();
+            world.insert_resource(SaveDelta::<M>::new(base));
+            world.load_from_bytes::<M>(value);
+            world.remove_resource::<SaveDelta<M>>();
+            if let Some(prev) = prev {
+                world.insert_resource(prev);
+            }
+            first_error::<M>(world)
+        })
+    }
+
+    fn save_with_tombstones<M: Marker, S: SerializationResult>(&mut self, prev: &S) -> Option<S> {
+        if reentrant::is_running::<M>(self) {
+            reentrant::reject::<M>("save_with_tombstones");
+            return None;
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            let key = world.get_resource::<SaveEncryptionKey<M>>().map(|k| *k.get());
+            let document: Document<ValueOf<M>> = M::Method::deserialize_keyed(prev.as_bytes(), key.as_ref()).ok()?;
+            world.insert_resource(TombstoneBase::<M>::new(&document));
+            let result = world.save_to::<M, S>();
+            world.remove_resource::<TombstoneBase<M>>();
+            result
+        })
     }
 
     #[cfg(feature="fs")]
-    fn load_from_file<M: Marker>(&mut self, file: &str) {
+    fn load_from_file<M: Marker>(&mut self, file: &str) -> Result<(), SaloError> {
         use crate::schedules::LoadSchedule;
-        self.remove_resource::<BytesInput<M>>();
-        self.insert_resource(FileInput::<M>::new(file));
-        self.run_schedule(LoadSchedule::with_marker::<M>());
+        if !HAS_FILE_SYSTEM {
+            return Err(SaloError::UnsupportedPlatform);
+        }
+        if reentrant::is_running::<M>(self) {
+            let file = file.to_owned();
+            return reentrant::defer::<M, _>(self, "load_from_file", move |w| { let _ = w.load_from_file::<M>(&file); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            world.remove_resource::<BytesInput<M>>();
+            world.remove_resource::<StringInput<M>>();
+            world.insert_resource(FileInput::<M>::new(file));
+            world.run_schedule(LoadSchedule::with_marker::<M>());
+            first_error::<M>(world)
+        })
+    }
+
+    #[cfg(not(feature="fs"))]
+    fn load_from_file<M: Marker>(&mut self, _: &str) -> Result<(), SaloError> {
+        Err(SaloError::UnsupportedPlatform)
     }
 
     fn load_from<M: Marker, S: SerializationResult>(&mut self, value: &S) {
+        self.load_from_bytes::<M>(value.as_bytes())
+    }
+
+    fn load_from_shared<M: Marker>(&mut self, value: impl Into<Arc<[u8]>>) {
         use crate::schedules::LoadSchedule;
-        self.remove_resource::<BytesInput<M>>();
-        self.insert_resource(BytesInput::<M>::new(value.as_bytes()));
-        self.run_schedule(LoadSchedule::with_marker::<M>());
+        let value: Arc<[u8]> = value.into();
+        if reentrant::is_running::<M>(self) {
+            return reentrant::defer::<M, _>(self, "load_from_shared", move |w| w.load_from_shared::<M>(value), ());
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            #[cfg(feature="fs")]
+            world.remove_resource::<FileInput<M>>();
+            world.remove_resource::<BytesInput<M>>();
+            world.remove_resource::<StringInput<M>>();
+            world.insert_resource(BytesInput::<M>::new(value));
+            world.run_schedule(LoadSchedule::with_marker::<M>());
+        })
     }
 
     fn load_from_bytes<M: Marker>(&mut self, value: &[u8]) {
         use crate::schedules::LoadSchedule;
-        self.remove_resource::<BytesInput<M>>();
-        self.insert_resource(BytesInput::<M>::new(value));
-        self.run_schedule(LoadSchedule::with_marker::<M>());
+        if reentrant::is_running::<M>(self) {
+            let value = value.to_vec();
+            return reentrant::defer::<M, _>(self, "load_from_bytes", move |w| w.load_from_bytes::<M>(&value), ());
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            #[cfg(feature="fs")]
+            world.remove_resource::<FileInput<M>>();
+            world.remove_resource::<BytesInput<M>>();
+            world.remove_resource::<StringInput<M>>();
+            world.insert_resource(BytesInput::<M>::new(value));
+            world.run_schedule(LoadSchedule::with_marker::<M>());
+        })
+    }
+
+    fn load_from_str<M: Marker>(&mut self, value: &str) {
+        use crate::schedules::LoadSchedule;
+        if reentrant::is_running::<M>(self) {
+            let value = value.to_owned();
+            return reentrant::defer::<M, _>(self, "load_from_str", move |w| w.load_from_str::<M>(&value), ());
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            #[cfg(feature="fs")]
+            world.remove_resource::<FileInput<M>>();
+            world.remove_resource::<BytesInput<M>>();
+            world.insert_resource(StringInput::<M>::new(value));
+            world.run_schedule(LoadSchedule::with_marker::<M>());
+        })
+    }
+
+    fn save_group<G: MarkerGroup>(&mut self, target: SaveTarget) -> Result<Saved, SaloError> {
+        group::save_group::<G>(self, target)
+    }
+
+    fn load_group<G: MarkerGroup>(&mut self, source: LoadSource) -> Result<(), SaloError> {
+        group::load_group::<G>(self, source)
+    }
+
+    fn save_group_to_file<G: MarkerGroup>(&mut self, file: &str) -> Result<(), SaloError> {
+        group::save_group::<G>(self, SaveTarget::File(file)).map(|_| ())
     }
-    
+
+    fn load_group_from_file<G: MarkerGroup>(&mut self, file: &str) -> Result<(), SaloError> {
+        group::load_group::<G>(self, LoadSource::File(file))
+    }
+
+    fn snapshot_then_reset<M: Marker>(&mut self) -> Result<SaloDocument<M>, SaloError> {
+        if reentrant::is_running::<M>(self) {
+            reentrant::reject::<M>("snapshot_then_reset");
+            return Err(SaloError::Reentrant);
+        }
+        reentrant::scope::<M, _>(self, document::snapshot_then_reset::<M>)
+    }
+
+    fn load_document<M: Marker>(&mut self, document: SaloDocument<M>) -> Result<(), SaloError> {
+        if reentrant::is_running::<M>(self) {
+            return reentrant::defer::<M, _>(self, "load_document", move |w| { let _ = w.load_document::<M>(document); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| document::load_value::<M>(world, document.into_value()))
+    }
+
+    fn store_snapshot<M: Marker>(&mut self, name: &str) -> Result<(), SaloError> {
+        if reentrant::is_running::<M>(self) {
+            let name = name.to_owned();
+            return reentrant::defer::<M, _>(self, "store_snapshot", move |w| { let _ = w.store_snapshot::<M>(&name); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| document::store_snapshot::<M>(world, name))
+    }
+
+    fn restore_snapshot<M: Marker>(&mut self, name: &str) -> Result<(), SaloError> {
+        if reentrant::is_running::<M>(self) {
+            let name = name.to_owned();
+            return reentrant::defer::<M, _>(self, "restore_snapshot", move |w| { let _ = w.restore_snapshot::<M>(&name); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| document::restore_snapshot::<M>(world, name))
+    }
+
+    fn save_anonymized<M: Marker>(&mut self, target: SaveTarget, policy: AnonymizePolicy<M>) -> Result<Saved, SaloError> {
+        if reentrant::is_running::<M>(self) {
+            if QueuedTarget::in_memory(&target) {
+                reentrant::reject::<M>("save_anonymized");
+                return Err(SaloError::Reentrant);
+            }
+            let target = QueuedTarget::new(target);
+            return reentrant::defer::<M, _>(self, "save_anonymized", move |w| { let _ = w.save_anonymized::<M>(target.get(), policy); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, |world| {
+            let prev = world.remove_resource::<AnonymizePolicy<M>>();
+            world.insert_resource(policy);
+            let result = world.save::<M>(target);
+            world.remove_resource::<AnonymizePolicy<M>>();
+            if let Some(prev) = prev {
+                world.insert_resource(prev);
+            }
+            result
+        })
+    }
+
     fn remove_serialized_components<M: Marker>(&mut self) {
-        self.run_schedule(ResetSchedule::with_marker::<M>());
+        if reentrant::is_running::<M>(self) {
+            return reentrant::defer::<M, _>(self, "remove_serialized_components", |w| w.remove_serialized_components::<M>(), ());
+        }
+        reentrant::scope::<M, _>(self, |world| world.run_schedule(ResetSchedule::with_marker::<M>()))
+    }
+    fn has_unsaved_changes<M: Marker>(&mut self) -> bool {
+        use crate::schedules::TrackSchedule;
+        if !self.contains_resource::<SaveState<M>>() {
+            return false;
+        }
+        self.run_schedule(TrackSchedule::with_marker::<M>());
+        self.resource::<SaveState<M>>().is_dirty()
+    }
+
+    fn estimate_save_size<M: Marker>(&mut self) -> usize {
+        use crate::schedules::EstimateSchedule;
+        if !self.contains_resource::<SaveSizeCache<M>>() {
+            return 0;
+        }
+        self.run_schedule(EstimateSchedule::with_marker::<M>());
+        self.resource::<SaveSizeCache<M>>().estimate()
+    }
+
+    fn save_stats<M: Marker>(&self) -> SaveStats {
+        self.get_resource::<SaveSizeCache<M>>()
+            .map(|cache| cache.stats().clone())
+            .unwrap_or_default()
+    }
+
+    fn validate_save<M: Marker>(&mut self, bytes: &[u8]) -> Result<SaveReport, SaloError> {
+        validate::validate_save::<M>(self, bytes)
+    }
+
+    fn export_path_tree<M: Marker>(&mut self) -> PathTree {
+        tree::export_path_tree::<M>(self)
+    }
+
+    fn load_pending<M: Marker>(&mut self) -> Result<bool, SaloError> {
+        if reentrant::is_running::<M>(self) {
+            return reentrant::defer::<M, _>(self, "load_pending", |w| { let _ = w.load_pending::<M>(); }, Err(SaloError::Queued));
+        }
+        reentrant::scope::<M, _>(self, budget::load_pending::<M>)
     }
+
     fn despawn_with_marker<M: Marker>(&mut self) {
-        use bevy_ecs::entity::Entity;
         use bevy_ecs::system::Commands;
         if M::IS_ALL {
             eprintln!("despawn_with_marker should not be used to despawn all entities.");
@@ -482,17 +1618,118 @@ impl SaveLoadExtension for World {
 
 /// Resource that contains the path of file output.
 #[derive(Debug, Clone, Resource)]
-pub struct FileOutput<M: Marker>(String, PhantomData<M>);
+#[cfg_attr(not(feature="fs"), allow(dead_code))]
+pub struct FileOutput<M: Marker>(String, FileWriteOptions, PhantomData<M>);
 
 #[cfg(feature="fs")]
 impl<M: Marker> FileOutput<M> {
     pub fn new(s: impl Into<String>) -> Self{
-        FileOutput(s.into(), PhantomData)
+        FileOutput(s.into(), FileWriteOptions::default(), PhantomData)
+    }
+
+    pub fn with_options(s: impl Into<String>, options: FileWriteOptions) -> Self{
+        FileOutput(s.into(), options, PhantomData)
     }
 
     pub fn get(&self) -> &str {
         &self.0
     }
+
+    pub fn options(&self) -> &FileWriteOptions {
+        &self.1
+    }
+}
+
+/// Options for writing to a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileWriteOptions {
+    /// Write to `file.tmp` then rename over `file`, so a crash mid-write does not corrupt the save.
+    /// 
+    /// Default is `true`.
+    pub atomic: bool,
+    /// Copy the previous file to `file.bak` before overwriting.
+    /// 
+    /// Default is `false`.
+    pub backup: bool,
+}
+
+impl Default for FileWriteOptions {
+    fn default() -> Self {
+        Self { atomic: true, backup: false }
+    }
+}
+
+#[cfg(feature="fs")]
+impl FileWriteOptions {
+    /// Write to a file with these options, `write` is called on the path to be written to.
+    pub fn write(&self, file: &str, write: impl FnOnce(&str) -> anyhow::Result<()>) -> anyhow::Result<()> {
+        #[cfg(feature="fs-lock")]
+        let _lock = lock_file(file, true)?;
+        let result = self.write_unlocked(file, write);
+        // Removed while still locked, processes waiting on the old sidecar retry on a new one.
+        #[cfg(feature="fs-lock")]
+        let _ = std::fs::remove_file(format!("{}.lock", file));
+        result
+    }
+
+    fn write_unlocked(&self, file: &str, write: impl FnOnce(&str) -> anyhow::Result<()>) -> anyhow::Result<()> {
+        let target = std::path::Path::new(file);
+        if self.backup && target.exists() {
+            std::fs::copy(target, format!("{}.bak", file))?;
+        }
+        if !self.atomic {
+            return write(file);
+        }
+        let temp = format!("{}.tmp", file);
+        let result = write(&temp)
+            .and_then(|_| Ok(std::fs::File::open(&temp)?.sync_all()?))
+            .and_then(|_| Ok(std::fs::rename(&temp, target)?));
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp);
+        }
+        result
+    }
+}
+
+/// Take an advisory lock on `{file}.lock`, released on drop.
+/// 
+/// Writers take an exclusive lock and readers take a shared lock,
+/// so concurrent processes cannot interleave writes to the same save.
+/// The sidecar is removed after a save, see [`FileWriteOptions::write`].
+#[cfg(feature="fs-lock")]
+pub(crate) fn lock_file(file: &str, exclusive: bool) -> std::io::Result<std::fs::File> {
+    let path = format!("{}.lock", file);
+    loop {
+        let lock = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+        if exclusive {
+            fs2::FileExt::lock_exclusive(&lock)?;
+        } else {
+            fs2::FileExt::lock_shared(&lock)?;
+        }
+        // The sidecar may have been removed by a writer while waiting.
+        if is_same_file(&lock, &path) {
+            return Ok(lock);
+        }
+    }
+}
+
+#[cfg(all(feature="fs-lock", unix))]
+fn is_same_file(lock: &std::fs::File, path: &str) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (lock.metadata(), std::fs::metadata(path)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+/// Open files cannot be removed on other platforms.
+#[cfg(all(feature="fs-lock", not(unix)))]
+fn is_same_file(_: &std::fs::File, path: &str) -> bool {
+    std::path::Path::new(path).exists()
 }
 
 /// Resource that contains the bytes output, unique for marker.
@@ -533,8 +1770,121 @@ impl<M: Marker> StringOutput<M> {
     }
 }
 
+/// A writer that can be downcast back to its type.
+pub(crate) trait AnyWrite: std::io::Write + std::any::Any + Send + Sync {}
+
+impl<T: std::io::Write + std::any::Any + Send + Sync> AnyWrite for T {}
+
+/// Resource that contains a writer output, unique per marker.
+///
+/// Written with [`SerializationMethod::serialize_writer`], i.e. to stream a save to a socket.
+#[derive(Resource)]
+pub struct WriterOutput<M: Marker>(pub(crate) Box<dyn AnyWrite>, PhantomData<M>);
+
+impl<M: Marker> WriterOutput<M> {
+    pub fn new(writer: impl std::io::Write + Send + Sync + 'static) -> Self {
+        WriterOutput(Box::new(writer), PhantomData)
+    }
+
+    /// Take the writer back, returns `None` if not a `W`.
+    pub fn take<W: std::io::Write + Send + Sync + 'static>(self) -> Option<W> {
+        let writer: Box<dyn std::any::Any> = self.0;
+        writer.downcast().ok().map(|w| *w)
+    }
+}
+
+impl<M: Marker> Debug for WriterOutput<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("WriterOutput").field(&"..").finish()
+    }
+}
+
+/// Output type of [`SaveLoadExtension::save_to`] and input type of [`SaveLoadExtension::load_from`].
+/// 
+/// Can be implemented on custom buffer types by taking [`BytesOutput`] or [`StringOutput`].
+pub trait SerializationResult: Sized {
+    /// Insert the output resource.
+    fn setup<M: Marker>(w: &mut World);
+    /// Take the result from the output resource.
+    fn get<M: Marker>(w: &mut World) -> Option<Self>;
+    fn as_bytes(&self) -> &[u8];
+}
+
+impl SerializationResult for String {
+    fn setup<M: Marker>(w: &mut World) {
+        w.init_resource::<StringOutput<M>>();
+    }
+    fn get<M: Marker>(w: &mut World) -> Option<Self>{
+        Some(w.remove_resource::<StringOutput<M>>()?.take())
+    }
+    fn as_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl SerializationResult for Vec<u8> {
+    fn setup<M: Marker>(w: &mut World) {
+        w.init_resource::<BytesOutput<M>>();
+    }
+    fn get<M: Marker>(w: &mut World) -> Option<Self>{
+        Some(w.remove_resource::<BytesOutput<M>>()?.take())
+    }
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl SerializationResult for Cursor<Vec<u8>> {
+    fn setup<M: Marker>(w: &mut World) {
+        w.init_resource::<BytesOutput<M>>();
+    }
+    fn get<M: Marker>(w: &mut World) -> Option<Self>{
+        Some(Cursor::new(w.remove_resource::<BytesOutput<M>>()?.take()))
+    }
+    fn as_bytes(&self) -> &[u8] {
+        self.get_ref()
+    }
+}
+
+impl SerializationResult for Arc<[u8]> {
+    fn setup<M: Marker>(w: &mut World) {
+        w.init_resource::<BytesOutput<M>>();
+    }
+    fn get<M: Marker>(w: &mut World) -> Option<Self>{
+        Some(w.remove_resource::<BytesOutput<M>>()?.take().into())
+    }
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl SerializationResult for Arc<str> {
+    fn setup<M: Marker>(w: &mut World) {
+        w.init_resource::<StringOutput<M>>();
+    }
+    fn get<M: Marker>(w: &mut World) -> Option<Self>{
+        Some(w.remove_resource::<StringOutput<M>>()?.take().into())
+    }
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+}
+
+impl SerializationResult for Rc<str> {
+    fn setup<M: Marker>(w: &mut World) {
+        w.init_resource::<StringOutput<M>>();
+    }
+    fn get<M: Marker>(w: &mut World) -> Option<Self>{
+        Some(w.remove_resource::<StringOutput<M>>()?.take().into())
+    }
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+}
+
 /// Resource that contains the path of file input, unique per marker.
 #[derive(Debug, Clone, Resource)]
+#[cfg_attr(not(feature="fs"), allow(dead_code))]
 pub struct FileInput<M: Marker>(String, PhantomData<M>);
 
 #[cfg(feature="fs")]
@@ -548,12 +1898,14 @@ impl<M: Marker> FileInput<M> {
     }
 }
 
-/// Resource that contains the bytes output, unique per marker.
+/// Resource that contains the bytes input, unique per marker.
+/// 
+/// The bytes are shared, so an `Arc<[u8]>` can be loaded without copying.
 #[derive(Debug, Clone, Resource, Default)]
-pub struct BytesInput<M: Marker>(Vec<u8>, PhantomData<M>);
+pub struct BytesInput<M: Marker>(Arc<[u8]>, PhantomData<M>);
 
 impl<M: Marker> BytesInput<M> {
-    pub fn new(b: impl Into<Vec<u8>>) -> Self{
+    pub fn new(b: impl Into<Arc<[u8]>>) -> Self{
         BytesInput(b.into(), PhantomData)
     }
 
@@ -561,7 +1913,266 @@ impl<M: Marker> BytesInput<M> {
         &self.0
     }
 
-    pub fn take(self) -> Vec<u8> {
+    pub fn take(self) -> Arc<[u8]> {
+        self.0
+    }
+}
+
+/// Resource that contains the string input, unique per marker.
+///
+/// Parsed with [`SerializationMethod::deserialize_str`], so only works with human-readable formats.
+#[derive(Debug, Clone, Resource)]
+pub struct StringInput<M: Marker>(Arc<str>, PhantomData<M>);
+
+impl<M: Marker> StringInput<M> {
+    pub fn new(s: impl Into<Arc<str>>) -> Self{
+        StringInput(s.into(), PhantomData)
+    }
+
+    pub fn get(&self) -> &str {
+        &self.0
+    }
+
+    pub fn take(self) -> Arc<str> {
         self.0
     }
 }
+
+/// Resource that contains a reader input, unique per marker.
+///
+/// Parsed with [`SerializationMethod::deserialize_reader`], the reader is dropped after loading.
+#[derive(Resource)]
+pub struct ReaderInput<M: Marker>(pub(crate) Box<dyn std::io::Read + Send + Sync>, PhantomData<M>);
+
+impl<M: Marker> ReaderInput<M> {
+    pub fn new(reader: impl std::io::Read + Send + Sync + 'static) -> Self {
+        ReaderInput(Box::new(reader), PhantomData)
+    }
+}
+
+impl<M: Marker> Debug for ReaderInput<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ReaderInput").field(&"..").finish()
+    }
+}
+
+/// Resource that contains the key used by [`Encrypted`](methods::Encrypted), unique per marker.
+///
+/// String inputs and outputs fail while present, as strings cannot hold encrypted bytes.
+#[derive(Clone, Resource)]
+pub struct SaveEncryptionKey<M: Marker>([u8; 32], PhantomData<M>);
+
+impl<M: Marker> SaveEncryptionKey<M> {
+    pub fn new(key: [u8; 32]) -> Self{
+        SaveEncryptionKey(key, PhantomData)
+    }
+
+    pub fn get(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl<M: Marker> Debug for SaveEncryptionKey<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SaveEncryptionKey").field(&"..").finish()
+    }
+}
+
+/// Resource that limits serialization to a set of entities, unique per marker.
+/// 
+/// Entities are saved if they are in the include list, or if there is no include list,
+/// and not in the exclude list. Entities still need to match the marker's query.
+/// Parents outside of the set are treated as not serialized.
+///
+/// If created with [`once`](SaveFilter::once), removed after one run of [`SaveSchedule`],
+/// i.e. to save a selection from an editor tool.
+#[derive(Debug, Clone, Resource)]
+pub struct SaveFilter<M: Marker> {
+    include: Option<HashSet<Entity>>,
+    exclude: HashSet<Entity>,
+    once: bool,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Default for SaveFilter<M> {
+    fn default() -> Self {
+        SaveFilter { include: None, exclude: HashSet::new(), once: false, p: PhantomData }
+    }
+}
+
+impl<M: Marker> SaveFilter<M> {
+    /// Save only these entities.
+    pub fn new(entities: impl IntoIterator<Item = Entity>) -> Self {
+        SaveFilter { include: Some(entities.into_iter().collect()), ..Default::default() }
+    }
+
+    /// Save all entities, narrow with [`with_exclude`](SaveFilter::with_exclude).
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    pub fn with_include(mut self, entities: impl IntoIterator<Item = Entity>) -> Self {
+        self.include.get_or_insert_with(HashSet::new).extend(entities);
+        self
+    }
+
+    pub fn with_exclude(mut self, entities: impl IntoIterator<Item = Entity>) -> Self {
+        self.exclude.extend(entities);
+        self
+    }
+
+    /// Remove the filter after the next save.
+    pub fn once(mut self) -> Self {
+        self.once = true;
+        self
+    }
+
+    /// Returns true if the filter is removed after the next save.
+    pub fn is_once(&self) -> bool {
+        self.once
+    }
+
+    /// Add an entity to the include list.
+    pub fn insert(&mut self, entity: Entity) -> bool {
+        self.include.get_or_insert_with(HashSet::new).insert(entity)
+    }
+
+    /// Remove an entity from the include list.
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        self.include.as_mut().is_some_and(|set| set.remove(&entity))
+    }
+
+    /// Add an entity to the exclude list.
+    pub fn exclude(&mut self, entity: Entity) -> bool {
+        self.exclude.insert(entity)
+    }
+
+    /// Returns true if the entity should be saved.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.include.as_ref().map_or(true, |set| set.contains(&entity)) && !self.exclude.contains(&entity)
+    }
+
+    /// Returns true if only entities of the include list are saved.
+    pub(crate) fn is_partial(&self) -> bool {
+        self.include.is_some()
+    }
+
+    /// Number of entities in the include list.
+    pub fn len(&self) -> usize {
+        self.include.as_ref().map_or(0, HashSet::len)
+    }
+
+    /// Returns true if the include list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clear the include and exclude lists, saving nothing.
+    pub fn clear(&mut self) {
+        self.include = Some(HashSet::new());
+        self.exclude.clear();
+    }
+}
+
+/// Resource that limits serialization to some registered types by name, unique per marker,
+/// i.e. a quick save without `debug_overlay` or `particle`.
+///
+/// Marker and bare entity records are not filtered.
+/// See [`save_to_file_filtered`](SaveLoadExtension::save_to_file_filtered).
+#[derive(Debug, Clone, Resource)]
+pub struct TypeFilter<M: Marker> {
+    names: HashSet<Cow<'static, str>>,
+    exclude: bool,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> TypeFilter<M> {
+    /// Save only these types.
+    pub fn include<S: Into<Cow<'static, str>>>(names: impl IntoIterator<Item = S>) -> Self {
+        TypeFilter { names: names.into_iter().map(Into::into).collect(), exclude: false, p: PhantomData }
+    }
+
+    /// Save all types except these.
+    pub fn exclude<S: Into<Cow<'static, str>>>(names: impl IntoIterator<Item = S>) -> Self {
+        TypeFilter { names: names.into_iter().map(Into::into).collect(), exclude: true, p: PhantomData }
+    }
+
+    /// Returns true if records of a type are saved.
+    pub fn contains(&self, type_name: &str) -> bool {
+        self.names.contains(type_name) != self.exclude
+    }
+}
+
+/// Resource that makes saves and loads deltas over a base save, unique per marker.
+///
+/// When saving, only items changed since the last complete save or load are written,
+/// with `base` as a reference to the base save.
+/// When loading, the input must be a delta over the same `base`.
+///
+/// Usually inserted by [`save_delta`](SaveLoadExtension::save_delta) and
+/// [`load_delta`](SaveLoadExtension::load_delta).
+#[derive(Debug, Clone, Resource)]
+pub struct SaveDelta<M: Marker>(String, PhantomData<M>);
+
+impl<M: Marker> SaveDelta<M> {
+    pub fn new(base: impl Into<String>) -> Self {
+        SaveDelta(base.into(), PhantomData)
+    }
+
+    pub fn base(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Skips entities for one save or load, inserted by
+/// [`save_resources`](SaveLoadExtension::save_resources) and [`load_resources`](SaveLoadExtension::load_resources).
+#[derive(Debug, Resource)]
+pub(crate) struct ResourcesOnly<M: Marker>(PhantomData<M>);
+
+/// Insert during [`PreSaveSchedule`](schedules::PreSaveSchedule) to abort the current save, unique per marker.
+///
+/// No output is written and [`SaloError::Aborted`] is recorded. Removed at the end of the save.
+#[derive(Debug, Clone, Resource)]
+pub struct AbortSave<M: Marker>(Cow<'static, str>, PhantomData<M>);
+
+impl<M: Marker> AbortSave<M> {
+    pub fn new(reason: impl Into<Cow<'static, str>>) -> Self {
+        AbortSave(reason.into(), PhantomData)
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Resource that contains the metadata header of a save, unique per marker.
+/// 
+/// If present during saving, written before all records.
+/// Inserted during loading if the input contains a header.
+#[derive(Debug, Clone, Resource)]
+pub struct SaveMetadata<M: Marker>(<M::Method as SerializationMethod>::Value, pub(crate) PhantomData<M>);
+
+impl<M: Marker> SaveMetadata<M> {
+    /// Serialize an arbitrary payload, i.e. timestamp, playtime or thumbnail bytes.
+    pub fn new(item: &impl serde::Serialize) -> anyhow::Result<Self> {
+        Ok(SaveMetadata(M::Method::serialize_value(item)?, PhantomData))
+    }
+
+    /// Deserialize the payload.
+    pub fn get<T: serde::de::DeserializeOwned>(&self) -> anyhow::Result<T> {
+        M::Method::deserialize_value(self.0.clone())
+    }
+
+    pub fn value(&self) -> &<M::Method as SerializationMethod>::Value {
+        &self.0
+    }
+}
+
+/// Read the metadata header of a save without loading any records.
+/// 
+/// Returns `None` if the save has no header.
+pub fn peek_metadata<M: Marker>(bytes: &[u8]) -> Result<Option<SaveMetadata<M>>, SaloError> {
+    M::Method::deserialize::<serde_impls::PeekMetadata<_>>(bytes)
+        .map(|peek| peek.0.map(|value| SaveMetadata(value, PhantomData)))
+        .map_err(SaloError::deserialization)
+}
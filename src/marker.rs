@@ -0,0 +1,85 @@
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Commands, Query, Res, ResMut};
+
+use crate::methods::SerializationMethod;
+use crate::propagation::Marked;
+use crate::saveload::{EntityPath, Parents, PathedValue, SaveSelection};
+use crate::{DeserializeContext, Marker, RegisteredType, SaloConfig, SaloRegistry, SerializeContext};
+
+/// Run condition, true if the marker is saved, see [`SaloConfig::with_persist_marker`].
+pub(crate) fn persist_marker<M: Marker>(config: Res<SaloConfig<M>>) -> bool {
+    config.persist_marker()
+}
+
+/// Record the marker in the registry unless registered as a type, see [`SaloConfig::with_persist_marker`].
+pub(crate) fn register_marker<M: Marker>(registry: &mut SaloRegistry<M>) {
+    if M::IS_ALL || registry.contains(&M::type_name()) {
+        return;
+    }
+    registry.push(RegisteredType {
+        name: M::type_name(),
+        rust_name: std::any::type_name::<M>(),
+        version: 0,
+        is_resource: false,
+        active: true,
+    })
+}
+
+/// Save a record without a value for each entity with the marker.
+pub(crate) fn serialize_marker<M: Marker>(
+    mut paths: ResMut<SerializeContext<M>>,
+    query: Query<Entity, M::Query>,
+    parents: Parents,
+    marked: Marked<M>,
+    selection: SaveSelection<M>,
+) {
+    for entity in query.iter() {
+        if !selection.contains(entity) {
+            continue;
+        }
+        if selection.is_delta() {
+            if let Some(path) = paths.paths.get(&entity) {
+                let path = EntityPath::Path(path.clone());
+                paths.present.entry(M::type_name().into_owned()).or_default().insert(path);
+            }
+        }
+        let Some(parent) = paths.parent_of(M::type_name(), entity, &parents, &marked, &selection) else {
+            continue;
+        };
+        let record = PathedValue {
+            parent,
+            path: paths.path_of(entity),
+            value: M::Method::serialize_value(&()).unwrap(),
+        };
+        paths.components.entry(M::type_name()).or_default().push(record);
+    }
+}
+
+/// Insert the marker on each entity with a record, records are dropped if the marker is not persisted.
+pub(crate) fn deserialize_marker<M: Marker>(
+    mut commands: Commands,
+    config: Res<SaloConfig<M>>,
+    mut context: ResMut<DeserializeContext<M>>,
+) {
+    let removed = context.delta.as_mut().and_then(|d| d.removed.remove(M::type_name().as_ref()));
+    let items = context.components.remove(M::type_name().as_ref());
+    if !config.persist_marker() {
+        return;
+    }
+    for path in removed.into_iter().flatten() {
+        if let Some(entity) = context.path_map.get(&path) {
+            commands.entity(*entity).remove::<M::Bundle>();
+        }
+    }
+    let Some(items) = items else { return };
+    for PathedValue { parent, path, value } in items {
+        if context.over_budget(&path) {
+            let type_name = M::type_name().into_owned();
+            context.deferred.entry(type_name).or_default().push(PathedValue { parent, path, value });
+            continue;
+        }
+        let entity = context.get_or_new(&mut commands, &path);
+        commands.entity(entity).insert(M::Bundle::default());
+        context.set_parent(&mut commands, entity, parent);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{any::type_name, fmt::Debug};
+use std::{any::type_name, fmt::Debug, marker::PhantomData, io::{Read, Write}};
 
 use anyhow::Ok;
 use serde::{de::DeserializeOwned, Serialize};
@@ -9,6 +9,14 @@ use std::{io::{BufWriter, BufReader}, fs::File};
 
 pub trait SerializeValue: Serialize + DeserializeOwned + Clone + Default + Debug + Send + Sync + 'static {
     fn is_empty(&self) -> bool;
+    /// Entries of a map value, used to write inline records, `None` if not a map.
+    fn entries(&self) -> Option<Vec<(&str, &Self)>> {
+        None
+    }
+    /// Build a map value from entries of an inline record.
+    fn from_entries(_entries: Vec<(String, Self)>) -> Option<Self> {
+        None
+    }
 }
 
 impl SerializeValue for Vec<u8> {
@@ -21,10 +29,20 @@ impl SerializeValue for serde_json::Value {
     fn is_empty(&self) -> bool {
         self.is_null()
     }
+    fn entries(&self) -> Option<Vec<(&str, &Self)>> {
+        Some(self.as_object()?.iter().map(|(k, v)| (k.as_str(), v)).collect())
+    }
+    fn from_entries(entries: Vec<(String, Self)>) -> Option<Self> {
+        Some(serde_json::Value::Object(entries.into_iter().collect()))
+    }
 }
 
 pub trait SerializationMethod: Debug + Send + Sync + 'static {
     type Value: SerializeValue;
+    /// File extension of the output, used to name files in archives.
+    const EXTENSION: &'static str = "bin";
+    /// Values can be decoded without their types, see [`convert`](crate::convert).
+    const SELF_DESCRIBING: bool = true;
     fn serialize_value(item: &impl serde::Serialize)-> anyhow::Result<Self::Value>;
     fn deserialize_value<T: DeserializeOwned>(item: Self::Value)-> anyhow::Result<T>;
     fn serialize_bytes(item: &impl serde::Serialize)-> anyhow::Result<Vec<u8>>;
@@ -32,6 +50,28 @@ pub trait SerializationMethod: Debug + Send + Sync + 'static {
         anyhow::bail!("Format {} is not human-readable.", type_name::<Self>())
     }
     fn deserialize<T: DeserializeOwned>(item: &[u8]) -> anyhow::Result<T>;
+    fn deserialize_str<T: DeserializeOwned>(_item: &str) -> anyhow::Result<T> {
+        anyhow::bail!("Format {} is not human-readable.", type_name::<Self>())
+    }
+    /// Serialize with a key from [`SaveEncryptionKey`](crate::SaveEncryptionKey), ignores the key by default.
+    fn serialize_bytes_keyed(item: &impl serde::Serialize, _key: Option<&[u8; 32]>)-> anyhow::Result<Vec<u8>> {
+        Self::serialize_bytes(item)
+    }
+    /// Deserialize with a key from [`SaveEncryptionKey`](crate::SaveEncryptionKey), ignores the key by default.
+    fn deserialize_keyed<T: DeserializeOwned>(item: &[u8], _key: Option<&[u8; 32]>) -> anyhow::Result<T> {
+        Self::deserialize(item)
+    }
+    /// Serialize into a writer, writes the output of `serialize_bytes` by default.
+    fn serialize_writer(writer: &mut impl Write, item: &impl serde::Serialize)-> anyhow::Result<()> {
+        writer.write_all(&Self::serialize_bytes(item)?)?;
+        anyhow::Ok(())
+    }
+    /// Deserialize from a reader, reads to the end then calls `deserialize` by default.
+    fn deserialize_reader<T: DeserializeOwned>(mut reader: impl Read)-> anyhow::Result<T> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Self::deserialize(&bytes)
+    }
     #[cfg(feature="fs")]
     fn serialize_file(file: &str, item: &impl serde::Serialize)-> anyhow::Result<()> {
         std::fs::write(file, Self::serialize_bytes(item)?)?;
@@ -49,6 +89,7 @@ pub struct SerdeJson<const PRETTY: bool=true>;
 
 impl<const PRETTY: bool> SerializationMethod for SerdeJson<PRETTY> {
     type Value = serde_json::Value;
+    const EXTENSION: &'static str = "json";
     fn serialize_value(item: &impl serde::Serialize)-> anyhow::Result<Self::Value>{
         Ok(serde_json::to_value(item)?)
     }
@@ -72,6 +113,20 @@ impl<const PRETTY: bool> SerializationMethod for SerdeJson<PRETTY> {
     fn deserialize<T: DeserializeOwned>(item: &[u8]) -> anyhow::Result<T>{
         Ok(serde_json::from_slice(item)?)
     }
+    fn deserialize_str<T: DeserializeOwned>(item: &str) -> anyhow::Result<T>{
+        Ok(serde_json::from_str(item)?)
+    }
+    fn serialize_writer(writer: &mut impl Write, item: &impl serde::Serialize)-> anyhow::Result<()> {
+        if PRETTY {
+            serde_json::to_writer_pretty(writer, item)?;
+        } else {
+            serde_json::to_writer(writer, item)?;
+        }
+        Ok(())
+    }
+    fn deserialize_reader<T: DeserializeOwned>(reader: impl Read)-> anyhow::Result<T> {
+        Ok(serde_json::from_reader(reader)?)
+    }
     #[cfg(feature="fs")]
     fn serialize_file(file: &str, item: &impl serde::Serialize)-> anyhow::Result<()> {
         if PRETTY {
@@ -87,6 +142,59 @@ impl<const PRETTY: bool> SerializationMethod for SerdeJson<PRETTY> {
     }
 }
 
+/// Pretty printed json with `WIDTH` spaces of indentation, `SerdeJson<true>` indents by 2.
+#[derive(Debug)]
+pub struct SerdeJsonIndent<const WIDTH: usize>;
+
+impl<const WIDTH: usize> SerdeJsonIndent<WIDTH> {
+    fn write(writer: impl Write, item: &impl serde::Serialize) -> anyhow::Result<()> {
+        let indent = [b' '; WIDTH];
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(&indent);
+        item.serialize(&mut serde_json::Serializer::with_formatter(writer, formatter))?;
+        Ok(())
+    }
+}
+
+impl<const WIDTH: usize> SerializationMethod for SerdeJsonIndent<WIDTH> {
+    type Value = serde_json::Value;
+    const EXTENSION: &'static str = "json";
+    fn serialize_value(item: &impl serde::Serialize)-> anyhow::Result<Self::Value>{
+        SerdeJson::<true>::serialize_value(item)
+    }
+    fn deserialize_value<T: DeserializeOwned>(item: Self::Value)-> anyhow::Result<T>{
+        SerdeJson::<true>::deserialize_value(item)
+    }
+    fn serialize_bytes(item: &impl serde::Serialize) -> anyhow::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        Self::write(&mut bytes, item)?;
+        Ok(bytes)
+    }
+    fn serialize_string(item: &impl serde::Serialize)-> anyhow::Result<String> {
+        // serde_json only writes valid utf-8.
+        Ok(String::from_utf8(Self::serialize_bytes(item)?)?)
+    }
+    fn deserialize<T: DeserializeOwned>(item: &[u8]) -> anyhow::Result<T>{
+        SerdeJson::<true>::deserialize(item)
+    }
+    fn deserialize_str<T: DeserializeOwned>(item: &str) -> anyhow::Result<T>{
+        SerdeJson::<true>::deserialize_str(item)
+    }
+    fn serialize_writer(writer: &mut impl Write, item: &impl serde::Serialize)-> anyhow::Result<()> {
+        Self::write(writer, item)
+    }
+    fn deserialize_reader<T: DeserializeOwned>(reader: impl Read)-> anyhow::Result<T> {
+        SerdeJson::<true>::deserialize_reader(reader)
+    }
+    #[cfg(feature="fs")]
+    fn serialize_file(file: &str, item: &impl serde::Serialize)-> anyhow::Result<()> {
+        Self::write(BufWriter::new(File::create(file)?), item)
+    }
+    #[cfg(feature="fs")]
+    fn deserialize_file<'de, T: DeserializeOwned>(file: &str)-> anyhow::Result<T> {
+        SerdeJson::<true>::deserialize_file(file)
+    }
+}
+
 #[cfg(feature="ron")]
 #[derive(Debug)]
 pub struct Ron<const PRETTY: bool=true>;
@@ -96,6 +204,7 @@ impl<const PRETTY: bool> SerializationMethod for Ron<PRETTY> {
     // ron::Value does not round trip and doesn't actually expand to the full ron syntax.
     // so we use serde_json for now.
     type Value = serde_json::Value;
+    const EXTENSION: &'static str = "ron";
     fn serialize_value(item: &impl serde::Serialize)-> anyhow::Result<Self::Value>{
         Ok(serde_json::to_value(item)?)
     }
@@ -121,6 +230,21 @@ impl<const PRETTY: bool> SerializationMethod for Ron<PRETTY> {
     fn deserialize<T: DeserializeOwned>(item: &[u8]) -> anyhow::Result<T>{
         Ok(ron::from_str(std::str::from_utf8(item)?)?)
     }
+    fn deserialize_str<T: DeserializeOwned>(item: &str) -> anyhow::Result<T>{
+        Ok(ron::from_str(item)?)
+    }
+    fn serialize_writer(writer: &mut impl Write, item: &impl serde::Serialize)-> anyhow::Result<()> {
+        use ron::ser::PrettyConfig;
+        if PRETTY {
+            ron::ser::to_writer_pretty(writer, item, PrettyConfig::default())?;
+        } else {
+            ron::ser::to_writer(writer, item)?;
+        }
+        Ok(())
+    }
+    fn deserialize_reader<T: DeserializeOwned>(reader: impl Read)-> anyhow::Result<T> {
+        Ok(ron::de::from_reader(reader)?)
+    }
     #[cfg(feature="fs")]
     fn serialize_file(file: &str, item: &impl serde::Serialize)-> anyhow::Result<()> {
         use ron::ser::PrettyConfig;
@@ -137,6 +261,73 @@ impl<const PRETTY: bool> SerializationMethod for Ron<PRETTY> {
     }
 }
 
+/// A [`PrettyConfig`](ron::ser::PrettyConfig) used by [`RonWith`].
+/// 
+/// ```
+/// # use bevy_salo::methods::*;
+/// #[derive(Debug)]
+/// struct Compact;
+/// 
+/// impl RonFormat for Compact {
+///     fn pretty_config() -> ron::ser::PrettyConfig {
+///         ron::ser::PrettyConfig::default()
+///             .indentor("\t".to_owned())
+///             .compact_arrays(true)
+///     }
+/// }
+/// 
+/// type All = bevy_salo::All<RonWith<Compact>>;
+/// ```
+#[cfg(feature="ron")]
+pub trait RonFormat: Debug + Send + Sync + 'static {
+    fn pretty_config() -> ron::ser::PrettyConfig;
+}
+
+/// Pretty printed ron with a user supplied [`RonFormat`].
+#[cfg(feature="ron")]
+#[derive(Debug)]
+pub struct RonWith<F: RonFormat>(PhantomData<F>);
+
+#[cfg(feature="ron")]
+impl<F: RonFormat> SerializationMethod for RonWith<F> {
+    type Value = serde_json::Value;
+    const EXTENSION: &'static str = "ron";
+    fn serialize_value(item: &impl serde::Serialize)-> anyhow::Result<Self::Value>{
+        Ron::<true>::serialize_value(item)
+    }
+    fn deserialize_value<T: DeserializeOwned>(item: Self::Value)-> anyhow::Result<T>{
+        Ron::<true>::deserialize_value(item)
+    }
+    fn serialize_bytes(item: &impl serde::Serialize) -> anyhow::Result<Vec<u8>> {
+        Ok(Self::serialize_string(item)?.into_bytes())
+    }
+    fn serialize_string(item: &impl serde::Serialize)-> anyhow::Result<String> {
+        Ok(ron::ser::to_string_pretty(item, F::pretty_config())?)
+    }
+    fn deserialize<T: DeserializeOwned>(item: &[u8]) -> anyhow::Result<T>{
+        Ron::<true>::deserialize(item)
+    }
+    fn deserialize_str<T: DeserializeOwned>(item: &str) -> anyhow::Result<T>{
+        Ron::<true>::deserialize_str(item)
+    }
+    fn serialize_writer(writer: &mut impl Write, item: &impl serde::Serialize)-> anyhow::Result<()> {
+        ron::ser::to_writer_pretty(writer, item, F::pretty_config())?;
+        Ok(())
+    }
+    fn deserialize_reader<T: DeserializeOwned>(reader: impl Read)-> anyhow::Result<T> {
+        Ron::<true>::deserialize_reader(reader)
+    }
+    #[cfg(feature="fs")]
+    fn serialize_file(file: &str, item: &impl serde::Serialize)-> anyhow::Result<()> {
+        ron::ser::to_writer_pretty(BufWriter::new(File::create(file)?), item, F::pretty_config())?;
+        Ok(())
+    }
+    #[cfg(feature="fs")]
+    fn deserialize_file<'de, T: DeserializeOwned>(file: &str)-> anyhow::Result<T> {
+        Ron::<true>::deserialize_file(file)
+    }
+}
+
 #[cfg(feature="postcard")]
 #[derive(Debug)]
 pub struct Postcard;
@@ -144,6 +335,7 @@ pub struct Postcard;
 #[cfg(feature="postcard")]
 impl SerializationMethod for Postcard {
     type Value = Vec<u8>;
+    const SELF_DESCRIBING: bool = false;
     fn serialize_value(item: &impl serde::Serialize)-> anyhow::Result<Self::Value>{
         Ok(postcard::to_allocvec(item)?)
     }
@@ -156,6 +348,10 @@ impl SerializationMethod for Postcard {
     fn deserialize<T: DeserializeOwned>(item: &[u8]) -> anyhow::Result<T>{
         Ok(postcard::from_bytes(item)?)
     }
+    fn serialize_writer(writer: &mut impl Write, item: &impl serde::Serialize)-> anyhow::Result<()> {
+        postcard::to_io(item, writer)?;
+        Ok(())
+    }
     #[cfg(feature="fs")]
     fn serialize_file(file: &str, item: &impl serde::Serialize)-> anyhow::Result<()> {
         postcard::to_io(item, BufWriter::new(File::create(file)?))?;
@@ -167,3 +363,222 @@ impl SerializationMethod for Postcard {
         Ok(postcard::from_io((File::open(file)?, &mut vec![0; 8 * 1024]))?.0)
     }
 }
+
+/// A compression algorithm used by [`Compressed`].
+pub trait Compression: Debug + Send + Sync + 'static {
+    fn compress(bytes: &[u8]) -> anyhow::Result<Vec<u8>>;
+    fn decompress(bytes: &[u8]) -> anyhow::Result<Vec<u8>>;
+}
+
+/// Compresses the output of another method, e.g. `Compressed<Postcard, Zstd>`.
+/// 
+/// Only affects byte and file outputs, values are handled by the inner method.
+#[derive(Debug)]
+pub struct Compressed<M: SerializationMethod, A: Compression>(PhantomData<(M, A)>);
+
+impl<M: SerializationMethod, A: Compression> SerializationMethod for Compressed<M, A> {
+    type Value = M::Value;
+    const SELF_DESCRIBING: bool = M::SELF_DESCRIBING;
+    fn serialize_value(item: &impl serde::Serialize)-> anyhow::Result<Self::Value>{
+        M::serialize_value(item)
+    }
+    fn deserialize_value<T: DeserializeOwned>(item: Self::Value)-> anyhow::Result<T>{
+        M::deserialize_value(item)
+    }
+    fn serialize_bytes(item: &impl serde::Serialize) -> anyhow::Result<Vec<u8>> {
+        A::compress(&M::serialize_bytes(item)?)
+    }
+    fn deserialize<T: DeserializeOwned>(item: &[u8]) -> anyhow::Result<T>{
+        M::deserialize(&A::decompress(item)?)
+    }
+    fn serialize_bytes_keyed(item: &impl serde::Serialize, key: Option<&[u8; 32]>)-> anyhow::Result<Vec<u8>> {
+        A::compress(&M::serialize_bytes_keyed(item, key)?)
+    }
+    fn deserialize_keyed<T: DeserializeOwned>(item: &[u8], key: Option<&[u8; 32]>) -> anyhow::Result<T> {
+        M::deserialize_keyed(&A::decompress(item)?, key)
+    }
+}
+
+#[cfg(feature="gzip")]
+#[derive(Debug)]
+pub struct Gzip;
+
+#[cfg(feature="gzip")]
+impl Compression for Gzip {
+    fn compress(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        use std::io::Write;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes)?;
+        Ok(encoder.finish()?)
+    }
+    fn decompress(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        use std::io::Read;
+        let mut result = Vec::new();
+        flate2::read::GzDecoder::new(bytes).read_to_end(&mut result)?;
+        Ok(result)
+    }
+}
+
+#[cfg(feature="zstd")]
+#[derive(Debug)]
+pub struct Zstd;
+
+#[cfg(feature="zstd")]
+impl Compression for Zstd {
+    fn compress(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Ok(zstd::encode_all(bytes, 0)?)
+    }
+    fn decompress(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Ok(zstd::decode_all(bytes)?)
+    }
+}
+
+#[cfg(feature="lz4")]
+#[derive(Debug)]
+pub struct Lz4;
+
+#[cfg(feature="lz4")]
+impl Compression for Lz4 {
+    fn compress(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Ok(lz4_flex::compress_prepend_size(bytes))
+    }
+    fn decompress(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Ok(lz4_flex::decompress_size_prepended(bytes)?)
+    }
+}
+
+/// A checksum algorithm used by [`Checksummed`].
+pub trait Checksum: Debug + Send + Sync + 'static {
+    /// Length of the checksum in bytes.
+    const LEN: usize;
+    fn checksum(bytes: &[u8]) -> Vec<u8>;
+}
+
+/// Appends a checksum to the output of another method, e.g. `Checksummed<Postcard, Crc32>`.
+/// 
+/// Fails with [`SaloError::CorruptSave`](crate::SaloError::CorruptSave) on mismatch.
+/// Only affects byte and file outputs.
+#[derive(Debug)]
+pub struct Checksummed<M: SerializationMethod, A: Checksum>(PhantomData<(M, A)>);
+
+impl<M: SerializationMethod, A: Checksum> Checksummed<M, A> {
+    fn append(mut bytes: Vec<u8>) -> Vec<u8> {
+        let checksum = A::checksum(&bytes);
+        bytes.extend(checksum);
+        bytes
+    }
+
+    fn validate(bytes: &[u8]) -> anyhow::Result<&[u8]> {
+        if bytes.len() < A::LEN {
+            return Err(crate::SaloError::CorruptSave.into());
+        }
+        let (payload, checksum) = bytes.split_at(bytes.len() - A::LEN);
+        if A::checksum(payload) != checksum {
+            return Err(crate::SaloError::CorruptSave.into());
+        }
+        Ok(payload)
+    }
+}
+
+impl<M: SerializationMethod, A: Checksum> SerializationMethod for Checksummed<M, A> {
+    type Value = M::Value;
+    const SELF_DESCRIBING: bool = M::SELF_DESCRIBING;
+    fn serialize_value(item: &impl serde::Serialize)-> anyhow::Result<Self::Value>{
+        M::serialize_value(item)
+    }
+    fn deserialize_value<T: DeserializeOwned>(item: Self::Value)-> anyhow::Result<T>{
+        M::deserialize_value(item)
+    }
+    fn serialize_bytes(item: &impl serde::Serialize) -> anyhow::Result<Vec<u8>> {
+        Ok(Self::append(M::serialize_bytes(item)?))
+    }
+    fn deserialize<T: DeserializeOwned>(item: &[u8]) -> anyhow::Result<T>{
+        M::deserialize(Self::validate(item)?)
+    }
+    fn serialize_bytes_keyed(item: &impl serde::Serialize, key: Option<&[u8; 32]>)-> anyhow::Result<Vec<u8>> {
+        Ok(Self::append(M::serialize_bytes_keyed(item, key)?))
+    }
+    fn deserialize_keyed<T: DeserializeOwned>(item: &[u8], key: Option<&[u8; 32]>) -> anyhow::Result<T> {
+        M::deserialize_keyed(Self::validate(item)?, key)
+    }
+}
+
+#[cfg(feature="crc32")]
+#[derive(Debug)]
+pub struct Crc32;
+
+#[cfg(feature="crc32")]
+impl Checksum for Crc32 {
+    const LEN: usize = 4;
+    fn checksum(bytes: &[u8]) -> Vec<u8> {
+        crc32fast::hash(bytes).to_le_bytes().to_vec()
+    }
+}
+
+#[cfg(feature="sha2")]
+#[derive(Debug)]
+pub struct Sha256;
+
+#[cfg(feature="sha2")]
+impl Checksum for Sha256 {
+    const LEN: usize = 32;
+    fn checksum(bytes: &[u8]) -> Vec<u8> {
+        use sha2::Digest;
+        sha2::Sha256::digest(bytes).to_vec()
+    }
+}
+
+/// Encrypts the output of another method with XChaCha20Poly1305,
+/// the key is supplied by the [`SaveEncryptionKey`](crate::SaveEncryptionKey) resource.
+/// 
+/// Fails if the key is missing or incorrect. Only affects byte, writer and file outputs,
+/// string outputs fail with a key.
+#[cfg(feature="encryption")]
+#[derive(Debug)]
+pub struct Encrypted<M: SerializationMethod>(PhantomData<M>);
+
+#[cfg(feature="encryption")]
+impl<M: SerializationMethod> SerializationMethod for Encrypted<M> {
+    type Value = M::Value;
+    const SELF_DESCRIBING: bool = M::SELF_DESCRIBING;
+    fn serialize_value(item: &impl serde::Serialize)-> anyhow::Result<Self::Value>{
+        M::serialize_value(item)
+    }
+    fn deserialize_value<T: DeserializeOwned>(item: Self::Value)-> anyhow::Result<T>{
+        M::deserialize_value(item)
+    }
+    fn serialize_bytes(item: &impl serde::Serialize) -> anyhow::Result<Vec<u8>> {
+        Self::serialize_bytes_keyed(item, None)
+    }
+    fn deserialize<T: DeserializeOwned>(item: &[u8]) -> anyhow::Result<T>{
+        Self::deserialize_keyed(item, None)
+    }
+    fn serialize_bytes_keyed(item: &impl serde::Serialize, key: Option<&[u8; 32]>)-> anyhow::Result<Vec<u8>> {
+        use chacha20poly1305::{XChaCha20Poly1305, aead::{Aead, AeadCore, KeyInit, OsRng}};
+        let Some(key) = key else {
+            anyhow::bail!("Format {} requires a SaveEncryptionKey.", type_name::<Self>())
+        };
+        let cipher = XChaCha20Poly1305::new(key.into());
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let encrypted = cipher.encrypt(&nonce, M::serialize_bytes_keyed(item, Some(key))?.as_ref())
+            .map_err(|_| anyhow::anyhow!("Encryption failed."))?;
+        let mut result = nonce.to_vec();
+        result.extend(encrypted);
+        Ok(result)
+    }
+    fn deserialize_keyed<T: DeserializeOwned>(item: &[u8], key: Option<&[u8; 32]>) -> anyhow::Result<T> {
+        use chacha20poly1305::{XChaCha20Poly1305, XNonce, aead::{Aead, KeyInit}};
+        let Some(key) = key else {
+            anyhow::bail!("Format {} requires a SaveEncryptionKey.", type_name::<Self>())
+        };
+        // XChaCha20 uses 192 bit nonces.
+        if item.len() < 24 {
+            anyhow::bail!("Input is too short to be encrypted.")
+        }
+        let (nonce, encrypted) = item.split_at(24);
+        let decrypted = XChaCha20Poly1305::new(key.into())
+            .decrypt(XNonce::from_slice(nonce), encrypted)
+            .map_err(|_| anyhow::anyhow!("Decryption failed, the key is incorrect or the input is corrupted."))?;
+        M::deserialize_keyed(&decrypted, Some(key))
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::marker::PhantomData;
+
+use bevy_ecs::component::Component;
+use bevy_ecs::system::{Res, ResMut, Resource};
+
+use crate::{DeserializeContext, EntityPath, Marker};
+
+/// Bit set on persistent ids in saves, never set by `Entity::to_bits` in practice
+/// since it requires a generation of `2^31`.
+pub(crate) const PERSISTENT_BIT: u64 = 1 << 63;
+
+/// A stable id of an unnamed entity, written instead of its [`Entity`](bevy_ecs::entity::Entity)
+/// so it can be matched across sessions.
+///
+/// When loading, records with a persistent id are applied to the entity with the same id,
+/// or a new entity with this component if not found.
+/// Ids must be unique, the highest bit is reserved and ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Component)]
+pub struct PersistentId(u64);
+
+impl PersistentId {
+    pub fn new(id: u64) -> Self {
+        PersistentId(id & !PERSISTENT_BIT)
+    }
+
+    /// Create a random id, collisions are unlikely but possible.
+    pub fn random() -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(0);
+        Self::new(hasher.finish())
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+
+    /// Value written in [`EntityPath::Entity`].
+    pub(crate) fn to_bits(self) -> u64 {
+        self.0 | PERSISTENT_BIT
+    }
+
+    /// Read an id from [`EntityPath::Entity`], returns `None` if not persistent.
+    pub(crate) fn from_path(path: &EntityPath) -> Option<Self> {
+        match path {
+            EntityPath::Entity(bits) if bits & PERSISTENT_BIT != 0 => Some(Self::new(*bits)),
+            _ => None,
+        }
+    }
+}
+
+/// Issues monotonic [`PersistentId`]s, unique per marker, inserted by the plugin.
+///
+/// Ids found when loading are reserved, so new ids do not collide with loaded ones.
+#[derive(Debug, Resource)]
+pub struct PersistentIdAllocator<M: Marker>(u64, PhantomData<M>);
+
+impl<M: Marker> Default for PersistentIdAllocator<M> {
+    fn default() -> Self {
+        PersistentIdAllocator(0, PhantomData)
+    }
+}
+
+impl<M: Marker> PersistentIdAllocator<M> {
+    /// Issue a new id.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> PersistentId {
+        let id = PersistentId::new(self.0);
+        self.0 += 1;
+        id
+    }
+
+    /// Make sure `id` is not issued.
+    pub fn reserve(&mut self, id: PersistentId) {
+        self.0 = self.0.max(id.get() + 1);
+    }
+}
+
+/// Reserve persistent ids of the last load.
+pub(crate) fn reserve_persistent_ids<M: Marker>(
+    ctx: Res<DeserializeContext<M>>,
+    allocator: Option<ResMut<PersistentIdAllocator<M>>>,
+) {
+    let Some(mut allocator) = allocator else { return };
+    for id in ctx.path_map.keys().filter_map(PersistentId::from_path) {
+        allocator.reserve(id);
+    }
+}
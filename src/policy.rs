@@ -0,0 +1,198 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use bevy_ecs::system::{Res, ResMut, Resource};
+
+use crate::methods::SerializationMethod;
+use crate::import::TypeRecords;
+use crate::{Marker, SaveLoad, SaveLoadBundle, SaveLoadRes, DeserializeContext, SaloErrors, SaloError, SaloRegistry, ResourcesOnly};
+
+/// How a type is treated when loading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TypePolicy {
+    /// Fail the whole load if the type is missing or cannot be deserialized.
+    Required,
+    /// Load if present, records that cannot be deserialized are skipped with a warning.
+    #[default]
+    Optional,
+    /// Never load, even if present.
+    Ignore,
+}
+
+/// Per-type [`TypePolicy`] used when loading, unique per marker, inserted by the plugin.
+///
+/// Types are identified by their `type_name`, unlisted types are [`TypePolicy::Optional`].
+#[derive(Debug, Resource)]
+pub struct LoadPolicy<M: Marker> {
+    policies: HashMap<Cow<'static, str>, TypePolicy>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Default for LoadPolicy<M> {
+    fn default() -> Self {
+        Self {
+            policies: HashMap::new(),
+            p: PhantomData,
+        }
+    }
+}
+
+impl<M: Marker> LoadPolicy<M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, type_name: impl Into<Cow<'static, str>>, policy: TypePolicy) -> Self {
+        self.set(type_name, policy);
+        self
+    }
+
+    pub fn set(&mut self, type_name: impl Into<Cow<'static, str>>, policy: TypePolicy) {
+        self.policies.insert(type_name.into(), policy);
+    }
+
+    pub fn get(&self, type_name: &str) -> TypePolicy {
+        self.policies.get(type_name).copied().unwrap_or_default()
+    }
+}
+
+/// Drop ignored types and check required types exist.
+pub(crate) fn apply_load_policy<M: Marker>(
+    policy: Res<LoadPolicy<M>>,
+    registry: Res<SaloRegistry<M>>,
+    resources_only: Option<Res<ResourcesOnly<M>>>,
+    type_records: Option<Res<TypeRecords<M>>>,
+    mut ctx: ResMut<DeserializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    if !errors.is_empty() {
+        return;
+    }
+    let is_resource = |name: &str| registry.get(name).is_some_and(|t| t.is_resource);
+    if resources_only.is_some() {
+        ctx.components.retain(|name, _| is_resource(name));
+    }
+    if let Some(records) = &type_records {
+        ctx.components.retain(|name, _| name == records.type_name.as_ref());
+    }
+    ctx.required = policy.policies.iter()
+        .filter(|(_, policy)| **policy == TypePolicy::Required)
+        .map(|(name, _)| name.clone())
+        .collect();
+    for (name, policy) in policy.policies.iter() {
+        if resources_only.is_some() && !is_resource(name) {
+            continue;
+        }
+        if type_records.as_ref().is_some_and(|r| r.type_name != *name) {
+            continue;
+        }
+        match policy {
+            // Ignored records are neither loaded nor counted as deserialized.
+            TypePolicy::Ignore => {
+                ctx.components.remove(name.as_ref());
+                ctx.counts.retain(|(n, _)| n != name);
+            },
+            // Deltas only contain changed types.
+            TypePolicy::Required => if ctx.delta.is_none() && !ctx.components.contains_key(name.as_ref()) {
+                errors.push(SaloError::MissingRequired(name.clone()));
+            },
+            TypePolicy::Optional => (),
+        }
+    }
+}
+
+fn validate<M: Marker, T: serde::de::DeserializeOwned>(
+    name: &str,
+    policy: &LoadPolicy<M>,
+    ctx: &DeserializeContext<M>,
+    errors: &mut SaloErrors<M>,
+) {
+    if policy.get(name) != TypePolicy::Required {
+        return;
+    }
+    for record in ctx.components.get(name).into_iter().flatten() {
+        if let Err(e) = M::Method::deserialize_value::<T>(record.value.clone()) {
+            errors.push(SaloError::deserialization(e.context(format!("Required type {} is corrupted.", name))));
+            return;
+        }
+    }
+}
+
+/// Check all records of a required component can be deserialized.
+pub(crate) fn validate_component<M: Marker, T: SaveLoad>(
+    policy: Res<LoadPolicy<M>>,
+    ctx: Res<DeserializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    validate::<M, T::De>(&T::type_name(), &policy, &ctx, &mut errors)
+}
+
+/// Check all records of a required bundle can be deserialized.
+pub(crate) fn validate_bundle<M: Marker, T: SaveLoadBundle>(
+    policy: Res<LoadPolicy<M>>,
+    ctx: Res<DeserializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    validate::<M, T::De>(&T::type_name(), &policy, &ctx, &mut errors)
+}
+
+/// Check all records of a required marker can be deserialized.
+pub(crate) fn validate_marker<M: Marker>(
+    policy: Res<LoadPolicy<M>>,
+    ctx: Res<DeserializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    validate::<M, ()>(&M::type_name(), &policy, &ctx, &mut errors)
+}
+
+/// Check the record of a required resource can be deserialized.
+pub(crate) fn validate_resource<M: Marker, T: SaveLoadRes>(
+    policy: Res<LoadPolicy<M>>,
+    ctx: Res<DeserializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    validate::<M, T::De>(&T::type_name(), &policy, &ctx, &mut errors)
+}
+
+/// Result of a [`LoadResolver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution<T> {
+    /// Keep the component on the entity, the record is discarded.
+    KeepExisting,
+    /// Replace the component with the record, the default behavior.
+    UseIncoming,
+    /// Replace the component with a merged value.
+    Merge(T),
+}
+
+/// Decides what happens when a record is loaded onto an entity that already has `T`,
+/// i.e. to sum currencies or keep the max progress. Unique per marker and type.
+///
+/// Only called for entities matched by path, insert as a resource to enable.
+///
+/// ```
+/// # use bevy_salo::*;
+/// # #[derive(bevy_ecs::component::Component, serde::Serialize, serde::Deserialize)]
+/// # struct Coins(u32);
+/// # impl SaveLoadCore for Coins {}
+/// LoadResolver::<All, Coins>::new(|existing, incoming| Resolution::Merge(Coins(existing.0 + incoming.0)));
+/// ```
+#[derive(Resource)]
+pub struct LoadResolver<M: Marker, T: SaveLoad>(fn(&T, &T::De) -> Resolution<T>, PhantomData<M>);
+
+impl<M: Marker, T: SaveLoad> LoadResolver<M, T> {
+    pub fn new(resolver: fn(&T, &T::De) -> Resolution<T>) -> Self {
+        LoadResolver(resolver, PhantomData)
+    }
+
+    pub fn resolve(&self, existing: &T, incoming: &T::De) -> Resolution<T> {
+        (self.0)(existing, incoming)
+    }
+}
+
+impl<M: Marker, T: SaveLoad> std::fmt::Debug for LoadResolver<M, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "LoadResolver<{}>", T::type_name())
+    }
+}
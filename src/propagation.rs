@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Query, Res, ResMut, Resource, SystemParam};
+#[cfg(feature="hierarchy")]
+use bevy_hierarchy::Children;
+
+use crate::{Marker, MarkerPropagation, SaloConfig};
+
+/// Unmarked descendants of marked entities, collected with [`MarkerPropagation::Descendants`].
+#[derive(Debug, Resource)]
+pub(crate) struct MarkedDescendants<M: Marker>(HashSet<Entity>, PhantomData<M>);
+
+impl<M: Marker> Default for MarkedDescendants<M> {
+    fn default() -> Self {
+        MarkedDescendants(HashSet::new(), PhantomData)
+    }
+}
+
+impl<M: Marker> MarkedDescendants<M> {
+    pub(crate) fn contains(&self, entity: Entity) -> bool {
+        self.0.contains(&entity)
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+/// Entities considered marked by `M`, including descendants with [`MarkerPropagation::Descendants`].
+#[derive(SystemParam)]
+pub struct Marked<'w, 's, M: Marker> {
+    marked: Query<'w, 's, (), <M as Marker>::Query>,
+    descendants: Res<'w, MarkedDescendants<M>>,
+}
+
+impl<M: Marker> Marked<'_, '_, M> {
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.marked.contains(entity) || self.descendants.contains(entity)
+    }
+
+    /// Unmarked descendants of marked entities.
+    pub fn descendants(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.descendants.iter()
+    }
+}
+
+/// Collect unmarked descendants of marked entities.
+pub(crate) fn collect_descendants<M: Marker>(
+    config: Res<SaloConfig<M>>,
+    mut descendants: ResMut<MarkedDescendants<M>>,
+    #[cfg(feature="hierarchy")]
+    marked: Query<Entity, M::Query>,
+    #[cfg(feature="hierarchy")]
+    children: Query<&Children>,
+) {
+    descendants.0.clear();
+    if !M::IS_ALL && config.marker_propagation() != MarkerPropagation::None {
+        #[cfg(feature="hierarchy")]
+        {
+            let mut stack: Vec<Entity> = marked.iter().collect();
+            while let Some(entity) = stack.pop() {
+                for child in children.get(entity).into_iter().flatten() {
+                    if !marked.contains(*child) && descendants.0.insert(*child) {
+                        stack.push(*child);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bevy_ecs::schedule::Schedules;
+use bevy_ecs::system::Resource;
+use bevy_ecs::world::World;
+use bevy_utils::get_short_name;
+use bevy_utils::tracing::warn;
+
+use crate::schedules::{LoadSchedule, ResetSchedule, SaveSchedule};
+use crate::{LoadSource, Marker, SaveTarget};
+
+type Queued = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+/// Calls made inside a running schedule of the marker, run after the outermost call returns.
+#[derive(Resource)]
+pub(crate) struct Reentrancy<M: Marker> {
+    /// Depth of extension method calls, shared with [`DepthGuard`] to recover from panics.
+    depth: Arc<AtomicUsize>,
+    queue: Vec<Queued>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Default for Reentrancy<M> {
+    fn default() -> Self {
+        Self { depth: Arc::default(), queue: Vec::new(), p: PhantomData }
+    }
+}
+
+struct DepthGuard(Arc<AtomicUsize>);
+
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Returns true if called by an extension method while a save, load or reset schedule of `M` is running,
+/// in which case running it again panics.
+pub(crate) fn is_running<M: Marker>(world: &World) -> bool {
+    let Some(reentrancy) = world.get_resource::<Reentrancy<M>>() else { return false };
+    let Some(schedules) = world.get_resource::<Schedules>() else { return false };
+    reentrancy.depth.load(Ordering::Relaxed) > 0 && !(
+        schedules.contains(SaveSchedule::with_marker::<M>())
+        && schedules.contains(LoadSchedule::with_marker::<M>())
+        && schedules.contains(ResetSchedule::with_marker::<M>())
+    )
+}
+
+/// Queue a call made while [`is_running`], returns `result` in its place.
+pub(crate) fn defer<M: Marker, R>(world: &mut World, name: &str, f: impl FnOnce(&mut World) + Send + Sync + 'static, result: R) -> R {
+    warn!("{} called inside a running schedule of {}, queued after the current run.",
+        name, get_short_name(std::any::type_name::<M>()));
+    world.get_resource_or_insert_with(Reentrancy::<M>::default).queue.push(Box::new(f));
+    result
+}
+
+/// Reject a call made while [`is_running`] whose output is returned in memory, as queueing it would discard the output.
+pub(crate) fn reject<M: Marker>(name: &str) {
+    warn!("{} called inside a running schedule of {}, in-memory saves cannot be queued.",
+        name, get_short_name(std::any::type_name::<M>()));
+}
+
+/// Run a call, then the calls queued during it if this is the outermost call.
+pub(crate) fn scope<M: Marker, R>(world: &mut World, f: impl FnOnce(&mut World) -> R) -> R {
+    let depth = world.get_resource_or_insert_with(Reentrancy::<M>::default).depth.clone();
+    depth.fetch_add(1, Ordering::Relaxed);
+    let result = {
+        let _guard = DepthGuard(depth.clone());
+        f(world)
+    };
+    if depth.load(Ordering::Relaxed) > 0 {
+        return result;
+    }
+    loop {
+        let queue = std::mem::take(&mut world.resource_mut::<Reentrancy<M>>().queue);
+        if queue.is_empty() {
+            return result;
+        }
+        queue.into_iter().for_each(|queued| queued(world));
+    }
+}
+
+/// Owned [`SaveTarget`] of a queued call.
+#[derive(Debug, Clone)]
+pub(crate) enum QueuedTarget {
+    File(String),
+    Bytes,
+    String,
+    Storage(String),
+}
+
+impl QueuedTarget {
+    /// Returns true if the output is returned in memory and cannot be queued.
+    pub(crate) fn in_memory(target: &SaveTarget) -> bool {
+        matches!(target, SaveTarget::Bytes | SaveTarget::String)
+    }
+
+    pub(crate) fn new(target: SaveTarget) -> Self {
+        match target {
+            SaveTarget::File(file) => QueuedTarget::File(file.to_owned()),
+            SaveTarget::Bytes => QueuedTarget::Bytes,
+            SaveTarget::String => QueuedTarget::String,
+            SaveTarget::Storage(key) => QueuedTarget::Storage(key.to_owned()),
+        }
+    }
+
+    pub(crate) fn get(&self) -> SaveTarget<'_> {
+        match self {
+            QueuedTarget::File(file) => SaveTarget::File(file),
+            QueuedTarget::Bytes => SaveTarget::Bytes,
+            QueuedTarget::String => SaveTarget::String,
+            QueuedTarget::Storage(key) => SaveTarget::Storage(key),
+        }
+    }
+}
+
+/// Owned [`LoadSource`] of a queued call.
+#[derive(Debug, Clone)]
+pub(crate) enum QueuedSource {
+    File(String),
+    Bytes(Vec<u8>),
+    String(String),
+    Storage(String),
+}
+
+impl QueuedSource {
+    pub(crate) fn new(source: LoadSource) -> Self {
+        match source {
+            LoadSource::File(file) => QueuedSource::File(file.to_owned()),
+            LoadSource::Bytes(bytes) => QueuedSource::Bytes(bytes.to_vec()),
+            LoadSource::String(string) => QueuedSource::String(string.to_owned()),
+            LoadSource::Storage(key) => QueuedSource::Storage(key.to_owned()),
+        }
+    }
+
+    pub(crate) fn get(&self) -> LoadSource<'_> {
+        match self {
+            QueuedSource::File(file) => LoadSource::File(file),
+            QueuedSource::Bytes(bytes) => LoadSource::Bytes(bytes),
+            QueuedSource::String(string) => LoadSource::String(string),
+            QueuedSource::Storage(key) => LoadSource::Storage(key),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use bevy_ecs::system::Resource;
+
+use crate::Marker;
+
+/// A type registered to a marker.
+#[derive(Debug, Clone)]
+pub struct RegisteredType {
+    /// Name used in the save file.
+    pub name: Cow<'static, str>,
+    /// Rust type name, for diagnostics only.
+    pub rust_name: &'static str,
+    /// Version of the saved format, see [`SaveLoad::version`](crate::SaveLoad::version).
+    pub version: u32,
+    /// Registered as a `Resource`.
+    pub is_resource: bool,
+    /// Saved and loaded, unset for inactive types of
+    /// [`register_if`](crate::SaveLoadPlugin::register_if).
+    pub active: bool,
+}
+
+/// Types registered to a marker, inserted by the plugin.
+///
+/// Registering two types with the same `type_name` panics on startup.
+///
+/// Each type name belongs to a section, which is its namespace by default,
+/// i.e. `base::Unit` is in section `base`, and `Unit` in section `""`.
+#[derive(Debug, Resource)]
+pub struct SaloRegistry<M: Marker> {
+    types: Vec<RegisteredType>,
+    sections: HashMap<Cow<'static, str>, Cow<'static, str>>,
+    renamed: HashMap<Cow<'static, str>, Cow<'static, str>>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Default for SaloRegistry<M> {
+    fn default() -> Self {
+        Self {
+            types: Vec::new(),
+            sections: HashMap::new(),
+            renamed: HashMap::new(),
+            p: PhantomData,
+        }
+    }
+}
+
+impl<M: Marker> SaloRegistry<M> {
+    /// Record a type, panics if the name is already registered.
+    pub(crate) fn push(&mut self, ty: RegisteredType) {
+        if let Some(prev) = self.get(&ty.name) {
+            panic!("Duplicate type name \"{}\" registered by {} and {}.", ty.name, prev.rust_name, ty.rust_name);
+        }
+        self.types.push(ty);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RegisteredType> {
+        self.types.iter().find(|t| t.name == name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Returns true if a type is registered and active.
+    pub fn is_active(&self, name: &str) -> bool {
+        self.get(name).is_some_and(|t| t.active)
+    }
+
+    pub(crate) fn deactivate(&mut self, name: &str) {
+        if let Some(ty) = self.types.iter_mut().find(|t| t.name == name) {
+            ty.active = false;
+        }
+    }
+
+    /// Iterate over registered types in registration order.
+    pub fn registered_types(&self) -> impl Iterator<Item = &RegisteredType> {
+        self.types.iter()
+    }
+
+    /// Place a type name in a section instead of its namespace.
+    ///
+    /// Type names do not need to be registered, i.e. for types of mods.
+    pub fn set_section(&mut self, type_name: impl Into<Cow<'static, str>>, section: impl Into<Cow<'static, str>>) {
+        self.sections.insert(type_name.into(), section.into());
+    }
+
+    /// Section of a type name.
+    pub fn section_of<'t>(&'t self, type_name: &'t str) -> &'t str {
+        match self.sections.get(type_name) {
+            Some(section) => section,
+            None => type_name.rsplit_once("::").map(|(namespace, _)| namespace).unwrap_or_default(),
+        }
+    }
+
+    /// Record that type name `old` was renamed to `new`.
+    ///
+    /// Records saved as `old` are loaded as `new`, and the rename is listed in
+    /// [`RegistryChanges`](crate::RegistryChanges) when loading a save with a fingerprint.
+    pub fn set_renamed(&mut self, old: impl Into<Cow<'static, str>>, new: impl Into<Cow<'static, str>>) {
+        self.renamed.insert(old.into(), new.into());
+    }
+
+    /// Current type name of a renamed type name.
+    pub fn renamed(&self, old: &str) -> Option<&str> {
+        self.renamed.get(old).map(|s| s.as_ref())
+    }
+}
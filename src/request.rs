@@ -0,0 +1,81 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::event::{Event, Events, ManualEventReader};
+use bevy_ecs::system::Local;
+use bevy_ecs::world::World;
+
+use crate::command::report;
+use crate::reentrant::{QueuedSource, QueuedTarget};
+use crate::{Marker, SaloHistory, SaveLoadExtension};
+
+/// Event that saves with a marker in `Last`, registered by the plugin with `bevy_app`.
+///
+/// Errors are logged and recorded in [`SaloErrors`](crate::SaloErrors).
+#[derive(Debug)]
+pub struct SaveRequest<M: Marker> {
+    target: QueuedTarget,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Event for SaveRequest<M> {}
+
+impl<M: Marker> SaveRequest<M> {
+    /// Save to a file, requires the `fs` feature.
+    pub fn file(file: impl Into<String>) -> Self {
+        Self { target: QueuedTarget::File(file.into()), p: PhantomData }
+    }
+
+    /// Save to a key in [`SaloStorage`](crate::SaloStorage).
+    pub fn storage(key: impl Into<String>) -> Self {
+        Self { target: QueuedTarget::Storage(key.into()), p: PhantomData }
+    }
+}
+
+/// Event that loads with a marker in `Last`, after [`SaveRequest`]s of the same frame.
+///
+/// Errors are logged and recorded in [`SaloErrors`](crate::SaloErrors).
+#[derive(Debug)]
+pub struct LoadRequest<M: Marker> {
+    source: QueuedSource,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Event for LoadRequest<M> {}
+
+impl<M: Marker> LoadRequest<M> {
+    /// Load from a file, requires the `fs` feature.
+    pub fn file(file: impl Into<String>) -> Self {
+        Self { source: QueuedSource::File(file.into()), p: PhantomData }
+    }
+
+    /// Load from a key in [`SaloStorage`](crate::SaloStorage).
+    pub fn storage(key: impl Into<String>) -> Self {
+        Self { source: QueuedSource::Storage(key.into()), p: PhantomData }
+    }
+
+    pub fn bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        Self { source: QueuedSource::Bytes(bytes.into()), p: PhantomData }
+    }
+}
+
+/// Run [`SaveRequest`]s then [`LoadRequest`]s sent since the last run, in order.
+pub(crate) fn handle_requests<M: Marker>(
+    world: &mut World,
+    mut saves: Local<ManualEventReader<SaveRequest<M>>>,
+    mut loads: Local<ManualEventReader<LoadRequest<M>>>,
+) {
+    let requests: Vec<_> = saves.read(world.resource::<Events<SaveRequest<M>>>()).map(|r| r.target.clone()).collect();
+    for target in requests {
+        if let Some(mut history) = world.get_resource_mut::<SaloHistory<M>>() {
+            history.request("SaveRequest", "event");
+        }
+        report("SaveRequest", world.save::<M>(target.get()));
+    }
+    let requests: Vec<_> = loads.read(world.resource::<Events<LoadRequest<M>>>()).map(|r| r.source.clone()).collect();
+    for source in requests {
+        if let Some(mut history) = world.get_resource_mut::<SaloHistory<M>>() {
+            history.request("LoadRequest", "event");
+        }
+        report("LoadRequest", world.load::<M>(source.get()));
+    }
+}
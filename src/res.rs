@@ -2,8 +2,8 @@ use std::borrow::Cow;
 
 use bevy_ecs::{system::{Resource, SystemParam, Commands, Res, ResMut, StaticSystemParam, SystemParamItem}, entity::Entity};
 use serde::{de::DeserializeOwned, Serialize};
-use crate::{methods::SerializationMethod, PathedValue, EntityParent, DeserializeContext};
-use crate::{Marker, SerializeContext, EntityPath};
+use crate::{methods::SerializationMethod, PathedValue, EntityParent, DeserializeContext, SaveSelection};
+use crate::{Marker, SerializeContext, EntityPath, SaloErrors, SaloError};
 
 /// Allows a resource to be saved and loaed with serde.
 pub trait SaveLoadResCore: Serialize + DeserializeOwned + Resource + Sized {
@@ -18,6 +18,11 @@ pub trait SaveLoadResCore: Serialize + DeserializeOwned + Resource + Sized {
     fn type_name() -> Cow<'static, str> {
         Cow::Borrowed(std::any::type_name::<Self>())
     }
+
+    /// Value inserted if the save has no record of this resource, see [`SaveLoadRes::on_missing`].
+    fn on_missing() -> Option<Self> {
+        None
+    }
 }
 
 impl<T> SaveLoadRes for T where T: SaveLoadResCore {
@@ -48,6 +53,10 @@ impl<T> SaveLoadRes for T where T: SaveLoadResCore {
     fn type_name() -> Cow<'static, str> {
         <Self as SaveLoadResCore>::type_name()
     }
+
+    fn on_missing() -> Option<Self> {
+        <Self as SaveLoadResCore>::on_missing()
+    }
 }
 
 /// The core trait for resources, allows a resource to be saved and loaed with context.
@@ -80,6 +89,18 @@ pub trait SaveLoadRes: Resource + Sized {
         ctx: &mut SystemParamItem<Self::ContextMut<'_, '_>>
     ) -> Self;
 
+    /// Fallible [`from_deserialize`](Self::from_deserialize), used when loading.
+    ///
+    /// Failed records are skipped and reported in [`SaloErrors`]. Calls `from_deserialize` by default.
+    fn try_from_deserialize(
+        de: Self::De,
+        commands: &mut Commands,
+        entity_fetcher: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+        ctx: &mut SystemParamItem<Self::ContextMut<'_, '_>>
+    ) -> anyhow::Result<Self> {
+        Ok(Self::from_deserialize(de, commands, entity_fetcher, ctx))
+    }
+
     /// Name associated with this type. 
     /// This is used in deserialization
     /// and must be unique accross for all generics.
@@ -91,23 +112,29 @@ pub trait SaveLoadRes: Resource + Sized {
         Cow::Borrowed(std::any::type_name::<Self>())
     }
 
+    /// Value inserted when the save has no record of this resource,
+    /// if [`SaloConfig::with_fill_defaults`](crate::SaloConfig::with_fill_defaults) is enabled.
+    fn on_missing() -> Option<Self> {
+        None
+    }
+
     /// System for serialization.
     fn serialize_system<M: Marker>(
         mut paths: ResMut<SerializeContext<M>>,
+        mut errors: ResMut<SaloErrors<M>>,
         res: Option<Res<Self>>,
+        selection: SaveSelection<M>,
         ctx: StaticSystemParam<Self::Context<'_, '_>>,
     ) {
-        if let Some(res) = res {
-            let path_fetcher = |e: Entity| {
-                match paths.paths.get(&e) {
-                    Some(path) => EntityPath::Path(path.clone()),
-                    None => EntityPath::Entity(e.to_bits()),
-                }
-            };
+        if !selection.contains_type(&Self::type_name()) {
+            return;
+        }
+        if let Some(res) = res.filter(|res| selection.is_changed(res)) {
+            let path_fetcher = |e: Entity| paths.path_of(e);
             let value = match M::Method::serialize_value(&res.to_serializable(path_fetcher, &ctx)) {
                 Ok(value) => value,
                 Err(e) => {
-                    eprintln!("{}", e);
+                    errors.push(SaloError::serialization(e));
                     return;
                 }
             };
@@ -126,6 +153,7 @@ pub trait SaveLoadRes: Resource + Sized {
     fn deserialize_system<M: Marker>(
         mut commands: Commands,
         mut context: ResMut<DeserializeContext<M>>,
+        mut errors: ResMut<SaloErrors<M>>,
         mut ctx_mut: StaticSystemParam<Self::ContextMut<'_, '_>>,
     ) {
         let Some(mut items) = context.components.remove(Self::type_name().as_ref()) else {return};
@@ -134,19 +162,20 @@ pub trait SaveLoadRes: Resource + Sized {
         let de = match M::Method::deserialize_value(value) { 
             Ok(de) => de,
             Err(e) => {
-                eprintln!("{}", e);
+                context.reject(&Self::type_name(), e, &mut errors);
                 return;
             }
         };
 
+        // Register spawned entities so records loaded later resolve to the same entity.
         let ctx_fetch = |commands: &mut Commands, path: &EntityPath| {
-            match context.path_map.get(path) {
-                Some(entity) => *entity,
-                None => commands.spawn_empty().id()
-            }
+            let path = context.remapped(path);
+            context.get_or_new(commands, &path)
         };
-        let res = Self::from_deserialize(de, &mut commands, ctx_fetch, &mut ctx_mut);
-        commands.insert_resource(res)
+        match Self::try_from_deserialize(de, &mut commands, ctx_fetch, &mut ctx_mut) {
+            Ok(res) => commands.insert_resource(res),
+            Err(e) => context.reject(&Self::type_name(), e, &mut errors),
+        }
     }
 
     /// Remove this resource.
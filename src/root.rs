@@ -0,0 +1,86 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Query, Res, ResMut, Resource};
+
+use crate::saveload::{split_path, EntityParent, PathNames};
+use crate::transfer::rewrite_paths;
+use crate::{DeserializeContext, Marker, SerializeContext};
+
+/// Scopes paths of a subtree, i.e. to compose chunks or levels saved
+/// in separate files into one world, where each file contains `Players::John`.
+///
+/// As a component, names the root entity of a subtree like [`PathName`](crate::PathName).
+///
+/// As a resource, paths under the root are saved relative to it, i.e. `chunk_3::Players::John`
+/// is saved as `Players::John`, and records at the root are saved without a parent.
+/// When loading, the root is prepended to all named paths and records at the root
+/// are added as children of the root entity, spawned if missing.
+/// Use with [`SaveFilter`](crate::SaveFilter) to save one subtree per file.
+///
+/// ```
+/// # use bevy_salo::*;
+/// # use bevy_ecs::world::World;
+/// # let mut world = World::new();
+/// # SaveLoadPlugin::new::<All>().build_world(&mut world);
+/// world.spawn(PathRoot::<All>::new("chunk_3"));
+/// world.insert_resource(PathRoot::<All>::new("chunk_3"));
+/// let save = world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+/// world.remove_resource::<PathRoot<All>>();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Component, Resource)]
+pub struct PathRoot<M: Marker>(Cow<'static, str>, PhantomData<M>);
+
+impl<M: Marker> PathRoot<M> {
+    pub fn new(s: &'static str) -> Self {
+        Self(Cow::Borrowed(s), PhantomData)
+    }
+
+    pub fn new_owned(s: String) -> Self {
+        Self(Cow::Owned(s), PhantomData)
+    }
+
+    pub fn get(&self) -> Cow<'static, str> {
+        self.0.clone()
+    }
+}
+
+/// Name entities with a `PathRoot` component.
+pub(crate) fn build_root_names<M: Marker>(mut res: ResMut<PathNames<M>>, roots: Query<(Entity, &PathRoot<M>)>) {
+    for (entity, root) in roots.iter() {
+        res.push(entity, root.get())
+    }
+}
+
+/// Make paths under the root relative.
+pub(crate) fn strip_path_root<M: Marker>(root: Option<Res<PathRoot<M>>>, mut ctx: ResMut<SerializeContext<M>>) {
+    let Some(root) = root else { return };
+    let prefix = split_path(&root.0);
+    let ctx = &mut *ctx;
+    ctx.roots.clear();
+    ctx.paths.retain(|entity, path| {
+        if path.len() == prefix.len() && path.starts_with(&prefix) {
+            ctx.roots.insert(*entity);
+            return false;
+        }
+        if path.starts_with(&prefix) {
+            path.drain(..prefix.len());
+        }
+        true
+    });
+}
+
+/// Prepend the root to named paths and parent records at the root.
+pub(crate) fn apply_path_root<M: Marker>(root: Option<Res<PathRoot<M>>>, mut ctx: ResMut<DeserializeContext<M>>) {
+    let Some(root) = root else { return };
+    let prefix = split_path(&root.0);
+    rewrite_paths(&mut ctx, &[(Vec::new(), prefix.clone())]);
+    for record in ctx.components.values_mut().flatten() {
+        if matches!(record.parent, EntityParent::Root) {
+            record.parent = EntityParent::Path(prefix.clone());
+        }
+    }
+    ctx.root = prefix;
+}
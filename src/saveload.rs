@@ -1,56 +1,333 @@
 
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::marker::PhantomData;
 
-use bevy_ecs::{component::Component, entity::Entity, query::With};
-use bevy_ecs::system::{Query, Resource, ResMut, Commands, SystemParam, SystemParamItem, StaticSystemParam};
+use bevy_ecs::{component::Component, entity::{Entities, Entity}, query::With};
+use bevy_ecs::change_detection::{DetectChanges, Ref};
+use bevy_ecs::system::{Query, Res, Resource, ResMut, Commands, CommandQueue, SystemParam, SystemParamItem, StaticSystemParam, SystemChangeTick};
+use bevy_ecs::world::World;
+#[cfg(feature="hierarchy")]
 use bevy_hierarchy::{Parent, BuildChildren};
-use serde::{Serialize, Deserialize};
+use bevy_utils::tracing::warn;
+use serde::Serialize;
 use serde::de::DeserializeOwned;
+use smol_str::SmolStr;
 use crate::methods::SerializationMethod;
-use crate::Marker;
+use crate::persistent::PersistentId;
+use crate::fingerprint::Fingerprint;
+use crate::version::Versions;
+use crate::alias::Aliases;
+use crate::tombstone::Tombstones;
+use crate::attributes::{AttributeEntry, AttributeTable, Attributes};
+use crate::disabled::{SaloDisabled, SpawnedDisabled};
+use crate::propagation::Marked;
+use crate::{Marker, SaveFilter, TypeFilter, SaveDelta, SaveState, SaloErrors, SaloError, ConflictPolicy, FormatLayout, OrphanPolicy, PathConflict, LoadResolver, Resolution};
 
 /// This collects names from various sources to build paths.
 #[derive(Debug, Resource, Default)]
-pub struct PathNames<M: Marker>(HashMap<Entity, Cow<'static, str>>, PhantomData<M>);
+pub struct PathNames<M: Marker>{
+    names: HashMap<Entity, Cow<'static, str>>,
+    policy: ConflictPolicy,
+    pub(crate) conflicts: Vec<PathConflict>,
+    p: PhantomData<M>,
+}
 
 impl<M: Marker> PathNames<M> {
+    pub(crate) fn with_policy(policy: ConflictPolicy) -> Self {
+        Self { names: HashMap::new(), policy, conflicts: Vec::new(), p: PhantomData }
+    }
+
+    /// Name an entity, conflicting names are handled by [`ConflictPolicy`].
     pub fn push(&mut self, entity: Entity, name: Cow<'static, str>) {
-        match self.0.get_mut(&entity) {
+        match self.names.get_mut(&entity) {
             Some(n) => if n != &name {
-                panic!("Trying to rename entity {:?} from {} to {}.", entity, n, name);
+                let conflict = PathConflict::Name { entity, first: n.clone(), second: name.clone() };
+                match self.policy {
+                    ConflictPolicy::LastWins => *n = name,
+                    ConflictPolicy::Panic | ConflictPolicy::Error | ConflictPolicy::FirstWins => (),
+                }
+                self.conflicts.push(conflict);
             },
             None => {
-                self.0.insert(entity, name);
+                self.names.insert(entity, name);
             },
         }
     }
 
     pub fn get(&self, e: Entity) -> Option<&str>{
-        self.0.get(&e).map(|x| x.as_ref())
+        self.names.get(&e).map(|x| x.as_ref())
     }
 
     pub fn iter(&self) -> impl IntoIterator<Item = (Entity, &str)>{
-        self.0.iter().map(|(k, v)| (*k, v.as_ref()))
+        self.names.iter().map(|(k, v)| (*k, v.as_ref()))
+    }
+}
+
+pub(crate) type ValueOf<M> = <<M as Marker>::Method as SerializationMethod>::Value;
+pub(crate) type PathedValueOf<M> = PathedValue<ValueOf<M>>;
+
+/// Signatures of sections by section name.
+pub(crate) type Signatures = BTreeMap<String, String>;
+
+/// A parsed save file, records keyed by type name, with an optional metadata header.
+#[derive(Debug)]
+pub(crate) struct Document<V> {
+    pub(crate) metadata: Option<V>,
+    pub(crate) delta: Option<DeltaHeader>,
+    pub(crate) signatures: Option<Signatures>,
+    pub(crate) fingerprint: Option<Fingerprint>,
+    pub(crate) versions: Option<Versions>,
+    pub(crate) aliases: Option<Aliases>,
+    pub(crate) tombstones: Option<Tombstones>,
+    pub(crate) attributes: Option<AttributeTable>,
+    pub(crate) layout: FormatLayout,
+    pub(crate) components: HashMap<String, Vec<PathedValue<V>>>,
+}
+
+impl<V> Default for Document<V> {
+    fn default() -> Self {
+        Self { metadata: None, delta: None, signatures: None, fingerprint: None, versions: None, aliases: None, tombstones: None, attributes: None, layout: FormatLayout::TypeMajor, components: HashMap::new() }
+    }
+}
+
+/// Header of a delta save, records are changes since `base`.
+#[derive(Debug, Clone, Default, Serialize, serde::Deserialize)]
+pub(crate) struct DeltaHeader {
+    pub(crate) base: String,
+    /// Named paths of components removed since the base, by type name.
+    #[serde(default)]
+    pub(crate) removed: BTreeMap<String, Vec<EntityPath>>,
+}
+
+/// Borrowed version of [`Document`] used in serialization.
+pub(crate) struct DocumentRef<'t, K, V> {
+    pub(crate) metadata: Option<&'t V>,
+    pub(crate) delta: Option<&'t DeltaHeader>,
+    pub(crate) signatures: Option<&'t Signatures>,
+    pub(crate) fingerprint: Option<&'t Fingerprint>,
+    pub(crate) versions: Option<&'t Versions>,
+    pub(crate) aliases: Option<&'t Aliases>,
+    pub(crate) tombstones: Option<&'t Tombstones>,
+    pub(crate) attributes: Option<&'t AttributeTable>,
+    pub(crate) layout: FormatLayout,
+    pub(crate) components: &'t HashMap<K, Vec<PathedValue<V>>>,
+    /// Type names written as [`InlineRecords`](crate::serde_impls::InlineRecords).
+    pub(crate) inline: Option<&'t HashSet<Cow<'static, str>>>,
+}
+
+/// Named paths of records by type name, unnamed records cannot be matched across loads.
+pub(crate) fn named_paths<'t, K: AsRef<str> + 't, V: 't>(
+    components: impl IntoIterator<Item = (&'t K, &'t Vec<PathedValue<V>>)>
+) -> HashMap<String, HashSet<EntityPath>> {
+    components.into_iter().filter_map(|(name, records)| {
+        let paths: HashSet<_> = records.iter()
+            .filter(|r| matches!(r.path, EntityPath::Path(_)))
+            .map(|r| r.path.clone())
+            .collect();
+        (!paths.is_empty()).then(|| (name.as_ref().to_owned(), paths))
+    }).collect()
+}
+
+/// Selects what is written by `serialize_system`.
+///
+/// Applies [`SaveFilter`] and [`TypeFilter`], and if [`SaveDelta`] is present,
+/// skips items unchanged since the last complete save or load.
+#[derive(SystemParam)]
+pub struct SaveSelection<'w, M: Marker> {
+    filter: Option<Res<'w, SaveFilter<M>>>,
+    types: Option<Res<'w, TypeFilter<M>>>,
+    delta: Option<Res<'w, SaveDelta<M>>>,
+    state: Res<'w, SaveState<M>>,
+    tick: SystemChangeTick,
+}
+
+impl<M: Marker> SaveSelection<'_, M> {
+    /// Returns true if the entity passes the [`SaveFilter`].
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.filter.as_ref().map_or(true, |f| f.contains(entity))
+    }
+
+    /// Returns true if records of a type pass the [`TypeFilter`].
+    pub fn contains_type(&self, type_name: &str) -> bool {
+        self.types.as_ref().map_or(true, |t| t.contains(type_name))
+    }
+
+    /// Returns true if this is a delta save.
+    pub fn is_delta(&self) -> bool {
+        self.delta.is_some()
+    }
+
+    /// Returns true if the item should be written, always true if not a delta save.
+    pub fn is_changed(&self, item: &impl DetectChanges) -> bool {
+        self.delta.is_none() || item.last_changed().is_newer_than(self.state.tick(), self.tick.this_run())
     }
 }
 
-type PathedValueOf<M> = PathedValue<<<M as Marker>::Method as SerializationMethod>::Value>;
+/// Looks up parents of entities, always `None` without the `hierarchy` feature.
+#[cfg(feature="hierarchy")]
+#[derive(SystemParam)]
+pub struct Parents<'w, 's>(Query<'w, 's, &'static Parent>);
+
+/// Looks up parents of entities, always `None` without the `hierarchy` feature.
+#[cfg(not(feature="hierarchy"))]
+#[derive(SystemParam)]
+pub struct Parents<'w, 's>(PhantomData<(&'w (), &'s ())>);
+
+impl Parents<'_, '_> {
+    #[cfg(feature="hierarchy")]
+    pub fn get(&self, entity: Entity) -> Option<Entity> {
+        self.0.get(entity).ok().map(|p| p.get())
+    }
+
+    #[cfg(not(feature="hierarchy"))]
+    pub fn get(&self, _: Entity) -> Option<Entity> {
+        None
+    }
+}
 
 /// Paths used in the serialization step.
 #[derive(Debug, Resource, Default)]
 pub struct SerializeContext<M: Marker>{
-    pub(crate) paths: HashMap<Entity, String>,
+    pub(crate) paths: HashMap<Entity, Vec<SmolStr>>,
+    pub(crate) ids: HashMap<Entity, PersistentId>,
     pub(crate) components: HashMap<Cow<'static, str>, Vec<PathedValueOf<M>>>,
+    pub(crate) metadata: Option<ValueOf<M>>,
+    pub(crate) delta: Option<DeltaHeader>,
+    pub(crate) signatures: Option<Signatures>,
+    pub(crate) fingerprint: Option<Fingerprint>,
+    pub(crate) versions: Versions,
+    pub(crate) aliases: Aliases,
+    pub(crate) tombstones: Tombstones,
+    /// Attributes of written records, see [`SaveLoad::attributes`].
+    pub(crate) attributes: AttributeTable,
+    /// Type names with [`SaveLoad::inline`] set.
+    pub(crate) inline: HashSet<Cow<'static, str>>,
+    /// Named paths of all selected items in a delta save, changed or not.
+    pub(crate) present: HashMap<String, HashSet<EntityPath>>,
+    pub(crate) orphan_policy: OrphanPolicy,
+    /// Records of orphaned entities by type name, see [`OrphanPolicy`].
+    pub(crate) orphans: Vec<(Cow<'static, str>, Entity)>,
+    /// Entities at the [`PathRoot`](crate::PathRoot), children are saved at the root.
+    pub(crate) roots: HashSet<Entity>,
+    pub(crate) layout: FormatLayout,
+    /// Sort records by path before writing, see [`SaloConfig::with_deterministic`].
+    pub(crate) deterministic: bool,
     p: PhantomData<M>
 }
 
 impl<M: Marker> SerializeContext<M> {
-    pub fn serialized(&self) -> &impl serde::Serialize {
-        &self.components
+    pub fn serialized(&self) -> impl serde::Serialize + '_ {
+        DocumentRef {
+            metadata: self.metadata.as_ref(),
+            delta: self.delta.as_ref(),
+            signatures: self.signatures.as_ref(),
+            fingerprint: self.fingerprint.as_ref(),
+            versions: (!self.versions.is_empty()).then_some(&self.versions),
+            aliases: (!self.aliases.is_empty()).then_some(&self.aliases),
+            tombstones: (!self.tombstones.is_empty()).then_some(&self.tombstones),
+            attributes: (!self.attributes.is_empty()).then_some(&self.attributes),
+            layout: self.layout,
+            components: &self.components,
+            inline: Some(&self.inline),
+        }
+    }
+
+    /// Path of an entity, or its [`PersistentId`] or `Entity` if unnamed.
+    pub fn path_of(&self, entity: Entity) -> EntityPath {
+        match (self.paths.get(&entity), self.ids.get(&entity)) {
+            (Some(path), _) => EntityPath::Path(path.clone()),
+            (None, Some(id)) => EntityPath::Entity(id.to_bits()),
+            (None, None) => EntityPath::Entity(entity.to_bits()),
+        }
     }
 
+    /// Parent of a record of `type_name` on `entity`, `None` if skipped by [`OrphanPolicy::Skip`].
+    pub(crate) fn parent_of(
+        &mut self,
+        type_name: Cow<'static, str>,
+        entity: Entity,
+        parents: &Parents,
+        marked: &Marked<M>,
+        selection: &SaveSelection<M>,
+    ) -> Option<EntityParent> {
+        let Some(parent) = parents.get(entity) else { return Some(EntityParent::Root) };
+        if self.roots.contains(&parent) {
+            return Some(EntityParent::Root);
+        }
+        if let Some(path) = self.paths.get(&parent) {
+            return Some(EntityParent::Path(path.clone()));
+        }
+        if marked.contains(parent) {
+            // A parent left out by the selection makes this entity a root of the save.
+            if !selection.contains(parent) {
+                return Some(EntityParent::Root);
+            }
+            return Some(match self.ids.get(&parent) {
+                Some(id) => EntityParent::Entity(id.to_bits()),
+                None => EntityParent::Entity(parent.to_bits()),
+            });
+        }
+        match self.orphan_policy {
+            OrphanPolicy::Panic => panic!("Trying to serialize component {} in orphaned entity {:?}. \
+                Parent {:?} is neither serialized nor named.",
+                type_name,
+                entity,
+                parent
+            ),
+            OrphanPolicy::TreatAsRoot => {
+                self.orphans.push((type_name, entity));
+                Some(EntityParent::Root)
+            },
+            OrphanPolicy::Skip => {
+                self.orphans.push((type_name, entity));
+                None
+            },
+        }
+    }
+}
+
+/// Records of unregistered types found while loading, unique per marker.
+///
+/// Only collected if [`SaloConfig::with_preserve_unknown`](crate::SaloConfig::with_preserve_unknown)
+/// is set, in which case they are written back verbatim on the next save.
+#[derive(Debug, Resource)]
+pub struct UnknownComponents<M: Marker>(pub(crate) HashMap<String, Vec<PathedValueOf<M>>>, pub(crate) PhantomData<M>);
+
+impl<M: Marker> Default for UnknownComponents<M> {
+    fn default() -> Self {
+        UnknownComponents(HashMap::new(), PhantomData)
+    }
+}
+
+impl<M: Marker> UnknownComponents<M> {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, type_name: &str) -> bool {
+        self.0.contains_key(type_name)
+    }
+
+    pub fn type_names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(|k| k.as_str())
+    }
+
+    /// Iterate over type name, path and value of all unknown records.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &EntityPath, &ValueOf<M>)> {
+        self.0.iter().flat_map(|(name, records)| {
+            records.iter().map(move |r| (name.as_str(), &r.path, &r.value))
+        })
+    }
+
+    /// Remove records of a type, so they will not be saved.
+    pub fn remove(&mut self, type_name: &str) -> bool {
+        self.0.remove(type_name).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
 }
 
 /// Paths used in the deserialization step.
@@ -58,12 +335,58 @@ impl<M: Marker> SerializeContext<M> {
 pub struct DeserializeContext<M: Marker>{
     pub(crate) components: HashMap<String, Vec<PathedValueOf<M>>>,
     pub(crate) path_map: HashMap<EntityPath, Entity>,
+    pub(crate) counts: Vec<(String, usize)>,
+    pub(crate) metadata: Option<ValueOf<M>>,
+    pub(crate) delta: Option<DeltaHeader>,
+    pub(crate) signatures: Option<Signatures>,
+    pub(crate) fingerprint: Option<Fingerprint>,
+    pub(crate) versions: Versions,
+    pub(crate) loaded: HashMap<String, HashSet<EntityPath>>,
+    pub(crate) policy: ConflictPolicy,
+    pub(crate) conflicts: Vec<PathConflict>,
+    pub(crate) remap: Vec<(Vec<SmolStr>, Vec<SmolStr>)>,
+    /// Rules of [`PathAliases`](crate::PathAliases), longest first.
+    pub(crate) aliases: Vec<(Vec<SmolStr>, Vec<SmolStr>)>,
+    /// Prefix of [`PathRoot`](crate::PathRoot), applied after remapping.
+    pub(crate) root: Vec<SmolStr>,
+    /// Alias table of the document header.
+    pub(crate) saved_aliases: Aliases,
+    /// Named paths of entities to despawn, see [`save_with_tombstones`](crate::SaveLoadExtension::save_with_tombstones).
+    pub(crate) tombstones: Tombstones,
+    /// Attributes of the loaded records, see [`RecordAttributes`](crate::RecordAttributes).
+    pub(crate) attributes: AttributeTable,
+    pub(crate) budget: Option<usize>,
+    pub(crate) spawned: usize,
+    /// Records over the entity budget, see [`PendingLoad`](crate::PendingLoad).
+    pub(crate) deferred: HashMap<String, Vec<PathedValueOf<M>>>,
+    /// Records are left in [`PendingLoad`](crate::PendingLoad) after this load.
+    pub(crate) pending: bool,
+    pub(crate) spawn_disabled: bool,
+    /// Types with [`TypePolicy::Required`](crate::TypePolicy::Required).
+    pub(crate) required: HashSet<Cow<'static, str>>,
+    /// Entities spawned with [`SaloDisabled`](crate::SaloDisabled).
+    pub(crate) disabled: Vec<Entity>,
+    /// Parent of root records, see [`LoadOptions`](crate::LoadOptions).
+    pub(crate) anchor: Option<Entity>,
     p: PhantomData<M>,
 }
 
 impl<M: Marker> DeserializeContext<M> {
-    pub(crate) fn load(&mut self, components: HashMap<String, Vec<PathedValueOf<M>>>) {
-        self.components = components;
+    /// Load a document.
+    pub(crate) fn load(&mut self, document: Document<ValueOf<M>>) {
+        self.counts = document.components.iter().map(|(k, v)| (k.clone(), v.len())).collect();
+        if document.delta.is_none() {
+            self.loaded = named_paths(document.components.iter());
+        }
+        self.components = document.components;
+        self.delta = document.delta;
+        self.signatures = document.signatures;
+        self.fingerprint = document.fingerprint;
+        self.versions = document.versions.unwrap_or_default();
+        self.saved_aliases = document.aliases.unwrap_or_default();
+        self.tombstones = document.tombstones.unwrap_or_default();
+        self.attributes = document.attributes.unwrap_or_default();
+        self.metadata = document.metadata;
     }
 
     pub fn get_or_new(&mut self, commands: &mut Commands, path: &EntityPath) -> Entity {
@@ -72,7 +395,7 @@ impl<M: Marker> DeserializeContext<M> {
             _ => match self.path_map.get(path) {
                 Some(entity) => *entity,
                 None => {
-                    let id = commands.spawn_empty().id();
+                    let id = self.spawn(commands, path);
                     self.path_map.insert(path.clone(), id);
                     id
                 }
@@ -80,25 +403,88 @@ impl<M: Marker> DeserializeContext<M> {
         }
     }
 
-    pub fn push(&mut self, entity: Entity, path: &str) {
-        if let Some(prev) = self.path_map.insert(EntityPath::Path(path.into()), entity) {
-            if prev != entity {
-                panic!("Duplicate path {} for entity {:?} and {:?}", path, prev, entity)
+    /// Add an entity as a child of its parent, ignored without the `hierarchy` feature.
+    #[cfg(feature="hierarchy")]
+    pub(crate) fn set_parent(&mut self, commands: &mut Commands, entity: Entity, parent: EntityParent) {
+        match parent {
+            EntityParent::Root => if let Some(anchor) = self.anchor {
+                commands.entity(anchor).add_child(entity);
+            },
+            p => {
+                let parent = self.get_or_new(commands, &p.into());
+                commands.entity(parent).add_child(entity);
             }
-        };
-    }   
+        }
+    }
+
+    #[cfg(not(feature="hierarchy"))]
+    pub(crate) fn set_parent(&mut self, _: &mut Commands, _: Entity, _: EntityParent) {}
+
+    /// Spawn an entity for a path, with its [`PersistentId`] if any.
+    pub(crate) fn spawn(&mut self, commands: &mut Commands, path: &EntityPath) -> Entity {
+        let entity = commands.spawn_empty().id();
+        self.init_spawned(commands, entity, path);
+        entity
+    }
+
+    /// Count an entity reserved for a path, then insert its [`PersistentId`] if any.
+    pub(crate) fn init_spawned(&mut self, commands: &mut Commands, entity: Entity, path: &EntityPath) {
+        self.spawned += 1;
+        if let Some(id) = PersistentId::from_path(path) {
+            commands.entity(entity).insert(id);
+        }
+        if self.spawn_disabled {
+            commands.entity(entity).insert((SaloDisabled, SpawnedDisabled));
+            self.disabled.push(entity);
+        }
+    }
+
+    /// Handle a record that cannot be deserialized, which only fails the load
+    /// if the type is [`TypePolicy::Required`](crate::TypePolicy::Required).
+    pub(crate) fn reject(&mut self, type_name: &str, error: anyhow::Error, errors: &mut SaloErrors<M>) {
+        if self.required.contains(type_name) {
+            errors.push(SaloError::deserialization(error));
+            return;
+        }
+        warn!("Skipped a record of {}: {}", type_name, error);
+        if let Some((_, count)) = self.counts.iter_mut().find(|(name, _)| name == type_name) {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    /// Returns true if a record of a new entity should be deferred to the next chunk.
+    pub(crate) fn over_budget(&self, path: &EntityPath) -> bool {
+        self.budget.is_some_and(|budget| self.spawned >= budget) && !self.path_map.contains_key(path)
+    }
+
+    /// Map a path to an entity, duplicate paths are handled by [`ConflictPolicy`].
+    pub fn push(&mut self, entity: Entity, path: Vec<SmolStr>) {
+        let path = EntityPath::Path(path);
+        match self.path_map.get(&path) {
+            Some(prev) if *prev != entity => {
+                let conflict = PathConflict::Path { path: path.to_string(), first: *prev, second: entity };
+                match self.policy {
+                    ConflictPolicy::LastWins => { self.path_map.insert(path, entity); },
+                    ConflictPolicy::Panic | ConflictPolicy::Error | ConflictPolicy::FirstWins => (),
+                }
+                self.conflicts.push(conflict);
+            },
+            Some(_) => (),
+            None => { self.path_map.insert(path, entity); },
+        }
+    }
 
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub(crate) enum EntityParent {
     #[default]
     Root,
-    Path(String),
+    Path(Vec<SmolStr>),
     Entity(u64),
 }
 
-/// Path of an entity. Either an entity number or a joined path.
+/// Path of an entity. Either an entity number or a list of names.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
 pub enum EntityPath {
     /// Unused when serializing. 
@@ -108,15 +494,126 @@ pub enum EntityPath {
     #[default]
     Unique,
     Entity(u64),
-    Path(String),
+    Path(Vec<SmolStr>),
+}
+
+/// Join segments of a path with `::`.
+///
+/// `\` is escaped as `\\`, and `:` as `\:` if followed by `:` or at the end of a segment,
+/// so names containing `::` round trip.
+pub(crate) fn join_escaped(segments: &[SmolStr]) -> String {
+    let mut result = String::new();
+    for (i, segment) in segments.iter().enumerate() {
+        if i > 0 {
+            result.push_str("::");
+        }
+        let mut chars = segment.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => result.push_str("\\\\"),
+                ':' if matches!(chars.peek(), Some(':') | None) => result.push_str("\\:"),
+                c => result.push(c),
+            }
+        }
+    }
+    result
+}
+
+/// Split a path into `::` delimited segments, reverses the escaping of [`join_escaped`].
+pub(crate) fn split_escaped(path: &str) -> Vec<SmolStr> {
+    let mut result = Vec::new();
+    let mut segment = String::new();
+    let mut chars = path.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => segment.push(chars.next().unwrap_or('\\')),
+            ':' if chars.peek() == Some(&':') => {
+                chars.next();
+                result.push(SmolStr::new(std::mem::take(&mut segment)));
+            },
+            c => segment.push(c),
+        }
+    }
+    result.push(SmolStr::new(segment));
+    result
+}
+
+/// Join segments of a path with `::`, without escaping.
+pub(crate) fn join_plain(segments: &[SmolStr]) -> String {
+    segments.join("::")
 }
 
+/// Split a path into `::` delimited segments, without escaping.
+pub(crate) fn split_plain(path: &str) -> Vec<SmolStr> {
+    path.split("::").map(SmolStr::new).collect()
+}
+
+#[cfg(not(feature="legacy-paths"))]
+pub(crate) use {join_escaped as join_path, split_escaped as split_path};
+#[cfg(feature="legacy-paths")]
+pub(crate) use {join_plain as join_path, split_plain as split_path};
+
 impl EntityPath {
+    /// Parse a `::` delimited path, an empty string is parsed as [`EntityPath::Unique`].
+    ///
+    /// `\` escapes the next character, i.e. `Sir\::Lancelot` is a single name.
+    pub fn parse(path: &str) -> Self {
+        if path.is_empty() {
+            EntityPath::Unique
+        } else {
+            EntityPath::Path(split_path(path))
+        }
+    }
+
     pub fn is_unique(&self) -> bool {
         self == &Self::Unique
     }
 
-    /// Get the last `::` delimited segment of path
+    /// Append a name to the path, a unique path becomes a single segment path.
+    /// 
+    /// # Panics
+    /// 
+    /// If `self` is an entity.
+    pub fn join(&self, name: &str) -> Self {
+        match self {
+            EntityPath::Unique => EntityPath::Path(vec![name.into()]),
+            EntityPath::Entity(e) => panic!("Cannot join {} to entity {:?}.", name, e),
+            EntityPath::Path(p) => {
+                let mut p = p.clone();
+                p.push(name.into());
+                EntityPath::Path(p)
+            },
+        }
+    }
+
+    /// Remove the last segment of the path, returns `None` if not a path or is a single segment.
+    pub fn parent(&self) -> Option<Self> {
+        match self {
+            EntityPath::Path(p) if p.len() > 1 => Some(EntityPath::Path(p[..p.len() - 1].to_vec())),
+            _ => None,
+        }
+    }
+
+    /// Iterate through segments of the path, empty if not a path.
+    pub fn segments(&self) -> impl DoubleEndedIterator<Item = &str> {
+        match self {
+            EntityPath::Path(p) => p.as_slice(),
+            _ => &[],
+        }.iter().map(|s| s.as_str())
+    }
+
+    /// Returns true if all segments of `prefix` are the leading segments of `self`.
+    /// 
+    /// A unique path is the prefix of every path.
+    pub fn starts_with(&self, prefix: &EntityPath) -> bool {
+        match (self, prefix) {
+            (EntityPath::Path(_), EntityPath::Unique) => true,
+            (EntityPath::Path(a), EntityPath::Path(b)) => a.starts_with(b),
+            (a, b) => a == b,
+        }
+    }
+
+    /// Get the last segment of path
     /// 
     /// # Panics
     /// 
@@ -125,21 +622,22 @@ impl EntityPath {
         match self {
             EntityPath::Unique => panic!("Empty path does not contain a name."),
             EntityPath::Entity(e) => panic!("Entity {:?} does not contain a name.", e),
-            EntityPath::Path(p) => match p.rsplit_once("::") {
-                Some((_, a)) => a,
-                None => p,
-            },
+            EntityPath::Path(p) => p.last().map(|s| s.as_str()).unwrap_or_default(),
         }
     }
 
-    /// Get the last `::` delimited segment of path
+    /// Get the last segment of path
     pub fn get_name(&self) -> Option<&str> {
+        self.segments().next_back()
+    }
+}
+
+impl std::fmt::Display for EntityPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            EntityPath::Path(p) => match p.rsplit_once("::") {
-                Some((_, a)) => Some(a),
-                None => Some(p),
-            },
-            _ => None,
+            EntityPath::Unique => Ok(()),
+            EntityPath::Entity(e) => write!(f, "{:?}", Entity::from_bits(*e)),
+            EntityPath::Path(p) => f.write_str(&join_path(p)),
         }
     }
 }
@@ -155,7 +653,7 @@ impl From<EntityParent> for EntityPath {
 }
 
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct PathedValue<V>{
     pub(crate) parent: EntityParent,
     pub(crate) path: EntityPath,
@@ -201,6 +699,19 @@ pub trait SaveLoad: Component + Sized {
         ctx: &mut SystemParamItem<Self::ContextMut<'_, '_>>
     ) -> Self;
 
+    /// Fallible [`from_deserialize`](Self::from_deserialize), used when loading.
+    ///
+    /// Failed records are skipped and reported in [`SaloErrors`]. Calls `from_deserialize` by default.
+    fn try_from_deserialize(
+        de: Self::De,
+        commands: &mut Commands,
+        self_entity: Entity,
+        entity_fetcher: impl FnMut(&mut Commands, &EntityPath) -> Entity,
+        ctx: &mut SystemParamItem<Self::ContextMut<'_, '_>>
+    ) -> anyhow::Result<Self> {
+        Ok(Self::from_deserialize(de, commands, self_entity, entity_fetcher, ctx))
+    }
+
     /// Name associated with this type. 
     /// This is used in deserialization
     /// and must be unique accross for all generics.
@@ -220,17 +731,66 @@ pub trait SaveLoad: Component + Sized {
     /// 
     /// # Panics
     /// 
-    /// When trying to assign a conflicting name.
+    /// When trying to assign a conflicting name with [`ConflictPolicy::Panic`].
     fn path_name(&self) -> Option<Cow<'static, str>> {
         None
     }
 
+    /// Write fields of this type next to `parent` and `path` in human-readable formats,
+    /// instead of nested in `value`. Only applies to values serialized as maps.
+    ///
+    /// Both layouts are accepted when loading.
+    fn inline() -> bool {
+        false
+    }
+
+    /// Version of the saved format of this type, recorded in saves if not 0.
+    ///
+    /// Records saved with a different version are passed to [`migrate`](Self::migrate) when loading.
+    fn version() -> u32 {
+        0
+    }
+
+    /// Convert a record saved with `version` to the current version,
+    /// i.e. by deserializing it as an old type with `S::deserialize_value`.
+    ///
+    /// Saves without a version of this type are version 0. Returns the value unchanged by default.
+    fn migrate<S: SerializationMethod>(version: u32, value: S::Value) -> anyhow::Result<S::Value> {
+        let _ = version;
+        Ok(value)
+    }
+
+    /// [`Attributes`] written next to the record of this item, i.e. an icon for editors.
+    ///
+    /// Ignored when loading, but kept in [`RecordAttributes`](crate::RecordAttributes) and written back.
+    fn attributes(&self) -> Attributes {
+        Attributes::new()
+    }
+
+    /// Value inserted on entities of a load when the save has no records of this type,
+    /// if [`SaloConfig::with_fill_defaults`](crate::SaloConfig::with_fill_defaults) is enabled.
+    ///
+    /// Useful for components added after a save was written, only inserted on entities
+    /// with records of a type in [`fills`](Self::fills).
+    fn on_missing() -> Option<Self> {
+        None
+    }
+
+    /// Type names of records whose entities get [`on_missing`](Self::on_missing),
+    /// i.e. `&["unit"]` for a component of units. Empty by default.
+    fn fills() -> &'static [&'static str] {
+        &[]
+    }
+
     /// Set the path name for the current entity if `path_name` is not none.
     fn build_path<M: Marker>(
         mut paths: ResMut<PathNames<M>>,
         query: Query<(Entity, &Self), M::Query>, 
+        all: Query<(Entity, &Self)>,
+        marked: Marked<M>,
     ) {
-        for (entity, item) in query.iter() {
+        let descendants = marked.descendants().filter_map(|e| all.get(e).ok());
+        for (entity, item) in query.iter().chain(descendants) {
             if let Some(path) = item.path_name() {
                 paths.push(entity, path);
             }
@@ -240,44 +800,55 @@ pub trait SaveLoad: Component + Sized {
     /// System for serialization.
     fn serialize_system<M: Marker>(
         mut paths: ResMut<SerializeContext<M>>,
-        query: Query<(Entity, &Self), M::Query>, 
-        parents: Query<&Parent>,
-        marked: Query<(), M::Query>,
+        query: Query<(Entity, Ref<Self>), M::Query>, 
+        all: Query<(Entity, Ref<Self>)>,
+        (parents, marked): (Parents, Marked<M>),
+        selection: SaveSelection<M>,
         ctx: StaticSystemParam<Self::Context<'_, '_>>,
+        mut errors: ResMut<SaloErrors<M>>,
     ) {
-        for (entity, item) in query.iter() {
-            let parent = match parents.get(entity) {
-                Ok(parent) => {
-                    if let Some(path) = paths.paths.get(&parent.get()) {
-                        EntityParent::Path(path.clone())
-                    } else if marked.contains(parent.get()) {
-                        EntityParent::Entity(parent.to_bits())
-                    } else {
-                        panic!("Trying to serialize component {} in orphaned entity {:?}. \
-                            Parent {:?} is neither serialized nor named.",
-                            Self::type_name(),
-                            entity,
-                            parent.get()
-                        );
-                    }
-                },
-                Err(_) => EntityParent::Root,
-            };
-            let path = if let Some(name) = paths.paths.get(&entity) {
-                EntityPath::Path(name.clone())
-            } else {
-                EntityPath::Entity(entity.to_bits())
+        if !selection.contains_type(&Self::type_name()) {
+            return;
+        }
+        if Self::inline() {
+            paths.inline.insert(Self::type_name());
+        }
+        if Self::version() != 0 {
+            paths.versions.insert(Self::type_name().into_owned(), Self::version());
+        }
+        let propagated = marked.descendants().filter_map(|e| all.get(e).ok());
+        for (entity, item) in query.iter().chain(propagated) {
+            // Unselected items still exist, so they are not recorded as removed.
+            if selection.is_delta() {
+                if let Some(path) = paths.paths.get(&entity) {
+                    let path = EntityPath::Path(path.clone());
+                    paths.present.entry(Self::type_name().into_owned()).or_default().insert(path);
+                }
+            }
+            if !selection.contains(entity) || !selection.is_changed(&item) {
+                continue;
+            }
+            let Some(parent) = paths.parent_of(Self::type_name(), entity, &parents, &marked, &selection) else {
+                continue;
             };
-            let path_fetcher = |e: Entity| {
-                match paths.paths.get(&e) {
-                    Some(path) => EntityPath::Path(path.clone()),
-                    None => EntityPath::Entity(e.to_bits()),
+            let path = paths.path_of(entity);
+            let path_fetcher = |e: Entity| paths.path_of(e);
+            let value = match M::Method::serialize_value(&Self::to_serializable(&item, entity, path_fetcher, &ctx)) {
+                Ok(value) => value,
+                Err(e) => {
+                    errors.push(SaloError::serialization(e));
+                    continue;
                 }
             };
+            let attributes = item.attributes();
+            if !attributes.is_empty() && !path.is_unique() {
+                let entry = AttributeEntry { path: path.clone(), attributes };
+                paths.attributes.entry(Self::type_name().into_owned()).or_default().push(entry);
+            }
             let path = PathedValue {
                 parent, 
                 path,
-                value: M::Method::serialize_value(&Self::to_serializable(item, entity, path_fetcher, &ctx)).unwrap()
+                value,
             };
             match paths.components.get_mut(&Self::type_name()) {
                 Some(vec) => vec.push(path),
@@ -294,49 +865,93 @@ pub trait SaveLoad: Component + Sized {
     /// System for deserialization.
     fn deserialize_system<M: Marker>(
         mut commands: Commands,
+        entities: &Entities,
         mut context: ResMut<DeserializeContext<M>>,
+        mut errors: ResMut<SaloErrors<M>>,
+        resolver: Option<Res<LoadResolver<M, Self>>>,
+        existing: Query<&Self>,
         mut ctx_mut: StaticSystemParam<Self::ContextMut<'_, '_>>,
     ) {
+        let removed = context.delta.as_mut().and_then(|d| d.removed.remove(Self::type_name().as_ref()));
+        for path in removed.into_iter().flatten() {
+            if let Some(entity) = context.path_map.get(&path) {
+                commands.entity(*entity).remove::<Self>();
+            }
+        }
         let Some(items) = context.components.remove(Self::type_name().as_ref()) else {return};
+        // Inserted with a single command, which is much faster than a command per item.
+        let mut batch = Vec::with_capacity(items.len());
+        // Commands of `from_deserialize` and parenting, applied in record order after the batch,
+        // so they see the inserted components. Dropped for rejected records.
+        let mut effects = CommandQueue::default();
         for PathedValue { parent, path, value } in items {
-            
-            let entity = match context.path_map.get(&path) {
-                Some(entity) => {
-                    commands.entity(*entity).id()
-                },
+            if context.over_budget(&path) {
+                let type_name = Self::type_name().into_owned();
+                context.deferred.entry(type_name).or_default().push(PathedValue { parent, path, value });
+                continue;
+            }
+            let de = match M::Method::deserialize_value(value) {
+                Ok(de) => de,
+                Err(e) => {
+                    context.reject(&Self::type_name(), e, &mut errors);
+                    continue;
+                }
+            };
+
+            // New entities are reserved, then set up only if the record is accepted.
+            let (entity, spawned) = match context.path_map.get(&path) {
+                Some(entity) => (*entity, false),
                 None => {
                     let e = commands.spawn_empty().id();
-                    context.path_map.insert(path, e);
-                    e
+                    context.path_map.insert(path.clone(), e);
+                    (e, true)
                 }
             };
-            let ctx_fetch = |commands: &mut Commands, path: &EntityPath| {
-                match context.path_map.get(path) {
-                    Some(entity) => *entity,
-                    None => commands.spawn_empty().id()
-                }
+            let resolution = match (&resolver, existing.get(entity)) {
+                (Some(resolver), Ok(current)) => resolver.resolve(current, &de),
+                _ => Resolution::UseIncoming,
+            };
+            let mut queue = CommandQueue::default();
+            let mut record = Commands::new_from_entities(&mut queue, entities);
+            // Register spawned entities so records loaded later resolve to the same entity.
+            let ctx_fetch = |_: &mut Commands, path: &EntityPath| {
+                let path = context.remapped(path);
+                context.get_or_new(&mut commands, &path)
             };
 
-            let item = Self::from_deserialize(
-                M::Method::deserialize_value(value).unwrap(), 
-                &mut commands,
-                entity,
-                ctx_fetch, 
-                &mut ctx_mut
-            );
-            commands.entity(entity).insert(item);
-            match parent {
-                EntityParent::Root => (),
-                p => {
-                    let p = p.into();
-                    let parent = match context.path_map.get(&p) {
-                        Some(entity) => *entity,
-                        None => commands.spawn_empty().id()
-                    };
-                    commands.entity(parent).add_child(entity);
+            let item = match resolution {
+                Resolution::KeepExisting => Ok(None),
+                Resolution::UseIncoming => Self::try_from_deserialize(
+                    de, 
+                    &mut record,
+                    entity,
+                    ctx_fetch, 
+                    &mut ctx_mut
+                ).map(Some),
+                Resolution::Merge(item) => Ok(Some(item)),
+            };
+            let item = match item {
+                Ok(item) => item,
+                Err(e) => {
+                    context.reject(&Self::type_name(), e, &mut errors);
+                    if spawned {
+                        context.path_map.remove(&path);
+                        commands.entity(entity).despawn();
+                    }
+                    continue;
                 }
+            };
+            if spawned {
+                context.init_spawned(&mut commands, entity, &path);
+            }
+            if let Some(item) = item {
+                batch.push((entity, item));
             }
+            context.set_parent(&mut record, entity, parent);
+            effects.push(move |world: &mut World| queue.apply(world));
         }
+        commands.insert_or_spawn_batch(batch);
+        commands.add(move |world: &mut World| effects.apply(world));
     }
 
     /// Remove all copies of the component.
@@ -366,6 +981,37 @@ pub trait SaveLoadCore: Serialize + DeserializeOwned + Component {
     fn path_name(&self) -> Option<Cow<'static, str>> {
         None
     }
+
+    /// Write fields inline in human-readable formats, see [`SaveLoad::inline`].
+    fn inline() -> bool {
+        false
+    }
+
+    /// Version of the saved format, see [`SaveLoad::version`].
+    fn version() -> u32 {
+        0
+    }
+
+    /// Convert a record of an older version, see [`SaveLoad::migrate`].
+    fn migrate<S: SerializationMethod>(version: u32, value: S::Value) -> anyhow::Result<S::Value> {
+        let _ = version;
+        Ok(value)
+    }
+
+    /// Attributes of the record, see [`SaveLoad::attributes`].
+    fn attributes(&self) -> Attributes {
+        Attributes::new()
+    }
+
+    /// Value inserted if the save has no records of this type, see [`SaveLoad::on_missing`].
+    fn on_missing() -> Option<Self> {
+        None
+    }
+
+    /// Type names of records whose entities get `on_missing`, see [`SaveLoad::fills`].
+    fn fills() -> &'static [&'static str] {
+        &[]
+    }
 }
 
 impl<T> SaveLoadMapped for T where T: SaveLoadCore {
@@ -378,6 +1024,24 @@ impl<T> SaveLoadMapped for T where T: SaveLoadCore {
     fn path_name(&self) -> Option<Cow<'static, str>> {
         <Self as SaveLoadCore>::path_name(self)
     }
+    fn inline() -> bool {
+        <Self as SaveLoadCore>::inline()
+    }
+    fn version() -> u32 {
+        <Self as SaveLoadCore>::version()
+    }
+    fn migrate<S: SerializationMethod>(version: u32, value: S::Value) -> anyhow::Result<S::Value> {
+        <Self as SaveLoadCore>::migrate::<S>(version, value)
+    }
+    fn attributes(&self) -> Attributes {
+        <Self as SaveLoadCore>::attributes(self)
+    }
+    fn on_missing() -> Option<Self> {
+        <Self as SaveLoadCore>::on_missing()
+    }
+    fn fills() -> &'static [&'static str] {
+        <Self as SaveLoadCore>::fills()
+    }
 
     fn to_serializable(&self) -> Self::Ser<'_> { self }
 
@@ -411,6 +1075,37 @@ pub trait SaveLoadMapped: Serialize + DeserializeOwned + Component {
     fn path_name(&self) -> Option<Cow<'static, str>> {
         None
     }
+
+    /// Write fields inline in human-readable formats, see [`SaveLoad::inline`].
+    fn inline() -> bool {
+        false
+    }
+
+    /// Version of the saved format, see [`SaveLoad::version`].
+    fn version() -> u32 {
+        0
+    }
+
+    /// Convert a record of an older version, see [`SaveLoad::migrate`].
+    fn migrate<S: SerializationMethod>(version: u32, value: S::Value) -> anyhow::Result<S::Value> {
+        let _ = version;
+        Ok(value)
+    }
+
+    /// Attributes of the record, see [`SaveLoad::attributes`].
+    fn attributes(&self) -> Attributes {
+        Attributes::new()
+    }
+
+    /// Value inserted if the save has no records of this type, see [`SaveLoad::on_missing`].
+    fn on_missing() -> Option<Self> {
+        None
+    }
+
+    /// Type names of records whose entities get `on_missing`, see [`SaveLoad::fills`].
+    fn fills() -> &'static [&'static str] {
+        &[]
+    }
 }
 
 impl<T> SaveLoad for T where T: SaveLoadMapped {
@@ -427,6 +1122,29 @@ impl<T> SaveLoad for T where T: SaveLoadMapped {
         <Self as SaveLoadMapped>::path_name(self)
     }
 
+    fn inline() -> bool {
+        <Self as SaveLoadMapped>::inline()
+    }
+
+    fn version() -> u32 {
+        <Self as SaveLoadMapped>::version()
+    }
+
+    fn migrate<S: SerializationMethod>(version: u32, value: S::Value) -> anyhow::Result<S::Value> {
+        <Self as SaveLoadMapped>::migrate::<S>(version, value)
+    }
+
+    fn attributes(&self) -> Attributes {
+        <Self as SaveLoadMapped>::attributes(self)
+    }
+
+    fn on_missing() -> Option<Self> {
+        <Self as SaveLoadMapped>::on_missing()
+    }
+    fn fills() -> &'static [&'static str] {
+        <Self as SaveLoadMapped>::fills()
+    }
+
     fn to_serializable<'t>(&'t self, 
         _: Entity,
         _: impl Fn(Entity) -> EntityPath, 
@@ -1,19 +1,63 @@
+use std::borrow::Cow;
 use std::marker::PhantomData;
+use std::io::{Read, Write};
 
 use bevy_ecs::entity::Entity;
-use bevy_ecs::schedule::{ScheduleLabel, SystemSet, Schedule, IntoSystemConfigs};
-use bevy_ecs::system::{Res, ResMut, Query};
+use bevy_ecs::schedule::{ScheduleLabel, SystemSet, Schedule, IntoSystemConfigs, ExecutorKind, apply_deferred};
+#[cfg(feature="bevy_app")]
+use bevy_ecs::schedule::common_conditions::resource_exists;
+use bevy_ecs::system::{Res, ResMut, Query, Commands};
 use bevy_ecs::world::World;
+use bevy_ecs::event::{Events, EventWriter};
 use bevy_ecs::schedule::IntoSystemSetConfigs;
-use bevy_hierarchy::Parent;
+use bevy_utils::get_short_name;
+use smol_str::SmolStr;
 use crate::methods::SerializationMethod;
-use crate::{SaveLoadPlugin, SaveLoad, PathNames, SerializeContext, DeserializeContext, BytesOutput, StringOutput, PathName, BytesInput, SaveLoadRes};
+use crate::document::{ValueInput, ValueOutput, SnapshotRegistry, RollbackEntities};
+use crate::transfer::apply_remap;
+use crate::alias::{apply_aliases, write_aliases};
+use crate::tombstone::{build_tombstones, apply_tombstones};
+use crate::attributes::{write_attributes, apply_attributes};
+use crate::root::{build_root_names, strip_path_root, apply_path_root};
+use crate::transform::{transform_document, transform_loaded};
+use crate::marker::{persist_marker, register_marker, serialize_marker, deserialize_marker};
+use crate::anonymize::anonymize;
+use crate::propagation::{MarkedDescendants, collect_descendants};
+#[cfg(feature="sha2")]
+use crate::signing::{sign_sections, verify_sections};
+use crate::persistent::{PersistentId, PersistentIdAllocator, reserve_persistent_ids};
+use crate::diagnostics::{PathCollisions, report_conflicts};
+use crate::archive::ArchiveInput;
+use crate::budget::{apply_pending_entities, collect_pending, is_first_chunk};
+use crate::disabled::enable_loaded;
+use crate::bare::{BARE_ENTITY, emit_bare_entities, load_bare_entities};
+use crate::fingerprint::{write_fingerprint, check_fingerprint, apply_renames};
+use crate::estimate::{SaveSizeCache, record_sizes};
+#[cfg(feature="fs")]
+use crate::archive::write_to_archive;
+#[cfg(feature="fs")]
+use crate::split::write_split;
+use crate::{SaveLoadPlugin, SharedTypes, SaveLoad, SaveLoadBundle, PathNames, SerializeContext, DeserializeContext, BytesOutput, StringOutput, WriterOutput, PathName, BytesInput, StringInput, ReaderInput, SaveLoadRes, SaloConfig, SaveEncryptionKey, SaloErrors, SaloError, SaveMetadata, SaveFilter, TypeFilter, SaloRegistry, SaveThrottled, SaveState, UnknownComponents, LoadPolicy, SaloEvent, SaloEventKind, SaloHistory, AbortSave, SaveDelta, EntityPath, Parents};
+use crate::saveload::{DeltaHeader, EntityParent};
+use crate::history::{record_save, record_load};
+use crate::analytics::record_analytics;
+use crate::policy::{apply_load_policy, validate_marker};
+use crate::import::{collect_type_roots, attach_type_roots};
+use crate::anchor::apply_load_anchor;
+use crate::tree::init_paths;
+use crate::state::{is_complete, is_complete_load, no_errors, with_entities, all_types, finish_save, finish_load};
 use crate::sealed::Build;
 use crate::{Marker, All};
 use std::fmt::Debug;
 use std::hash::Hash;
 
 use crate::FileInput;
+#[cfg(feature="fs")]
+use std::collections::HashMap;
+#[cfg(feature="fs")]
+use bevy_utils::tracing::warn;
+#[cfg(feature="fs")]
+use crate::saveload::{DocumentRef, PathedValue, ValueOf};
 
 macro_rules! schedules {
     ($($names: ident),* $(,)?) => {
@@ -39,7 +83,7 @@ macro_rules! schedules {
 
             impl<M: Marker> Debug for $names<M> {
                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                    write!(f, "{}<{}>", stringify!($names), std::any::type_name::<M>())
+                    write!(f, "{}<{}>", stringify!($names), get_short_name(std::any::type_name::<M>()))
                 }
             }
 
@@ -74,128 +118,533 @@ macro_rules! system_sets {
 
 
 fn init_serialize<M: Marker>(w: &mut World) {
-    w.remove_resource::<PathNames<M>>();
-    w.init_resource::<PathNames<M>>();
+    let policy = w.resource::<SaloConfig<M>>().conflict_policy();
+    w.insert_resource(PathNames::<M>::with_policy(policy));
     w.remove_resource::<SerializeContext<M>>();
     w.init_resource::<SerializeContext<M>>();
+    w.remove_resource::<SaloErrors<M>>();
+    w.init_resource::<SaloErrors<M>>();
+    w.resource_mut::<SaloHistory<M>>().begin();
+    w.send_event(SaloEvent::<M>::new(SaloEventKind::SaveStarted));
 }
 
-fn init_deserialize<M: Marker>(w: &mut World) {
+/// Run condition, true if buffers are removed after a run, see [`SaloConfig::with_retain_buffers`].
+fn free_buffers<M: Marker>(config: Res<SaloConfig<M>>) -> bool {
+    !config.retain_buffers()
+}
+
+fn teardown_serialize<M: Marker>(w: &mut World) {
     w.remove_resource::<PathNames<M>>();
-    w.init_resource::<PathNames<M>>();
+    w.remove_resource::<SerializeContext<M>>();
+}
+
+fn teardown_deserialize<M: Marker>(w: &mut World) {
+    w.remove_resource::<PathNames<M>>();
+    w.remove_resource::<DeserializeContext<M>>();
+    w.remove_resource::<FileInput<M>>();
+    w.remove_resource::<BytesInput<M>>();
+    w.remove_resource::<StringInput<M>>();
+    w.remove_resource::<ValueInput<M>>();
+    w.remove_resource::<ReaderInput<M>>();
+    w.remove_resource::<ArchiveInput<M>>();
+}
+
+fn init_deserialize<M: Marker>(w: &mut World) {
+    let policy = w.resource::<SaloConfig<M>>().conflict_policy();
+    w.insert_resource(PathNames::<M>::with_policy(policy));
     w.remove_resource::<DeserializeContext<M>>();
     w.init_resource::<DeserializeContext<M>>();
+    w.remove_resource::<SaloErrors<M>>();
+    w.init_resource::<SaloErrors<M>>();
+    w.resource_mut::<SaloHistory<M>>().begin();
+    w.send_event(SaloEvent::<M>::new(SaloEventKind::LoadStarted));
+}
+
+fn emit_serialized<M: Marker>(ctx: Res<SerializeContext<M>>, mut events: EventWriter<SaloEvent<M>>) {
+    for (name, records) in ctx.components.iter() {
+        events.send(SaloEvent::new(SaloEventKind::TypeSerialized {
+            name: name.clone(),
+            count: records.len(),
+        }));
+    }
+    for (name, entity) in ctx.orphans.iter() {
+        events.send(SaloEvent::new(SaloEventKind::Orphan { name: name.clone(), entity: *entity }));
+    }
+}
+
+fn emit_deserialized<M: Marker>(ctx: Res<DeserializeContext<M>>, mut events: EventWriter<SaloEvent<M>>) {
+    for (name, count) in ctx.counts.iter() {
+        if !ctx.components.contains_key(name) {
+            events.send(SaloEvent::new(SaloEventKind::TypeDeserialized {
+                name: Cow::Owned(name.clone()),
+                count: *count,
+            }));
+        }
+    }
+}
+
+fn run_pre_save<M: Marker>(world: &mut World) {
+    world.run_schedule(PreSaveSchedule::<M>(PhantomData));
+    if let Some(abort) = world.get_resource::<AbortSave<M>>() {
+        let error = SaloError::Aborted(abort.0.clone());
+        world.resource_mut::<SaloErrors<M>>().push(error);
+    }
+}
+
+/// Run condition, false if aborted by [`AbortSave`] or a conflict.
+fn not_aborted<M: Marker>(abort: Option<Res<AbortSave<M>>>, errors: Res<SaloErrors<M>>) -> bool {
+    abort.is_none() && !errors.iter().any(|e| matches!(e, SaloError::Conflict(_) | SaloError::Aborted(_)))
+}
+
+fn run_post_load<M: Marker>(world: &mut World) {
+    world.run_schedule(PostLoadSchedule::<M>(PhantomData));
+}
+
+fn save_finished<M: Marker>(errors: Res<SaloErrors<M>>, mut events: EventWriter<SaloEvent<M>>) {
+    events.send(SaloEvent::new(match errors.is_empty() {
+        true => SaloEventKind::SaveFinished,
+        false => SaloEventKind::SaveFailed,
+    }));
+}
+
+fn load_finished<M: Marker>(errors: Res<SaloErrors<M>>, mut events: EventWriter<SaloEvent<M>>) {
+    events.send(SaloEvent::new(match errors.is_empty() {
+        true => SaloEventKind::LoadFinished,
+        false => SaloEventKind::LoadFailed,
+    }));
 }
 
 #[cfg(feature="fs")]
-fn write_to_file<M: Marker>(file: Option<Res<crate::FileOutput<M>>>, data: Res<SerializeContext<M>>) {
+fn write_to_file<M: Marker>(
+    file: Option<Res<crate::FileOutput<M>>>, 
+    key: Option<Res<SaveEncryptionKey<M>>>,
+    data: Res<SerializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+    mut history: ResMut<SaloHistory<M>>,
+) {
     if let Some(fo) = file {
-        match M::Method::serialize_file(&fo.0, data.serialized()) {
-            Ok(_) => (),
-            Err(e) => eprintln!("Serialization failed: {}", e),
+        let result = fo.options().write(fo.get(), |file| match &key {
+            Some(key) => M::Method::serialize_bytes_keyed(&data.serialized(), Some(key.get()))
+                .and_then(|bytes| Ok(std::fs::write(file, bytes)?)),
+            None => M::Method::serialize_file(file, &data.serialized()),
+        });
+        match result {
+            Ok(_) => history.size = std::fs::metadata(fo.get()).ok().map(|m| m.len() as usize),
+            Err(e) => errors.push(SaloError::serialization(e)),
         }
     }
 }
 
+/// Value of a record in a debug dump, opaque values are written as hex.
+#[cfg(feature="fs")]
+fn debug_value<M: Marker>(value: &ValueOf<M>) -> anyhow::Result<serde_json::Value> {
+    let json = serde_json::to_value(value)?;
+    if M::Method::SELF_DESCRIBING {
+        return Ok(json);
+    }
+    let bytes: Vec<u8> = serde_json::from_value(json)?;
+    Ok(serde_json::Value::String(bytes.iter().map(|b| format!("{:02x}", b)).collect()))
+}
+
+/// Write a pretty json copy of a file save, see [`SaloConfig::with_mirror_debug_dump`].
+///
+/// Failures are logged and do not fail the save.
+#[cfg(feature="fs")]
+fn write_debug_dump<M: Marker>(
+    file: Option<Res<crate::FileOutput<M>>>,
+    key: Option<Res<SaveEncryptionKey<M>>>,
+    config: Res<SaloConfig<M>>,
+    data: Res<SerializeContext<M>>,
+    errors: Res<SaloErrors<M>>,
+) {
+    let Some(fo) = file else { return };
+    // Encrypted saves are not mirrored in plain text.
+    if key.is_some() || !errors.is_empty() {
+        return;
+    }
+    let Some(path) = config.debug_dump_path(fo.get()) else { return };
+    let result = (|| {
+        let metadata = data.metadata.as_ref().map(debug_value::<M>).transpose()?;
+        let components = data.components.iter().map(|(name, records)| {
+            let records = records.iter().map(|record| Ok(PathedValue {
+                parent: record.parent.clone(),
+                path: record.path.clone(),
+                value: debug_value::<M>(&record.value)?,
+            })).collect::<anyhow::Result<Vec<_>>>()?;
+            Ok((name.clone(), records))
+        }).collect::<anyhow::Result<HashMap<_, _>>>()?;
+        crate::methods::SerdeJson::<true>::serialize_file(&path, &DocumentRef {
+            metadata: metadata.as_ref(),
+            delta: data.delta.as_ref(),
+            signatures: data.signatures.as_ref(),
+            fingerprint: data.fingerprint.as_ref(),
+            versions: (!data.versions.is_empty()).then_some(&data.versions),
+            aliases: (!data.aliases.is_empty()).then_some(&data.aliases),
+            tombstones: (!data.tombstones.is_empty()).then_some(&data.tombstones),
+            attributes: (!data.attributes.is_empty()).then_some(&data.attributes),
+            layout: data.layout,
+            components: &components,
+            inline: Some(&data.inline),
+        })
+    })();
+    if let Err(e) = result {
+        warn!("Failed to write debug dump {}: {}", path, e);
+    }
+}
+
 fn write_to_bytes<M: Marker>(
     buffer: Option<ResMut<BytesOutput<M>>>,
-    data: Res<SerializeContext<M>>
+    key: Option<Res<SaveEncryptionKey<M>>>,
+    data: Res<SerializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+    mut history: ResMut<SaloHistory<M>>,
 ) {
     if let Some(mut buffer) = buffer {
-        match M::Method::serialize_bytes(data.serialized()) {
-            Ok(bytes) => buffer.0 = bytes,
-            Err(e) => eprintln!("Serialization failed: {}", e),
+        match M::Method::serialize_bytes_keyed(&data.serialized(), key.as_ref().map(|k| k.get())) {
+            Ok(bytes) => {
+                history.size = Some(bytes.len());
+                buffer.0 = bytes
+            },
+            Err(e) => errors.push(SaloError::serialization(e)),
         }
     }
 }
 
+/// Strings cannot hold encrypted bytes, so string inputs and outputs fail with a [`SaveEncryptionKey`]
+/// instead of silently skipping encryption.
+pub(crate) fn keyed_string_error() -> anyhow::Error {
+    anyhow::anyhow!("Strings cannot be encrypted, use bytes or files with a SaveEncryptionKey.")
+}
+
 fn write_to_string<M: Marker>(
     buffer: Option<ResMut<StringOutput<M>>>, 
-    data: Res<SerializeContext<M>>
+    key: Option<Res<SaveEncryptionKey<M>>>,
+    data: Res<SerializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+    mut history: ResMut<SaloHistory<M>>,
+) {
+    if let Some(mut buffer) = buffer {
+        if key.is_some() {
+            errors.push(SaloError::Serialization(keyed_string_error()));
+            return;
+        }
+        match M::Method::serialize_string(&data.serialized()) {
+            Ok(string) => {
+                history.size = Some(string.len());
+                buffer.0 = string
+            },
+            Err(e) => errors.push(SaloError::serialization(e)),
+        }
+    }
+}
+
+fn write_to_writer<M: Marker>(
+    writer: Option<ResMut<WriterOutput<M>>>,
+    key: Option<Res<SaveEncryptionKey<M>>>,
+    data: Res<SerializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+    mut history: ResMut<SaloHistory<M>>,
+) {
+    if let Some(mut writer) = writer {
+        let mut writer = CountingWriter(&mut writer.0, 0);
+        let result = match &key {
+            Some(key) => M::Method::serialize_bytes_keyed(&data.serialized(), Some(key.get()))
+                .and_then(|bytes| Ok(writer.write_all(&bytes)?)),
+            None => M::Method::serialize_writer(&mut writer, &data.serialized()),
+        };
+        match result.and_then(|_| Ok(writer.flush()?)) {
+            Ok(_) => history.size = Some(writer.1),
+            Err(e) => errors.push(SaloError::serialization(e)),
+        }
+    }
+}
+
+/// Counts bytes written for [`SaloHistory`].
+struct CountingWriter<W: Write>(W, usize);
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let len = self.0.write(buf)?;
+        self.1 += len;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+fn write_to_value<M: Marker>(
+    buffer: Option<ResMut<ValueOutput<M>>>,
+    data: Res<SerializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
 ) {
     if let Some(mut buffer) = buffer {
-        match M::Method::serialize_string(data.serialized()) {
-            Ok(bytes) => buffer.0 = bytes,
-            Err(e) => eprintln!("Serialization failed: {}", e),
+        match M::Method::serialize_value(&data.serialized()) {
+            Ok(value) => buffer.0 = Some(value),
+            Err(e) => errors.push(SaloError::serialization(e)),
         }
     }
 }
 
+/// Keep unknown records, with entity references resolved to the loaded entities.
+///
+/// Entities only referenced by unknown records are reserved, so their ids are not reused.
+fn collect_unknown<M: Marker>(
+    mut commands: Commands,
+    config: Res<SaloConfig<M>>,
+    mut ctx: ResMut<DeserializeContext<M>>,
+    ids: Query<&PersistentId>,
+) {
+    if !config.preserve_unknown() {
+        return;
+    }
+    let ctx = ctx.as_mut();
+    let mut components = std::mem::take(&mut ctx.components);
+    let mut resolve = |e: &mut u64| {
+        let entity = *ctx.path_map.entry(EntityPath::Entity(*e))
+            .or_insert_with(|| commands.spawn_empty().id());
+        *e = ids.get(entity).map_or(entity.to_bits(), |id| id.to_bits());
+    };
+    for record in components.values_mut().flatten() {
+        if let EntityPath::Entity(e) = &mut record.path {
+            resolve(e);
+        }
+        if let EntityParent::Entity(e) = &mut record.parent {
+            resolve(e);
+        }
+    }
+    commands.insert_resource(UnknownComponents::<M>(components, PhantomData));
+}
+
+fn emit_unknown<M: Marker>(
+    config: Res<SaloConfig<M>>,
+    unknown: Option<Res<UnknownComponents<M>>>,
+    delta: Option<Res<SaveDelta<M>>>,
+    mut ctx: ResMut<SerializeContext<M>>,
+) {
+    let Some(unknown) = unknown else { return };
+    if !config.preserve_unknown() || delta.is_some() {
+        return;
+    }
+    for (name, records) in unknown.0.iter() {
+        ctx.components.entry(Cow::Owned(name.clone())).or_insert_with(|| records.clone());
+    }
+}
+
+/// Sort records of each type by path and parent if [`SaloConfig::deterministic`].
+fn sort_records<M: Marker>(mut ctx: ResMut<SerializeContext<M>>) {
+    if !ctx.deterministic {
+        return;
+    }
+    for records in ctx.components.values_mut() {
+        records.sort_by_cached_key(|r| (r.path.to_string(), match &r.parent {
+            EntityParent::Root => String::new(),
+            parent => EntityPath::from(parent.clone()).to_string(),
+        }));
+    }
+}
+
+fn build_delta<M: Marker>(
+    delta: Option<Res<SaveDelta<M>>>,
+    types: Option<Res<TypeFilter<M>>>,
+    state: Res<SaveState<M>>,
+    mut ctx: ResMut<SerializeContext<M>>,
+) {
+    let Some(delta) = delta else { return };
+    let ctx = ctx.as_mut();
+    let removed = state.paths().iter()
+        .filter(|(name, _)| name.as_str() != BARE_ENTITY)
+        .filter(|(name, _)| types.as_ref().map_or(true, |t| t.contains(name)))
+        .filter_map(|(name, paths)| {
+            let present = ctx.present.get(name);
+            let mut removed: Vec<_> = paths.iter()
+                .filter(|path| present.map_or(true, |p| !p.contains(path)))
+                .cloned()
+                .collect();
+            removed.sort_by_cached_key(|path| path.to_string());
+            (!removed.is_empty()).then(|| (name.clone(), removed))
+        }).collect();
+    ctx.delta = Some(DeltaHeader {
+        base: delta.base().to_owned(),
+        removed,
+    });
+}
+
+fn clear_scope<M: Marker>(mut commands: Commands, filter: Option<Res<SaveFilter<M>>>) {
+    if filter.is_some_and(|f| f.is_once()) {
+        commands.remove_resource::<SaveFilter<M>>();
+    }
+    commands.remove_resource::<AbortSave<M>>();
+}
+
 fn build_names<M: Marker>(mut res: ResMut<PathNames<M>>, names: Query<(Entity, &PathName)>) {
     for (entity, name) in names.iter() {
         res.push(entity, name.get())
     }
 }
 
+/// Name entities with `Name` not named by any other source.
+///
+/// Runs after [`InitSerialize`] or [`InitDeserialize`], so `PathName`, `PathRoot`
+/// and [`SaveLoad::path_name`] take priority without conflicts.
+#[cfg(feature="name-paths")]
+fn build_bevy_names<M: Marker>(
+    mut res: ResMut<PathNames<M>>,
+    names: Query<(Entity, &bevy_core::Name)>,
+) {
+    for (entity, name) in names.iter() {
+        if res.get(entity).is_none() {
+            res.push(entity, Cow::Owned(name.as_str().to_owned()))
+        }
+    }
+}
+
 fn build_ser_context<M: Marker>(
     names: ResMut<PathNames<M>>, 
+    config: Res<SaloConfig<M>>,
+    metadata: Option<Res<SaveMetadata<M>>>,
     mut ctx: ResMut<SerializeContext<M>>, 
-    parents: Query<&Parent>
+    parents: Parents,
+    ids: Query<(Entity, &PersistentId)>,
 ) {
+    ctx.metadata = metadata.map(|m| m.value().clone());
+    ctx.orphan_policy = config.orphan_policy();
+    ctx.layout = config.format_layout();
+    ctx.deterministic = config.deterministic();
+    ctx.ids = ids.iter().map(|(entity, id)| (entity, *id)).collect();
     for (original, name) in names.iter() {
         let mut entity = original;
-        let mut path = vec![name];
-        while let Ok(parent) = parents.get(entity) {
-            entity = parent.get();
+        let mut path = vec![SmolStr::new(name)];
+        while let Some(parent) = parents.get(entity) {
+            entity = parent;
             if let Some(name) = names.get(entity) {
-                path.push(name);
+                path.push(SmolStr::new(name));
             } else {
                 break;
             }
         }
         path.reverse();
-        ctx.paths.insert(original, path.join("::"));
+        ctx.paths.insert(original, config.rooted(path));
     }
 }
 
-fn build_de_context<M: Marker>(
-    names: ResMut<PathNames<M>>,
-    file: Option<ResMut<FileInput<M>>>, 
-    bytes: Option<Res<BytesInput<M>>>, 
+type Inputs<'w, M> = (
+    Option<Res<'w, FileInput<M>>>,
+    Option<Res<'w, BytesInput<M>>>,
+    Option<Res<'w, StringInput<M>>>,
+    Option<ResMut<'w, ValueInput<M>>>,
+    Option<ResMut<'w, ReaderInput<M>>>,
+    Option<Res<'w, ArchiveInput<M>>>,
+);
+
+fn read_input<M: Marker>(
+    (file, bytes, string, value, reader, archive): Inputs<M>,
+    key: Option<Res<SaveEncryptionKey<M>>>,
     mut ctx: ResMut<DeserializeContext<M>>,
-    parents: Query<&Parent>
+    mut errors: ResMut<SaloErrors<M>>,
+    mut history: ResMut<SaloHistory<M>>,
+    delta: Option<Res<SaveDelta<M>>>,
 ) {
-    match (file, bytes) {
-        (Some(_), Some(_)) => {
-            eprintln!("FileInput and BytesInput both exists, pick only one.");
+    if file.is_some() as usize + bytes.is_some() as usize + string.is_some() as usize
+        + value.is_some() as usize + reader.is_some() as usize + archive.is_some() as usize > 1 {
+        errors.push(SaloError::ConflictingInput);
+        return;
+    }
+    let result = match (file, bytes, string, value, reader, archive) {
+        #[cfg(feature="fs")]
+        (Some(file), _, _, _, _, _) => {
+            let read = || {
+                #[cfg(feature="fs-lock")]
+                let _lock = crate::lock_file(file.get(), false)?;
+                match &key {
+                    Some(key) => std::fs::read(file.get()).map_err(Into::into)
+                        .and_then(|bytes| M::Method::deserialize_keyed(&bytes, Some(key.get()))),
+                    None => M::Method::deserialize_file(file.get()),
+                }
+            };
+            let result = read();
+            history.size = std::fs::metadata(file.get()).ok().map(|m| m.len() as usize);
+            result
+        },
+        (_, Some(bytes), _, _, _, _) => {
+            history.size = Some(bytes.get().len());
+            M::Method::deserialize_keyed(bytes.get(), key.as_ref().map(|k| k.get()))
+        },
+        (_, _, Some(_), _, _, _) if key.is_some() => Err(keyed_string_error()),
+        (_, _, Some(string), _, _, _) => {
+            history.size = Some(string.get().len());
+            M::Method::deserialize_str(string.get())
+        },
+        (_, _, _, Some(mut value), _, _) => M::Method::deserialize_value(std::mem::take(&mut value.0)),
+        (_, _, _, _, Some(mut reader), _) => match &key {
+            Some(key) => {
+                let mut bytes = Vec::new();
+                let result = reader.0.read_to_end(&mut bytes).map_err(Into::into)
+                    .and_then(|_| M::Method::deserialize_keyed(&bytes, Some(key.get())));
+                history.size = Some(bytes.len());
+                result
+            },
+            None => M::Method::deserialize_reader(&mut reader.0),
         },
         #[cfg(feature="fs")]
-        (Some(file), None) => {
-            ctx.load(match M::Method::deserialize_file(file.get()) {
-                Ok(v) => v,
-                Err(e) => {
-                    eprintln!("Deserialization Failed: {}", e);
-                    return;
-                },
-            });
+        (_, _, _, _, _, Some(archive)) => {
+            crate::archive::read_archive(&archive, key.as_ref().map(|k| k.get())).map(|(document, size)| {
+                history.size = Some(size);
+                document
+            })
         },
-        (None, Some(bytes)) => {
-            ctx.load(match M::Method::deserialize(bytes.get()) {
-                Ok(v) => v,
-                Err(e) => {
-                    eprintln!("Deserialization Failed: {}", e);
-                    return;
-                },
-            });
-        }
         _ => {
-            eprintln!("No input found in deserialization.")
+            errors.push(SaloError::NoInput);
+            return;
         },
+    };
+    let document = match result {
+        Ok(v) => v,
+        Err(e) => {
+            errors.push(SaloError::deserialization(e));
+            return;
+        },
+    };
+    ctx.load(document);
+    if let Some(delta) = delta {
+        let found = ctx.delta.as_ref().map(|d| d.base.clone());
+        if found.as_deref() != Some(delta.base()) {
+            errors.push(SaloError::BaseMismatch { expected: delta.base().to_owned(), found });
+        }
     }
+}
 
+fn build_de_context<M: Marker>(
+    names: ResMut<PathNames<M>>,
+    config: Res<SaloConfig<M>>,
+    mut ctx: ResMut<DeserializeContext<M>>,
+    parents: Parents,
+    ids: Query<(Entity, &PersistentId)>,
+    rollback: Option<Res<RollbackEntities<M>>>,
+) {
+    ctx.policy = config.conflict_policy();
+    ctx.budget = config.entity_budget();
+    ctx.spawn_disabled = config.spawn_disabled();
+    for entity in rollback.iter().flat_map(|rollback| rollback.0.iter()) {
+        ctx.path_map.insert(EntityPath::Entity(entity.to_bits()), *entity);
+    }
+    for (entity, id) in ids.iter() {
+        ctx.path_map.insert(EntityPath::Entity(id.to_bits()), entity);
+    }
     for (original, name) in names.iter() {
         let mut entity = original;
-        let mut path = vec![name];
-        while let Ok(parent) = parents.get(entity) {
-            entity = parent.get();
+        let mut path = vec![SmolStr::new(name)];
+        while let Some(parent) = parents.get(entity) {
+            entity = parent;
             if let Some(name) = names.get(entity) {
-                path.push(name);
+                path.push(SmolStr::new(name));
             } else {
                 break;
             }
         }
         path.reverse();
-        ctx.push(original, &path.join("::"));
+        ctx.push(original, config.rooted(path));
     }
 }
 
@@ -203,58 +652,364 @@ fn build_de_context<M: Marker>(
 #[doc(hidden)]
 pub struct BuildRes<T>(PhantomData<T>);
 
+/// Builder for bundles.
+#[doc(hidden)]
+pub struct BuildBundle<T>(PhantomData<T>);
+
+/// Builder for components registered with `register_if`.
+#[doc(hidden)]
+pub struct BuildIf<T>(PhantomData<T>);
+
+/// Systems of a registered component.
+#[derive(SystemSet)]
+pub(crate) struct TypeSet<T: 'static>(PhantomData<T>);
+
+impl<T> Default for TypeSet<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Debug for TypeSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TypeSet<{}>", get_short_name(std::any::type_name::<T>()))
+    }
+}
+
+impl<T> Clone for TypeSet<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TypeSet<T> {}
+
+impl<T> PartialEq for TypeSet<T> {
+    fn eq(&self, _: &Self) -> bool { true }
+}
+
+impl<T> Eq for TypeSet<T> {}
+
+impl<T> Hash for TypeSet<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
 /// Builder for names only.
 #[doc(hidden)]
 pub struct Names<T>(PhantomData<T>);
 
-schedules!(SaveSchedule, LoadSchedule, ResetSchedule);
-system_sets!(InitSerialize, RunSerialize, InitDeserialize, RunDeserialize, WriteOutput);
+/// Schedule a hook added by the plugin applies to.
+pub(crate) enum HookTarget {
+    PreSave,
+    PostLoad,
+}
+
+schedules!(SaveSchedule, LoadSchedule, ResetSchedule, TrackSchedule, PreSaveSchedule, PostLoadSchedule, EstimateSchedule, PathSchedule, ValidateSchedule);
+system_sets!(InitSerialize, RunSerialize, InitDeserialize, DeserializeResources, RunDeserialize, WriteOutput, RecordState, ValidateDeserialize, Teardown);
 
 impl<M: Marker, C: Build> SaveLoadPlugin<M, C> {
     pub fn build_world(&self, world: &mut World) {
         let mut ser = Schedule::new(SaveSchedule::<M>(PhantomData));
         let mut de = Schedule::new(LoadSchedule::<M>(PhantomData));
         let mut reset = Schedule::new(ResetSchedule::<M>(PhantomData));
-        ser.add_systems(init_serialize::<M>);
-        ser.configure_sets(InitSerialize.after(init_serialize::<M>));
-        ser.add_systems(build_ser_context::<M>.after(InitSerialize));
-        ser.configure_sets(RunSerialize.after(build_ser_context::<M>));
-        ser.configure_sets(WriteOutput.after(RunSerialize));
+        let mut track = Schedule::new(TrackSchedule::<M>(PhantomData));
+        let mut pre_save = Schedule::new(PreSaveSchedule::<M>(PhantomData));
+        let mut post_load = Schedule::new(PostLoadSchedule::<M>(PhantomData));
+        let mut estimate = Schedule::new(EstimateSchedule::<M>(PhantomData));
+        let mut paths = Schedule::new(PathSchedule::<M>(PhantomData));
+        let mut validate = Schedule::new(ValidateSchedule::<M>(PhantomData));
+        ser.add_systems((init_serialize::<M>, run_pre_save::<M>).chain());
+        ser.add_systems(collect_descendants::<M>.after(run_pre_save::<M>).run_if(not_aborted::<M>).run_if(with_entities::<M>));
+        ser.configure_sets(InitSerialize.after(collect_descendants::<M>).run_if(not_aborted::<M>).run_if(with_entities::<M>));
+        ser.add_systems(build_ser_context::<M>.after(InitSerialize).run_if(not_aborted::<M>).run_if(with_entities::<M>));
+        ser.add_systems(strip_path_root::<M>.after(build_ser_context::<M>).before(report_conflicts::<M>));
+        ser.add_systems(report_conflicts::<M>.after(build_ser_context::<M>).run_if(not_aborted::<M>).run_if(with_entities::<M>));
+        ser.configure_sets(RunSerialize.after(report_conflicts::<M>).run_if(not_aborted::<M>));
+        ser.configure_sets(WriteOutput.after(RunSerialize).run_if(not_aborted::<M>));
+        ser.add_systems(emit_serialized::<M>.after(RunSerialize).before(emit_unknown::<M>));
+        ser.add_systems(emit_bare_entities::<M>.after(emit_serialized::<M>).before(emit_unknown::<M>).run_if(with_entities::<M>));
+        ser.add_systems(emit_unknown::<M>.after(RunSerialize).before(WriteOutput).run_if(with_entities::<M>));
+        ser.add_systems(build_delta::<M>.after(RunSerialize).before(WriteOutput));
+        ser.add_systems(build_tombstones::<M>.after(emit_unknown::<M>).before(anonymize::<M>));
+        ser.add_systems(write_fingerprint::<M>.after(RunSerialize).before(WriteOutput));
+        ser.add_systems(anonymize::<M>.after(emit_unknown::<M>).after(build_delta::<M>).before(WriteOutput));
+        #[cfg(feature="sha2")]
+        ser.add_systems(sign_sections::<M>.after(transform_document::<M>).before(WriteOutput));
+        ser.add_systems(sort_records::<M>.after(anonymize::<M>).before(transform_document::<M>));
+        ser.add_systems(write_attributes::<M>.after(emit_unknown::<M>).before(anonymize::<M>));
+        ser.add_systems(transform_document::<M>.after(anonymize::<M>).after(build_tombstones::<M>).after(write_fingerprint::<M>).before(WriteOutput));
+        ser.add_systems(save_finished::<M>.after(WriteOutput));
         ser.add_systems(build_names::<M>.in_set(InitSerialize));
+        ser.add_systems(build_root_names::<M>.after(build_names::<M>).in_set(InitSerialize));
+        #[cfg(feature="name-paths")]
+        ser.add_systems(build_bevy_names::<M>.after(InitSerialize).before(build_ser_context::<M>));
+        ser.add_systems(write_aliases::<M>.after(build_tombstones::<M>).after(build_delta::<M>).before(anonymize::<M>));
         ser.add_systems((
-            #[cfg(feature="fs")] write_to_file::<M>, 
-            write_to_bytes::<M>, write_to_string::<M>
+            #[cfg(feature="fs")] write_to_file::<M>, #[cfg(feature="fs")] write_to_archive::<M>, #[cfg(feature="fs")] write_split::<M>,
+            write_to_bytes::<M>, write_to_string::<M>, write_to_value::<M>, write_to_writer::<M>
         ).in_set(WriteOutput));
+        #[cfg(feature="fs")]
+        ser.add_systems(write_debug_dump::<M>.after(write_to_file::<M>).in_set(WriteOutput));
+        ser.add_systems(clear_scope::<M>.after(WriteOutput));
+        ser.add_systems(record_sizes::<M>.after(WriteOutput).before(record_save::<M>));
+        ser.add_systems(record_save::<M>.after(save_finished::<M>).after(finish_save::<M>));
+        ser.configure_sets(RecordState.after(WriteOutput).run_if(is_complete::<M>));
+        ser.add_systems(finish_save::<M>.after(RecordState).run_if(is_complete::<M>));
+        ser.configure_sets(Teardown.after(record_save::<M>).after(clear_scope::<M>).run_if(free_buffers::<M>));
+        ser.add_systems(teardown_serialize::<M>.in_set(Teardown));
         de.add_systems(init_deserialize::<M>);
-        de.configure_sets(InitDeserialize.after(init_deserialize::<M>));
-        de.add_systems(build_de_context::<M>.after(InitDeserialize));
-        de.configure_sets(RunDeserialize.after(build_de_context::<M>));
+        de.add_systems(collect_descendants::<M>.after(init_deserialize::<M>).run_if(with_entities::<M>));
+        de.configure_sets(InitDeserialize.after(collect_descendants::<M>).run_if(with_entities::<M>));
+        de.add_systems((
+            read_input::<M>,
+            check_fingerprint::<M>.run_if(is_first_chunk::<M>),
+            #[cfg(feature="sha2")] verify_sections::<M>.run_if(is_first_chunk::<M>),
+            transform_loaded::<M>.run_if(is_first_chunk::<M>),
+            apply_renames::<M>.run_if(is_first_chunk::<M>),
+            apply_aliases::<M>.run_if(is_first_chunk::<M>),
+            apply_remap::<M>.run_if(is_first_chunk::<M>),
+            apply_path_root::<M>.run_if(is_first_chunk::<M>),
+            apply_load_policy::<M>,
+            collect_type_roots::<M>,
+            apply_load_anchor::<M>,
+            build_de_context::<M>.run_if(with_entities::<M>),
+            apply_pending_entities::<M>,
+            report_conflicts::<M>,
+        ).chain().after(InitDeserialize));
+        de.configure_sets(ValidateDeserialize.after(report_conflicts::<M>));
+        // Resources are inserted before other records are loaded, so types that intern
+        // into a saved resource, i.e. `interned_enum!` servers, see the loaded resource.
+        de.configure_sets(DeserializeResources.after(ValidateDeserialize).run_if(no_errors::<M>));
+        de.add_systems(apply_deferred.after(DeserializeResources).before(RunDeserialize));
+        de.configure_sets(RunDeserialize.after(ValidateDeserialize).run_if(no_errors::<M>));
+        de.add_systems(load_bare_entities::<M>.after(ValidateDeserialize).before(RunDeserialize).run_if(no_errors::<M>));
+        de.add_systems(apply_tombstones::<M>.after(ValidateDeserialize).before(RunDeserialize).run_if(no_errors::<M>).run_if(with_entities::<M>));
         de.add_systems(build_names::<M>.in_set(InitDeserialize));
-        C::build::<M>(&mut ser, &mut de, &mut reset);
+        de.add_systems(build_root_names::<M>.after(build_names::<M>).in_set(InitDeserialize));
+        #[cfg(feature="name-paths")]
+        de.add_systems(build_bevy_names::<M>.after(InitDeserialize).before(read_input::<M>));
+        de.add_systems(emit_deserialized::<M>.after(RunDeserialize).before(collect_unknown::<M>));
+        de.add_systems(collect_unknown::<M>.after(RunDeserialize).run_if(no_errors::<M>).run_if(with_entities::<M>).run_if(all_types::<M>));
+        de.add_systems(attach_type_roots::<M>.after(RunDeserialize).run_if(no_errors::<M>));
+        de.add_systems(apply_attributes::<M>.after(RunDeserialize).before(collect_unknown::<M>).run_if(no_errors::<M>));
+        de.add_systems(collect_pending::<M>.after(RunDeserialize).before(apply_attributes::<M>).before(run_post_load::<M>).run_if(with_entities::<M>));
+        de.add_systems((apply_deferred, run_post_load::<M>.run_if(no_errors::<M>).run_if(is_first_chunk::<M>)).chain().after(collect_unknown::<M>).after(finish_load::<M>));
+        de.add_systems(enable_loaded::<M>.after(run_post_load::<M>).after(collect_pending::<M>).before(load_finished::<M>).run_if(with_entities::<M>));
+        de.add_systems(load_finished::<M>.after(run_post_load::<M>));
+        de.add_systems(record_load::<M>.after(load_finished::<M>));
+        de.add_systems(record_analytics::<M>.after(record_load::<M>));
+        de.add_systems(apply_deferred.after(RunDeserialize).before(RecordState));
+        de.configure_sets(RecordState.after(RunDeserialize).run_if(is_complete_load::<M>));
+        de.add_systems(finish_load::<M>.after(RecordState).run_if(is_complete_load::<M>));
+        de.add_systems(reserve_persistent_ids::<M>.after(RunDeserialize).run_if(no_errors::<M>).run_if(with_entities::<M>));
+        de.configure_sets(Teardown.after(record_analytics::<M>).after(reserve_persistent_ids::<M>).after(attach_type_roots::<M>).run_if(free_buffers::<M>));
+        de.add_systems(teardown_deserialize::<M>.in_set(Teardown));
+        if !M::IS_ALL {
+            ser.add_systems(serialize_marker::<M>.in_set(RunSerialize).run_if(with_entities::<M>).run_if(persist_marker::<M>));
+            de.add_systems(validate_marker::<M>.in_set(ValidateDeserialize).run_if(persist_marker::<M>));
+            de.add_systems(deserialize_marker::<M>.in_set(RunDeserialize));
+        }
+        paths.add_systems(init_paths::<M>);
+        paths.add_systems(collect_descendants::<M>.after(init_paths::<M>));
+        paths.configure_sets(InitSerialize.after(collect_descendants::<M>));
+        paths.add_systems(build_names::<M>.in_set(InitSerialize));
+        paths.add_systems(build_root_names::<M>.after(build_names::<M>).in_set(InitSerialize));
+        #[cfg(feature="name-paths")]
+        paths.add_systems(build_bevy_names::<M>.after(InitSerialize).before(build_ser_context::<M>));
+        paths.add_systems((build_ser_context::<M>, strip_path_root::<M>).chain().after(InitSerialize));
+        validate.add_systems((apply_aliases::<M>, apply_remap::<M>).chain());
+        validate.configure_sets(ValidateDeserialize.after(apply_remap::<M>));
+        C::build::<M>(&mut ser, &mut de, &mut reset, &mut track, &mut estimate, &mut paths, &mut validate);
+        for (target, f) in &self.2 {
+            match target {
+                HookTarget::PreSave => f(&mut pre_save),
+                HookTarget::PostLoad => f(&mut post_load),
+            }
+        }
+        for f in &self.1 {
+            f(&mut ser);
+            f(&mut de);
+            f(&mut reset);
+            f(&mut track);
+            f(&mut pre_save);
+            f(&mut post_load);
+            f(&mut estimate);
+            f(&mut paths);
+            f(&mut validate);
+        }
+        world.init_resource::<SaloConfig<M>>();
+        world.init_resource::<LoadPolicy<M>>();
+        world.init_resource::<SaloHistory<M>>();
+        world.init_resource::<PersistentIdAllocator<M>>();
+        world.init_resource::<MarkedDescendants<M>>();
+        world.init_resource::<PathCollisions<M>>();
+        world.init_resource::<SaveSizeCache<M>>();
+        world.init_resource::<SnapshotRegistry<M>>();
+        world.init_resource::<Events<SaveThrottled<M>>>();
+        world.init_resource::<Events<SaloEvent<M>>>();
+        world.insert_resource(SaveState::<M>::default());
+        let mut registry = SaloRegistry::<M>::default();
+        C::register::<M>(&mut registry);
+        register_marker::<M>(&mut registry);
+        for name in &self.3 {
+            registry.deactivate(name);
+        }
+        world.insert_resource(registry);
         world.add_schedule(ser);
         world.add_schedule(de);
         world.add_schedule(reset);
+        world.add_schedule(track);
+        world.add_schedule(pre_save);
+        world.add_schedule(post_load);
+        world.add_schedule(estimate);
+        world.add_schedule(paths);
+        world.add_schedule(validate);
+    }
+
+    /// Modify all schedules of this marker after they are built,
+    /// i.e. to change [`ScheduleBuildSettings`](bevy_ecs::schedule::ScheduleBuildSettings) or the executor.
+    ///
+    /// ```
+    /// # use bevy_salo::*;
+    /// # use bevy_ecs::schedule::{ScheduleBuildSettings, LogLevel, ExecutorKind};
+    /// SaveLoadPlugin::new::<All>()
+    ///     .configure_schedule(|schedule| {
+    ///         schedule.set_build_settings(ScheduleBuildSettings {
+    ///             ambiguity_detection: LogLevel::Warn,
+    ///             ..Default::default()
+    ///         });
+    ///         schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    ///     });
+    /// ```
+    pub fn configure_schedule(mut self, f: fn(&mut Schedule)) -> Self {
+        self.1.push(f);
+        self
+    }
+
+    /// Run all schedules of this marker with the single-threaded executor.
+    ///
+    /// This guarantees records of a type are pushed in the same order across machines,
+    /// useful for reproducible save files.
+    pub fn single_threaded(self) -> Self {
+        self.configure_schedule(|schedule| {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        })
+    }
+
+    /// Add a system to [`PreSaveSchedule`], which runs at the start of each save with commands applied after,
+    /// i.e. to normalize data or insert [`AbortSave`] if the world cannot be saved.
+    ///
+    /// Systems can also be added to the schedule directly after the plugin is built.
+    pub fn add_pre_save_system<P>(mut self, system: impl IntoSystemConfigs<P> + Clone + Send + Sync + 'static) -> Self {
+        self.2.push((HookTarget::PreSave, Box::new(move |schedule: &mut Schedule| {
+            schedule.add_systems(system.clone());
+        })));
+        self
+    }
+
+    /// Add a system to [`PostLoadSchedule`], which runs at the end of each successful load
+    /// with commands applied, i.e. to rebuild derived state.
+    ///
+    /// Systems can also be added to the schedule directly after the plugin is built.
+    pub fn add_post_load_system<P>(mut self, system: impl IntoSystemConfigs<P> + Clone + Send + Sync + 'static) -> Self {
+        self.2.push((HookTarget::PostLoad, Box::new(move |schedule: &mut Schedule| {
+            schedule.add_systems(system.clone());
+        })));
+        self
     }
 
     /// Register serialization of a `Component`
     pub fn register<T: SaveLoad>(self) -> SaveLoadPlugin<M, (C, T)> {
-        SaveLoadPlugin(PhantomData)
+        SaveLoadPlugin(PhantomData, self.1, self.2, self.3)
+    }
+
+    /// Register serialization of a `Component` if `active`, i.e. for demo builds or platforms
+    /// that exclude a type.
+    ///
+    /// Inactive types are still in [`SaloRegistry`] with [`RegisteredType::active`](crate::RegisteredType::active) unset,
+    /// so their records are recognized but not loaded, and kept with
+    /// [`SaloConfig::with_preserve_unknown`].
+    pub fn register_if<T: SaveLoad>(mut self, active: bool) -> SaveLoadPlugin<M, (C, BuildIf<T>)> {
+        if !active {
+            self.3.push(T::type_name());
+        }
+        SaveLoadPlugin(PhantomData, self.1, self.2, self.3)
+    }
+
+    /// Register serialization of a tuple of up to 16 `Component`s, i.e. `(Unit, Weapon, Armor)`.
+    ///
+    /// Produces a flatter plugin type than chaining [`register`](Self::register),
+    /// tuples can be nested for more types.
+    pub fn register_tuple<T: Build>(self) -> SaveLoadPlugin<M, (C, T)> {
+        SaveLoadPlugin(PhantomData, self.1, self.2, self.3)
     }
 
     /// Register serialization of a `Resource`.
     pub fn register_resource<T: SaveLoadRes>(self) -> SaveLoadPlugin<M, (C, BuildRes<T>)> {
-        SaveLoadPlugin(PhantomData)
+        SaveLoadPlugin(PhantomData, self.1, self.2, self.3)
+    }
+
+    /// Register serialization of multiple components as a single record, see [`SaveLoadBundle`].
+    pub fn register_bundle<T: SaveLoadBundle>(self) -> SaveLoadPlugin<M, (C, BuildBundle<T>)> {
+        SaveLoadPlugin(PhantomData, self.1, self.2, self.3)
+    }
+
+    /// Register `Transform`, `Name` and `Visibility` if enabled, see [`bevy_types`](crate::bevy_types).
+    #[cfg(feature="bevy_types")]
+    pub fn register_bevy_core_types(self) -> SaveLoadPlugin<M, (C, crate::bevy_types::BevyCoreTypes)> {
+        SaveLoadPlugin(PhantomData, self.1, self.2, self.3)
     }
 
     /// Register names of an externally serialized `Component`, but does not perform serialization.
     pub fn register_names<T: SaveLoad>(self) -> SaveLoadPlugin<M, (C, Names<T>)> {
-        SaveLoadPlugin(PhantomData)
+        SaveLoadPlugin(PhantomData, self.1, self.2, self.3)
+    }
+
+    /// Register all types of a [`SharedTypes`], which can be used on multiple plugins.
+    pub fn register_shared<S: Build>(self, _: SharedTypes<S>) -> SaveLoadPlugin<M, (C, S)> {
+        SaveLoadPlugin(PhantomData, self.1, self.2, self.3)
+    }
+}
+
+impl<C: Build> SharedTypes<C> {
+    /// Register serialization of a `Component`
+    pub fn register<T: SaveLoad>(self) -> SharedTypes<(C, T)> {
+        SharedTypes(PhantomData)
+    }
+
+    /// Register serialization of a `Resource`.
+    pub fn register_resource<T: SaveLoadRes>(self) -> SharedTypes<(C, BuildRes<T>)> {
+        SharedTypes(PhantomData)
+    }
+
+    /// Register serialization of multiple components as a single record, see [`SaveLoadBundle`].
+    pub fn register_bundle<T: SaveLoadBundle>(self) -> SharedTypes<(C, BuildBundle<T>)> {
+        SharedTypes(PhantomData)
+    }
+
+    /// Register names of an externally serialized `Component`, but does not perform serialization.
+    pub fn register_names<T: SaveLoad>(self) -> SharedTypes<(C, Names<T>)> {
+        SharedTypes(PhantomData)
     }
 }
 
 #[cfg(feature="bevy_app")]
 impl<M: Marker, C: Build> bevy_app::Plugin for SaveLoadPlugin<M, C> where Self: Send + Sync + 'static  {
     fn build(&self, app: &mut bevy_app::App) {
+        app.add_event::<crate::SaveThrottled<M>>();
+        app.add_event::<crate::SaloEvent<M>>();
+        app.add_event::<crate::SaveRequest<M>>();
+        app.add_event::<crate::LoadRequest<M>>();
+        app.add_systems(bevy_app::Last, crate::request::handle_requests::<M>);
+        app.add_systems(bevy_app::Last, (|world: &mut World| {
+            world.run_schedule(TrackSchedule::<M>(PhantomData))
+        }).run_if(crate::state::is_clean::<M>));
+        // Errors are recorded in `SaloErrors` and `SaloHistory`.
+        app.add_systems(bevy_app::First, (|world: &mut World| {
+            let _ = crate::budget::load_pending::<M>(world);
+        }).run_if(resource_exists::<crate::PendingLoad<M>>()));
         self.build_world(&mut app.world)
     }
 }
@@ -1,10 +1,17 @@
 
 use std::marker::PhantomData;
-use bevy_ecs::world::World;
-use bevy_ecs::schedule::{Schedule, IntoSystemConfigs};
+use bevy_ecs::schedule::{Schedule, IntoSystemConfigs, IntoSystemSetConfigs};
 use crate::methods::SerializationMethod;
-use crate::{SaveLoad, StringOutput, BytesOutput, Marker, SaveLoadRes};
+use crate::{SaveLoad, SaveLoadBundle, Marker, SaveLoadRes, SaloRegistry, RegisteredType};
 use crate::schedules::*;
+use crate::policy::{validate_bundle, validate_component, validate_resource};
+use crate::bundle::{serialize_bundle, deserialize_bundle, remove_bundle};
+use crate::state::{is_active, no_errors, with_entities, record_bundle, record_count, record_resource, track_bundle, track_changes, track_resource};
+use crate::estimate::{count_bundle, count_items, count_resource};
+use crate::version::migrate_records;
+use crate::diagnostics::report_conflicts;
+use crate::validate::{check_bundle, check_component, check_resource};
+use crate::defaults::{fill_component, fill_resource};
 
 pub trait Sealed {}
 pub trait MarkerSeal {}
@@ -26,95 +33,152 @@ impl<S: SerializationMethod, const FORK: char> Default for All<S, FORK> {
 }
 
 pub trait Build {
-    fn build<M: Marker>(ser: &mut Schedule, de: &mut Schedule, reset: &mut Schedule);
-    fn build_names<M: Marker>(_: &mut Schedule, _: &mut Schedule);
+    fn build<M: Marker>(ser: &mut Schedule, de: &mut Schedule, reset: &mut Schedule, track: &mut Schedule, estimate: &mut Schedule, paths: &mut Schedule, validate: &mut Schedule);
+    fn build_names<M: Marker>(_: &mut Schedule, _: &mut Schedule, _: &mut Schedule);
+    fn register<M: Marker>(_: &mut SaloRegistry<M>) {}
 }
 
 impl Build for () {
-    fn build<M: Marker>(_: &mut Schedule, _: &mut Schedule, _: &mut Schedule) {}
-    fn build_names<M: Marker>(_: &mut Schedule, _: &mut Schedule) {}
+    fn build<M: Marker>(_: &mut Schedule, _: &mut Schedule, _: &mut Schedule, _: &mut Schedule, _: &mut Schedule, _: &mut Schedule, _: &mut Schedule) {}
+    fn build_names<M: Marker>(_: &mut Schedule, _: &mut Schedule, _: &mut Schedule) {}
 }
 
 macro_rules! build_tuple {
     ($first: ident) => {};
     ($first: ident, $($rest: ident),*) => {
         impl<$first: Build $(,$rest: Build)*> Build for ($first $(,$rest)*) {
-            fn build<M: Marker>(ser: &mut Schedule, de: &mut Schedule, reset: &mut Schedule) {
-                $first::build::<M>(ser, de, reset);
-                $($rest::build::<M>(ser, de, reset);)*
+            fn build<M: Marker>(ser: &mut Schedule, de: &mut Schedule, reset: &mut Schedule, track: &mut Schedule, estimate: &mut Schedule, paths: &mut Schedule, validate: &mut Schedule) {
+                $first::build::<M>(ser, de, reset, track, estimate, paths, validate);
+                $($rest::build::<M>(ser, de, reset, track, estimate, paths, validate);)*
             }
-            fn build_names<M: Marker>(ser: &mut Schedule, de: &mut Schedule) {
-                $first::build_names::<M>(ser, de);
-                $($rest::build_names::<M>(ser, de);)*
+            fn build_names<M: Marker>(ser: &mut Schedule, de: &mut Schedule, paths: &mut Schedule) {
+                $first::build_names::<M>(ser, de, paths);
+                $($rest::build_names::<M>(ser, de, paths);)*
+            }
+            fn register<M: Marker>(registry: &mut SaloRegistry<M>) {
+                $first::register::<M>(registry);
+                $($rest::register::<M>(registry);)*
             }
         }
         build_tuple!($($rest),*);
     };
 }
 
-build_tuple!(A,B,C,D,E,F,G);
+build_tuple!(A,B,C,D,E,F,G,H,I,J,K,L,N,O,P,Q);
 
 
 impl<T> Build for T where T: SaveLoad {
-    fn build<M: Marker>(ser: &mut Schedule, de: &mut Schedule, reset: &mut Schedule) {
-        ser.add_systems(Self::build_path::<M>.in_set(InitSerialize));
-        ser.add_systems(Self::serialize_system::<M>.in_set(RunSerialize));
-        de.add_systems(Self::build_path::<M>.in_set(InitDeserialize));
-        de.add_systems(Self::deserialize_system::<M>.in_set(RunDeserialize));
-        reset.add_systems(Self::remove_all::<M>);
+    fn build<M: Marker>(ser: &mut Schedule, de: &mut Schedule, reset: &mut Schedule, track: &mut Schedule, estimate: &mut Schedule, paths: &mut Schedule, validate: &mut Schedule) {
+        let set = TypeSet::<Self>::default;
+        ser.add_systems(Self::build_path::<M>.in_set(InitSerialize).in_set(set()));
+        paths.add_systems(Self::build_path::<M>.in_set(InitSerialize).in_set(set()));
+        ser.add_systems(Self::serialize_system::<M>.in_set(RunSerialize).in_set(set()).run_if(with_entities::<M>));
+        ser.add_systems(record_count::<M, Self>.in_set(RecordState).in_set(set()));
+        de.add_systems(Self::build_path::<M>.in_set(InitDeserialize).in_set(set()));
+        de.add_systems(migrate_records::<M, Self>.after(report_conflicts::<M>).before(ValidateDeserialize).in_set(set()).run_if(no_errors::<M>));
+        de.add_systems(validate_component::<M, Self>.in_set(ValidateDeserialize).in_set(set()));
+        de.add_systems(Self::deserialize_system::<M>.in_set(RunDeserialize).in_set(set()));
+        de.add_systems(record_count::<M, Self>.in_set(RecordState).in_set(set()));
+        de.add_systems(fill_component::<M, Self>.after(RunDeserialize).before(RecordState).in_set(set()).run_if(no_errors::<M>));
+        reset.add_systems(Self::remove_all::<M>.in_set(set()));
+        track.add_systems(track_changes::<M, Self>.in_set(set()));
+        estimate.add_systems(count_items::<M, Self>.in_set(set()));
+        validate.add_systems(migrate_records::<M, Self>.before(ValidateDeserialize).in_set(set()));
+        validate.add_systems(check_component::<M, Self>.in_set(ValidateDeserialize).in_set(set()));
     }
 
-    fn build_names<M: Marker>(ser: &mut Schedule, de: &mut Schedule) {
+    fn build_names<M: Marker>(ser: &mut Schedule, de: &mut Schedule, paths: &mut Schedule) {
         ser.add_systems(Self::build_path::<M>.in_set(InitSerialize));
         de.add_systems(Self::build_path::<M>.in_set(InitDeserialize));
+        paths.add_systems(Self::build_path::<M>.in_set(InitSerialize));
+    }
+
+    fn register<M: Marker>(registry: &mut SaloRegistry<M>) {
+        registry.push(RegisteredType {
+            name: <T as SaveLoad>::type_name(),
+            rust_name: std::any::type_name::<T>(),
+            version: <T as SaveLoad>::version(),
+            is_resource: false,
+            active: true,
+        })
     }
 }
 
 impl<T> Build for BuildRes<T> where T: SaveLoadRes {
-    fn build<M: Marker>(ser: &mut Schedule, de: &mut Schedule, reset: &mut Schedule) {
+    fn build<M: Marker>(ser: &mut Schedule, de: &mut Schedule, reset: &mut Schedule, track: &mut Schedule, estimate: &mut Schedule, _: &mut Schedule, validate: &mut Schedule) {
         ser.add_systems(T::serialize_system::<M>.in_set(RunSerialize));
-        de.add_systems(T::deserialize_system::<M>.in_set(RunDeserialize));
+        ser.add_systems(record_resource::<M, T>.in_set(RecordState));
+        de.add_systems(validate_resource::<M, T>.in_set(ValidateDeserialize));
+        de.add_systems(T::deserialize_system::<M>.in_set(DeserializeResources));
+        de.add_systems(record_resource::<M, T>.in_set(RecordState));
+        de.add_systems(fill_resource::<M, T>.after(RunDeserialize).before(RecordState).run_if(no_errors::<M>));
         reset.add_systems(T::remove::<M>);
+        track.add_systems(track_resource::<M, T>);
+        estimate.add_systems(count_resource::<M, T>);
+        validate.add_systems(check_resource::<M, T>.in_set(ValidateDeserialize));
     }
 
-    fn build_names<M: Marker>(_: &mut Schedule, _: &mut Schedule) {}
-}
+    fn build_names<M: Marker>(_: &mut Schedule, _: &mut Schedule, _: &mut Schedule) {}
 
-impl<T> Build for Names<T> where T: Build {
-    fn build<M: Marker>(ser: &mut Schedule, de: &mut Schedule, _: &mut Schedule) {
-        T::build_names::<M>(ser, de)
-    }
-    fn build_names<M: Marker>(ser: &mut Schedule, de: &mut Schedule) {
-        T::build_names::<M>(ser, de)
+    fn register<M: Marker>(registry: &mut SaloRegistry<M>) {
+        registry.push(RegisteredType {
+            name: <T as SaveLoadRes>::type_name(),
+            rust_name: std::any::type_name::<T>(),
+            version: 0,
+            is_resource: true,
+            active: true,
+        })
     }
 }
 
-pub trait SerializationResult: Sized {
-    fn setup<M: Marker>(w: &mut World);
-    fn get<M: Marker>(w: &mut World) -> Option<Self>;
-    fn as_bytes(&self) -> &[u8];
+impl<T> Build for BuildBundle<T> where T: SaveLoadBundle {
+    fn build<M: Marker>(ser: &mut Schedule, de: &mut Schedule, reset: &mut Schedule, track: &mut Schedule, estimate: &mut Schedule, _: &mut Schedule, validate: &mut Schedule) {
+        ser.add_systems(serialize_bundle::<M, T>.in_set(RunSerialize).run_if(with_entities::<M>));
+        ser.add_systems(record_bundle::<M, T>.in_set(RecordState));
+        de.add_systems(validate_bundle::<M, T>.in_set(ValidateDeserialize));
+        de.add_systems(deserialize_bundle::<M, T>.in_set(RunDeserialize));
+        de.add_systems(record_bundle::<M, T>.in_set(RecordState));
+        reset.add_systems(remove_bundle::<M, T>);
+        track.add_systems(track_bundle::<M, T>);
+        estimate.add_systems(count_bundle::<M, T>);
+        validate.add_systems(check_bundle::<M, T>.in_set(ValidateDeserialize));
+    }
+
+    fn build_names<M: Marker>(_: &mut Schedule, _: &mut Schedule, _: &mut Schedule) {}
+
+    fn register<M: Marker>(registry: &mut SaloRegistry<M>) {
+        registry.push(RegisteredType {
+            name: T::type_name(),
+            rust_name: std::any::type_name::<T>(),
+            version: 0,
+            is_resource: false,
+            active: true,
+        })
+    }
 }
 
-impl SerializationResult for String {
-    fn setup<M: Marker>(w: &mut World) {
-        w.init_resource::<StringOutput<M>>();
+impl<T> Build for BuildIf<T> where T: SaveLoad {
+    fn build<M: Marker>(ser: &mut Schedule, de: &mut Schedule, reset: &mut Schedule, track: &mut Schedule, estimate: &mut Schedule, paths: &mut Schedule, validate: &mut Schedule) {
+        T::build::<M>(ser, de, reset, track, estimate, paths, validate);
+        for schedule in [ser, de, reset, track, estimate, paths, validate] {
+            schedule.configure_sets(TypeSet::<T>::default().run_if(is_active::<M, T>));
+        }
     }
-    fn get<M: Marker>(w: &mut World) -> Option<Self>{
-        Some(w.remove_resource::<StringOutput<M>>()?.take())
+
+    fn build_names<M: Marker>(ser: &mut Schedule, de: &mut Schedule, paths: &mut Schedule) {
+        T::build_names::<M>(ser, de, paths)
     }
-    fn as_bytes(&self) -> &[u8] {
-        self.as_bytes()
+
+    fn register<M: Marker>(registry: &mut SaloRegistry<M>) {
+        T::register::<M>(registry)
     }
 }
 
-impl SerializationResult for Vec<u8> {
-    fn setup<M: Marker>(w: &mut World) {
-        w.init_resource::<BytesOutput<M>>();
-    }
-    fn get<M: Marker>(w: &mut World) -> Option<Self>{
-        Some(w.remove_resource::<BytesOutput<M>>()?.take())
+impl<T> Build for Names<T> where T: Build {
+    fn build<M: Marker>(ser: &mut Schedule, de: &mut Schedule, _: &mut Schedule, _: &mut Schedule, _: &mut Schedule, paths: &mut Schedule, _: &mut Schedule) {
+        T::build_names::<M>(ser, de, paths)
     }
-    fn as_bytes(&self) -> &[u8] {
-        self
+    fn build_names<M: Marker>(ser: &mut Schedule, de: &mut Schedule, paths: &mut Schedule) {
+        T::build_names::<M>(ser, de, paths)
     }
 }
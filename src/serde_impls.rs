@@ -2,7 +2,56 @@ use std::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{PathedValue, EntityParent, EntityPath, methods::SerializeValue};
+use std::fmt::Formatter;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+
+use crate::{PathedValue, EntityParent, EntityPath, FormatLayout, methods::SerializeValue};
+use crate::saveload::{join_path, DeltaHeader, Document, DocumentRef, Signatures};
+use crate::wire::{encoding_of, split_decoded, upgrade_document, DecodingScope, PathEncoding};
+use crate::fingerprint::Fingerprint;
+use crate::version::Versions;
+use crate::alias::Aliases;
+use crate::tombstone::Tombstones;
+use crate::attributes::AttributeTable;
+
+/// Reserved key of the [`WIRE_VERSION`](crate::wire::WIRE_VERSION) of the writer.
+///
+/// Type names starting with `$` are reserved for header entries.
+pub(crate) const WIRE_KEY: &str = "$wire";
+
+/// Reserved key of the metadata header.
+pub(crate) const METADATA_KEY: &str = "$metadata";
+
+/// Reserved key of the delta header.
+pub(crate) const DELTA_KEY: &str = "$delta";
+
+/// Reserved key of section signatures.
+pub(crate) const SIGNATURES_KEY: &str = "$signatures";
+
+/// Reserved key of the registry fingerprint.
+pub(crate) const FINGERPRINT_KEY: &str = "$fingerprint";
+
+/// Reserved key of type versions.
+pub(crate) const VERSIONS_KEY: &str = "$versions";
+
+/// Reserved key of path aliases.
+pub(crate) const ALIASES_KEY: &str = "$aliases";
+
+/// Reserved key of tombstones.
+pub(crate) const TOMBSTONES_KEY: &str = "$tombstones";
+
+/// Reserved key of record attributes.
+pub(crate) const ATTRIBUTES_KEY: &str = "$attributes";
+
+/// Reserved key of the [`FormatLayout`] tag, only written for [`FormatLayout::EntityMajor`].
+pub(crate) const LAYOUT_KEY: &str = "$layout";
+
+/// Reserved key of records in [`FormatLayout::EntityMajor`].
+pub(crate) const ENTITIES_KEY: &str = "$entities";
 
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
 #[serde(untagged)]
@@ -20,6 +69,14 @@ pub enum EntityPathTagged<'t> {
     Path(Cow<'t, str>)
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename = "EntityParent")]
+enum EntityParentTagged<'t> {
+    Root,
+    Path(Cow<'t, str>),
+    Entity(u64),
+}
+
 impl EntityPathUntagged<'_> {
     pub fn is_default(&self) -> bool {
         self == &Self::None
@@ -46,7 +103,7 @@ impl<'t> From<&'t EntityParent> for EntityPathUntagged<'t> {
     fn from(value: &'t EntityParent) -> Self {
         match value {
             EntityParent::Root => Self::None,
-            EntityParent::Path(p) => Self::Path(Cow::Borrowed(p)),
+            EntityParent::Path(p) => Self::Path(Cow::Owned(join_path(p))),
             EntityParent::Entity(e) => Self::Entity(*e),
         }
     }
@@ -56,7 +113,7 @@ impl<'t> From<&'t EntityPath> for EntityPathUntagged<'t> {
     fn from(value: &'t EntityPath) -> Self {
         match value {
             EntityPath::Unique => Self::None,
-            EntityPath::Path(p) => Self::Path(Cow::Borrowed(p)),
+            EntityPath::Path(p) => Self::Path(Cow::Owned(join_path(p))),
             EntityPath::Entity(e) => Self::Entity(*e),
         }
     }
@@ -66,7 +123,7 @@ impl<'t> From<&'t EntityPath> for EntityPathTagged<'t> {
     fn from(value: &'t EntityPath) -> Self {
         match value {
             EntityPath::Unique => Self::Unique,
-            EntityPath::Path(p) => Self::Path(Cow::Borrowed(p)),
+            EntityPath::Path(p) => Self::Path(Cow::Owned(join_path(p))),
             EntityPath::Entity(e) => Self::Entity(*e),
         }
     }
@@ -76,7 +133,7 @@ impl<'t> From<EntityPathUntagged<'t>> for EntityParent {
     fn from(value: EntityPathUntagged<'t>) -> Self {
         match value {
             EntityPathUntagged::None => Self::Root,
-            EntityPathUntagged::Path(p) => Self::Path(p.into_owned()),
+            EntityPathUntagged::Path(p) => Self::Path(split_decoded(&p)),
             EntityPathUntagged::Entity(e) => Self::Entity(e),
         }
     }
@@ -86,7 +143,7 @@ impl<'t> From<EntityPathUntagged<'t>> for EntityPath {
     fn from(value: EntityPathUntagged<'t>) -> Self {
         match value {
             EntityPathUntagged::None => Self::Unique,
-            EntityPathUntagged::Path(p) => Self::Path(p.into_owned()),
+            EntityPathUntagged::Path(p) => Self::Path(split_decoded(&p)),
             EntityPathUntagged::Entity(e) => Self::Entity(e),
         }
     }
@@ -96,12 +153,44 @@ impl<'t> From<EntityPathTagged<'t>> for EntityPath {
     fn from(value: EntityPathTagged<'t>) -> Self {
         match value {
             EntityPathTagged::Unique => Self::Unique,
-            EntityPathTagged::Path(p) => Self::Path(p.into_owned()),
+            EntityPathTagged::Path(p) => Self::Path(split_decoded(&p)),
             EntityPathTagged::Entity(e) => Self::Entity(e),
         }
     }
 }
 
+impl From<&EntityParent> for EntityParentTagged<'_> {
+    fn from(value: &EntityParent) -> Self {
+        match value {
+            EntityParent::Root => Self::Root,
+            EntityParent::Path(p) => Self::Path(Cow::Owned(join_path(p))),
+            EntityParent::Entity(e) => Self::Entity(*e),
+        }
+    }
+}
+
+impl<'t> From<EntityParentTagged<'t>> for EntityParent {
+    fn from(value: EntityParentTagged<'t>) -> Self {
+        match value {
+            EntityParentTagged::Root => Self::Root,
+            EntityParentTagged::Path(p) => Self::Path(split_decoded(&p)),
+            EntityParentTagged::Entity(e) => Self::Entity(e),
+        }
+    }
+}
+
+impl serde::Serialize for EntityParent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        EntityParentTagged::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for EntityParent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::Deserializer<'de> {
+        Ok(EntityParentTagged::deserialize(deserializer)?.into())
+    }
+}
+
 impl serde::Serialize for EntityPath {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
         if serializer.is_human_readable() {
@@ -143,18 +232,432 @@ impl<V: SerializeValue> serde::Serialize for PathedValue<V> {
 
 
 
+/// Records of a type written inline, fields of map values are written
+/// next to `parent` and `path` instead of in `value`.
+///
+/// Only affects human-readable formats, values that are not maps or contain
+/// reserved keys are written as usual.
+pub(crate) struct InlineRecords<'t, V>(pub(crate) &'t [PathedValue<V>]);
+
+struct InlineRecord<'t, V>(&'t PathedValue<V>);
+
+const RECORD_KEYS: [&str; 3] = ["parent", "path", "value"];
+
+impl<V: SerializeValue> serde::Serialize for InlineRecords<'_, V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        if serializer.is_human_readable() {
+            serializer.collect_seq(self.0.iter().map(InlineRecord))
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<V: SerializeValue> serde::Serialize for InlineRecord<'_, V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        use serde::ser::SerializeMap;
+        let entries = match self.0.value.entries() {
+            Some(entries) if !entries.is_empty() && !entries.iter().any(|(k, _)| RECORD_KEYS.contains(k)) => entries,
+            _ => return self.0.serialize(serializer),
+        };
+        let parent = EntityPathUntagged::from(&self.0.parent);
+        let path = EntityPathUntagged::from(&self.0.path);
+        let mut map = serializer.serialize_map(None)?;
+        if !parent.is_default() {
+            map.serialize_entry("parent", &parent)?;
+        }
+        if !path.is_default() {
+            map.serialize_entry("path", &path)?;
+        }
+        for (key, value) in entries {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+/// A key read with `deserialize_str`, so struct fields in `ron` are accepted.
+struct RecordKey(String);
+
+impl<'de> serde::Deserialize<'de> for RecordKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::Deserializer<'de> {
+        struct KeyVisitor;
+        impl Visitor<'_> for KeyVisitor {
+            type Value = RecordKey;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                formatter.write_str("a field name")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(RecordKey(v.to_owned()))
+            }
+        }
+        deserializer.deserialize_str(KeyVisitor)
+    }
+}
+
+/// Reads a record, with its value either in `value` or inline.
+struct RecordVisitor<V>(PhantomData<V>);
+
+impl<'de, V: SerializeValue> Visitor<'de> for RecordVisitor<V> {
+    type Value = PathedValue<V>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a record")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut parent = EntityPathUntagged::None;
+        let mut path = EntityPathUntagged::None;
+        let mut value = None;
+        let mut entries = Vec::new();
+        while let Some(RecordKey(key)) = map.next_key()? {
+            match key.as_str() {
+                "parent" => parent = map.next_value()?,
+                "path" => path = map.next_value()?,
+                "value" => value = Some(map.next_value()?),
+                _ => entries.push((key, map.next_value()?)),
+            }
+        }
+        let value = match value {
+            Some(value) => value,
+            None if entries.is_empty() => V::default(),
+            None => V::from_entries(entries)
+                .ok_or_else(|| serde::de::Error::custom("inline records are not supported by this format"))?,
+        };
+        Ok(PathedValue { parent: parent.into(), path: path.into(), value })
+    }
+}
+
 impl<'de, V: SerializeValue> serde::Deserialize<'de> for PathedValue<V> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::Deserializer<'de> {
         if deserializer.is_human_readable() {
-            let v: PathedValueSer<'_, V> = PathedValueSer::deserialize(deserializer)?;
-            Ok(Self { 
-                parent: v.parent.into(), 
-                path: v.path.into(), 
-                value: v.value.into_owned(), 
-            })
+            deserializer.deserialize_any(RecordVisitor(PhantomData))
         } else {
             let (parent, path, value) = <(EntityParent, EntityPath, V)>::deserialize(deserializer)?;
             Ok(Self { parent, path, value })
         }
     }
-}
\ No newline at end of file
+}
+/// Records of an entity in [`FormatLayout::EntityMajor`].
+///
+/// Written as a map of `parent`, `path` and `components` in human-readable formats,
+/// and as a tuple of them otherwise.
+struct EntityRecords<'t, V> {
+    parent: &'t EntityParent,
+    path: &'t EntityPath,
+    components: Vec<(&'t str, &'t V)>,
+}
+
+/// Group records by entity, type names are visited in order so the output is stable.
+fn entity_records<'t, K: AsRef<str> + Eq + Hash, V>(
+    components: &'t HashMap<K, Vec<PathedValue<V>>>
+) -> Vec<EntityRecords<'t, V>> {
+    let mut names: Vec<_> = components.iter().map(|(name, records)| (name.as_ref(), records)).collect();
+    names.sort_by_key(|(name, _)| *name);
+    let mut entities = Vec::new();
+    let mut index = HashMap::new();
+    for (name, records) in names {
+        for record in records {
+            let key = (&record.parent, &record.path);
+            let i = match index.get(&key) {
+                Some(i) if record.path != EntityPath::Unique => *i,
+                _ => {
+                    index.insert(key, entities.len());
+                    entities.push(EntityRecords { parent: &record.parent, path: &record.path, components: Vec::new() });
+                    entities.len() - 1
+                }
+            };
+            entities[i].components.push((name, &record.value));
+        }
+    }
+    entities
+}
+
+/// Components of an entity, duplicate keys are kept.
+struct EntityComponents<'a, 't, V>(&'a [(&'t str, &'t V)]);
+
+impl<V: SerializeValue> serde::Serialize for EntityComponents<'_, '_, V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        if serializer.is_human_readable() {
+            serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+        } else {
+            serializer.collect_seq(self.0.iter())
+        }
+    }
+}
+
+impl<V: SerializeValue> serde::Serialize for EntityRecords<'_, V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        if serializer.is_human_readable() {
+            use serde::ser::SerializeMap;
+            let parent = EntityPathUntagged::from(self.parent);
+            let path = EntityPathUntagged::from(self.path);
+            let mut map = serializer.serialize_map(None)?;
+            if !parent.is_default() {
+                map.serialize_entry("parent", &parent)?;
+            }
+            if !path.is_default() {
+                map.serialize_entry("path", &path)?;
+            }
+            map.serialize_entry("components", &EntityComponents(&self.components))?;
+            map.end()
+        } else {
+            use serde::ser::SerializeTuple;
+            let mut tuple = serializer.serialize_tuple(3)?;
+            tuple.serialize_element(self.parent)?;
+            tuple.serialize_element(self.path)?;
+            tuple.serialize_element(&EntityComponents(&self.components))?;
+            tuple.end()
+        }
+    }
+}
+
+/// Owned version of [`EntityRecords`] read when loading.
+struct EntityEntry<V> {
+    parent: EntityParent,
+    path: EntityPath,
+    components: Vec<(String, V)>,
+}
+
+/// Reads a map of type names to values, keeping duplicate keys.
+struct ComponentsVisitor<V>(PhantomData<V>);
+
+impl<'de, V: SerializeValue> Visitor<'de> for ComponentsVisitor<V> {
+    type Value = Vec<(String, V)>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a map of type names to values")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut components = Vec::new();
+        while let Some(RecordKey(key)) = map.next_key()? {
+            components.push((key, map.next_value()?));
+        }
+        Ok(components)
+    }
+}
+
+struct EntityComponentsDe<V>(Vec<(String, V)>);
+
+impl<'de, V: SerializeValue> serde::Deserialize<'de> for EntityComponentsDe<V> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::Deserializer<'de> {
+        deserializer.deserialize_map(ComponentsVisitor(PhantomData)).map(EntityComponentsDe)
+    }
+}
+
+/// Reads an entity of [`FormatLayout::EntityMajor`] in human-readable formats.
+struct EntityVisitor<V>(PhantomData<V>);
+
+impl<'de, V: SerializeValue> Visitor<'de> for EntityVisitor<V> {
+    type Value = EntityEntry<V>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an entity")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut parent = EntityPathUntagged::None;
+        let mut path = EntityPathUntagged::None;
+        let mut components = Vec::new();
+        while let Some(RecordKey(key)) = map.next_key()? {
+            match key.as_str() {
+                "parent" => parent = map.next_value()?,
+                "path" => path = map.next_value()?,
+                "components" => components = map.next_value::<EntityComponentsDe<V>>()?.0,
+                _ => { map.next_value::<IgnoredAny>()?; },
+            }
+        }
+        Ok(EntityEntry { parent: parent.into(), path: path.into(), components })
+    }
+}
+
+impl<'de, V: SerializeValue> serde::Deserialize<'de> for EntityEntry<V> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::Deserializer<'de> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_map(EntityVisitor(PhantomData))
+        } else {
+            let (parent, path, components) = <(EntityParent, EntityPath, Vec<(String, V)>)>::deserialize(deserializer)?;
+            Ok(Self { parent, path, components })
+        }
+    }
+}
+
+impl<K: AsRef<str> + Eq + Hash, V: SerializeValue> serde::Serialize for DocumentRef<'_, K, V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        use serde::ser::SerializeMap;
+        let len = 1 + self.components.len() + self.metadata.is_some() as usize
+            + self.delta.is_some() as usize + self.signatures.is_some() as usize
+            + self.fingerprint.is_some() as usize + self.versions.is_some() as usize
+            + self.aliases.is_some() as usize + self.tombstones.is_some() as usize
+            + self.attributes.is_some() as usize;
+        let len = match self.layout {
+            FormatLayout::TypeMajor => len,
+            FormatLayout::EntityMajor => len + 2 - self.components.len(),
+        };
+        let mut map = serializer.serialize_map(Some(len))?;
+        // Header goes first so it can be read without visiting the records,
+        // the wire version before any path.
+        map.serialize_entry(WIRE_KEY, &PathEncoding::current().version())?;
+        if let Some(metadata) = self.metadata {
+            map.serialize_entry(METADATA_KEY, metadata)?;
+        }
+        if let Some(delta) = self.delta {
+            map.serialize_entry(DELTA_KEY, delta)?;
+        }
+        if let Some(signatures) = self.signatures {
+            map.serialize_entry(SIGNATURES_KEY, signatures)?;
+        }
+        if let Some(fingerprint) = self.fingerprint {
+            map.serialize_entry(FINGERPRINT_KEY, fingerprint)?;
+        }
+        if let Some(versions) = self.versions {
+            map.serialize_entry(VERSIONS_KEY, versions)?;
+        }
+        if let Some(aliases) = self.aliases {
+            map.serialize_entry(ALIASES_KEY, aliases)?;
+        }
+        if let Some(tombstones) = self.tombstones {
+            map.serialize_entry(TOMBSTONES_KEY, tombstones)?;
+        }
+        if let Some(attributes) = self.attributes {
+            map.serialize_entry(ATTRIBUTES_KEY, attributes)?;
+        }
+        if self.layout == FormatLayout::EntityMajor {
+            map.serialize_entry(LAYOUT_KEY, &self.layout)?;
+            map.serialize_entry(ENTITIES_KEY, &entity_records(self.components))?;
+            return map.end();
+        }
+        // Types are written in order for a stable output.
+        let mut components: Vec<_> = self.components.iter().collect();
+        components.sort_by(|(a, _), (b, _)| a.as_ref().cmp(b.as_ref()));
+        for (name, records) in components {
+            if self.inline.is_some_and(|inline| inline.contains(name.as_ref())) {
+                map.serialize_entry(name.as_ref(), &InlineRecords(records))?;
+            } else {
+                map.serialize_entry(name.as_ref(), records)?;
+            }
+        }
+        map.end()
+    }
+}
+
+struct DocumentVisitor<V>(PhantomData<V>);
+
+impl<'de, V: SerializeValue> Visitor<'de> for DocumentVisitor<V> {
+    type Value = Document<V>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a map of type names to records")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut document = Document::default();
+        let mut encoding = PathEncoding::current();
+        let mut _scope = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == WIRE_KEY {
+                encoding = encoding_of(map.next_value()?).map_err(serde::de::Error::custom)?;
+                _scope = Some(DecodingScope::enter(encoding));
+            } else if key == METADATA_KEY {
+                document.metadata = Some(map.next_value()?);
+            } else if key == DELTA_KEY {
+                document.delta = Some(map.next_value()?);
+            } else if key == SIGNATURES_KEY {
+                document.signatures = Some(map.next_value()?);
+            } else if key == FINGERPRINT_KEY {
+                document.fingerprint = Some(map.next_value()?);
+            } else if key == VERSIONS_KEY {
+                document.versions = Some(map.next_value()?);
+            } else if key == ALIASES_KEY {
+                document.aliases = Some(map.next_value()?);
+            } else if key == TOMBSTONES_KEY {
+                document.tombstones = Some(map.next_value()?);
+            } else if key == ATTRIBUTES_KEY {
+                document.attributes = Some(map.next_value()?);
+            } else if key == LAYOUT_KEY {
+                document.layout = map.next_value()?;
+            } else if key == ENTITIES_KEY {
+                for EntityEntry { parent, path, components } in map.next_value::<Vec<EntityEntry<V>>>()? {
+                    for (name, value) in components {
+                        let record = PathedValue { parent: parent.clone(), path: path.clone(), value };
+                        document.components.entry(name).or_default().push(record);
+                    }
+                }
+            } else {
+                document.components.entry(key).or_default().extend(map.next_value::<Vec<_>>()?);
+            }
+        }
+        upgrade_document(&mut document, encoding);
+        Ok(document)
+    }
+}
+
+impl<'de, V: SerializeValue> serde::Deserialize<'de> for Document<V> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::Deserializer<'de> {
+        deserializer.deserialize_map(DocumentVisitor(PhantomData))
+    }
+}
+
+/// Reads only the metadata header of a document.
+pub(crate) struct PeekMetadata<V>(pub(crate) Option<V>);
+
+struct PeekVisitor<V>{
+    human_readable: bool,
+    p: PhantomData<V>,
+}
+
+impl<'de, V: SerializeValue> Visitor<'de> for PeekVisitor<V> {
+    type Value = PeekMetadata<V>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a map of type names to records")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut metadata = None;
+        while let Some(key) = map.next_key::<Cow<str>>()? {
+            if key == METADATA_KEY {
+                metadata = Some(map.next_value()?);
+                // Binary formats do not check for trailing input, stop early.
+                if !self.human_readable {
+                    break;
+                }
+            } else if self.human_readable {
+                map.next_value::<IgnoredAny>()?;
+            } else if key == WIRE_KEY {
+                map.next_value::<u32>()?;
+            } else if key == DELTA_KEY {
+                map.next_value::<DeltaHeader>()?;
+            } else if key == SIGNATURES_KEY {
+                map.next_value::<Signatures>()?;
+            } else if key == FINGERPRINT_KEY {
+                map.next_value::<Fingerprint>()?;
+            } else if key == VERSIONS_KEY {
+                map.next_value::<Versions>()?;
+            } else if key == ALIASES_KEY {
+                map.next_value::<Aliases>()?;
+            } else if key == TOMBSTONES_KEY {
+                map.next_value::<Tombstones>()?;
+            } else if key == ATTRIBUTES_KEY {
+                map.next_value::<AttributeTable>()?;
+            } else if key == LAYOUT_KEY {
+                map.next_value::<FormatLayout>()?;
+            } else if key == ENTITIES_KEY {
+                map.next_value::<Vec<EntityEntry<V>>>()?;
+            } else {
+                map.next_value::<Vec<PathedValue<V>>>()?;
+            }
+        }
+        Ok(PeekMetadata(metadata))
+    }
+}
+
+impl<'de, V: SerializeValue> serde::Deserialize<'de> for PeekMetadata<V> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::Deserializer<'de> {
+        let human_readable = deserializer.is_human_readable();
+        deserializer.deserialize_map(PeekVisitor { human_readable, p: PhantomData })
+    }
+}
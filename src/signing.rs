@@ -0,0 +1,173 @@
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
+use bevy_ecs::system::{Res, ResMut, Resource};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+
+use crate::methods::{SerializationMethod, SerializeValue};
+use crate::saveload::{DeltaHeader, PathedValue, Signatures, ValueOf};
+use crate::{DeserializeContext, EntityPath, Marker, SaloError, SaloErrors, SaloRegistry, SerializeContext};
+
+/// Signs sections of the output with HMAC-SHA256, unique per marker,
+/// i.e. to verify base game data while accepting unsigned data of mods.
+///
+/// Sections are defined by [`SaloRegistry::section_of`]. When saving, each signed section
+/// is signed even if empty. When loading, a signed section that is unsigned or modified fails
+/// with [`SaloError::InvalidSignature`], other sections are not checked.
+///
+/// Each signature also covers the metadata and the removed items of the section in a delta save.
+#[derive(Clone, Resource)]
+pub struct SectionSigning<M: Marker> {
+    key: [u8; 32],
+    sections: BTreeSet<Cow<'static, str>>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> std::fmt::Debug for SectionSigning<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SectionSigning")
+            .field("key", &"<redacted>")
+            .field("sections", &self.sections)
+            .finish()
+    }
+}
+
+/// Signed content of a section.
+#[derive(Serialize)]
+#[serde(bound = "V: SerializeValue")]
+struct Signed<'t, V> {
+    section: &'t str,
+    metadata: Option<&'t V>,
+    base: Option<&'t str>,
+    removed: Vec<(&'t str, &'t Vec<EntityPath>)>,
+    records: Vec<(&'t str, &'t Vec<PathedValue<V>>)>,
+}
+
+impl<M: Marker> SectionSigning<M> {
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            key,
+            sections: BTreeSet::new(),
+            p: PhantomData,
+        }
+    }
+
+    /// Sign and verify a section.
+    pub fn with_section(mut self, section: impl Into<Cow<'static, str>>) -> Self {
+        self.sections.insert(section.into());
+        self
+    }
+
+    pub fn sections(&self) -> impl Iterator<Item = &str> {
+        self.sections.iter().map(|s| s.as_ref())
+    }
+
+    /// HMAC of the metadata, removed items and records of a section.
+    fn mac<'t, K: AsRef<str> + 't>(
+        &self,
+        section: &'t str,
+        registry: &SaloRegistry<M>,
+        metadata: Option<&'t ValueOf<M>>,
+        delta: Option<&'t DeltaHeader>,
+        components: impl IntoIterator<Item = (&'t K, &'t Vec<PathedValue<ValueOf<M>>>)>,
+    ) -> anyhow::Result<Hmac<Sha256>> {
+        let mut records: Vec<_> = components.into_iter()
+            .map(|(name, records)| (name.as_ref(), records))
+            .filter(|(name, _)| registry.section_of(name) == section)
+            .collect();
+        records.sort_by_key(|(name, _)| *name);
+        let removed = delta.iter()
+            .flat_map(|d| d.removed.iter())
+            .map(|(name, paths)| (name.as_str(), paths))
+            .filter(|(name, _)| registry.section_of(name) == section)
+            .collect();
+        let bytes = M::Method::serialize_bytes(&Signed {
+            section,
+            metadata,
+            base: delta.map(|d| d.base.as_str()),
+            removed,
+            records,
+        })?;
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.key)?;
+        mac.update(&bytes);
+        Ok(mac)
+    }
+}
+
+/// Write signatures of signed sections.
+pub(crate) fn sign_sections<M: Marker>(
+    signing: Option<Res<SectionSigning<M>>>,
+    registry: Res<SaloRegistry<M>>,
+    mut ctx: ResMut<SerializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    let Some(signing) = signing else { return };
+    let mut signatures = Signatures::new();
+    for section in signing.sections() {
+        let ctx = &*ctx;
+        match signing.mac(section, &registry, ctx.metadata.as_ref(), ctx.delta.as_ref(), ctx.components.iter()) {
+            Ok(mac) => {
+                let signature = mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+                signatures.insert(section.to_owned(), signature);
+            },
+            Err(e) => {
+                errors.push(SaloError::Serialization(e));
+                return;
+            },
+        }
+    }
+    ctx.signatures = Some(signatures);
+}
+
+/// Check signatures of signed sections.
+pub(crate) fn verify_sections<M: Marker>(
+    signing: Option<Res<SectionSigning<M>>>,
+    registry: Res<SaloRegistry<M>>,
+    ctx: Res<DeserializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    let Some(signing) = signing else { return };
+    if !errors.is_empty() {
+        return;
+    }
+    for section in signing.sections() {
+        let found = ctx.signatures.as_ref().and_then(|s| s.get(section)).and_then(|s| from_hex(s));
+        match signing.mac(section, &registry, ctx.metadata.as_ref(), ctx.delta.as_ref(), ctx.components.iter()) {
+            Ok(mac) => {
+                // Compared in constant time.
+                if !found.is_some_and(|found| mac.verify_slice(&found).is_ok()) {
+                    errors.push(SaloError::InvalidSignature(section.to_owned()));
+                }
+            },
+            Err(e) => errors.push(SaloError::Deserialization(e)),
+        }
+    }
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len()).step_by(2)
+        .map(|i| s.get(i..i + 2).and_then(|b| u8::from_str_radix(b, 16).ok()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_hex, SectionSigning};
+
+    #[test]
+    fn hex_and_debug() {
+        assert_eq!(from_hex("00ff7a"), Some(vec![0, 255, 122]));
+        assert_eq!(from_hex("0"), None);
+        assert_eq!(from_hex("zz"), None);
+        let signing = SectionSigning::<crate::All<crate::methods::SerdeJson>>::new([7; 32]).with_section("base");
+        let debug = format!("{:?}", signing);
+        assert!(debug.contains("redacted") && debug.contains("base"));
+        assert!(!debug.contains("7, 7"));
+    }
+}
@@ -0,0 +1,109 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use bevy_ecs::system::{Res, ResMut, Resource};
+
+use crate::methods::SerializationMethod;
+use crate::saveload::{join_path, DocumentRef, EntityParent, PathedValueOf};
+use crate::{EntityPath, FileWriteOptions, Marker, SaloError, SaloErrors, SaloHistory, SaveEncryptionKey, SerializeContext};
+
+type FileName = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Resource that maps paths of records to files, see
+/// [`save_split`](crate::SaveLoadExtension::save_split).
+#[derive(Resource)]
+pub(crate) struct SplitOutput<M: Marker> {
+    file_name: FileName,
+    pub(crate) written: Vec<String>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> SplitOutput<M> {
+    pub(crate) fn new(file_name: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        Self { file_name: Arc::new(file_name), written: Vec::new(), p: PhantomData }
+    }
+}
+
+/// Path of the nearest named ancestor of each unnamed entity, empty at the root.
+fn unnamed_keys<M: Marker>(components: &HashMap<Cow<'static, str>, Vec<PathedValueOf<M>>>) -> HashMap<u64, String> {
+    let parents: HashMap<u64, &EntityParent> = components.values().flatten()
+        .filter_map(|r| match r.path {
+            EntityPath::Entity(e) => Some((e, &r.parent)),
+            _ => None,
+        })
+        .collect();
+    let mut keys = HashMap::new();
+    for entity in parents.keys() {
+        let mut chain = vec![*entity];
+        let key = loop {
+            let current = *chain.last().unwrap();
+            if let Some(key) = keys.get(&current) {
+                break String::clone(key);
+            }
+            match parents.get(&current) {
+                Some(EntityParent::Path(path)) => break join_path(path),
+                Some(EntityParent::Entity(parent)) if !chain.contains(parent) => chain.push(*parent),
+                _ => break String::new(),
+            }
+        };
+        for entity in chain {
+            keys.insert(entity, key.clone());
+        }
+    }
+    keys
+}
+
+/// Write records to files by the path of their entity or its nearest named ancestor.
+pub(crate) fn write_split<M: Marker>(
+    output: Option<ResMut<SplitOutput<M>>>,
+    key: Option<Res<SaveEncryptionKey<M>>>,
+    data: Res<SerializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+    mut history: ResMut<SaloHistory<M>>,
+) {
+    let Some(mut output) = output else { return };
+    let key = key.as_ref().map(|k| k.get());
+    let unnamed = unnamed_keys::<M>(&data.components);
+    let mut files: BTreeMap<String, HashMap<Cow<'static, str>, Vec<PathedValueOf<M>>>> = BTreeMap::new();
+    for (type_name, records) in data.components.iter() {
+        for record in records {
+            let path = match (&record.path, &record.parent) {
+                (EntityPath::Path(path), _) => join_path(path),
+                (EntityPath::Entity(e), _) => unnamed.get(e).cloned().unwrap_or_default(),
+                (EntityPath::Unique, EntityParent::Path(path)) => join_path(path),
+                (EntityPath::Unique, EntityParent::Entity(e)) => unnamed.get(e).cloned().unwrap_or_default(),
+                (EntityPath::Unique, EntityParent::Root) => String::new(),
+            };
+            files.entry((output.file_name)(&path)).or_default()
+                .entry(type_name.clone()).or_default()
+                .push(record.clone());
+        }
+    }
+    let result = files.iter().try_fold(0, |size, (file, components)| {
+        // Signatures cover the whole save and are not written.
+        let bytes = M::Method::serialize_bytes_keyed(&DocumentRef {
+            metadata: data.metadata.as_ref(),
+            delta: data.delta.as_ref(),
+            signatures: None,
+            fingerprint: data.fingerprint.as_ref(),
+            versions: (!data.versions.is_empty()).then_some(&data.versions),
+            aliases: (!data.aliases.is_empty()).then_some(&data.aliases),
+            tombstones: (!data.tombstones.is_empty()).then_some(&data.tombstones),
+            attributes: (!data.attributes.is_empty()).then_some(&data.attributes),
+            layout: data.layout,
+            components,
+            inline: Some(&data.inline),
+        }, key)?;
+        FileWriteOptions::default().write(file, |file| Ok(std::fs::write(file, &bytes)?))?;
+        anyhow::Ok(size + bytes.len())
+    });
+    match result {
+        Ok(size) => {
+            history.size = Some(size);
+            output.written = files.into_keys().collect();
+        },
+        Err(e) => errors.push(SaloError::serialization(e)),
+    }
+}
@@ -0,0 +1,222 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use bevy_ecs::change_detection::{DetectChanges, Ref};
+use bevy_ecs::component::Tick;
+use bevy_ecs::system::{Query, Res, ResMut, Resource, SystemChangeTick};
+use bevy_utils::Instant;
+
+use crate::document::SnapshotScope;
+use crate::import::TypeRecords;
+use crate::{Marker, SaveLoad, SaveLoadBundle, SaveLoadRes, SaloErrors, SaveFilter, TypeFilter, SaveDelta, EntityPath, AnonymizePolicy, ResourcesOnly, SaloRegistry};
+use crate::{SerializeContext, DeserializeContext};
+use crate::saveload::named_paths;
+
+/// Tracks changes of registered types since the last save or load, unique per marker.
+///
+/// Updated by [`TrackSchedule`](crate::schedules::TrackSchedule), which runs in `Last` with `bevy_app`
+/// and before [`has_unsaved_changes`](crate::SaveLoadExtension::has_unsaved_changes).
+#[derive(Debug, Resource)]
+pub struct SaveState<M: Marker> {
+    last_saved: Option<Instant>,
+    dirty: bool,
+    tick: Tick,
+    counts: HashMap<Cow<'static, str>, usize>,
+    pending: HashMap<Cow<'static, str>, usize>,
+    paths: HashMap<String, HashSet<EntityPath>>,
+    p: PhantomData<M>,
+}
+
+impl<M: Marker> Default for SaveState<M> {
+    fn default() -> Self {
+        Self {
+            last_saved: None,
+            dirty: false,
+            tick: Tick::new(0),
+            counts: HashMap::new(),
+            pending: HashMap::new(),
+            paths: HashMap::new(),
+            p: PhantomData,
+        }
+    }
+}
+
+impl<M: Marker> SaveState<M> {
+    /// Time of the last complete save.
+    pub fn last_saved(&self) -> Option<Instant> {
+        self.last_saved
+    }
+
+    /// Returns true if registered types changed since the last save or load.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    fn is_stale(&self, name: &str, count: usize) -> bool {
+        self.counts.get(name).copied().unwrap_or(0) != count
+    }
+
+    /// Tick of the last complete save or load.
+    pub(crate) fn tick(&self) -> Tick {
+        self.tick
+    }
+
+    /// Named paths written in the last complete save or load, by type name.
+    pub(crate) fn paths(&self) -> &HashMap<String, HashSet<EntityPath>> {
+        &self.paths
+    }
+}
+
+/// Run condition, true if the save contains everything.
+pub(crate) fn is_complete<M: Marker>(
+    errors: Res<SaloErrors<M>>,
+    filter: Option<Res<SaveFilter<M>>>,
+    delta: Option<Res<SaveDelta<M>>>,
+    anonymize: Option<Res<AnonymizePolicy<M>>>,
+    resources_only: Option<Res<ResourcesOnly<M>>>,
+    types: Option<Res<TypeFilter<M>>>,
+    snapshot: Option<Res<SnapshotScope<M>>>,
+) -> bool {
+    errors.is_empty() && filter.is_none() && delta.is_none() && anonymize.is_none()
+        && resources_only.is_none() && types.is_none() && snapshot.is_none()
+}
+
+/// Run condition, true if entities are saved or loaded, see [`ResourcesOnly`].
+pub(crate) fn with_entities<M: Marker>(resources_only: Option<Res<ResourcesOnly<M>>>) -> bool {
+    resources_only.is_none()
+}
+
+/// Run condition, true if a type is active, see [`register_if`](crate::SaveLoadPlugin::register_if).
+pub(crate) fn is_active<M: Marker, T: SaveLoad>(registry: Res<SaloRegistry<M>>) -> bool {
+    registry.is_active(&T::type_name())
+}
+
+/// Run condition, true if changes can still be detected, tracking stops once dirty until the next save or load.
+#[cfg(feature="bevy_app")]
+pub(crate) fn is_clean<M: Marker>(state: Option<Res<SaveState<M>>>) -> bool {
+    state.is_some_and(|state| !state.is_dirty())
+}
+
+/// Run condition, true if no errors are recorded.
+pub(crate) fn no_errors<M: Marker>(errors: Res<SaloErrors<M>>) -> bool {
+    errors.is_empty()
+}
+
+/// Run condition, true if the load replaces everything, i.e. not a delta.
+pub(crate) fn is_complete_load<M: Marker>(
+    errors: Res<SaloErrors<M>>,
+    ctx: Res<DeserializeContext<M>>,
+    resources_only: Option<Res<ResourcesOnly<M>>>,
+    type_records: Option<Res<TypeRecords<M>>>,
+    snapshot: Option<Res<SnapshotScope<M>>>,
+) -> bool {
+    errors.is_empty() && ctx.delta.is_none() && resources_only.is_none() && type_records.is_none()
+        && snapshot.is_none()
+}
+
+/// Run condition, true if all types are loaded, see [`TypeRecords`].
+pub(crate) fn all_types<M: Marker>(type_records: Option<Res<TypeRecords<M>>>) -> bool {
+    type_records.is_none()
+}
+
+/// Record the number of items of a type.
+pub(crate) fn record_count<M: Marker, T: SaveLoad>(
+    mut state: ResMut<SaveState<M>>,
+    query: Query<(), (bevy_ecs::query::With<T>, M::Query)>,
+) {
+    state.pending.insert(T::type_name(), query.iter().count());
+}
+
+/// Record the number of items of a bundle.
+pub(crate) fn record_bundle<M: Marker, T: SaveLoadBundle>(
+    mut state: ResMut<SaveState<M>>,
+    query: Query<(), (T::Query, M::Query)>,
+) {
+    state.pending.insert(T::type_name(), query.iter().count());
+}
+
+/// Record the existence of a resource.
+pub(crate) fn record_resource<M: Marker, T: SaveLoadRes>(
+    mut state: ResMut<SaveState<M>>,
+    res: Option<Res<T>>,
+) {
+    state.pending.insert(T::type_name(), res.is_some() as usize);
+}
+
+pub(crate) fn finish_save<M: Marker>(
+    mut state: ResMut<SaveState<M>>,
+    ctx: Res<SerializeContext<M>>,
+    tick: SystemChangeTick,
+) {
+    state.last_saved = Some(Instant::now());
+    state.paths = named_paths(ctx.components.iter());
+    finish(&mut state, tick);
+}
+
+pub(crate) fn finish_load<M: Marker>(
+    mut state: ResMut<SaveState<M>>,
+    mut ctx: ResMut<DeserializeContext<M>>,
+    tick: SystemChangeTick,
+) {
+    state.paths = std::mem::take(&mut ctx.loaded);
+    finish(&mut state, tick);
+}
+
+fn finish<M: Marker>(state: &mut SaveState<M>, tick: SystemChangeTick) {
+    state.counts = std::mem::take(&mut state.pending);
+    state.tick = tick.this_run();
+    state.dirty = false;
+}
+
+pub(crate) fn track_changes<M: Marker, T: SaveLoad>(
+    mut state: ResMut<SaveState<M>>,
+    query: Query<Ref<T>, M::Query>,
+    tick: SystemChangeTick,
+) {
+    if state.dirty {
+        return;
+    }
+    let mut count = 0;
+    for item in query.iter() {
+        if item.last_changed().is_newer_than(state.tick, tick.this_run()) {
+            state.dirty = true;
+            return;
+        }
+        count += 1;
+    }
+    if state.is_stale(&T::type_name(), count) {
+        state.dirty = true;
+    }
+}
+
+pub(crate) fn track_resource<M: Marker, T: SaveLoadRes>(
+    mut state: ResMut<SaveState<M>>,
+    res: Option<Res<T>>,
+    tick: SystemChangeTick,
+) {
+    if state.dirty {
+        return;
+    }
+    let changed = match &res {
+        Some(res) => res.last_changed().is_newer_than(state.tick, tick.this_run()),
+        None => false,
+    };
+    if changed || state.is_stale(&T::type_name(), res.is_some() as usize) {
+        state.dirty = true;
+    }
+}
+
+/// Bundles have no change ticks, only additions and removals are tracked.
+pub(crate) fn track_bundle<M: Marker, T: SaveLoadBundle>(
+    mut state: ResMut<SaveState<M>>,
+    query: Query<(), (T::Query, M::Query)>,
+) {
+    if !state.dirty && state.is_stale(&T::type_name(), query.iter().count()) {
+        state.dirty = true;
+    }
+}
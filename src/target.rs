@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use bevy_ecs::system::Resource;
+
+use crate::Marker;
+
+/// Where [`save`](crate::SaveLoadExtension::save) writes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveTarget<'t> {
+    /// Write to a file, requires the `fs` feature.
+    File(&'t str),
+    /// Return the output as [`Saved::Bytes`].
+    Bytes,
+    /// Return the output as [`Saved::String`], only works with human-readable formats.
+    String,
+    /// Write to a key in [`SaloStorage`].
+    Storage(&'t str),
+}
+
+/// Where [`load`](crate::SaveLoadExtension::load) reads from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadSource<'t> {
+    /// Read from a file, requires the `fs` feature.
+    File(&'t str),
+    Bytes(&'t [u8]),
+    /// Parse a string, only works with human-readable formats.
+    String(&'t str),
+    /// Read from a key in [`SaloStorage`].
+    Storage(&'t str),
+}
+
+/// Output of [`save`](crate::SaveLoadExtension::save), matches the [`SaveTarget`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Saved {
+    File,
+    Bytes(Vec<u8>),
+    String(String),
+    Storage,
+}
+
+impl Saved {
+    pub fn into_bytes(self) -> Option<Vec<u8>> {
+        match self {
+            Saved::Bytes(bytes) => Some(bytes),
+            Saved::String(string) => Some(string.into_bytes()),
+            _ => None,
+        }
+    }
+
+    pub fn into_string(self) -> Option<String> {
+        match self {
+            Saved::String(string) => Some(string),
+            _ => None,
+        }
+    }
+}
+
+/// Outputs of one run of [`save_outputs`](crate::SaveLoadExtension::save_outputs),
+/// the world is serialized once and written to each of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutputSpec {
+    pub(crate) file: Option<String>,
+    pub(crate) bytes: bool,
+    pub(crate) string: bool,
+    pub(crate) storage: Vec<String>,
+}
+
+impl OutputSpec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write to a file, requires the `fs` feature.
+    pub fn file(file: impl Into<String>) -> Self {
+        Self::new().and_file(file)
+    }
+
+    pub fn bytes() -> Self {
+        Self::new().and_bytes()
+    }
+
+    /// Only works with human-readable formats.
+    pub fn string() -> Self {
+        Self::new().and_string()
+    }
+
+    /// Write to a key in [`SaloStorage`].
+    pub fn storage(key: impl Into<String>) -> Self {
+        Self::new().and_storage(key)
+    }
+
+    /// Write to a file, replaces the previous file.
+    pub fn and_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// Return the output as [`SavedOutputs::bytes`].
+    pub fn and_bytes(mut self) -> Self {
+        self.bytes = true;
+        self
+    }
+
+    /// Return the output as [`SavedOutputs::string`].
+    pub fn and_string(mut self) -> Self {
+        self.string = true;
+        self
+    }
+
+    /// Also write to a key in [`SaloStorage`].
+    pub fn and_storage(mut self, key: impl Into<String>) -> Self {
+        self.storage.push(key.into());
+        self
+    }
+}
+
+/// Output of [`save_outputs`](crate::SaveLoadExtension::save_outputs), matches the [`OutputSpec`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SavedOutputs {
+    pub bytes: Option<Vec<u8>>,
+    pub string: Option<String>,
+}
+
+/// A key value store of saves, i.e. browser local storage or a platform save API.
+pub trait SaveStorage: Send + Sync + 'static {
+    fn read(&self, key: &str) -> anyhow::Result<Vec<u8>>;
+    fn write(&mut self, key: &str, bytes: &[u8]) -> anyhow::Result<()>;
+}
+
+/// A [`SaveStorage`] in memory, useful for testing.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage(HashMap<String, Vec<u8>>);
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&[u8]> {
+        self.0.get(key).map(|v| v.as_slice())
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Vec<u8>> {
+        self.0.remove(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(|k| k.as_str())
+    }
+}
+
+impl SaveStorage for MemoryStorage {
+    fn read(&self, key: &str) -> anyhow::Result<Vec<u8>> {
+        match self.0.get(key) {
+            Some(bytes) => Ok(bytes.clone()),
+            None => anyhow::bail!("Key {:?} not found in storage.", key),
+        }
+    }
+
+    fn write(&mut self, key: &str, bytes: &[u8]) -> anyhow::Result<()> {
+        self.0.insert(key.to_owned(), bytes.to_vec());
+        Ok(())
+    }
+}
+
+/// Resource that contains the [`SaveStorage`] used by [`SaveTarget::Storage`]
+/// and [`LoadSource::Storage`], unique per marker.
+#[derive(Resource)]
+pub struct SaloStorage<M: Marker>(Box<dyn SaveStorage>, PhantomData<M>);
+
+impl<M: Marker> SaloStorage<M> {
+    pub fn new(storage: impl SaveStorage) -> Self {
+        SaloStorage(Box::new(storage), PhantomData)
+    }
+
+    pub fn get(&self) -> &dyn SaveStorage {
+        self.0.as_ref()
+    }
+
+    pub fn get_mut(&mut self) -> &mut dyn SaveStorage {
+        self.0.as_mut()
+    }
+}
+
+impl<M: Marker> std::fmt::Debug for SaloStorage<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SaloStorage(..)")
+    }
+}
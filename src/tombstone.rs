@@ -0,0 +1,70 @@
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use bevy_ecs::system::{Commands, Res, ResMut, Resource};
+
+use crate::saveload::{named_paths, Document};
+use crate::{DeserializeContext, EntityPath, Marker, SaveFilter, SerializeContext, TypeFilter};
+
+/// Named paths of despawned entities in the header of a save.
+pub(crate) type Tombstones = Vec<EntityPath>;
+
+/// Named paths of the previous save by type name, inserted by
+/// [`save_with_tombstones`](crate::SaveLoadExtension::save_with_tombstones).
+#[derive(Debug, Resource)]
+pub(crate) struct TombstoneBase<M: Marker>(HashMap<String, HashSet<EntityPath>>, PhantomData<M>);
+
+impl<M: Marker> TombstoneBase<M> {
+    pub(crate) fn new<V>(document: &Document<V>) -> Self {
+        TombstoneBase(named_paths(document.components.iter()), PhantomData)
+    }
+}
+
+/// Record paths of the previous save whose entities were despawned.
+///
+/// Paths of live entities and paths only saved with types excluded by [`TypeFilter`] are skipped,
+/// nothing is written if a [`SaveFilter`] limits the save to some entities.
+pub(crate) fn build_tombstones<M: Marker>(
+    base: Option<Res<TombstoneBase<M>>>,
+    filter: Option<Res<SaveFilter<M>>>,
+    types: Option<Res<TypeFilter<M>>>,
+    mut ctx: ResMut<SerializeContext<M>>,
+) {
+    let Some(base) = base else { return };
+    if filter.is_some_and(|f| f.is_partial()) {
+        return;
+    }
+    let mut present: HashSet<_> = named_paths(ctx.components.iter()).into_values().flatten().collect();
+    present.extend(ctx.paths.values().map(|path| EntityPath::Path(path.clone())));
+    let mut tombstones: Vec<_> = base.0.iter()
+        .filter(|(type_name, _)| types.as_ref().map_or(true, |t| t.contains(type_name)))
+        .flat_map(|(_, paths)| paths)
+        .filter(|path| !present.contains(path))
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    tombstones.sort_by_cached_key(|path| path.to_string());
+    ctx.tombstones = tombstones;
+}
+
+/// Despawn entities matching tombstones of the loaded save.
+pub(crate) fn apply_tombstones<M: Marker>(mut commands: Commands, mut ctx: ResMut<DeserializeContext<M>>) {
+    for path in std::mem::take(&mut ctx.tombstones) {
+        let path = ctx.remapped(&path);
+        if let Some(entity) = ctx.path_map.remove(&path) {
+            despawn(&mut commands, entity);
+        }
+    }
+}
+
+#[cfg(feature="hierarchy")]
+fn despawn(commands: &mut Commands, entity: bevy_ecs::entity::Entity) {
+    use bevy_hierarchy::DespawnRecursiveExt;
+    commands.entity(entity).despawn_recursive();
+}
+
+#[cfg(not(feature="hierarchy"))]
+fn despawn(commands: &mut Commands, entity: bevy_ecs::entity::Entity) {
+    commands.entity(entity).despawn();
+}
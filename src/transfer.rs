@@ -0,0 +1,196 @@
+use std::marker::PhantomData;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::ReadOnlyWorldQuery;
+use bevy_ecs::system::{Query, Res, ResMut, Resource, RunSystemOnce};
+use bevy_ecs::world::World;
+use smol_str::SmolStr;
+
+use crate::document::{save_value, load_value};
+use crate::saveload::{split_path, EntityParent};
+use crate::{DeserializeContext, EntityPath, Marker, SaloError, SaveFilter};
+
+/// Rewrites path prefixes when loading, unique per marker, i.e. `level1::Doors` to `level2::Doors`.
+///
+/// Applies to record paths, parents and entity references, the first matching rule is used.
+#[derive(Debug, Clone, Resource)]
+pub struct PathRemap<M: Marker>(Vec<(Vec<SmolStr>, Vec<SmolStr>)>, PhantomData<M>);
+
+impl<M: Marker> Default for PathRemap<M> {
+    fn default() -> Self {
+        PathRemap(Vec::new(), PhantomData)
+    }
+}
+
+impl<M: Marker> PathRemap<M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the prefix `from` with `to`, both are `::` delimited paths.
+    ///
+    /// An empty `to` removes the prefix, an empty `from` prepends `to` to all paths.
+    pub fn with(mut self, from: &str, to: &str) -> Self {
+        self.insert(from, to);
+        self
+    }
+
+    pub fn insert(&mut self, from: &str, to: &str) {
+        self.0.push((segments(from), segments(to)));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Remap a path, returns `None` if no rule matches.
+    pub fn apply(&self, path: &EntityPath) -> Option<EntityPath> {
+        let EntityPath::Path(segments) = path else { return None };
+        remap(&self.0, segments).map(EntityPath::Path)
+    }
+}
+
+pub(crate) fn segments(path: &str) -> Vec<SmolStr> {
+    if path.is_empty() {
+        Vec::new()
+    } else {
+        split_path(path)
+    }
+}
+
+pub(crate) fn remap(rules: &[(Vec<SmolStr>, Vec<SmolStr>)], segments: &[SmolStr]) -> Option<Vec<SmolStr>> {
+    rules.iter().find(|(from, _)| segments.starts_with(from)).map(|(from, to)| {
+        to.iter().chain(&segments[from.len()..]).cloned().collect()
+    })
+}
+
+/// Rewrite paths of loaded records and store the rules for entity references.
+pub(crate) fn apply_remap<M: Marker>(remap: Option<Res<PathRemap<M>>>, mut ctx: ResMut<DeserializeContext<M>>) {
+    let Some(rules) = remap.map(|r| r.0.clone()) else { return };
+    rewrite_paths(&mut ctx, &rules);
+    ctx.remap = rules;
+}
+
+/// Rewrite record paths, parents and removed paths of a delta with prefix rules.
+pub(crate) fn rewrite_paths<M: Marker>(ctx: &mut DeserializeContext<M>, rules: &[(Vec<SmolStr>, Vec<SmolStr>)]) {
+    for record in ctx.components.values_mut().flatten() {
+        if let EntityPath::Path(segments) = &record.path {
+            if let Some(path) = remap(rules, segments) {
+                record.path = EntityPath::Path(path);
+            }
+        }
+        if let EntityParent::Path(segments) = &record.parent {
+            if let Some(path) = remap(rules, segments) {
+                record.parent = EntityParent::Path(path);
+            }
+        }
+    }
+    if let Some(delta) = ctx.delta.as_mut() {
+        for path in delta.removed.values_mut().flatten() {
+            if let EntityPath::Path(segments) = path {
+                if let Some(remapped) = remap(rules, segments) {
+                    *segments = remapped;
+                }
+            }
+        }
+    }
+}
+
+impl<M: Marker> DeserializeContext<M> {
+    /// Apply [`PathAliases`](crate::PathAliases), [`PathRemap`] and [`PathRoot`](crate::PathRoot)
+    /// to an entity reference.
+    pub(crate) fn remapped(&self, path: &EntityPath) -> EntityPath {
+        match path {
+            EntityPath::Path(segments) => {
+                let aliased = remap(&self.aliases, segments);
+                let segments = aliased.as_deref().unwrap_or(segments);
+                let remapped = remap(&self.remap, segments);
+                let segments = remapped.as_deref().unwrap_or(segments);
+                EntityPath::Path(self.root.iter().chain(segments).cloned().collect())
+            },
+            _ => path.clone(),
+        }
+    }
+}
+
+/// Options of [`transfer`].
+#[derive(Debug, Clone, Default)]
+pub struct TransferOptions {
+    remap: Vec<(String, String)>,
+    despawn: bool,
+}
+
+impl TransferOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the path prefix `from` with `to` in the target world, see [`PathRemap`].
+    pub fn with_remap(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.remap.push((from.into(), to.into()));
+        self
+    }
+
+    /// Despawn transferred entities in the source world, default is `false`.
+    pub fn with_despawn(mut self, despawn: bool) -> Self {
+        self.despawn = despawn;
+        self
+    }
+}
+
+/// Capture entities with marker `M` matching the query filter `F` in `from`
+/// and apply them to `to`, i.e. for level streaming with multiple worlds.
+///
+/// Both worlds need the plugin of `M`. Named entities are matched by path in `to`,
+/// references to entities that are not transferred are spawned as new entities.
+/// Nothing is changed if the capture fails.
+///
+/// ```
+/// # use bevy_salo::*;
+/// # use bevy_ecs::{world::World, query::With, component::Component};
+/// # #[derive(Component)] struct Level1;
+/// # let mut world1 = World::new();
+/// # let mut world2 = World::new();
+/// # SaveLoadPlugin::new::<All>().build_world(&mut world1);
+/// # SaveLoadPlugin::new::<All>().build_world(&mut world2);
+/// transfer::<All, With<Level1>>(&mut world1, &mut world2, TransferOptions::new()
+///     .with_remap("level1", "level2")
+///     .with_despawn(true)
+/// ).unwrap();
+/// ```
+pub fn transfer<M: Marker, F: ReadOnlyWorldQuery + 'static>(
+    from: &mut World,
+    to: &mut World,
+    options: TransferOptions,
+) -> Result<(), SaloError> {
+    let entities = from.run_system_once(|query: Query<Entity, (F, M::Query)>| {
+        query.iter().collect::<Vec<_>>()
+    });
+    let prev = from.remove_resource::<SaveFilter<M>>();
+    from.insert_resource(SaveFilter::<M>::new(entities.iter().copied()));
+    let result = save_value::<M>(from);
+    from.remove_resource::<SaveFilter<M>>();
+    if let Some(prev) = prev {
+        from.insert_resource(prev);
+    }
+    let value = result?;
+    if options.despawn {
+        for entity in entities {
+            from.despawn(entity);
+        }
+    }
+    let prev = to.remove_resource::<PathRemap<M>>();
+    if !options.remap.is_empty() {
+        let mut remap = PathRemap::<M>::new();
+        for (from, to) in &options.remap {
+            remap.insert(from, to);
+        }
+        to.insert_resource(remap);
+    }
+    let result = load_value::<M>(to, value);
+    to.remove_resource::<PathRemap<M>>();
+    if let Some(prev) = prev {
+        to.insert_resource(prev);
+    }
+    result
+}
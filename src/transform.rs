@@ -0,0 +1,163 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use bevy_ecs::system::{Commands, Res, ResMut, Resource};
+
+use crate::saveload::{named_paths, Document, PathedValueOf, ValueOf};
+use crate::{DeserializeContext, EntityPath, Marker, SaloError, SaloErrors, SaveMetadata, SerializeContext};
+
+type Transform<M> = Box<dyn Fn(&mut DocumentParts<M>) -> anyhow::Result<()> + Send + Sync>;
+
+/// Callbacks that rewrite the serialized document of a marker,
+/// i.e. to compress or redact sections, or to inject metadata.
+///
+/// Save callbacks run after records and the header are built, before sections are signed and the output is written.
+/// Load callbacks run after the input is parsed and signatures are checked, before anything is applied,
+/// so a load callback should undo its save counterpart and signatures cover the written document.
+///
+/// An error in a save callback aborts the save with [`SaloError::Aborted`],
+/// an error in a load callback fails the load.
+///
+/// ```
+/// # use bevy_salo::*;
+/// # type All = bevy_salo::All<methods::SerdeJson>;
+/// DocumentTransform::<All>::new()
+///     .on_save(|document| {
+///         document.remove_type("debug_overlay");
+///         Ok(())
+///     });
+/// ```
+#[derive(Resource)]
+pub struct DocumentTransform<M: Marker> {
+    save: Vec<Transform<M>>,
+    load: Vec<Transform<M>>,
+}
+
+impl<M: Marker> Default for DocumentTransform<M> {
+    fn default() -> Self {
+        Self { save: Vec::new(), load: Vec::new() }
+    }
+}
+
+impl<M: Marker> std::fmt::Debug for DocumentTransform<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DocumentTransform")
+            .field("save", &self.save.len())
+            .field("load", &self.load.len())
+            .finish()
+    }
+}
+
+impl<M: Marker> DocumentTransform<M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a callback run on each save, in insertion order.
+    pub fn on_save(mut self, f: impl Fn(&mut DocumentParts<M>) -> anyhow::Result<()> + Send + Sync + 'static) -> Self {
+        self.save.push(Box::new(f));
+        self
+    }
+
+    /// Add a callback run on each load, in insertion order.
+    pub fn on_load(mut self, f: impl Fn(&mut DocumentParts<M>) -> anyhow::Result<()> + Send + Sync + 'static) -> Self {
+        self.load.push(Box::new(f));
+        self
+    }
+
+    pub(crate) fn apply_load(&self, document: &mut Document<ValueOf<M>>) -> anyhow::Result<()> {
+        let mut parts = DocumentParts { metadata: &mut document.metadata, components: &mut document.components };
+        self.load.iter().try_for_each(|f| f(&mut parts))
+    }
+}
+
+/// Metadata and records of a document, modified by [`DocumentTransform`].
+pub struct DocumentParts<'t, M: Marker> {
+    metadata: &'t mut Option<ValueOf<M>>,
+    components: &'t mut HashMap<String, Vec<PathedValueOf<M>>>,
+}
+
+impl<M: Marker> DocumentParts<'_, M> {
+    /// The metadata header, see [`SaveMetadata`](crate::SaveMetadata).
+    pub fn metadata(&self) -> Option<&ValueOf<M>> {
+        self.metadata.as_ref()
+    }
+
+    pub fn set_metadata(&mut self, metadata: Option<ValueOf<M>>) {
+        *self.metadata = metadata;
+    }
+
+    /// Type names with records in this document.
+    pub fn type_names(&self) -> impl Iterator<Item = &str> {
+        self.components.keys().map(|k| k.as_str())
+    }
+
+    /// Paths and values of records of a type.
+    pub fn records(&self, type_name: &str) -> impl Iterator<Item = (&EntityPath, &ValueOf<M>)> {
+        self.components.get(type_name).into_iter().flatten().map(|r| (&r.path, &r.value))
+    }
+
+    /// Paths and mutable values of records of a type.
+    pub fn records_mut(&mut self, type_name: &str) -> impl Iterator<Item = (&EntityPath, &mut ValueOf<M>)> {
+        self.components.get_mut(type_name).into_iter().flatten().map(|r| (&r.path, &mut r.value))
+    }
+
+    /// Keep only records of a type for which `f` returns true.
+    pub fn retain(&mut self, type_name: &str, mut f: impl FnMut(&EntityPath, &ValueOf<M>) -> bool) {
+        if let Some(records) = self.components.get_mut(type_name) {
+            records.retain(|r| f(&r.path, &r.value));
+        }
+    }
+
+    /// Remove all records of a type, returns true if any existed.
+    pub fn remove_type(&mut self, type_name: &str) -> bool {
+        self.components.remove(type_name).is_some()
+    }
+}
+
+/// Apply save callbacks of [`DocumentTransform`].
+pub(crate) fn transform_document<M: Marker>(
+    transform: Option<Res<DocumentTransform<M>>>,
+    mut ctx: ResMut<SerializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    let Some(transform) = transform else { return };
+    let ctx = &mut *ctx;
+    let mut components = std::mem::take(&mut ctx.components).into_iter()
+        .map(|(name, records)| (name.into_owned(), records))
+        .collect();
+    let mut parts = DocumentParts { metadata: &mut ctx.metadata, components: &mut components };
+    let result = transform.save.iter().try_for_each(|f| f(&mut parts));
+    ctx.components = components.into_iter().map(|(name, records)| (Cow::Owned(name), records)).collect();
+    if let Err(e) = result {
+        errors.push(SaloError::Aborted(Cow::Owned(format!("document transform failed: {e:#}"))));
+    }
+}
+
+/// Apply load callbacks of [`DocumentTransform`] and insert the loaded [`SaveMetadata`].
+pub(crate) fn transform_loaded<M: Marker>(
+    mut commands: Commands,
+    transform: Option<Res<DocumentTransform<M>>>,
+    mut ctx: ResMut<DeserializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    if !errors.is_empty() {
+        return;
+    }
+    let ctx = &mut *ctx;
+    if let Some(transform) = transform {
+        let mut parts = DocumentParts { metadata: &mut ctx.metadata, components: &mut ctx.components };
+        if let Err(e) = transform.load.iter().try_for_each(|f| f(&mut parts)) {
+            errors.push(SaloError::deserialization(e));
+            return;
+        }
+        ctx.counts = ctx.components.iter().map(|(k, v)| (k.clone(), v.len())).collect();
+        if ctx.delta.is_none() {
+            ctx.loaded = named_paths(ctx.components.iter());
+        }
+    }
+    if let Some(metadata) = &ctx.metadata {
+        commands.insert_resource(SaveMetadata::<M>(metadata.clone(), PhantomData));
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::world::World;
+use serde::{Deserialize, Serialize};
+
+use crate::saveload::join_path;
+use crate::schedules::PathSchedule;
+use crate::{Marker, PathNames, SaloConfig, SerializeContext};
+
+/// An entity in a [`PathTree`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathNode {
+    /// Bits of the entity, see [`Entity::from_bits`].
+    pub entity: u64,
+    /// Name of the entity, `None` if unnamed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Path of the entity in saves, `None` if unnamed or at the [`PathRoot`](crate::PathRoot).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<PathNode>,
+}
+
+impl PathNode {
+    pub fn entity(&self) -> Entity {
+        Entity::from_bits(self.entity)
+    }
+}
+
+/// Named entities of a marker and their ancestors, as named by the next save,
+/// created by [`export_path_tree`](crate::SaveLoadExtension::export_path_tree).
+///
+/// Unnamed ancestors of named entities cannot be loaded correctly and are listed with no name.
+/// Children are sorted by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathTree {
+    pub roots: Vec<PathNode>,
+    /// Naming conflicts, see [`PathCollisions`](crate::PathCollisions).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<String>,
+}
+
+impl PathTree {
+    /// Depth first iterator of all nodes.
+    pub fn iter(&self) -> impl Iterator<Item = &PathNode> {
+        let mut stack: Vec<_> = self.roots.iter().rev().collect();
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+
+    /// Find the node of a path in saves.
+    pub fn find(&self, path: &str) -> Option<&PathNode> {
+        self.iter().find(|node| node.path.as_deref() == Some(path))
+    }
+}
+
+fn write_node(f: &mut std::fmt::Formatter<'_>, node: &PathNode, depth: usize) -> std::fmt::Result {
+    write!(f, "{:indent$}{} {:?}", "", node.name.as_deref().unwrap_or("(unnamed)"), node.entity(), indent = depth * 2)?;
+    if let Some(path) = &node.path {
+        write!(f, " `{}`", path)?;
+    }
+    writeln!(f)?;
+    node.children.iter().try_for_each(|child| write_node(f, child, depth + 1))
+}
+
+impl Display for PathTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.roots.iter().try_for_each(|node| write_node(f, node, 0))?;
+        for conflict in &self.conflicts {
+            writeln!(f, "conflict: {}", conflict)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fresh buffers for [`PathSchedule`].
+pub(crate) fn init_paths<M: Marker>(w: &mut World) {
+    let policy = w.resource::<SaloConfig<M>>().conflict_policy();
+    w.insert_resource(PathNames::<M>::with_policy(policy));
+    w.insert_resource(SerializeContext::<M>::default());
+}
+
+#[cfg(feature="hierarchy")]
+fn parent_of(world: &World, entity: Entity) -> Option<Entity> {
+    world.get::<bevy_hierarchy::Parent>(entity).map(|p| p.get())
+}
+
+#[cfg(not(feature="hierarchy"))]
+fn parent_of(_: &World, _: Entity) -> Option<Entity> {
+    None
+}
+
+fn build_node<M: Marker>(
+    entity: Entity,
+    names: &PathNames<M>,
+    ctx: &SerializeContext<M>,
+    children: &HashMap<Option<Entity>, Vec<Entity>>,
+) -> PathNode {
+    PathNode {
+        entity: entity.to_bits(),
+        name: names.get(entity).map(|n| n.to_owned()),
+        path: ctx.paths.get(&entity).map(|p| join_path(p)),
+        children: build_nodes(children.get(&Some(entity)), names, ctx, children),
+    }
+}
+
+/// Nodes of entities sorted by name.
+fn build_nodes<M: Marker>(
+    entities: Option<&Vec<Entity>>,
+    names: &PathNames<M>,
+    ctx: &SerializeContext<M>,
+    children: &HashMap<Option<Entity>, Vec<Entity>>,
+) -> Vec<PathNode> {
+    let mut nodes: Vec<_> = entities.into_iter().flatten().map(|e| build_node(*e, names, ctx, children)).collect();
+    nodes.sort_by(|a, b| (&a.name, a.entity).cmp(&(&b.name, b.entity)));
+    nodes
+}
+
+/// Run [`PathSchedule`] and return its buffers, buffers of a retained save are kept.
+pub(crate) fn run_path_schedule<M: Marker>(world: &mut World) -> (PathNames<M>, SerializeContext<M>) {
+    let names = world.remove_resource::<PathNames<M>>();
+    let ctx = world.remove_resource::<SerializeContext<M>>();
+    world.run_schedule(PathSchedule::with_marker::<M>());
+    let result = (
+        world.remove_resource::<PathNames<M>>().unwrap_or_default(),
+        world.remove_resource::<SerializeContext<M>>().unwrap_or_default(),
+    );
+    if let Some(names) = names {
+        world.insert_resource(names);
+    }
+    if let Some(ctx) = ctx {
+        world.insert_resource(ctx);
+    }
+    result
+}
+
+/// Build the tree from the buffers of [`PathSchedule`].
+pub(crate) fn export_path_tree<M: Marker>(world: &mut World) -> PathTree {
+    let (tree_names, tree_ctx) = run_path_schedule::<M>(world);
+
+    let mut included = HashSet::new();
+    let mut children: HashMap<Option<Entity>, Vec<Entity>> = HashMap::new();
+    for (entity, _) in tree_names.iter() {
+        let mut current = entity;
+        while included.insert(current) {
+            let parent = parent_of(world, current);
+            children.entry(parent).or_default().push(current);
+            match parent {
+                Some(parent) => current = parent,
+                None => break,
+            }
+        }
+    }
+    PathTree {
+        roots: build_nodes(children.get(&None), &tree_names, &tree_ctx, &children),
+        conflicts: tree_names.conflicts.iter().map(|c| c.to_string()).collect(),
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::{BTreeMap, HashSet};
+
+use bevy_ecs::system::{Res, ResMut};
+use bevy_ecs::world::World;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::bare::BARE_ENTITY;
+use crate::methods::SerializationMethod;
+use crate::saveload::{Document, EntityParent, ValueOf};
+use crate::schedules::ValidateSchedule;
+use crate::transform::DocumentTransform;
+use crate::tree::run_path_schedule;
+use crate::{DeserializeContext, EntityPath, Marker, PersistentId, SaloError, SaloErrors, SaloRegistry};
+use crate::{SaveEncryptionKey, SaveLoad, SaveLoadBundle, SaveLoadRes};
+
+/// Result of [`validate_save`](crate::SaveLoadExtension::validate_save).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveReport {
+    /// Number of records by type name.
+    pub records: BTreeMap<String, usize>,
+    /// Type names not registered to the marker.
+    pub unknown_types: Vec<String>,
+    /// Records that cannot be migrated or deserialized.
+    pub errors: Vec<String>,
+    /// Parents that are neither records of the save nor named entities of the world,
+    /// which a load spawns as empty entities.
+    pub unresolved_parents: Vec<String>,
+}
+
+impl SaveReport {
+    /// Returns true if all types are registered and all records can be deserialized.
+    pub fn is_compatible(&self) -> bool {
+        self.unknown_types.is_empty() && self.errors.is_empty()
+    }
+}
+
+fn check<M: Marker, T: DeserializeOwned>(name: &str, ctx: &DeserializeContext<M>, errors: &mut SaloErrors<M>) {
+    for record in ctx.components.get(name).into_iter().flatten() {
+        if let Err(e) = M::Method::deserialize_value::<T>(record.value.clone()) {
+            errors.push(SaloError::deserialization(e.context(format!("Record {} of {} is invalid.", record.path, name))));
+        }
+    }
+}
+
+/// Check all records of a component can be deserialized.
+pub(crate) fn check_component<M: Marker, T: SaveLoad>(ctx: Res<DeserializeContext<M>>, mut errors: ResMut<SaloErrors<M>>) {
+    check::<M, T::De>(&T::type_name(), &ctx, &mut errors)
+}
+
+/// Check all records of a bundle can be deserialized.
+pub(crate) fn check_bundle<M: Marker, T: SaveLoadBundle>(ctx: Res<DeserializeContext<M>>, mut errors: ResMut<SaloErrors<M>>) {
+    check::<M, T::De>(&T::type_name(), &ctx, &mut errors)
+}
+
+/// Check the record of a resource can be deserialized.
+pub(crate) fn check_resource<M: Marker, T: SaveLoadRes>(ctx: Res<DeserializeContext<M>>, mut errors: ResMut<SaloErrors<M>>) {
+    check::<M, T::De>(&T::type_name(), &ctx, &mut errors)
+}
+
+/// Parse a save and run [`ValidateSchedule`] over it, buffers of a running or retained load are kept.
+pub(crate) fn validate_save<M: Marker>(world: &mut World, bytes: &[u8]) -> Result<SaveReport, SaloError> {
+    let key = world.get_resource::<SaveEncryptionKey<M>>().map(|k| *k.get());
+    let mut document: Document<ValueOf<M>> = M::Method::deserialize_keyed(bytes, key.as_ref())
+        .map_err(SaloError::deserialization)?;
+    if let Some(transform) = world.get_resource::<DocumentTransform<M>>() {
+        transform.apply_load(&mut document).map_err(SaloError::deserialization)?;
+    }
+
+    let prev_ctx = world.remove_resource::<DeserializeContext<M>>();
+    let prev_errors = world.remove_resource::<SaloErrors<M>>();
+    let mut ctx = DeserializeContext::<M>::default();
+    ctx.load(document);
+    world.insert_resource(ctx);
+    world.init_resource::<SaloErrors<M>>();
+    world.run_schedule(ValidateSchedule::with_marker::<M>());
+    let ctx = world.remove_resource::<DeserializeContext<M>>().unwrap_or_default();
+    let errors = world.remove_resource::<SaloErrors<M>>().unwrap_or_default();
+    if let Some(prev) = prev_ctx {
+        world.insert_resource(prev);
+    }
+    if let Some(prev) = prev_errors {
+        world.insert_resource(prev);
+    }
+
+    let registry = world.resource::<SaloRegistry<M>>();
+    let mut unknown_types: Vec<_> = ctx.counts.iter()
+        .map(|(name, _)| name)
+        .filter(|name| !registry.contains(name) && name.as_str() != BARE_ENTITY)
+        .filter(|name| M::IS_ALL || **name != M::type_name())
+        .cloned()
+        .collect();
+    unknown_types.sort();
+
+    let (_, named) = run_path_schedule::<M>(world);
+    let mut known: HashSet<EntityPath> = named.paths.into_values().map(EntityPath::Path).collect();
+    known.extend(world.query::<&PersistentId>().iter(world).map(|id| EntityPath::Entity(id.to_bits())));
+    known.extend(ctx.components.values().flatten().map(|r| r.path.clone()));
+    let mut unresolved_parents: Vec<_> = ctx.components.values().flatten()
+        .filter(|r| !matches!(r.parent, EntityParent::Root))
+        .map(|r| EntityPath::from(r.parent.clone()))
+        .filter(|path| !known.contains(path))
+        .map(|path| path.to_string())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    unresolved_parents.sort();
+
+    Ok(SaveReport {
+        records: ctx.counts.into_iter().collect(),
+        unknown_types,
+        errors: errors.iter().map(|e| e.to_string()).collect(),
+        unresolved_parents,
+    })
+}
@@ -0,0 +1,38 @@
+use std::collections::BTreeMap;
+
+use bevy_ecs::system::ResMut;
+
+use crate::{DeserializeContext, Marker, SaloError, SaloErrors, SaveLoad};
+
+/// Versions of saved types by type name, types with version 0 are omitted.
+pub(crate) type Versions = BTreeMap<String, u32>;
+
+/// Migrate records of a type saved with a different [`SaveLoad::version`].
+pub(crate) fn migrate_records<M: Marker, T: SaveLoad>(
+    mut ctx: ResMut<DeserializeContext<M>>,
+    mut errors: ResMut<SaloErrors<M>>,
+) {
+    let name = T::type_name();
+    let saved = ctx.versions.get(name.as_ref()).copied().unwrap_or(0);
+    if saved == T::version() {
+        return;
+    }
+    if let Some(records) = ctx.components.remove(name.as_ref()) {
+        let mut migrated = Vec::with_capacity(records.len());
+        for mut record in records {
+            match T::migrate::<M::Method>(saved, record.value) {
+                Ok(value) => {
+                    record.value = value;
+                    migrated.push(record);
+                },
+                Err(e) => errors.push(SaloError::deserialization(e)),
+            }
+        }
+        ctx.components.insert(name.to_string(), migrated);
+    }
+    // Records are now of the current version, i.e. when deferred by the entity budget.
+    match T::version() {
+        0 => ctx.versions.remove(name.as_ref()),
+        version => ctx.versions.insert(name.into_owned(), version),
+    };
+}
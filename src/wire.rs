@@ -0,0 +1,200 @@
+//! The encoding of paths and records in saves.
+//!
+//! Everything in this module is part of the wire contract, a change to it bumps
+//! [`WIRE_VERSION`] and keeps a [`PathEncoding`] to read saves of older versions.
+//!
+//! # Human-readable formats
+//!
+//! An [`EntityPath`] is untagged:
+//!
+//! | Path | Encoding |
+//! | --- | --- |
+//! | `Unique` | `null` or omitted |
+//! | `Entity(4)` | `4` |
+//! | `Path(["a", "b"])` | `"a::b"` |
+//!
+//! A record is a map of `parent`, `path` and `value`, each omitted if empty or unique.
+//! Records of inline types write fields of `value` in place of it.
+//!
+//! ```json
+//! {"unit":[{"parent":"Players","path":"Players::John","value":{"name":"John"}}]}
+//! ```
+//!
+//! # Binary formats
+//!
+//! An [`EntityPath`] is an externally tagged enum with variant indices
+//! `0: Unique`, `1: Entity(u64)`, `2: Path(String)`. The parent of a record is
+//! a separate enum with variant indices `0: Root`, `1: Path(String)`, `2: Entity(u64)`.
+//!
+//! A record is a tuple of `(parent, path, value)`.
+//!
+//! # Documents
+//!
+//! A save is a map of type names to lists of records.
+//! Type names starting with `$` are reserved for headers, i.e. `$metadata`,
+//! and are skipped by versions that do not know them.
+//!
+//! `$wire` is the first entry of every save, the [`WIRE_VERSION`] of the writer.
+//! Paths of the save are read with the [`PathEncoding`] of that version,
+//! saves without it are read with [`PathEncoding::current`].
+//!
+//! `$attributes` maps type names to lists of `path` and `attributes`, a map of strings
+//! attached to the record of that type at `path`.
+//!
+//! # Entity-major layout
+//!
+//! Saves written with [`FormatLayout::EntityMajor`](crate::FormatLayout::EntityMajor) are tagged by
+//! `"$layout": "EntityMajor"` and store records under `$entities` instead of type names,
+//! a list of entities each with a `parent`, a `path` and `components`, a map of type names to values.
+//! `parent` and `path` are encoded and omitted as in records, records with the same
+//! path and parent are grouped, and values of inline types are written as is.
+//!
+//! ```json
+//! {"$wire":1,"$layout":"EntityMajor","$entities":[{"path":"John","components":{"unit":{"name":"John"}}}]}
+//! ```
+//!
+//! In binary formats an entity is a tuple of `(parent, path, components)`,
+//! with `parent` and `path` encoded as in records.
+
+use std::cell::Cell;
+
+use smol_str::SmolStr;
+
+use crate::alias::Aliases;
+use crate::saveload::{join_escaped, join_plain, split_escaped, split_plain, Document};
+use crate::EntityPath;
+
+/// Version of the wire contract written by this crate.
+///
+/// | Version | Changes |
+/// | --- | --- |
+/// | 0 | Initial encoding. |
+/// | 1 | `\` escapes in paths, names may contain `::`. |
+pub const WIRE_VERSION: u32 = 1;
+
+/// Encoding of a path as a `::` delimited string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PathEncoding {
+    /// Names are joined with `::` as is, written by version 0 and the `legacy-paths` feature.
+    Unescaped,
+    /// `\` escapes the next character, written by version 1 and later.
+    #[default]
+    Escaped,
+}
+
+impl PathEncoding {
+    /// The encoding written by a version of the wire contract, `None` if newer than [`WIRE_VERSION`].
+    pub fn from_version(version: u32) -> Option<Self> {
+        match version {
+            0 => Some(PathEncoding::Unescaped),
+            1 => Some(PathEncoding::Escaped),
+            _ => None,
+        }
+    }
+
+    /// The version of the wire contract written with this encoding, see [`from_version`](Self::from_version).
+    pub fn version(self) -> u32 {
+        match self {
+            PathEncoding::Unescaped => 0,
+            PathEncoding::Escaped => WIRE_VERSION,
+        }
+    }
+
+    /// The encoding used by this build, [`PathEncoding::Unescaped`] with the `legacy-paths` feature.
+    pub fn current() -> Self {
+        if cfg!(feature="legacy-paths") {
+            PathEncoding::Unescaped
+        } else {
+            PathEncoding::Escaped
+        }
+    }
+
+    /// Parse a path written in this encoding, an empty string is parsed as [`EntityPath::Unique`].
+    ///
+    /// ```
+    /// # use bevy_salo::{EntityPath, wire::PathEncoding};
+    /// let path = PathEncoding::Unescaped.parse(r"C:\Saves::John");
+    /// assert_eq!(path, EntityPath::Path(vec![r"C:\Saves".into(), "John".into()]));
+    /// ```
+    pub fn parse(self, path: &str) -> EntityPath {
+        if path.is_empty() {
+            return EntityPath::Unique;
+        }
+        EntityPath::Path(self.split(path))
+    }
+
+    fn split(self, path: &str) -> Vec<SmolStr> {
+        match self {
+            PathEncoding::Unescaped => split_plain(path),
+            PathEncoding::Escaped => split_escaped(path),
+        }
+    }
+
+    /// Write the names of a path in this encoding, `None` for entities.
+    ///
+    /// Unescaped paths do not round trip if a name contains `::`.
+    pub fn format(self, path: &EntityPath) -> Option<String> {
+        match path {
+            EntityPath::Unique => Some(String::new()),
+            EntityPath::Entity(_) => None,
+            EntityPath::Path(names) => Some(match self {
+                PathEncoding::Unescaped => join_plain(names),
+                PathEncoding::Escaped => join_escaped(names),
+            }),
+        }
+    }
+
+    /// Rewrite a path string from this encoding to another, i.e. to upgrade paths of an old save.
+    pub fn convert(self, path: &str, to: PathEncoding) -> String {
+        to.format(&self.parse(path)).unwrap_or_default()
+    }
+}
+
+thread_local! {
+    static DECODING: Cell<Option<PathEncoding>> = const { Cell::new(None) };
+}
+
+/// Paths deserialized on this thread use an encoding until dropped, see [`split_decoded`].
+pub(crate) struct DecodingScope(Option<PathEncoding>);
+
+impl DecodingScope {
+    pub(crate) fn enter(encoding: PathEncoding) -> Self {
+        DecodingScope(DECODING.with(|d| d.replace(Some(encoding))))
+    }
+}
+
+impl Drop for DecodingScope {
+    fn drop(&mut self) {
+        DECODING.with(|d| d.set(self.0));
+    }
+}
+
+/// The encoding of paths being deserialized, [`PathEncoding::current`] outside of a [`DecodingScope`].
+pub(crate) fn decoding() -> PathEncoding {
+    DECODING.with(|d| d.get()).unwrap_or_else(PathEncoding::current)
+}
+
+/// Split a deserialized path with the encoding of the current [`DecodingScope`].
+pub(crate) fn split_decoded(path: &str) -> Vec<SmolStr> {
+    decoding().split(path)
+}
+
+/// The encoding of a `$wire` header, fails if the version is newer than [`WIRE_VERSION`].
+pub(crate) fn encoding_of(version: u32) -> anyhow::Result<PathEncoding> {
+    PathEncoding::from_version(version)
+        .ok_or_else(|| anyhow::anyhow!("Wire version {} is newer than {}.", version, WIRE_VERSION))
+}
+
+/// Rewrite paths of a document read with `encoding` that are not parsed when deserialized.
+pub(crate) fn upgrade_document<V>(document: &mut Document<V>, encoding: PathEncoding) {
+    let current = PathEncoding::current();
+    if encoding == current {
+        return;
+    }
+    if let Some(aliases) = document.aliases.take() {
+        let aliases: Aliases = aliases.into_iter()
+            .map(|(old, new)| (encoding.convert(&old, current), encoding.convert(&new, current)))
+            .collect();
+        document.aliases = Some(aliases);
+    }
+}
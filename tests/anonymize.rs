@@ -0,0 +1,120 @@
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaveTarget, LoadSource, AnonymizePolicy, Redaction, SaveMetadata, LoadPolicy, TypePolicy, SaloConfig, PathAliases, SaloError};
+use bevy_utils::Duration;
+use std::borrow::Cow;
+
+mod common;
+use common::Unit;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Gold(u32);
+
+impl bevy_salo::SaveLoadCore for Gold {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("gold")
+    }
+}
+
+#[derive(Debug, Clone, Component, serde::Serialize, serde::Deserialize)]
+struct Diary(String);
+
+impl bevy_salo::SaveLoadCore for Diary {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("diary")
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Gold>()
+        .register::<Diary>()
+    );
+    app
+}
+
+#[test]
+pub fn anonymize() {
+    let mut app = app();
+    app.world.insert_resource(SaveMetadata::<All>::new(&"Jimothy").unwrap());
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Unit { name: "Jimothy".to_owned() }, Gold(100), Diary("Dear diary".to_owned())));
+        commands.spawn((Unit { name: "Jane".to_owned() }, Gold(100)));
+    });
+
+    let save = app.world.save_anonymized::<All>(SaveTarget::String, AnonymizePolicy::new()
+        .with("unit", Redaction::Hash)
+        .with("gold", Redaction::Keep)
+        .with("diary", Redaction::Remove)
+    ).unwrap().into_string().unwrap();
+    assert!(!save.contains("Jimothy"));
+    assert!(!save.contains("Jane"));
+    assert!(!save.contains("diary"));
+    assert!(save.contains("100"));
+    assert!(app.world.has_unsaved_changes::<All>());
+
+    let value: serde_json::Value = serde_json::from_str(&save).unwrap();
+    let units = value["unit"].as_array().unwrap();
+    assert_eq!(units.len(), 2);
+    assert_ne!(units[0]["path"], units[1]["path"]);
+    assert_ne!(units[0]["value"], units[1]["value"]);
+
+    let save = app.world.save_anonymized::<All>(SaveTarget::String, AnonymizePolicy::new()
+        .with_default::<Gold>()
+        .with_fallback(Redaction::Clear)
+        .with_metadata(Redaction::Hash)
+        .with_hash_names(false)
+    ).unwrap().into_string().unwrap();
+    assert!(!save.contains("\"$metadata\":\"Jimothy\""));
+    assert!(!save.contains("Dear diary"));
+    assert!(!save.contains("100"));
+    assert!(save.contains("Jane"));
+
+    // Cleared records are skipped, unless the type is required.
+    let mut app2 = self::app();
+    app2.world.load::<All>(LoadSource::String(&save)).unwrap();
+    assert_eq!(app2.world.run_system_once(|q: Query<&Diary>| q.iter().count()), 0);
+    let mut app2 = self::app();
+    app2.world.insert_resource(LoadPolicy::<All>::new().with("diary", TypePolicy::Required));
+    app2.world.load::<All>(LoadSource::String(&save)).unwrap_err();
+    let save = app.world.save_anonymized::<All>(SaveTarget::String, AnonymizePolicy::new()
+        .with_default::<Gold>()
+        .with("diary", Redaction::Remove)
+    ).unwrap().into_string().unwrap();
+    let mut app3 = self::app();
+    app3.world.load::<All>(LoadSource::String(&save)).unwrap();
+    let gold = app3.world.run_system_once(|q: Query<&Gold>| q.iter().cloned().collect::<Vec<_>>());
+    assert_eq!(gold, vec![Gold(0), Gold(0)]);
+}
+
+#[test]
+pub fn anonymize_defaults() {
+    let mut app = app();
+    app.world.insert_resource(SaloConfig::<All>::new().with_min_save_interval(Duration::from_secs(60)));
+    app.world.insert_resource(PathAliases::<All>::new().with("John", "Jimothy"));
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Unit::new("John"), Gold(100)));
+        commands.spawn(Unit::new("Jane"));
+    });
+    let prev = app.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+    app.world.run_system_once(|mut commands: Commands, units: Query<(bevy_ecs::entity::Entity, &Unit)>| {
+        let (jane, _) = units.iter().find(|(_, u)| u.name == "Jane").unwrap();
+        commands.entity(jane).despawn();
+    });
+
+    // Hashes values and every path by default, and is not throttled.
+    app.world.insert_resource(AnonymizePolicy::<All>::new());
+    let save = app.world.save_with_tombstones::<All, String>(&prev).unwrap();
+    app.world.remove_resource::<AnonymizePolicy<All>>();
+    assert!(save.contains("$tombstones"));
+    assert!(save.contains("$aliases"));
+    for name in ["John", "Jane", "Jimothy", "100"] {
+        assert!(!save.contains(name), "{} in {}", name, save);
+    }
+    assert!(app.world.save_anonymized::<All>(SaveTarget::String, AnonymizePolicy::new()).is_ok());
+    assert!(matches!(app.world.save::<All>(SaveTarget::String), Err(SaloError::Throttled)));
+}
@@ -0,0 +1,43 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_asset::{Assets, Handle, LoadedFolder};
+use bevy_ecs::system::{RunSystemOnce, Commands, Query};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaloHandle, AssetFallback};
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<SaloHandle<LoadedFolder>>()
+    );
+    app
+}
+
+#[test]
+pub fn asset_handles() {
+    let mut app = app();
+    let mut assets = Assets::<LoadedFolder>::default();
+    let procedural = assets.add(LoadedFolder { handles: Vec::new() });
+    app.world.run_system_once(move |mut commands: Commands| {
+        commands.spawn(SaloHandle(Handle::<LoadedFolder>::weak_from_u128(42)));
+        commands.spawn(SaloHandle(procedural.clone()));
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains("SaloHandle<LoadedFolder>"));
+    assert!(save.contains(r#"{"Uuid":42}"#));
+    assert!(save.contains(r#""Untracked""#));
+
+    let mut app2 = self::app();
+    let fallback = Handle::<LoadedFolder>::weak_from_u128(7);
+    app2.world.insert_resource(AssetFallback(fallback.clone()));
+    app2.world.load_from::<All, _>(&save);
+    let mut handles = app2.world.run_system_once(|query: Query<&SaloHandle<LoadedFolder>>| {
+        query.iter().map(|h| h.0.clone()).collect::<Vec<_>>()
+    });
+    handles.sort_by_key(|h| h.id());
+    let mut expected = vec![Handle::weak_from_u128(42), fallback];
+    expected.sort_by_key(|h| h.id());
+    assert_eq!(handles, expected);
+}
@@ -0,0 +1,90 @@
+use bevy_app::App;
+use bevy_ecs::{component::Component, event::Event, system::{Res, Resource}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaloHistory, SaloStorage, MemoryStorage};
+use bevy_salo::{AutoSavePlugin, AutoSaveConfig, AutoSaveState};
+use bevy_utils::Duration;
+use std::borrow::Cow;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Unit;
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+}
+
+#[derive(Debug, Event)]
+struct Checkpoint;
+
+#[derive(Debug, Resource)]
+struct Cutscene;
+
+fn keys(app: &App) -> Vec<String> {
+    let storage = app.world.resource::<SaloStorage<All>>().get();
+    let mut keys: Vec<_> = ["autosave_1", "autosave_2", "autosave_3"].into_iter()
+        .filter(|k| storage.read(k).is_ok())
+        .map(|k| k.to_owned())
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+pub fn autosave_events() {
+    let mut app = App::new();
+    app.add_event::<Checkpoint>();
+    app.add_plugins(SaveLoadPlugin::new::<All>().register::<Unit>());
+    app.add_plugins(AutoSavePlugin::<All>::new()
+        .with_config(AutoSaveConfig::new().with_interval(None).with_max_rotations(2))
+        .on_event::<Checkpoint>()
+        .pause_if(|cutscene: Option<Res<Cutscene>>| cutscene.is_some())
+    );
+    app.insert_resource(SaloStorage::<All>::new(MemoryStorage::new()));
+
+    app.update();
+    assert!(keys(&app).is_empty());
+
+    app.world.send_event(Checkpoint);
+    app.update();
+    assert_eq!(keys(&app), ["autosave_1"]);
+    let history = app.world.resource::<SaloHistory<All>>();
+    assert_eq!(history.last().unwrap().requester.as_deref(), Some("autosave"));
+    assert_eq!(history.last().unwrap().reason.as_deref(), Some("Checkpoint"));
+
+    // Deferred while paused.
+    app.insert_resource(Cutscene);
+    app.world.send_event(Checkpoint);
+    app.update();
+    assert_eq!(keys(&app), ["autosave_1"]);
+    assert!(app.world.resource::<AutoSaveState<All>>().is_pending());
+    app.world.remove_resource::<Cutscene>();
+    app.update();
+    assert_eq!(keys(&app), ["autosave_1", "autosave_2"]);
+
+    // Rotates back to the first slot.
+    app.world.send_event(Checkpoint);
+    app.update();
+    assert_eq!(app.world.resource::<AutoSaveState<All>>().next_slot(), 2);
+    assert_eq!(keys(&app), ["autosave_1", "autosave_2"]);
+}
+
+#[test]
+pub fn autosave_interval() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>().register::<Unit>());
+    app.add_plugins(AutoSavePlugin::<All>::new()
+        .with_config(AutoSaveConfig::new().with_interval(Some(Duration::ZERO)))
+    );
+    app.insert_resource(SaloStorage::<All>::new(MemoryStorage::new()));
+    app.update();
+    app.update();
+    assert_eq!(keys(&app), ["autosave_1", "autosave_2"]);
+
+    app.world.resource_mut::<AutoSaveConfig<All>>().pause();
+    app.update();
+    assert_eq!(keys(&app), ["autosave_1", "autosave_2"]);
+    assert_eq!(app.world.resource::<SaloHistory<All>>().len(), 2);
+}
@@ -0,0 +1,54 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::system::{RunSystemOnce, Commands, Query};
+use bevy_hierarchy::BuildChildren;
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, PathName, SaveLoadExtension, SaloConfig};
+
+mod common;
+use common::{Unit, units};
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+fn app() -> App {
+    common::players::<All>()
+}
+
+#[test]
+pub fn bare_entities() {
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(PathName::new("Spawns")).with_children(|b| {
+            b.spawn(PathName::new("North"));
+        });
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(!save.contains("North"));
+
+    app.world.resource_mut::<SaloConfig<All>>().set_bare_entities(true);
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains(bevy_salo::BARE_ENTITY));
+    assert!(save.contains(r#"{"parent":"Spawns","path":"Spawns::North"}"#));
+
+    let mut app2 = App::new();
+    app2.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app2.world.load_from::<All, _>(&save);
+    let mut names = app2.world.run_system_once(|q: Query<(&PathName, Option<&bevy_hierarchy::Parent>)>, names: Query<&PathName>| {
+        q.iter().map(|(name, parent)| (
+            name.get().into_owned(),
+            parent.and_then(|p| names.get(p.get()).ok()).map(|n| n.get().into_owned()),
+        )).collect::<Vec<_>>()
+    });
+    names.sort();
+    assert_eq!(names, vec![
+        ("North".to_owned(), Some("Spawns".to_owned())),
+        ("Players".to_owned(), None),
+        ("Spawns".to_owned(), None),
+    ]);
+    assert_eq!(units(&mut app2), 1);
+    app2.world.resource_mut::<SaloConfig<All>>().set_bare_entities(true);
+    let save2 = app2.world.save_to::<All, String>().unwrap();
+    assert!(bevy_salo::diff::<All>(save.as_bytes(), save2.as_bytes()).unwrap().is_empty());
+}
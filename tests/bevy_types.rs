@@ -0,0 +1,59 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{entity::Entity, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension};
+use bevy_salo::bevy_types::{GlobalTransform, Name, Transform};
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register_bevy_core_types()
+    );
+    app
+}
+
+#[test]
+pub fn bevy_types() {
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        let mut transform = Transform::from_xyz(1.0, 2.0, 3.0);
+        transform.scale.x = 2.0;
+        commands.spawn((Name::new("Player"), transform));
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains(r#""Name":[{"path":"Player","value":"Player"}]"#));
+    assert!(save.contains(r#""translation":[1.0,2.0,3.0]"#));
+
+    // Loaded onto the existing entity with the same name.
+    let mut app2 = self::app();
+    let player = app2.world.spawn(Name::new("Player")).id();
+    app2.world.load_from::<All, _>(&save);
+    let loaded = app2.world.run_system_once(|query: Query<(Entity, &Transform)>| {
+        query.iter().map(|(e, t)| (e, *t)).collect::<Vec<_>>()
+    });
+    let mut expected = Transform::from_xyz(1.0, 2.0, 3.0);
+    expected.scale.x = 2.0;
+    assert_eq!(loaded, vec![(player, expected)]);
+    assert_eq!(app2.world.get::<GlobalTransform>(player), Some(&GlobalTransform::from(expected)));
+}
+
+#[cfg(feature="visibility")]
+#[test]
+pub fn visibility() {
+    use bevy_ecs::query::With;
+    use bevy_salo::bevy_types::{InheritedVisibility, ViewVisibility, Visibility};
+    let mut app = app();
+    app.world.spawn((Name::new("Player"), Visibility::Hidden));
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains(r#""Visibility":[{"path":"Player","value":"Hidden"}]"#));
+
+    let mut app2 = self::app();
+    app2.world.load_from::<All, _>(&save);
+    let loaded = app2.world.run_system_once(|query: Query<&Visibility>| query.iter().copied().collect::<Vec<_>>());
+    assert_eq!(loaded, vec![Visibility::Hidden]);
+    let inserted = app2.world.run_system_once(|query: Query<(), (With<InheritedVisibility>, With<ViewVisibility>)>| query.iter().count());
+    assert_eq!(inserted, 1);
+}
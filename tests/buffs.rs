@@ -1,7 +1,7 @@
 use bevy_app::App;
 use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}, entity::Entity, query::With};
 use bevy_hierarchy::BuildChildren;
-use bevy_salo::{SaveLoadPlugin, methods::{Ron, Postcard, SerdeJson}, Marker, PathName, SaveLoadExtension, All};
+use bevy_salo::{SaveLoadPlugin, methods::{Ron, Postcard, SerdeJson}, Marker, PathName, SaveLoadExtension, All, SaveTarget, LoadSource};
 use std::borrow::Cow;
 
 macro_rules! component {
@@ -158,7 +158,7 @@ pub fn test<P: Marker>(ext: Option<&str>) {
             builder.spawn((Enemies, PathName::new("Enemies")));
         });
     });
-    let buffer = app.world.save_to::<P, Vec<u8>>().unwrap();
+    let buffer = app.world.save::<P>(SaveTarget::Bytes).unwrap().into_bytes().unwrap();
     app.world.remove_serialized_components::<P>();
     assert_eq!(app.world.run_system_once(|e: Query<&Units>| e.iter().count()), 1);
     assert_eq!(app.world.run_system_once(|e: Query<&Players>| e.iter().count()), 1);
@@ -169,7 +169,7 @@ pub fn test<P: Marker>(ext: Option<&str>) {
     assert_eq!(app.world.run_system_once(|e: Query<&Item>| e.iter().count()), 0);
     assert_eq!(app.world.run_system_once(|e: Query<&Buff>| e.iter().count()), 0);
 
-    app.world.load_from_bytes::<P>(&buffer);
+    app.world.load::<P>(LoadSource::Bytes(&buffer)).unwrap();
     assert_eq!(app.world.run_system_once(|e: Query<&Units>| e.iter().count()), 1);
     assert_eq!(app.world.run_system_once(|e: Query<&Players>| e.iter().count()), 1);
     assert_eq!(app.world.run_system_once(|e: Query<&Enemies>| e.iter().count()), 1);
@@ -181,7 +181,7 @@ pub fn test<P: Marker>(ext: Option<&str>) {
 
 
     // Load again, note Unit, Weapon and Offhand are named, they should not duplicate and just update.
-    app.world.load_from_bytes::<P>(&buffer);
+    app.world.load::<P>(LoadSource::Bytes(&buffer)).unwrap();
     assert_eq!(app.world.run_system_once(|e: Query<&Units>| e.iter().count()), 1);
     assert_eq!(app.world.run_system_once(|e: Query<&Players>| e.iter().count()), 1);
     assert_eq!(app.world.run_system_once(|e: Query<&Enemies>| e.iter().count()), 1);
@@ -199,7 +199,7 @@ pub fn test<P: Marker>(ext: Option<&str>) {
         q.single_mut().set_static("Players")
     });
     // This doubles the amount of units due to new paths
-    app.world.load_from_bytes::<P>(&buffer);
+    app.world.load::<P>(LoadSource::Bytes(&buffer)).unwrap();
     assert_eq!(app.world.run_system_once(|e: Query<&Units>| e.iter().count()), 1);
     assert_eq!(app.world.run_system_once(|e: Query<&Players>| e.iter().count()), 1);
     assert_eq!(app.world.run_system_once(|e: Query<&Enemies>| e.iter().count()), 1);
@@ -210,6 +210,7 @@ pub fn test<P: Marker>(ext: Option<&str>) {
     assert_eq!(app.world.run_system_once(|e: Query<&Buff>| e.iter().count()), 6 * 2 + 6);
     
     if let Some(ext) = ext{
-        app.world.save_to_file::<P>(&format!("test_buffs{}", ext));
+        let file = std::env::temp_dir().join(format!("bevy_salo_test_buffs{}", ext));
+        app.world.save::<P>(SaveTarget::File(file.to_str().unwrap())).unwrap();
     }
 }
\ No newline at end of file
@@ -0,0 +1,70 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, query::QueryItem, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, SaveLoadBundle, methods::SerdeJson, SaveLoadExtension};
+use std::borrow::Cow;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Component, serde::Serialize, serde::Deserialize)]
+struct Position(i32, i32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Component, serde::Serialize, serde::Deserialize)]
+struct Velocity(i32, i32);
+
+struct Body;
+
+impl SaveLoadBundle for Body {
+    type Query = (&'static Position, &'static Velocity);
+    type Bundle = (Position, Velocity);
+    type Ser<'ser> = (&'ser Position, &'ser Velocity);
+    type De = (Position, Velocity);
+
+    fn to_serializable(item: QueryItem<'_, Self::Query>) -> Self::Ser<'_> {
+        item
+    }
+
+    fn from_deserialize(de: Self::De) -> Self::Bundle {
+        de
+    }
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("body")
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register_bundle::<Body>()
+    );
+    app
+}
+
+#[test]
+pub fn bundle() {
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Position(1, 2), Velocity(3, 4)));
+        // Incomplete bundles are not saved.
+        commands.spawn(Position(5, 6));
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains(r#""body":[{"#));
+    assert!(save.contains("[[1,2],[3,4]]"));
+    assert!(!save.contains("[5,6]"));
+
+    let mut app2 = self::app();
+    app2.world.load_from::<All, _>(&save);
+    let bodies = app2.world.run_system_once(|query: Query<(&Position, &Velocity)>| {
+        query.iter().map(|(p, v)| (*p, *v)).collect::<Vec<_>>()
+    });
+    assert_eq!(bodies, vec![(Position(1, 2), Velocity(3, 4))]);
+
+    app2.world.remove_serialized_components::<All>();
+    let count = app2.world.run_system_once(|p: Query<&Position>, v: Query<&Velocity>| {
+        p.iter().count() + v.iter().count()
+    });
+    assert_eq!(count, 0);
+}
@@ -0,0 +1,49 @@
+//! Fixtures shared by the integration tests.
+#![allow(dead_code)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Query}};
+use std::borrow::Cow;
+
+/// A component that names its entity.
+#[derive(Debug, Clone, Component, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Unit {
+    pub name: String,
+}
+
+impl Unit {
+    pub fn new(name: &str) -> Self {
+        Unit { name: name.to_owned() }
+    }
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+
+    fn path_name(&self) -> Option<Cow<'static, str>> {
+        Some(self.name.clone().into())
+    }
+}
+
+pub fn units(app: &mut App) -> usize {
+    app.world.run_system_once(|e: Query<&Unit>| e.iter().count())
+}
+
+/// An app with [`Unit`] registered and `Players::John` spawned.
+#[cfg(feature="hierarchy")]
+pub fn players<M: bevy_salo::Marker>() -> App {
+    use bevy_ecs::system::Commands;
+    use bevy_hierarchy::BuildChildren;
+    let mut app = App::new();
+    app.add_plugins(bevy_salo::SaveLoadPlugin::new::<M>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(bevy_salo::PathName::new("Players")).with_children(|b| {
+            b.spawn(Unit::new("John"));
+        });
+    });
+    app
+}
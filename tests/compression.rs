@@ -0,0 +1,65 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::{Compressed, Gzip, Zstd, Lz4, Postcard, SerdeJson, Checksummed, Crc32, Sha256}, Marker, SaveLoadExtension, All, SaloErrors, SaloError};
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, Component, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Unit {
+    name: String,
+    hp: i32,
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+}
+
+#[test]
+pub fn compression() {
+    roundtrip::<All<Compressed<Postcard, Zstd>>>();
+    roundtrip::<All<Compressed<Postcard, Lz4>>>();
+    roundtrip::<All<Compressed<SerdeJson, Gzip>>>();
+}
+
+#[test]
+pub fn checksum() {
+    roundtrip::<All<Checksummed<Postcard, Crc32>>>();
+    roundtrip::<All<Checksummed<Compressed<SerdeJson, Zstd>, Sha256>>>();
+    corrupted::<All<Checksummed<Postcard, Crc32>>>();
+    corrupted::<All<Checksummed<SerdeJson, Sha256>>>();
+}
+
+fn corrupted<M: Marker>() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<M>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "Slime".to_owned(), hp: 1 });
+    });
+    let mut buffer = app.world.save_to::<M, Vec<u8>>().unwrap();
+    app.world.remove_serialized_components::<M>();
+    buffer.pop();
+    app.world.load_from_bytes::<M>(&buffer);
+    assert_eq!(app.world.run_system_once(|e: Query<&Unit>| e.iter().count()), 0);
+    assert!(matches!(app.world.resource::<SaloErrors<M>>().iter().next(), Some(SaloError::CorruptSave)));
+}
+
+fn roundtrip<M: Marker>() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<M>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        for hp in 0..100 {
+            commands.spawn(Unit { name: "Slime".to_owned(), hp });
+        }
+    });
+    let buffer = app.world.save_to::<M, Vec<u8>>().unwrap();
+    app.world.remove_serialized_components::<M>();
+    app.world.load_from_bytes::<M>(&buffer);
+    assert_eq!(app.world.run_system_once(|e: Query<&Unit>| e.iter().map(|x| x.hp).sum::<i32>()), (0..100).sum::<i32>());
+}
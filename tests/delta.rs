@@ -0,0 +1,151 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, entity::Entity, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaloError, PathName, SaveFilter, TypeFilter};
+use std::borrow::Cow;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Unit {
+    name: String,
+    hp: u32,
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+
+    fn path_name(&self) -> Option<Cow<'static, str>> {
+        Some(self.name.clone().into())
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app
+}
+
+fn spawn(app: &mut App, name: &'static str, hp: u32) {
+    app.world.run_system_once(move |mut commands: Commands| {
+        commands.spawn(Unit { name: name.to_owned(), hp });
+    });
+}
+
+fn find(app: &mut App, name: &'static str) -> Option<Entity> {
+    app.world.run_system_once(move |q: Query<(Entity, &Unit)>| {
+        q.iter().find(|(_, u)| u.name == name).map(|(e, _)| e)
+    })
+}
+
+fn units(app: &mut App) -> Vec<(String, u32)> {
+    let mut units = app.world.run_system_once(|q: Query<&Unit>| {
+        q.iter().map(|u| (u.name.clone(), u.hp)).collect::<Vec<_>>()
+    });
+    units.sort();
+    units
+}
+
+#[test]
+pub fn delta() {
+    let mut app = app();
+    spawn(&mut app, "John", 10);
+    spawn(&mut app, "Jane", 10);
+    spawn(&mut app, "Jim", 10);
+    let base = app.world.save_to::<All, String>().unwrap();
+    assert!(!base.contains("$delta"));
+
+    let john = find(&mut app, "John").unwrap();
+    app.world.get_mut::<Unit>(john).unwrap().hp = 5;
+    let jim = find(&mut app, "Jim").unwrap();
+    app.world.despawn(jim);
+    spawn(&mut app, "Joe", 10);
+
+    let delta = app.world.save_delta::<All, String>("base").unwrap();
+    assert!(delta.contains("\"$delta\""));
+    assert!(delta.contains("\"John\""));
+    assert!(delta.contains("\"Joe\""));
+    assert!(!delta.contains("Jane"));
+    assert!(delta.contains("\"removed\":{\"unit\":[\"Jim\"]}"));
+    // Deltas are not complete saves.
+    assert!(app.world.has_unsaved_changes::<All>());
+
+    let mut other = self::app();
+    other.world.load_from::<All, _>(&base);
+    assert_eq!(other.world.load_delta::<All>(delta.as_bytes(), "base").ok(), Some(()));
+    assert_eq!(units(&mut other), units(&mut app));
+
+    // Deltas are cumulative.
+    let jane = find(&mut app, "Jane").unwrap();
+    app.world.get_mut::<Unit>(jane).unwrap().hp = 1;
+    let delta2 = app.world.save_delta::<All, String>("base").unwrap();
+    assert!(delta2.contains("\"John\""));
+    assert!(delta2.contains("\"Jane\""));
+
+    let mut other = self::app();
+    other.world.load_from::<All, _>(&base);
+    other.world.load_delta::<All>(delta2.as_bytes(), "base").unwrap();
+    assert_eq!(units(&mut other), units(&mut app));
+}
+
+#[test]
+pub fn delta_mismatch() {
+    let mut app = app();
+    spawn(&mut app, "John", 10);
+    let base = app.world.save_to::<All, String>().unwrap();
+    let delta = app.world.save_delta::<All, String>("base").unwrap();
+    assert!(matches!(
+        app.world.load_delta::<All>(delta.as_bytes(), "other"),
+        Err(SaloError::BaseMismatch { expected, found: Some(found) }) if expected == "other" && found == "base"
+    ));
+    assert!(matches!(
+        app.world.load_delta::<All>(base.as_bytes(), "base"),
+        Err(SaloError::BaseMismatch { found: None, .. })
+    ));
+    assert_eq!(units(&mut app), [("John".to_owned(), 10)]);
+}
+
+#[test]
+pub fn tombstones() {
+    let mut app = app();
+    spawn(&mut app, "John", 10);
+    spawn(&mut app, "Jane", 10);
+    let prev = app.world.save_to::<All, String>().unwrap();
+
+    let mut replica = self::app();
+    replica.world.load_from::<All, _>(&prev);
+    assert_eq!(units(&mut replica).len(), 2);
+
+    let jane = find(&mut app, "Jane").unwrap();
+    app.world.despawn(jane);
+    spawn(&mut app, "Jim", 5);
+    let save = app.world.save_with_tombstones::<All, String>(&prev).unwrap();
+    assert!(save.contains(r#""$tombstones":["Jane"]"#));
+    assert!(!app.world.save_to::<All, String>().unwrap().contains("$tombstones"));
+
+    replica.world.load_from::<All, _>(&save);
+    assert_eq!(units(&mut replica), [("Jim".to_owned(), 5), ("John".to_owned(), 10)]);
+
+    // Live entities and filtered saves are not tombstoned.
+    let prev = app.world.save_to::<All, String>().unwrap();
+    let jim = find(&mut app, "Jim").unwrap();
+    app.world.entity_mut(jim).remove::<Unit>().insert(PathName::new("Jim"));
+    let save = app.world.save_with_tombstones::<All, String>(&prev).unwrap();
+    assert!(!save.contains("$tombstones"), "{}", save);
+
+    let john = find(&mut app, "John").unwrap();
+    app.world.despawn(john);
+    app.world.insert_resource(TypeFilter::<All>::exclude(["unit"]));
+    assert!(!app.world.save_with_tombstones::<All, String>(&prev).unwrap().contains("$tombstones"));
+    app.world.remove_resource::<TypeFilter<All>>();
+    app.world.insert_resource(SaveFilter::<All>::new([jim]));
+    assert!(!app.world.save_with_tombstones::<All, String>(&prev).unwrap().contains("$tombstones"));
+    app.world.remove_resource::<SaveFilter<All>>();
+    let save = app.world.save_with_tombstones::<All, String>(&prev).unwrap();
+    assert!(save.contains(r#""$tombstones":["John"]"#), "{}", save);
+}
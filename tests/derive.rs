@@ -0,0 +1,253 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaveLoad, SaveLoadMapped, SaveLoadCore};
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, serde::Serialize, serde::Deserialize, SaveLoad)]
+#[salo(name = "unit", path = "self.name")]
+struct Unit {
+    name: String,
+}
+
+#[derive(Debug, Clone, Component, serde::Serialize, serde::Deserialize, SaveLoad)]
+#[salo(name = "weapon")]
+struct Weapon {
+    #[salo(path)]
+    id: u32,
+    damage: f32,
+}
+
+#[derive(Debug, Clone, Component, serde::Serialize, serde::Deserialize, SaveLoad)]
+struct Anonymous(#[salo(path)] String);
+
+#[derive(Debug, Clone, Component, serde::Serialize, serde::Deserialize, SaveLoad)]
+#[salo(name = "hp", via = "HpData")]
+struct Hp(f32, #[serde(skip)] f32);
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct HpData(f32);
+
+impl From<&Hp> for HpData {
+    fn from(hp: &Hp) -> Self {
+        HpData(hp.0)
+    }
+}
+
+impl From<HpData> for Hp {
+    fn from(hp: HpData) -> Self {
+        Hp(hp.0, hp.0)
+    }
+}
+
+#[derive(Debug, Clone, Component, serde::Serialize, serde::Deserialize, SaveLoad)]
+#[salo(name = "stats")]
+struct Stats {
+    #[salo(path)]
+    id: String,
+    #[salo(rename = "hp")]
+    health: u32,
+    #[salo(default)]
+    mana: u32,
+    #[salo(skip)]
+    cache: Vec<u32>,
+}
+
+#[test]
+pub fn derive_core() {
+    assert_eq!(<Unit as SaveLoadCore>::type_name(), "unit");
+    assert_eq!(<Weapon as SaveLoadCore>::type_name(), "weapon");
+    assert_eq!(<Hp as SaveLoadMapped>::type_name(), "hp");
+    assert_eq!(<Unit as SaveLoadCore>::path_name(&Unit { name: "John".to_owned() }).unwrap(), "John");
+    assert_eq!(<Weapon as SaveLoadCore>::path_name(&Weapon { id: 3, damage: 1.0 }).unwrap(), "3");
+    assert_eq!(<Anonymous as SaveLoadCore>::path_name(&Anonymous("A".to_owned())).unwrap(), "A");
+}
+
+#[test]
+pub fn derive_roundtrip() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Weapon>()
+        .register::<Hp>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Unit { name: "John".to_owned() }, Hp(5.0, 0.0)));
+        commands.spawn(Weapon { id: 1, damage: 2.0 });
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains("\"unit\""));
+    assert!(save.contains("\"weapon\""));
+    assert!(save.contains("\"hp\""));
+
+    let mut app2 = App::new();
+    app2.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Weapon>()
+        .register::<Hp>()
+    );
+    app2.world.load_from::<All, _>(&save);
+    let hp = app2.world.run_system_once(|q: Query<&Hp>| q.single().clone());
+    assert_eq!(hp.1, 5.0);
+    assert_eq!(app2.world.run_system_once(|q: Query<&Weapon>| q.single().damage), 2.0);
+}
+
+#[test]
+pub fn derive_fields() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Stats>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Stats { id: "a".to_owned(), health: 3, mana: 4, cache: vec![1, 2] });
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains("\"hp\":3"));
+    assert!(!save.contains("health"));
+    assert!(!save.contains("cache"));
+    assert_eq!(<Stats as SaveLoadMapped>::path_name(&Stats { id: "b".to_owned(), health: 0, mana: 0, cache: Vec::new() }).unwrap(), "b");
+
+    let mut app2 = App::new();
+    app2.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Stats>()
+    );
+    app2.world.load_from::<All, _>(&save.replace(",\"mana\":4", ""));
+    let stats = app2.world.run_system_once(|q: Query<&Stats>| q.single().clone());
+    assert_eq!((stats.id.as_str(), stats.health, stats.mana, stats.cache.len()), ("a", 3, 0, 0));
+}
+
+#[derive(Debug, Clone, PartialEq, Component, serde::Serialize, serde::Deserialize, SaveLoad)]
+#[salo(name = "pos", path = "\"Origin\"", inline)]
+struct Pos {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Component, serde::Serialize, serde::Deserialize, SaveLoad)]
+#[salo(name = "level", inline)]
+struct Level(u32);
+
+fn inline_roundtrip<M: bevy_salo::Marker>(check: impl Fn(&str)) {
+    let new_app = || {
+        let mut app = App::new();
+        app.add_plugins(SaveLoadPlugin::new::<M>()
+            .register::<Pos>()
+            .register::<Level>()
+        );
+        app
+    };
+    let mut app = new_app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Pos { x: 1, y: 2 }, Level(3)));
+    });
+    let save = app.world.save_to::<M, String>().unwrap();
+    check(&save);
+
+    let mut app2 = new_app();
+    app2.world.load_from::<M, _>(&save);
+    let loaded = app2.world.run_system_once(|q: Query<(&Pos, &Level)>| {
+        q.iter().map(|(p, l)| (p.clone(), l.clone())).collect::<Vec<_>>()
+    });
+    assert_eq!(loaded, vec![(Pos { x: 1, y: 2 }, Level(3))]);
+}
+
+#[test]
+pub fn derive_inline() {
+    assert!(<Pos as SaveLoadCore>::inline());
+    assert!(!<Unit as SaveLoadCore>::inline());
+    inline_roundtrip::<All>(|save| {
+        assert!(save.contains(r#"{"path":"Origin","x":1,"y":2}"#));
+        // Not a map, written in `value`.
+        assert!(save.contains(r#""value":3"#));
+    });
+    #[cfg(feature="ron")]
+    inline_roundtrip::<bevy_salo::All<bevy_salo::methods::Ron<false>>>(|save| {
+        assert!(save.contains(r#"{"path":"Origin","x":1,"y":2}"#));
+    });
+}
+
+#[derive(Debug, Clone, PartialEq, Component, serde::Serialize, serde::Deserialize, SaveLoad)]
+#[salo(name = "stat", version = 1, migrate = "migrate_stat")]
+struct Stat {
+    health: u32,
+    max: u32,
+}
+
+#[derive(Debug, Clone, Component, serde::Serialize, serde::Deserialize, SaveLoad)]
+#[salo(name = "stat")]
+struct StatV0 {
+    hp: u32,
+}
+
+fn migrate_stat<S: bevy_salo::methods::SerializationMethod>(version: u32, value: S::Value) -> anyhow::Result<S::Value> {
+    match version {
+        0 => {
+            let old: StatV0 = S::deserialize_value(value)?;
+            S::serialize_value(&Stat { health: old.hp, max: 10 })
+        },
+        _ => Ok(value),
+    }
+}
+
+fn version_roundtrip<M: bevy_salo::Marker>(check: impl Fn(&[u8])) {
+    fn stats(app: &mut App) -> Vec<Stat> {
+        app.world.run_system_once(|q: Query<&Stat>| q.iter().cloned().collect::<Vec<_>>())
+    }
+    let mut old = App::new();
+    old.add_plugins(SaveLoadPlugin::new::<M>().register::<StatV0>());
+    old.world.spawn(StatV0 { hp: 5 });
+    let save = old.world.save_to::<M, Vec<u8>>().unwrap();
+
+    let new_app = || {
+        let mut app = App::new();
+        app.add_plugins(SaveLoadPlugin::new::<M>().register::<Stat>());
+        app
+    };
+    let mut app = new_app();
+    app.world.load_from_bytes::<M>(&save);
+    assert_eq!(stats(&mut app), [Stat { health: 5, max: 10 }]);
+
+    app.world.run_system_once(|mut q: Query<&mut Stat>| q.single_mut().max = 20);
+    let save = app.world.save_to::<M, Vec<u8>>().unwrap();
+    check(&save);
+    let mut app2 = new_app();
+    app2.world.load_from_bytes::<M>(&save);
+    assert_eq!(stats(&mut app2), [Stat { health: 5, max: 20 }]);
+}
+
+#[test]
+pub fn derive_version() {
+    assert_eq!(<Stat as SaveLoad>::version(), 1);
+    assert_eq!(<StatV0 as SaveLoad>::version(), 0);
+    version_roundtrip::<All>(|save| {
+        assert!(std::str::from_utf8(save).unwrap().contains(r#""$versions":{"stat":1}"#));
+    });
+    // Values of binary formats are migrated the same way.
+    #[cfg(feature="postcard")]
+    version_roundtrip::<bevy_salo::All<bevy_salo::methods::Postcard>>(|_| ());
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Component, serde::Serialize, serde::Deserialize, SaveLoad)]
+#[salo(name = "gold", on_missing)]
+struct Gold(u32);
+
+#[derive(Debug, Clone, PartialEq, Component, serde::Serialize, serde::Deserialize, SaveLoad)]
+#[salo(name = "mana", on_missing = "Mana::full", fills = "unit", fills = "npc")]
+struct Mana(u32);
+
+impl Mana {
+    fn full() -> Self {
+        Mana(100)
+    }
+}
+
+#[test]
+pub fn derive_on_missing() {
+    assert_eq!(<Gold as SaveLoad>::on_missing(), Some(Gold(0)));
+    assert_eq!(<Mana as SaveLoad>::on_missing(), Some(Mana(100)));
+    assert_eq!(<Mana as SaveLoad>::fills(), ["unit", "npc"]);
+    assert!(<Gold as SaveLoad>::fills().is_empty());
+    assert_eq!(<Unit as SaveLoad>::on_missing().map(|u| u.name), None);
+}
@@ -0,0 +1,55 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, entity::Entity, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, EntityPath, DiffKind, diff};
+use std::borrow::Cow;
+
+mod common;
+use common::Unit;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Weapon(u32);
+
+impl bevy_salo::SaveLoadCore for Weapon {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("weapon")
+    }
+}
+
+#[test]
+pub fn diff_saves() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Weapon>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Unit { name: "John".to_owned() }, Weapon(1)));
+        commands.spawn(Unit { name: "Jane".to_owned() });
+    });
+    let a = app.world.save_to::<All, Vec<u8>>().unwrap();
+    assert!(diff::<All>(&a, &a).unwrap().is_empty());
+
+    app.world.run_system_once(|mut commands: Commands, q: Query<(Entity, &Unit)>| {
+        for (entity, unit) in q.iter() {
+            match unit.name.as_str() {
+                "John" => { commands.entity(entity).insert(Weapon(2)); },
+                _ => commands.entity(entity).despawn(),
+            }
+        }
+        commands.spawn(Unit { name: "Bob".to_owned() });
+    });
+    let b = app.world.save_to::<All, Vec<u8>>().unwrap();
+    let result = diff::<All>(&a, &b).unwrap();
+    assert_eq!(result.added, vec![EntityPath::parse("Bob")]);
+    assert_eq!(result.removed, vec![EntityPath::parse("Jane")]);
+    let john = EntityPath::parse("John");
+    let changes: Vec<_> = result.get(&john).map(|c| (c.type_name.as_str(), c.kind)).collect();
+    assert_eq!(changes, vec![("weapon", DiffKind::Changed)]);
+    assert_eq!(result.to_string(), "+ Bob\n- Jane\n+ Bob [unit]\n- Jane [unit]\n~ John [weapon]\n");
+
+    assert!(diff::<All>(&a, b"not a save").is_err());
+}
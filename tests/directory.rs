@@ -0,0 +1,35 @@
+use bevy_salo::SaveDirectory;
+
+#[test]
+pub fn save_directory() {
+    let root = std::env::temp_dir().join("bevy_salo_save_directory");
+    let _ = std::fs::remove_dir_all(&root);
+    let dir = SaveDirectory::new(&root).with_extension("json");
+    assert_eq!(dir.list().unwrap(), Vec::<String>::new());
+
+    assert_eq!(dir.path("../../etc/passwd").unwrap(), root.join("______etc_passwd.json"));
+    assert_eq!(dir.path("  Slot 1 ").unwrap(), root.join("Slot 1.json"));
+    assert!(dir.path("..").is_err());
+    assert!(dir.path("").is_err());
+
+    std::fs::write(dir.file("slot2").unwrap(), "{}").unwrap();
+    std::fs::write(dir.file("slot1").unwrap(), "{}").unwrap();
+    std::fs::write(root.join("other.txt"), "").unwrap();
+    assert_eq!(dir.list().unwrap(), ["slot1", "slot2"]);
+    assert!(dir.exists("slot1"));
+
+    assert!(dir.rename("slot1", "slot2").is_err());
+    dir.rename("slot1", "slot3").unwrap();
+    assert_eq!(dir.list().unwrap(), ["slot2", "slot3"]);
+    dir.delete("slot2").unwrap();
+    assert_eq!(dir.list().unwrap(), ["slot3"]);
+    assert!(!dir.exists("slot2"));
+}
+
+#[cfg(feature="dirs")]
+#[test]
+pub fn platform_default() {
+    if let Some(dir) = SaveDirectory::platform_default("My Game") {
+        assert!(dir.root().ends_with("My Game/saves"));
+    }
+}
@@ -0,0 +1,126 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, AbortSave, SaloError, SaloDocument, SaveTarget};
+
+mod common;
+use common::Unit;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app
+}
+
+fn units(app: &mut App) -> Vec<String> {
+    let mut units = app.world.run_system_once(|q: Query<&Unit>| q.iter().map(|u| u.name.clone()).collect::<Vec<_>>());
+    units.sort();
+    units
+}
+
+#[test]
+pub fn snapshot_then_reset() {
+    let mut level1 = app();
+    level1.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+        commands.spawn(Unit { name: "Jane".to_owned() });
+    });
+
+    level1.world.insert_resource(AbortSave::<All>::new("cutscene"));
+    assert!(matches!(level1.world.snapshot_then_reset::<All>(), Err(SaloError::Aborted(_))));
+    assert_eq!(units(&mut level1).len(), 2);
+
+    let document = level1.world.snapshot_then_reset::<All>().unwrap();
+    assert!(units(&mut level1).is_empty());
+
+    let mut level2 = app();
+    level2.world.load_document::<All>(document.clone()).unwrap();
+    assert_eq!(units(&mut level2), ["Jane", "John"]);
+
+    // Named entities are matched when applied again.
+    level2.world.load_document::<All>(document).unwrap();
+    assert_eq!(units(&mut level2).len(), 2);
+}
+
+#[test]
+pub fn snapshot_registry() {
+    use bevy_salo::SnapshotRegistry;
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+        commands.spawn(Unit { name: "Jane".to_owned() });
+    });
+    app.world.store_snapshot::<All>("before_boss").unwrap();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "Boss".to_owned() });
+    });
+    app.world.store_snapshot::<All>("boss").unwrap();
+    let names: Vec<_> = app.world.resource::<SnapshotRegistry<All>>().list().map(str::to_owned).collect();
+    assert_eq!(names, ["before_boss", "boss"]);
+
+    app.world.restore_snapshot::<All>("before_boss").unwrap();
+    assert_eq!(units(&mut app), ["Jane", "John"]);
+    app.world.restore_snapshot::<All>("boss").unwrap();
+    assert_eq!(units(&mut app), ["Boss", "Jane", "John"]);
+
+    assert!(app.world.resource_mut::<SnapshotRegistry<All>>().drop("boss"));
+    assert!(matches!(app.world.restore_snapshot::<All>("boss"), Err(SaloError::Deserialization(_))));
+    assert_eq!(units(&mut app), ["Boss", "Jane", "John"]);
+
+    // A snapshot that fails to load does not reset the world.
+    app.world.resource_mut::<SnapshotRegistry<All>>().insert("broken", SaloDocument::from_value(serde_json::json!(5)));
+    assert!(app.world.restore_snapshot::<All>("broken").is_err());
+    assert_eq!(units(&mut app), ["Boss", "Jane", "John"]);
+
+    // Snapshots are not complete saves or loads.
+    app.world.save::<All>(SaveTarget::String).unwrap();
+    assert!(!app.world.has_unsaved_changes::<All>());
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "Jim".to_owned() });
+    });
+    app.world.store_snapshot::<All>("jim").unwrap();
+    assert!(app.world.has_unsaved_changes::<All>());
+    app.world.restore_snapshot::<All>("jim").unwrap();
+    assert!(app.world.has_unsaved_changes::<All>());
+}
+
+#[test]
+pub fn document_transform() {
+    use bevy_salo::{DocumentTransform, SaloErrors};
+
+    let mut level1 = app();
+    level1.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+        commands.spawn(Unit { name: "Jane".to_owned() });
+    });
+    level1.world.insert_resource(DocumentTransform::<All>::new()
+        .on_save(|document| {
+            document.retain("unit", |path, _| path.name() != "Jane");
+            document.set_metadata(Some(serde_json::json!("redacted")));
+            Ok(())
+        })
+    );
+    let bytes = level1.world.save_to::<All, Vec<u8>>().unwrap();
+
+    let mut level2 = app();
+    level2.world.insert_resource(DocumentTransform::<All>::new()
+        .on_load(|document| {
+            assert_eq!(document.metadata(), Some(&serde_json::json!("redacted")));
+            document.set_metadata(None);
+            Ok(())
+        })
+    );
+    level2.world.load_from_bytes::<All>(&bytes);
+    assert_eq!(units(&mut level2), ["John"]);
+
+    level1.world.insert_resource(DocumentTransform::<All>::new()
+        .on_save(|_| Err(anyhow::anyhow!("too large")))
+    );
+    assert!(level1.world.save_to::<All, Vec<u8>>().is_none());
+    assert!(level1.world.resource::<SaloErrors<All>>().iter().any(|e| matches!(e, SaloError::Aborted(_))));
+}
@@ -0,0 +1,50 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::{Encrypted, SerdeJson}, SaveLoadExtension, SaveEncryptionKey};
+use std::borrow::Cow;
+
+type All = bevy_salo::All<Encrypted<SerdeJson>>;
+
+#[derive(Debug, Clone, Component, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Unit {
+    name: String,
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+}
+
+fn units(app: &mut App) -> usize {
+    app.world.run_system_once(|e: Query<&Unit>| e.iter().count())
+}
+
+#[test]
+pub fn encryption() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+    });
+
+    // No key, saving fails.
+    assert_eq!(app.world.save_to::<All, Vec<u8>>(), Some(Vec::new()));
+
+    app.world.insert_resource(SaveEncryptionKey::<All>::new([7; 32]));
+    let buffer = app.world.save_to::<All, Vec<u8>>().unwrap();
+    assert!(!buffer.windows(4).any(|w| w == b"John"));
+    app.world.remove_serialized_components::<All>();
+
+    app.world.insert_resource(SaveEncryptionKey::<All>::new([8; 32]));
+    app.world.load_from_bytes::<All>(&buffer);
+    assert_eq!(units(&mut app), 0);
+
+    app.world.insert_resource(SaveEncryptionKey::<All>::new([7; 32]));
+    app.world.load_from_bytes::<All>(&buffer);
+    assert_eq!(units(&mut app), 1);
+}
@@ -0,0 +1,72 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{entity::Entity, system::{RunSystemOnce, Commands, Query, Res}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaloEntityMap};
+
+mod common;
+use common::Unit;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<SaloEntityMap<u32>>()
+        .register_resource::<SaloEntityMap<String>>()
+    );
+    app
+}
+
+fn find(app: &mut App, name: &'static str) -> Entity {
+    app.world.run_system_once(move |query: Query<(Entity, &Unit)>| {
+        query.iter().find(|(_, unit)| unit.name == name).unwrap().0
+    })
+}
+
+#[test]
+pub fn entity_map() {
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        let john = commands.spawn(Unit { name: "John".to_owned() }).id();
+        let jane = commands.spawn(Unit { name: "Jane".to_owned() }).id();
+        commands.spawn(SaloEntityMap::from_iter([(john, 1u32), (jane, 2u32)]));
+        commands.insert_resource(SaloEntityMap::from_iter([(john, "friend".to_owned())]));
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains("SaloEntityMap<u32>"));
+    assert!(save.contains("SaloEntityMap<String>"));
+    assert!(save.contains("\"John\""));
+
+    let mut app2 = self::app();
+    app2.world.load_from::<All, _>(&save);
+    let john = find(&mut app2, "John");
+    let jane = find(&mut app2, "Jane");
+    let map = app2.world.run_system_once(|query: Query<&SaloEntityMap<u32>>| {
+        query.single().clone()
+    });
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&john), Some(&1));
+    assert_eq!(map.get(&jane), Some(&2));
+    let res = app2.world.run_system_once(|res: Res<SaloEntityMap<String>>| res.clone());
+    assert_eq!(res.get(&john).map(|s| s.as_str()), Some("friend"));
+}
+
+#[test]
+pub fn entity_map_order_and_collisions() {
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        let entities: Vec<_> = ["Jim", "Ann", "Zed", "Bob"].into_iter()
+            .map(|name| commands.spawn(Unit { name: name.to_owned() }).id())
+            .collect();
+        commands.spawn(SaloEntityMap::from_iter(entities.into_iter().zip(0u32..)));
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains(r#"[["Ann",1],["Bob",3],["Jim",0],["Zed",2]]"#));
+
+    let save = r#"{"unit":[{"path":"John","value":{"name":"John"}}],"SaloEntityMap<u32>":[{"value":[["John",1],["John",2]]}]}"#;
+    let mut app2 = self::app();
+    app2.world.load_from_str::<All>(save);
+    assert_eq!(app2.world.run_system_once(|query: Query<&SaloEntityMap<u32>>| query.iter().count()), 0);
+}
@@ -0,0 +1,53 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands}, event::Events};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaloEvent, SaloEventKind};
+use std::borrow::Cow;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Unit {
+    name: String,
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+}
+
+fn drain(app: &mut App) -> Vec<SaloEventKind> {
+    app.world.resource_mut::<Events<SaloEvent<All>>>().drain().map(|e| e.kind).collect()
+}
+
+#[test]
+pub fn progress_events() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+        commands.spawn(Unit { name: "Jane".to_owned() });
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert_eq!(drain(&mut app), [
+        SaloEventKind::SaveStarted,
+        SaloEventKind::TypeSerialized { name: "unit".into(), count: 2 },
+        SaloEventKind::SaveFinished,
+    ]);
+    app.world.load_from::<All, _>(&save);
+    assert_eq!(drain(&mut app), [
+        SaloEventKind::LoadStarted,
+        SaloEventKind::TypeDeserialized { name: "unit".into(), count: 2 },
+        SaloEventKind::LoadFinished,
+    ]);
+
+    app.world.load_from::<All, _>(&"{".to_owned());
+    assert_eq!(drain(&mut app), [
+        SaloEventKind::LoadStarted,
+        SaloEventKind::LoadFailed,
+    ]);
+}
@@ -0,0 +1,317 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, FileWriteOptions, SaloError, SaloErrors};
+use std::borrow::Cow;
+
+type All = bevy_salo::All<SerdeJson>;
+
+#[derive(Debug, Clone, Component, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Unit {
+    name: String,
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+}
+
+fn units(app: &mut App) -> usize {
+    app.world.run_system_once(|e: Query<&Unit>| e.iter().count())
+}
+
+#[test]
+pub fn atomic_write() {
+    let dir = std::env::temp_dir().join("bevy_salo_atomic_write");
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("save.json");
+    let file = file.to_str().unwrap();
+    let _ = std::fs::remove_file(format!("{}.bak", file));
+
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+    });
+    app.world.save_to_file::<All>(file).unwrap();
+    assert!(!std::path::Path::new(&format!("{}.tmp", file)).exists());
+
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "Jane".to_owned() });
+    });
+    app.world.save_to_file_opts::<All>(file, FileWriteOptions { atomic: true, backup: true }).unwrap();
+    assert!(!std::path::Path::new(&format!("{}.tmp", file)).exists());
+
+    app.world.remove_serialized_components::<All>();
+    app.world.load_from_file::<All>(file).unwrap();
+    assert_eq!(units(&mut app), 2);
+    app.world.remove_serialized_components::<All>();
+    app.world.load_from_file::<All>(&format!("{}.bak", file)).unwrap();
+    assert_eq!(units(&mut app), 1);
+
+    // A failed write removes its temporary file.
+    let failed = FileWriteOptions::default().write(file, |temp| {
+        std::fs::write(temp, "partial")?;
+        anyhow::bail!("interrupted")
+    });
+    assert!(failed.is_err());
+    assert!(!std::path::Path::new(&format!("{}.tmp", file)).exists());
+}
+
+#[test]
+pub fn file_errors() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    let dir = std::env::temp_dir().join("bevy_salo_file_errors");
+    let file = dir.join("missing.json");
+    assert!(matches!(
+        app.world.load_from_file::<All>(file.to_str().unwrap()),
+        Err(SaloError::Deserialization(_))
+    ));
+    assert!(app.world.resource::<SaloErrors<All>>().is_empty());
+    assert!(app.world.save_to_file::<All>(file.to_str().unwrap()).is_err());
+}
+
+#[derive(Debug, Clone, Component, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Weapon(u32);
+
+impl bevy_salo::SaveLoadCore for Weapon {}
+
+fn archive(format: bevy_salo::ArchiveFormat, path: &std::path::Path) {
+    let path = path.to_str().unwrap();
+    let plugin = || SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Weapon>();
+    let mut app = App::new();
+    app.add_plugins(plugin());
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Unit { name: "John".to_owned() }, Weapon(3)));
+        commands.spawn(Unit { name: "Jane".to_owned() });
+    });
+    app.world.save_to_archive::<All>(path, format).unwrap();
+
+    let mut app = App::new();
+    app.add_plugins(plugin());
+    app.world.load_from_archive::<All>(path, format).unwrap();
+    assert_eq!(units(&mut app), 2);
+    let weapons = app.world.run_system_once(|e: Query<&Weapon>| e.iter().cloned().collect::<Vec<_>>());
+    assert_eq!(weapons, vec![Weapon(3)]);
+}
+
+#[test]
+pub fn archive_directory() {
+    let dir = std::env::temp_dir().join("bevy_salo_archive");
+    let _ = std::fs::remove_dir_all(&dir);
+    archive(bevy_salo::ArchiveFormat::Directory, &dir);
+    assert!(dir.join("unit.json").exists());
+    assert!(dir.join("manifest.json").exists());
+    assert!(dir.join("files__Weapon.json").exists());
+}
+
+#[derive(Debug, Clone, Component, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Shouting(u32);
+
+impl bevy_salo::SaveLoadCore for Shouting {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("UNIT")
+    }
+}
+
+#[test]
+pub fn archive_file_names() {
+    let dir = std::env::temp_dir().join("bevy_salo_archive_names");
+    let _ = std::fs::remove_dir_all(&dir);
+    let path = dir.to_str().unwrap();
+    let format = bevy_salo::ArchiveFormat::Directory;
+    let plugin = || SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Shouting>();
+    let mut app = App::new();
+    app.add_plugins(plugin());
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Unit { name: "John".to_owned() }, Shouting(1)));
+    });
+    app.world.save_to_archive::<All>(path, format).unwrap();
+    // Names differing only in case do not collide on case-insensitive file systems.
+    let manifest: serde_json::Value = serde_json::from_slice(&std::fs::read(dir.join("manifest.json")).unwrap()).unwrap();
+    let types = manifest["types"].as_object().unwrap();
+    let files: std::collections::HashSet<_> = types.values().map(|f| f.as_str().unwrap().to_ascii_lowercase()).collect();
+    assert_eq!(files.len(), 2);
+
+    let mut app = App::new();
+    app.add_plugins(plugin());
+    app.world.load_from_archive::<All>(path, format).unwrap();
+    assert_eq!(units(&mut app), 1);
+
+    // Files outside of the archive are rejected.
+    for file in ["../unit.json", "/etc/passwd", "sub/unit.json", "..", ""] {
+        let mut manifest = manifest.clone();
+        manifest["types"]["unit"] = file.into();
+        std::fs::write(dir.join("manifest.json"), manifest.to_string()).unwrap();
+        let mut app = App::new();
+        app.add_plugins(plugin());
+        assert!(app.world.load_from_archive::<All>(path, format).is_err(), "{}", file);
+    }
+}
+
+#[cfg(feature="zip")]
+#[test]
+pub fn archive_zip() {
+    let dir = std::env::temp_dir().join("bevy_salo_archive_zip");
+    std::fs::create_dir_all(&dir).unwrap();
+    archive(bevy_salo::ArchiveFormat::Zip, &dir.join("save.zip"));
+}
+
+#[derive(Debug, Clone, PartialEq, bevy_ecs::system::Resource, serde::Serialize, serde::Deserialize)]
+struct Settings {
+    volume: f32,
+}
+
+impl bevy_salo::SaveLoadResCore for Settings {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("settings")
+    }
+}
+
+#[test]
+pub fn resources_only() {
+    use bevy_salo::{LoadPolicy, TypePolicy, SaveState};
+    let dir = std::env::temp_dir().join("bevy_salo_resources_only");
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("settings.json");
+    let file = file.to_str().unwrap();
+
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register_resource::<Settings>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+    });
+    app.world.insert_resource(Settings { volume: 0.5 });
+    app.world.save_resources_to_file::<All>(file).unwrap();
+    let saved = std::fs::read_to_string(file).unwrap();
+    assert!(saved.contains("settings"));
+    assert!(!saved.contains("unit"));
+    assert!(app.world.resource::<SaveState<All>>().last_saved().is_none());
+
+    app.world.insert_resource(Settings { volume: 1.0 });
+    // Component records and required components are ignored.
+    app.world.insert_resource(LoadPolicy::<All>::new().with("unit", TypePolicy::Required));
+    std::fs::write(file, saved.replacen('{', r#"{"unit":[{"value":{"name":"Jane"}}],"#, 1)).unwrap();
+    app.world.load_resources_from_file::<All>(file).unwrap();
+    assert_eq!(app.world.resource::<Settings>(), &Settings { volume: 0.5 });
+    assert_eq!(units(&mut app), 1);
+}
+
+#[test]
+pub fn mirror_debug_dump() {
+    let dir = std::env::temp_dir().join("bevy_salo_debug_dump");
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("save.json");
+    let file = file.to_str().unwrap();
+    let dump = format!("{}.debug.json", file);
+    let _ = std::fs::remove_file(&dump);
+
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+    });
+    app.world.save_to_file::<All>(file).unwrap();
+    assert!(!std::path::Path::new(&dump).exists());
+
+    app.world.resource_mut::<bevy_salo::SaloConfig<All>>().set_mirror_debug_dump(Some("{path}.debug.json".into()));
+    app.world.save_to_file::<All>(file).unwrap();
+    let text = std::fs::read_to_string(&dump).unwrap();
+    assert!(text.starts_with("{\n"));
+    assert!(text.contains("\"John\""));
+
+    // Skipped for encrypted saves.
+    std::fs::remove_file(&dump).unwrap();
+    app.world.insert_resource(bevy_salo::SaveEncryptionKey::<All>::new([1; 32]));
+    app.world.save_to_file::<All>(file).unwrap();
+    assert!(!std::path::Path::new(&dump).exists());
+    // Strings cannot be encrypted, so they fail instead of skipping the key.
+    assert!(app.world.save::<All>(bevy_salo::SaveTarget::String).is_err());
+    assert!(app.world.load::<All>(bevy_salo::LoadSource::String(&text)).is_err());
+    app.world.remove_resource::<bevy_salo::SaveEncryptionKey<All>>();
+
+    // Failing to write the copy does not fail the save.
+    let missing = dir.join("missing").join("{path}");
+    app.world.resource_mut::<bevy_salo::SaloConfig<All>>().set_mirror_debug_dump(Some(missing.to_str().unwrap().to_owned().into()));
+    app.world.save_to_file::<All>(file).unwrap();
+
+    #[cfg(feature="postcard")]
+    {
+        type Bin = bevy_salo::All<bevy_salo::methods::Postcard>;
+        let file = dir.join("save.bin");
+        let file = file.to_str().unwrap();
+        let mut app = App::new();
+        app.add_plugins(SaveLoadPlugin::new::<Bin>()
+            .register::<Unit>()
+        );
+        app.world.run_system_once(|mut commands: Commands| {
+            commands.spawn(Unit { name: "John".to_owned() });
+        });
+        app.world.resource_mut::<bevy_salo::SaloConfig<Bin>>().set_mirror_debug_dump(Some("{path}.debug.json".into()));
+        app.world.save_to_file::<Bin>(file).unwrap();
+        let text = std::fs::read_to_string(format!("{}.debug.json", file)).unwrap();
+        // Opaque values are written as hex.
+        assert!(text.contains("\"044a6f686e\""), "{}", text);
+    }
+}
+
+#[cfg(feature="hierarchy")]
+#[test]
+pub fn save_split() {
+    use bevy_hierarchy::BuildChildren;
+    use bevy_salo::PathName;
+    let dir = std::env::temp_dir().join("bevy_salo_split");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let plugin = || SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Weapon>();
+
+    let mut app = App::new();
+    app.add_plugins(plugin());
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(PathName::new("Players")).with_children(|b| {
+            b.spawn((PathName::new("John"), Unit { name: "John".to_owned() })).with_children(|b| {
+                b.spawn(Weapon(3));
+            });
+            b.spawn((PathName::new("Jane"), Unit { name: "Jane".to_owned() }));
+        });
+        commands.spawn(Unit { name: "Jim".to_owned() });
+    });
+    let root = dir.clone();
+    let files = app.world.save_split::<All>(move |path| {
+        let name = path.split("::").take(2).collect::<Vec<_>>().join("-");
+        let name = if name.is_empty() { "world" } else { &name };
+        root.join(format!("{}.json", name)).to_str().unwrap().to_owned()
+    }).unwrap();
+    let file = |name: &str| dir.join(name).to_str().unwrap().to_owned();
+    for name in ["Players-John.json", "Players-Jane.json", "world.json"] {
+        assert!(files.contains(&file(name)));
+    }
+
+    let mut app = App::new();
+    app.add_plugins(plugin());
+    app.world.load_from_file::<All>(&file("Players-John.json")).unwrap();
+    let loaded = app.world.run_system_once(|u: Query<&Unit>, w: Query<&Weapon>| {
+        (u.iter().map(|u| u.name.clone()).collect::<Vec<_>>(), w.iter().cloned().collect::<Vec<_>>())
+    });
+    assert_eq!(loaded, (vec!["John".to_owned()], vec![Weapon(3)]));
+    app.world.load_from_file::<All>(&file("world.json")).unwrap();
+    assert_eq!(units(&mut app), 2);
+}
@@ -0,0 +1,86 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_hierarchy::BuildChildren;
+use bevy_salo::{methods::SerdeJson, SaveLoadExtension};
+
+mod common;
+use common::Unit;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+fn app() -> App {
+    common::players::<All>()
+}
+
+#[derive(Debug, Component)]
+struct Chunk;
+
+#[test]
+pub fn save_filtered() {
+    use bevy_ecs::query::With;
+    use bevy_salo::SaveFilter;
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Unit { name: "Jane".to_owned() }, Chunk));
+        commands.spawn((Unit { name: "Jim".to_owned() }, Chunk));
+    });
+    let save = app.world.save_filtered::<All, With<Chunk>, String>().unwrap();
+    assert!(save.contains("\"Jane\""));
+    assert!(save.contains("\"Jim\""));
+    assert!(!save.contains("John"));
+    assert!(app.world.get_resource::<SaveFilter<All>>().is_none());
+
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains("John"));
+
+    let jane = app.world.run_system_once(|q: Query<(bevy_ecs::entity::Entity, &Unit)>| {
+        q.iter().find(|(_, u)| u.name == "Jane").unwrap().0
+    });
+    app.world.insert_resource(SaveFilter::<All>::new([jane]));
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains("\"Jane\""));
+    assert!(!save.contains("Jim"));
+
+    // Children of filtered out parents are saved as roots.
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn_empty().with_children(|b| {
+            b.spawn((Unit::new("Kid"), Chunk));
+        });
+    });
+    let save = app.world.save_filtered::<All, With<Chunk>, String>().unwrap();
+    assert!(save.contains(r#"{"path":"Kid","value":{"name":"Kid"}}"#));
+}
+
+#[test]
+pub fn save_scope() {
+    use bevy_ecs::entity::Entity;
+    use bevy_salo::SaveFilter;
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "Jane".to_owned() });
+        commands.spawn(Unit { name: "Jim".to_owned() });
+    });
+    let find = |app: &mut App, name: &'static str| app.world.run_system_once(move |q: Query<(Entity, &Unit)>| {
+        q.iter().find(|(_, u)| u.name == name).unwrap().0
+    });
+    let jane = find(&mut app, "Jane");
+    let jim = find(&mut app, "Jim");
+
+    app.world.insert_resource(SaveFilter::<All>::new([jane, jim]).with_exclude([jim]).once());
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains("\"Jane\""));
+    assert!(!save.contains("Jim"));
+    assert!(!save.contains("John"));
+    assert!(app.world.get_resource::<SaveFilter<All>>().is_none());
+
+    app.world.insert_resource(SaveFilter::<All>::all().with_exclude([jane]).once());
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(!save.contains("Jane"));
+    assert!(save.contains("\"Jim\""));
+    assert!(save.contains("John"));
+
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains("\"Jane\""));
+}
@@ -0,0 +1,46 @@
+#![allow(deprecated)]
+
+use bevy_salo::fixtures::{self, Shape};
+use bevy_salo::methods::SerdeJson;
+use bevy_salo::{FormatLayout, LoadSource, SaloConfig, SaveLoadExtension};
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+fn roundtrip(shape: &Shape, layout: FormatLayout) {
+    let mut world = fixtures::world::<All>();
+    world.resource_mut::<SaloConfig<All>>().set_format_layout(layout);
+    let expected = fixtures::generate(&mut world, shape);
+    expected.check(&mut world).unwrap();
+    let bytes = world.save_to::<All, Vec<u8>>().unwrap();
+
+    let mut world = fixtures::world::<All>();
+    expected.check_report(&world.validate_save::<All>(&bytes).unwrap()).unwrap();
+    world.load::<All>(LoadSource::Bytes(&bytes)).unwrap();
+    expected.check(&mut world).unwrap();
+}
+
+#[test]
+pub fn shapes() {
+    for layout in [FormatLayout::TypeMajor, FormatLayout::EntityMajor] {
+        roundtrip(&Shape::new(), layout);
+        roundtrip(&Shape::new().with_roots(1).with_depth(8).with_children(1).with_links(8), layout);
+        roundtrip(&Shape::new().with_roots(20).with_depth(1).with_unnamed(50).with_seed(7), layout);
+        roundtrip(&Shape::new().with_depth(4).with_children(3).with_links(100).with_resources(false), layout);
+    }
+}
+
+#[test]
+pub fn detects_changes() {
+    let mut world = fixtures::world::<All>();
+    let expected = fixtures::generate(&mut world, &Shape::new());
+    assert_eq!(expected.paths.len(), 6);
+    assert!(expected.paths.contains("r1::r1_0"));
+    assert_eq!(expected, fixtures::generate(&mut fixtures::world::<All>(), &Shape::new()));
+
+    let node = world.query::<(bevy_ecs::entity::Entity, &fixtures::Node)>().iter(&world)
+        .find(|(_, n)| n.0 == "r0_1")
+        .map(|(e, _)| e)
+        .unwrap();
+    world.entity_mut(node).insert(fixtures::Node("renamed".to_owned()));
+    assert!(expected.check(&mut world).is_err());
+}
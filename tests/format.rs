@@ -0,0 +1,87 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, ResMut}};
+use bevy_hierarchy::BuildChildren;
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, PathName, SaveLoadExtension, SaloConfig};
+use std::borrow::Cow;
+
+mod common;
+use common::{Unit, units};
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+fn app() -> App {
+    common::players::<All>()
+}
+
+#[test]
+pub fn format_layout() {
+    use bevy_salo::FormatLayout;
+    let mut app = app();
+    app.world.run_system_once(|mut config: ResMut<SaloConfig<All>>| {
+        config.set_format_layout(FormatLayout::EntityMajor)
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains("\"$layout\":\"EntityMajor\""));
+    assert!(save.contains("\"$entities\""));
+    assert!(save.contains("\"components\":{\"unit\":{\"name\":\"John\"}}"));
+
+    // Layouts load regardless of the config.
+    let mut fresh = App::new();
+    fresh.add_plugins(SaveLoadPlugin::new::<All>().register::<Unit>());
+    fresh.world.load_from::<All, _>(&save);
+    assert_eq!(units(&mut fresh), 1);
+    let type_major = fresh.world.save_to::<All, String>().unwrap();
+    assert!(!type_major.contains("$entities"));
+
+    // Hand-written files may list an entity once with all its components.
+    let scene = r#"{"$layout":"EntityMajor","$entities":[
+        {"path":"Players::Jane","parent":"Players","components":{"unit":{"name":"Jane"}}}
+    ]}"#.to_owned();
+    fresh.world.load_from::<All, _>(&scene);
+    assert_eq!(units(&mut fresh), 2);
+    fresh.world.load_from::<All, _>(&type_major);
+    assert_eq!(units(&mut fresh), 2);
+}
+
+#[test]
+pub fn deterministic_output() {
+    #[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+    struct Hp(u32);
+
+    impl bevy_salo::SaveLoadCore for Hp {
+        fn type_name() -> Cow<'static, str> {
+            Cow::Borrowed("hp")
+        }
+    }
+
+    let names = ["Ann", "Bob", "Cid", "Dan", "Eve", "Fay"];
+    let save = |reverse: bool| {
+        let mut app = App::new();
+        app.add_plugins(SaveLoadPlugin::new::<All>()
+            .register::<Unit>()
+            .register::<Hp>()
+        );
+        app.world.resource_mut::<SaloConfig<All>>().set_deterministic(true);
+        let mut names = names.to_vec();
+        if reverse {
+            names.reverse();
+        }
+        app.world.run_system_once(move |mut commands: Commands| {
+            commands.spawn(PathName::new("Players")).with_children(|b| {
+                for name in &names {
+                    b.spawn((Unit { name: name.to_string() }, Hp(name.len() as u32)));
+                }
+            });
+            commands.spawn(Unit { name: "Root".to_owned() });
+        });
+        app.world.save_to::<All, String>().unwrap()
+    };
+    let a = save(false);
+    assert_eq!(a, save(true));
+    let ann = a.find("Players::Ann").unwrap();
+    let fay = a.find("Players::Fay").unwrap();
+    assert!(ann < fay);
+    assert!(a.find("\"hp\"").unwrap() < a.find("\"unit\"").unwrap());
+}
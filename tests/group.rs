@@ -0,0 +1,121 @@
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaveTarget, LoadSource, MarkerComponent, SaloError};
+use std::borrow::Cow;
+
+#[derive(Debug, Default, Component)]
+struct Terrain;
+
+impl MarkerComponent for Terrain {
+    type Method = SerdeJson<false>;
+}
+
+#[derive(Debug, Default, Component)]
+struct Units;
+
+impl MarkerComponent for Units {
+    type Method = SerdeJson<false>;
+}
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Tile {
+    name: String,
+}
+
+impl bevy_salo::SaveLoadCore for Tile {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("tile")
+    }
+
+    fn path_name(&self) -> Option<Cow<'static, str>> {
+        Some(self.name.clone().into())
+    }
+}
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Unit {
+    name: String,
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+
+    fn path_name(&self) -> Option<Cow<'static, str>> {
+        Some(self.name.clone().into())
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<Terrain>().register::<Tile>());
+    app.add_plugins(SaveLoadPlugin::new::<Units>().register::<Unit>());
+    app
+}
+
+fn count(app: &mut App) -> (usize, usize) {
+    app.world.run_system_once(|tiles: Query<&Tile>, units: Query<&Unit>| {
+        (tiles.iter().count(), units.iter().count())
+    })
+}
+
+#[test]
+pub fn group() {
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Terrain, Tile { name: "Grass".to_owned() }));
+        commands.spawn((Terrain, Tile { name: "Water".to_owned() }));
+        commands.spawn((Units, Unit { name: "John".to_owned() }));
+    });
+    let save = app.world.save_group::<(Terrain, Units)>(SaveTarget::String).unwrap().into_string().unwrap();
+    assert!(save.contains("\"group::Terrain\":{"));
+    assert!(save.contains("\"group::Units\":{"));
+
+    let bytes = app.world.save_group::<(Terrain, Units)>(SaveTarget::Bytes).unwrap().into_bytes().unwrap();
+    // Single marker saves are not groups.
+    let single = app.world.save::<Terrain>(SaveTarget::String).unwrap().into_string().unwrap();
+
+    let mut app2 = self::app();
+    app2.world.load_group::<(Terrain, Units)>(LoadSource::String(&save)).unwrap();
+    assert_eq!(count(&mut app2), (2, 1));
+
+    let mut app2 = self::app();
+    app2.world.load_group::<(Terrain, Units)>(LoadSource::Bytes(&bytes)).unwrap();
+    assert_eq!(count(&mut app2), (2, 1));
+
+    let mut app2 = self::app();
+    assert!(matches!(
+        app2.world.load_group::<(Terrain, Units)>(LoadSource::String(&single)),
+        Err(SaloError::Deserialization(_))
+    ));
+    assert_eq!(count(&mut app2), (0, 0));
+}
+
+mod other {
+    #[derive(Debug, Default, bevy_ecs::component::Component)]
+    pub struct Terrain;
+
+    impl bevy_salo::MarkerComponent for Terrain {
+        type Method = bevy_salo::methods::SerdeJson<false>;
+    }
+}
+
+#[test]
+pub fn group_keys() {
+    let mut app = app();
+    app.add_plugins(SaveLoadPlugin::new::<other::Terrain>().register::<Tile>());
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Terrain, Tile { name: "Grass".to_owned() }));
+        commands.spawn((other::Terrain, Tile { name: "Sand".to_owned() }));
+    });
+    let save = app.world.save_group::<(Terrain, other::Terrain)>(SaveTarget::String).unwrap().into_string().unwrap();
+    assert!(save.contains("\"group::Terrain\":{"));
+    assert!(save.contains("\"group::other::Terrain\":{"));
+    assert!(save.contains("Sand"));
+
+    assert!(matches!(
+        app.world.save_group::<(Terrain, Terrain)>(SaveTarget::String),
+        Err(SaloError::Serialization(_))
+    ));
+}
@@ -0,0 +1,173 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, ResMut}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaloHistory, SaloOperation, SaloConfig};
+use std::borrow::Cow;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Unit {
+    name: String,
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+}
+
+#[test]
+pub fn history() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+    });
+    app.world.resource_mut::<SaloHistory<All>>().request("autosave", "checkpoint");
+    let save = app.world.save_to::<All, String>().unwrap();
+    app.world.load_from::<All, _>(&save);
+    app.world.load_from_bytes::<All>(b"not json");
+
+    let history = app.world.resource::<SaloHistory<All>>();
+    let entries: Vec<_> = history.iter().collect();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[0].operation, SaloOperation::Save);
+    assert_eq!(entries[0].requester.as_deref(), Some("autosave"));
+    assert_eq!(entries[0].reason.as_deref(), Some("checkpoint"));
+    assert_eq!(entries[0].size, Some(save.len()));
+    assert!(entries[0].is_ok());
+    assert_eq!(entries[1].operation, SaloOperation::Load);
+    assert_eq!(entries[1].requester, None);
+    assert_eq!(entries[1].size, Some(save.len()));
+    assert!(entries[1].is_ok());
+    assert!(!entries[2].is_ok());
+    let report = history.report();
+    assert_eq!(report.lines().count(), 3);
+    assert!(report.lines().next().unwrap().contains("by autosave (checkpoint): ok"));
+
+    app.world.run_system_once(|mut history: ResMut<SaloHistory<All>>| history.set_capacity(1));
+    let history = app.world.resource::<SaloHistory<All>>();
+    assert_eq!(history.len(), 1);
+    assert!(!history.last().unwrap().is_ok());
+}
+
+#[test]
+pub fn estimate_save_size() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    let spawn = |app: &mut App| app.world.run_system_once(|mut commands: Commands| {
+        for i in 0..10 {
+            commands.spawn(Unit { name: format!("Unit{}", i) });
+        }
+    });
+    spawn(&mut app);
+    assert_eq!(app.world.estimate_save_size::<All>(), 0);
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert_eq!(app.world.estimate_save_size::<All>(), save.len());
+
+    spawn(&mut app);
+    let estimate = app.world.estimate_save_size::<All>();
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(estimate.abs_diff(save.len()) <= save.len() / 10, "{} vs {}", estimate, save.len());
+
+    // Measured by type with stats.
+    app.world.resource_mut::<SaloConfig<All>>().set_save_stats(true);
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert_eq!(app.world.estimate_save_size::<All>(), save.len());
+}
+
+#[test]
+pub fn save_stats() {
+    #[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+    struct Inventory(Vec<u32>);
+
+    impl bevy_salo::SaveLoadCore for Inventory {
+        fn type_name() -> Cow<'static, str> {
+            Cow::Borrowed("inventory")
+        }
+    }
+
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Inventory>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "Unit".to_owned() });
+    });
+    app.world.save_to::<All, String>().unwrap();
+    // Disabled by default.
+    assert!(app.world.save_stats::<All>().is_empty());
+    app.world.resource_mut::<SaloConfig<All>>().set_save_stats(true);
+    app.world.run_system_once(|mut commands: Commands| {
+        for i in 0..2 {
+            commands.spawn(Unit { name: format!("Unit{}", i) });
+        }
+        commands.spawn(Inventory((0..1000).collect()));
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    let stats = app.world.save_stats::<All>();
+    assert_eq!(stats.types[0].type_name, "inventory");
+    assert_eq!(stats.get("unit").unwrap().entries, 3);
+    assert_eq!(stats.entries(), 4);
+    assert!(stats.bytes() <= save.len());
+    assert_eq!(stats.total, Some(save.len()));
+    assert!(stats.to_string().lines().nth(1).unwrap().starts_with("inventory"));
+}
+
+#[test]
+pub fn analytics() {
+    use bevy_salo::{SaloAnalytics, LoadSample};
+    use std::sync::{Arc, Mutex};
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    let exported = Arc::new(Mutex::new(Vec::new()));
+    let sink = exported.clone();
+    app.world.insert_resource(SaloAnalytics::<All>::default()
+        .with_exporter(move |sample: &LoadSample| sink.lock().unwrap().push(sample.records())));
+
+    app.world.load_from::<All, _>(&r#"{"unit":[{"value":{"name":"A"}}]}"#.to_owned());
+    app.world.load_from::<All, _>(&r#"{"unit":[{"value":{"name":"A"}},{"value":{"name":"B"}},{"value":{"name":"C"}}],"old":[]}"#.to_owned());
+    app.world.load_from_bytes::<All>(b"not json");
+
+    let analytics = app.world.resource::<SaloAnalytics<All>>();
+    assert_eq!(analytics.len(), 3);
+    assert_eq!(analytics.type_names(), ["old", "unit"]);
+    let stats = analytics.type_stats("unit").unwrap();
+    assert_eq!((stats.loads, stats.min, stats.max, stats.mean), (2, 1, 3, 2.0));
+    assert!(analytics.type_stats("missing").is_none());
+    assert!(analytics.iter().last().unwrap().failed);
+    assert_eq!(*exported.lock().unwrap(), [1, 3, 0]);
+}
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Grid {
+    cells: std::collections::HashMap<(u8, u8), u8>,
+}
+
+impl bevy_salo::SaveLoadCore for Grid {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("grid")
+    }
+}
+
+#[test]
+pub fn serialize_error() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Grid>()
+    );
+    // Json map keys must be strings.
+    app.world.spawn(Grid { cells: [((0, 0), 1)].into() });
+    let result = app.world.save::<All>(bevy_salo::SaveTarget::String);
+    assert!(matches!(result, Err(bevy_salo::SaloError::Serialization(_))));
+    assert!(!app.world.resource::<SaloHistory<All>>().last().unwrap().is_ok());
+}
@@ -0,0 +1,40 @@
+use bevy_ecs::{component::Component, world::World};
+use bevy_salo::{SaloEntityMap, immediate, methods::SerdeJson};
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, Component, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Gold(u32);
+
+impl bevy_salo::SaveLoadCore for Gold {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("gold")
+    }
+}
+
+type Debts = SaloEntityMap<u32>;
+
+#[test]
+pub fn immediate() {
+    let mut world = World::new();
+    let entity = world.spawn(Gold(10)).id();
+    let value = immediate::save_component::<Gold, SerdeJson>(&mut world, entity).unwrap();
+    let copy = world.spawn_empty().id();
+    immediate::apply_record::<Gold, SerdeJson>(&mut world, copy, value).unwrap();
+    assert_eq!(world.get::<Gold>(copy), Some(&Gold(10)));
+
+    assert!(immediate::save_component::<Debts, SerdeJson>(&mut world, entity).is_err());
+    assert!(immediate::apply_record::<Gold, SerdeJson>(&mut world, copy, serde_json::json!("ten")).is_err());
+
+    let debts: Debts = [(entity, 5), (copy, 6)].into_iter().collect();
+    let debtor = world.spawn(debts).id();
+    let value = immediate::save_component::<Debts, SerdeJson>(&mut world, debtor).unwrap();
+    let count = world.entities().len();
+    immediate::apply_record::<Debts, SerdeJson>(&mut world, debtor, value).unwrap();
+    // References spawn new entities.
+    assert_eq!(world.entities().len(), count + 2);
+    let mut debts: Vec<_> = world.get::<Debts>(debtor).unwrap().iter()
+        .map(|(e, v)| { assert!(*e != entity && *e != copy); *v })
+        .collect();
+    debts.sort();
+    assert_eq!(debts, vec![5, 6]);
+}
@@ -0,0 +1,40 @@
+use bevy_app::App;
+use bevy_ecs::system::{RunSystemOnce, Query};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaveTarget, LoadSource, interned_flags};
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+interned_flags!(#[save] TagsServer, Tags: u32 {
+    Fire, Water, Poison
+});
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Tags>()
+        .register_resource::<TagsServer>()
+    );
+    app.init_resource::<TagsServer>();
+    app
+}
+
+#[test]
+pub fn server_roundtrip() {
+    let mut source = app();
+    let holy = source.world.resource_mut::<TagsServer>().get_single("Holy");
+    source.world.spawn(holy | Tags::Fire);
+    let save = source.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+
+    // The loading session interned a different string in the same slot.
+    let mut target = app();
+    let ice = target.world.resource_mut::<TagsServer>().get_single("Ice");
+    assert_eq!(ice, holy);
+    target.world.load::<All>(LoadSource::String(&save)).unwrap();
+    let server = target.world.resource::<TagsServer>().clone();
+    assert_eq!(server.len(), 4);
+    assert_eq!(server.try_get_single("Holy"), Some(holy));
+    assert_eq!(server.try_get_single("Ice"), None);
+    let tags = target.world.run_system_once(|q: Query<&Tags>| q.iter().copied().collect::<Vec<_>>());
+    assert_eq!(tags, [holy | Tags::Fire]);
+    assert_eq!(server.as_str(tags[0]), "Fire|Holy");
+}
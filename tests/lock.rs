@@ -0,0 +1,56 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension};
+use std::borrow::Cow;
+use std::time::{Duration, Instant};
+
+type All = bevy_salo::All<SerdeJson>;
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Unit {
+    name: String,
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+}
+
+#[test]
+pub fn save_waits_for_lock() {
+    let dir = std::env::temp_dir().join("bevy_salo_lock");
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("save.json").to_str().unwrap().to_owned();
+
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+    });
+
+    // Simulate another process holding the lock.
+    let lock = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(format!("{}.lock", file))
+        .unwrap();
+    fs2::FileExt::lock_exclusive(&lock).unwrap();
+    let handle = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(200));
+        drop(lock);
+    });
+
+    let start = Instant::now();
+    app.world.save_to_file::<All>(&file).unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(150));
+    handle.join().unwrap();
+    assert!(!std::path::Path::new(&format!("{}.lock", file)).exists());
+
+    app.world.load_from_file::<All>(&file).unwrap();
+}
@@ -0,0 +1,235 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::{SerdeJson, Postcard}, SaveLoadExtension, SaveMetadata, peek_metadata, Marker, SaloConfig, FormatLayout, SaloError};
+use std::borrow::Cow;
+
+mod common;
+use common::Unit;
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Header {
+    timestamp: u64,
+    playtime: f32,
+    thumbnail: Vec<u8>,
+}
+
+fn header() -> Header {
+    Header { timestamp: 1700000000, playtime: 3.5, thumbnail: vec![1, 2, 3, 4] }
+}
+
+fn roundtrip<All: Marker>() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+        commands.spawn(Unit { name: "Jane".to_owned() });
+    });
+
+    // No header.
+    let bytes = app.world.save_to::<All, Vec<u8>>().unwrap();
+    assert!(peek_metadata::<All>(&bytes).unwrap().is_none());
+
+    app.world.insert_resource(SaveMetadata::<All>::new(&header()).unwrap());
+    let bytes = app.world.save_to::<All, Vec<u8>>().unwrap();
+    let peeked = peek_metadata::<All>(&bytes).unwrap().unwrap();
+    assert_eq!(peeked.get::<Header>().unwrap(), header());
+
+    let mut app2 = App::new();
+    app2.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app2.world.load_from_bytes::<All>(&bytes);
+    assert_eq!(app2.world.run_system_once(|q: Query<&Unit>| q.iter().count()), 2);
+    assert_eq!(app2.world.resource::<SaveMetadata<All>>().get::<Header>().unwrap(), header());
+
+    // Entity-major saves are read the same way, the header is skipped over.
+    app.world.remove_resource::<SaveMetadata<All>>();
+    app.world.resource_mut::<SaloConfig<All>>().set_format_layout(FormatLayout::EntityMajor);
+    let bytes = app.world.save_to::<All, Vec<u8>>().unwrap();
+    assert!(peek_metadata::<All>(&bytes).unwrap().is_none());
+    let mut app3 = App::new();
+    app3.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app3.world.load_from_bytes::<All>(&bytes);
+    assert_eq!(app3.world.run_system_once(|q: Query<&Unit>| q.iter().count()), 2);
+}
+
+#[test]
+pub fn metadata_json() {
+    roundtrip::<bevy_salo::All<SerdeJson<false>>>();
+}
+
+#[test]
+pub fn metadata_postcard() {
+    roundtrip::<bevy_salo::All<Postcard>>();
+}
+
+#[test]
+pub fn legacy_save() {
+    type All = bevy_salo::All<SerdeJson<false>>;
+    let save = r#"{"unit":[{"path":"John","value":{"name":"John"}}]}"#;
+    assert!(peek_metadata::<All>(save.as_bytes()).unwrap().is_none());
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app.world.load_from_bytes::<All>(save.as_bytes());
+    assert_eq!(app.world.run_system_once(|q: Query<&Unit>| q.iter().count()), 1);
+}
+
+#[test]
+pub fn convert_methods() {
+    type Json = bevy_salo::All<SerdeJson<false>>;
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<Json>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+    });
+    app.world.insert_resource(SaveMetadata::<Json>::new(&header()).unwrap());
+    let bytes = app.world.save_to::<Json, Vec<u8>>().unwrap();
+
+    #[cfg(feature="ron")]
+    {
+        use bevy_salo::methods::Ron;
+        type RonAll = bevy_salo::All<Ron<false>>;
+        let converted = bevy_salo::convert::<SerdeJson<false>, Ron<false>>(&bytes).unwrap();
+        let mut app2 = App::new();
+        app2.add_plugins(SaveLoadPlugin::new::<RonAll>()
+            .register::<Unit>()
+        );
+        app2.world.load_from_bytes::<RonAll>(&converted);
+        assert_eq!(app2.world.run_system_once(|q: Query<&Unit>| q.iter().count()), 1);
+        assert_eq!(app2.world.resource::<SaveMetadata<RonAll>>().get::<Header>().unwrap(), header());
+    }
+
+    // Postcard values cannot be decoded without their types.
+    type Bin = bevy_salo::All<Postcard>;
+    let mut app3 = App::new();
+    app3.add_plugins(SaveLoadPlugin::new::<Bin>()
+        .register::<Unit>()
+    );
+    app3.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "Jane".to_owned() });
+    });
+    let bytes = app3.world.save_to::<Bin, Vec<u8>>().unwrap();
+    assert!(matches!(bevy_salo::convert::<Postcard, SerdeJson<false>>(&bytes), Err(SaloError::Deserialization(_))));
+    let json = app.world.save_to::<Json, Vec<u8>>().unwrap();
+    assert!(matches!(bevy_salo::convert::<SerdeJson<false>, Postcard>(&json), Err(SaloError::Serialization(_))));
+}
+
+#[test]
+pub fn pretty_methods() {
+    use bevy_salo::methods::SerdeJsonIndent;
+    type Json = bevy_salo::All<SerdeJsonIndent<4>>;
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<Json>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+    });
+    let save = app.world.save_to::<Json, String>().unwrap();
+    assert!(save.starts_with("{\n    \"$wire\": "));
+    assert!(save.contains("\n    \"unit\": [\n        {"));
+    app.world.load_from::<Json, _>(&save);
+    assert_eq!(app.world.run_system_once(|q: Query<&Unit>| q.iter().count()), 1);
+
+    #[cfg(feature="ron")]
+    {
+        use bevy_salo::methods::{RonWith, RonFormat};
+        #[derive(Debug)]
+        struct Tabs;
+        impl RonFormat for Tabs {
+            fn pretty_config() -> ron::ser::PrettyConfig {
+                ron::ser::PrettyConfig::default().indentor("\t".to_owned())
+            }
+        }
+        type RonAll = bevy_salo::All<RonWith<Tabs>>;
+        let mut app2 = App::new();
+        app2.add_plugins(SaveLoadPlugin::new::<RonAll>()
+            .register::<Unit>()
+        );
+        app2.world.run_system_once(|mut commands: Commands| {
+            commands.spawn(Unit { name: "John".to_owned() });
+        });
+        let save = app2.world.save_to::<RonAll, String>().unwrap();
+        assert!(save.contains("\n\t\"unit\""));
+        app2.world.load_from::<RonAll, _>(&save);
+        assert_eq!(app2.world.run_system_once(|q: Query<&Unit>| q.iter().count()), 1);
+    }
+}
+
+#[test]
+pub fn record_attributes() {
+    #[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+    struct Weapon(String);
+
+    impl bevy_salo::SaveLoadCore for Weapon {
+        fn type_name() -> Cow<'static, str> {
+            Cow::Borrowed("weapon")
+        }
+
+        fn attributes(&self) -> bevy_salo::Attributes {
+            [("icon".to_owned(), format!("{}.png", self.0))].into()
+        }
+    }
+
+    fn build<All: Marker>() -> App {
+        let mut app = App::new();
+        app.add_plugins(SaveLoadPlugin::new::<All>()
+            .register::<Unit>()
+            .register::<Weapon>()
+        );
+        app
+    }
+
+    type Json = bevy_salo::All<SerdeJson<false>>;
+    type Binary = bevy_salo::All<Postcard>;
+
+    // Written by an editor.
+    let save = r##"{
+        "$attributes": {
+            "unit": [{"path": "John", "attributes": {"editor_color": "#ff0000"}}],
+            "weapon": [{"path": "John", "attributes": {"icon": "old.png", "note": "sharp"}}]
+        },
+        "unit": [{"path": "John", "value": {"name": "John"}}],
+        "weapon": [{"path": "John", "value": "sword"}]
+    }"##;
+    let mut app = build::<Json>();
+    app.world.load_from_str::<Json>(save);
+    let attributes = app.world.run_system_once(|q: Query<&bevy_salo::RecordAttributes>| q.single().clone());
+    assert_eq!(attributes.attribute("unit", "editor_color"), Some("#ff0000"));
+    assert_eq!(attributes.attribute("weapon", "note"), Some("sharp"));
+
+    let save = app.world.save_to::<Json, String>().unwrap();
+    assert!(save.contains(r##""editor_color":"#ff0000""##));
+    assert!(save.contains(r#""note":"sharp""#));
+    // Attributes of the type take precedence.
+    assert!(save.contains(r#""icon":"sword.png""#));
+    assert!(!save.contains("old.png"));
+
+    // Not written for records that are not saved.
+    app.world.run_system_once(|mut commands: Commands, q: Query<bevy_ecs::entity::Entity, bevy_ecs::query::With<Weapon>>| {
+        commands.entity(q.single()).remove::<Weapon>();
+    });
+    let save = app.world.save_to::<Json, String>().unwrap();
+    assert!(save.contains("editor_color"));
+    assert!(!save.contains("sharp"));
+
+    // Kept in binary formats.
+    let mut app = build::<Binary>();
+    app.world.spawn((Unit { name: "John".to_owned() }, Weapon("axe".to_owned())));
+    let bytes = app.world.save_to::<Binary, Vec<u8>>().unwrap();
+    let mut app = build::<Binary>();
+    app.world.load_from_bytes::<Binary>(&bytes);
+    let attributes = app.world.run_system_once(|q: Query<&bevy_salo::RecordAttributes>| q.single().clone());
+    assert_eq!(attributes.attribute("weapon", "icon"), Some("axe.png"));
+    assert!(attributes.get("unit").is_none());
+}
@@ -0,0 +1,60 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_core::Name;
+use bevy_ecs::{component::Component, entity::Entity, system::{RunSystemOnce, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, PathName, SaveLoadExtension};
+use std::borrow::Cow;
+
+mod common;
+use common::Unit;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Component, serde::Serialize, serde::Deserialize)]
+struct Health(u32);
+
+impl bevy_salo::SaveLoadCore for Health {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("health")
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Health>()
+        .register::<Unit>()
+    );
+    app
+}
+
+#[test]
+pub fn name_paths() {
+    let mut app = app();
+    app.world.spawn((Name::new("Player"), Health(10)));
+    app.world.spawn((Name::new("Editor Label"), PathName::new("Enemy"), Health(5)));
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains(r#"{"path":"Player","value":10}"#));
+    assert!(save.contains(r#"{"path":"Enemy","value":5}"#));
+    assert!(!save.contains("Editor Label"));
+
+    // Loaded onto the existing entity with the same name.
+    let mut app2 = self::app();
+    let player = app2.world.spawn(Name::new("Player")).id();
+    app2.world.load_from::<All, _>(&save);
+    let loaded = app2.world.run_system_once(|query: Query<(Entity, &Health)>| {
+        query.iter().map(|(e, h)| (e, *h)).collect::<Vec<_>>()
+    });
+    assert_eq!(loaded.len(), 2);
+    assert!(loaded.contains(&(player, Health(10))));
+}
+
+#[test]
+pub fn name_paths_fallback() {
+    let mut app = app();
+    app.world.spawn((Name::new("Editor Label"), Unit::new("John"), Health(10)));
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains(r#"{"path":"John","value":10}"#), "{}", save);
+    assert!(!save.contains("Editor Label"));
+}
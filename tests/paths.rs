@@ -0,0 +1,202 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{system::{RunSystemOnce, Commands, ResMut}};
+use bevy_hierarchy::BuildChildren;
+use bevy_salo::{methods::SerdeJson, PathName, SaveLoadExtension, SaloConfig};
+
+mod common;
+use common::{Unit, units};
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+fn app() -> App {
+    common::players::<All>()
+}
+
+#[test]
+pub fn path_root() {
+    let mut app = app();
+    app.world.run_system_once(|mut config: ResMut<SaloConfig<All>>| {
+        config.set_root("level1")
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains("\"level1::Players::John\""));
+    assert!(save.contains("\"level1::Players\""));
+
+    // Same root, matches existing entities.
+    app.world.load_from::<All, _>(&save);
+    assert_eq!(units(&mut app), 1);
+
+    // Different root, does not collide with existing entities.
+    app.world.run_system_once(|mut config: ResMut<SaloConfig<All>>| {
+        config.set_root("level2")
+    });
+    app.world.load_from::<All, _>(&save);
+    assert_eq!(units(&mut app), 2);
+
+    // Switching back still matches.
+    app.world.run_system_once(|mut config: ResMut<SaloConfig<All>>| {
+        config.set_root("level1")
+    });
+    app.world.load_from::<All, _>(&save);
+    assert_eq!(units(&mut app), 2);
+}
+
+#[test]
+pub fn entity_path() {
+    use bevy_salo::EntityPath;
+    let path = EntityPath::parse("Players::John::mainhand");
+    assert_eq!(path.segments().collect::<Vec<_>>(), ["Players", "John", "mainhand"]);
+    assert_eq!(path.name(), "mainhand");
+    assert_eq!(path.parent(), Some(EntityPath::parse("Players::John")));
+    assert_eq!(EntityPath::parse("Players").parent(), None);
+    assert_eq!(EntityPath::parse(""), EntityPath::Unique);
+    assert_eq!(EntityPath::Unique.join("Players").join("John"), EntityPath::parse("Players::John"));
+    assert!(path.starts_with(&EntityPath::parse("Players::John")));
+    assert!(path.starts_with(&EntityPath::Unique));
+    assert!(!path.starts_with(&EntityPath::parse("Players::Jo")));
+    assert!(!EntityPath::parse("Players").starts_with(&path));
+    assert_eq!(EntityPath::Entity(1).segments().count(), 0);
+}
+
+#[test]
+pub fn conflict_policy() {
+    use bevy_ecs::event::Events;
+    use bevy_salo::{ConflictPolicy, PathConflict, SaloError, SaloEvent, SaloEventKind, SaveTarget, LoadSource};
+    let set_policy = |app: &mut App, policy: ConflictPolicy| {
+        app.world.resource_mut::<SaloConfig<All>>().set_conflict_policy(policy);
+        app.world.resource_mut::<Events<SaloEvent<All>>>().clear();
+    };
+    let conflicts = |app: &mut App| app.world.resource_mut::<Events<SaloEvent<All>>>().drain()
+        .filter(|e| matches!(e.kind, SaloEventKind::Conflict(_)))
+        .count();
+
+    let mut app = app();
+    let save = app.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((PathName::new("Jim"), Unit { name: "Jimmy".to_owned() }));
+    });
+
+    set_policy(&mut app, ConflictPolicy::Error);
+    assert!(matches!(
+        app.world.save::<All>(SaveTarget::String),
+        Err(SaloError::Conflict(PathConflict::Name { .. }))
+    ));
+    assert!(app.world.save_to::<All, String>().is_none());
+
+    for policy in [ConflictPolicy::FirstWins, ConflictPolicy::LastWins] {
+        set_policy(&mut app, policy);
+        let result = app.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+        assert!(result.contains("\"path\":\"Jim\"") != result.contains("\"path\":\"Jimmy\""));
+        assert_eq!(conflicts(&mut app), 1);
+    }
+
+    // Two entities with the same path.
+    let mut app = self::app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(PathName::new("Players"));
+    });
+    set_policy(&mut app, ConflictPolicy::Error);
+    assert!(matches!(
+        app.world.load::<All>(LoadSource::String(&save)),
+        Err(SaloError::Conflict(PathConflict::Path { .. }))
+    ));
+    assert_eq!(units(&mut app), 1);
+
+    set_policy(&mut app, ConflictPolicy::FirstWins);
+    app.world.load::<All>(LoadSource::String(&save)).unwrap();
+    assert_eq!(conflicts(&mut app), 1);
+}
+
+#[cfg(not(feature="legacy-paths"))]
+#[test]
+pub fn escaped_names() {
+    use bevy_ecs::system::Query;
+    use bevy_salo::{EntityPath, SaveLoadPlugin};
+    for name in ["Sir::Lancelot", "a:", ":b", "a:::b", "back\\slash", "::", "Dr: No"] {
+        let path = EntityPath::parse("Players").join(name);
+        assert_eq!(path.name(), name);
+        assert_eq!(EntityPath::parse(&path.to_string()), path);
+    }
+    assert_eq!(EntityPath::parse("Sir\\::Lancelot").to_string(), "Sir\\::Lancelot");
+    assert_eq!(EntityPath::parse("Sir\\:\\:Lancelot").name(), "Sir::Lancelot");
+    assert_eq!(EntityPath::parse("Dr: No::Julius").segments().collect::<Vec<_>>(), ["Dr: No", "Julius"]);
+
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(PathName::new("Players::Knights")).with_children(|b| {
+            b.spawn(Unit { name: "Sir::Lancelot".to_owned() });
+        });
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains(r#""Players\\::Knights::Sir\\::Lancelot""#));
+    app.world.load_from::<All, _>(&save);
+    assert_eq!(units(&mut app), 2);
+
+    let mut app2 = App::new();
+    app2.add_plugins(SaveLoadPlugin::new::<All>().register::<Unit>());
+    app2.world.load_from::<All, _>(&save);
+    // `Players::Knights` is a single root entity.
+    let nested = app2.world.run_system_once(|q: Query<(&Unit, &bevy_hierarchy::Parent)>, parents: Query<&bevy_hierarchy::Parent>| {
+        q.iter().find(|(u, _)| u.name == "Sir::Lancelot").map(|(_, p)| parents.contains(p.get()))
+    });
+    assert_eq!(nested, Some(false));
+}
+
+#[test]
+pub fn collision_diagnostics() {
+    use bevy_salo::{ConflictPolicy, PathCollisions, SaveTarget, LoadSource};
+    let mut app = app();
+    let save = app.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(PathName::new("Players")).with_children(|b| {
+            b.spawn(Unit { name: "John".to_owned() });
+        });
+    });
+    app.world.resource_mut::<SaloConfig<All>>().set_conflict_policy(ConflictPolicy::FirstWins);
+    app.world.load::<All>(LoadSource::String(&save)).unwrap();
+    let collisions = app.world.resource::<PathCollisions<All>>();
+    assert_eq!(collisions.iter().count(), 2);
+    let collision = collisions.iter().find(|c| c.entities[0].components.iter().any(|c| c == "Unit")).unwrap();
+    assert_eq!(collision.entities.len(), 2);
+    assert_eq!(collision.entities[1].hierarchy.len(), 2);
+    assert_eq!(collision.entities[1].hierarchy[1].1.as_deref(), Some("John"));
+    assert_eq!(collision.suggestion.as_deref(), Some("Players::John_2"));
+
+    app.world.resource_mut::<SaloConfig<All>>().set_conflict_policy(ConflictPolicy::Panic);
+    let message = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        app.world.load::<All>(LoadSource::String(&save)).ok();
+    })).unwrap_err();
+    let message = message.downcast_ref::<String>().unwrap();
+    assert!(message.contains("(root)::Players::John"));
+    assert!(message.contains("Unit"));
+    assert!(message.contains("Players::John_2"));
+
+    app.world.save::<All>(SaveTarget::String).ok();
+    assert!(app.world.resource::<PathCollisions<All>>().is_empty());
+}
+
+#[test]
+pub fn path_tree() {
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn_empty().with_children(|b| {
+            b.spawn(Unit { name: "Lost".to_owned() });
+        });
+    });
+    let tree = app.world.export_path_tree::<All>();
+    let john = tree.find("Players::John").unwrap();
+    assert_eq!(john.name.as_deref(), Some("John"));
+    assert!(app.world.get::<Unit>(john.entity()).is_some());
+    assert_eq!(tree.find("Players").unwrap().children.len(), 1);
+    // The unnamed parent is listed to find the mistake.
+    assert!(tree.roots.iter().any(|n| n.name.is_none() && n.children.len() == 1));
+    assert!(tree.to_string().contains("John"));
+
+    let json = serde_json::to_string(&tree).unwrap();
+    assert_eq!(serde_json::from_str::<bevy_salo::PathTree>(&json).unwrap(), tree);
+    // Does not affect saves.
+    assert!(app.world.save_to::<All, String>().unwrap().contains("\"Players::John\""));
+}
+
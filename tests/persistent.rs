@@ -0,0 +1,63 @@
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_hierarchy::{BuildChildren, Parent};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaveTarget, LoadSource, PersistentId, PersistentIdAllocator};
+use std::borrow::Cow;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Gold(u32);
+
+impl bevy_salo::SaveLoadCore for Gold {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("gold")
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Gold>()
+    );
+    app
+}
+
+fn gold(app: &mut App) -> Vec<(Option<u64>, u32, Option<u64>)> {
+    let mut gold = app.world.run_system_once(|q: Query<(Option<&PersistentId>, &Gold, Option<&Parent>)>, ids: Query<&PersistentId>| {
+        q.iter().map(|(id, gold, parent)| (
+            id.map(|id| id.get()),
+            gold.0,
+            parent.and_then(|p| ids.get(p.get()).ok()).map(|id| id.get()),
+        )).collect::<Vec<_>>()
+    });
+    gold.sort();
+    gold
+}
+
+#[test]
+pub fn persistent_id() {
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        let bank = commands.spawn((PersistentId::new(1), Gold(1000))).id();
+        commands.spawn((PersistentId::new(2), Gold(10))).set_parent(bank);
+    });
+    let save = app.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+
+    let mut app2 = self::app();
+    app2.world.load::<All>(LoadSource::String(&save)).unwrap();
+    assert_eq!(gold(&mut app2), vec![(Some(1), 1000, None), (Some(2), 10, Some(1))]);
+    assert_eq!(app2.world.resource_mut::<PersistentIdAllocator<All>>().next(), PersistentId::new(3));
+
+    // Loading again matches existing entities instead of spawning new ones.
+    app.world.run_system_once(|mut q: Query<&mut Gold>| q.iter_mut().for_each(|mut g| g.0 += 1));
+    let save = app.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+    app2.world.load::<All>(LoadSource::String(&save)).unwrap();
+    assert_eq!(gold(&mut app2), vec![(Some(1), 1001, None), (Some(2), 11, Some(1))]);
+
+    // Entities without ids are not matched.
+    app2.world.spawn(Gold(5));
+    let save = app2.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+    app2.world.load::<All>(LoadSource::String(&save)).unwrap();
+    assert_eq!(gold(&mut app2), vec![(None, 5, None), (None, 5, None), (Some(1), 1001, None), (Some(2), 11, Some(1))]);
+}
@@ -0,0 +1,381 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, entity::Entity, query::With, system::{RunSystemOnce, Query, Commands, Res, Resource}, event::Events};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, LoadPolicy, TypePolicy, SaloErrors, SaloError, LoadResolver, Resolution, PathName, SaloEvent, SaloEventKind};
+use std::borrow::Cow;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Unit {
+    name: String,
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+}
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Weapon {
+    damage: i32,
+}
+
+impl bevy_salo::SaveLoadCore for Weapon {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("weapon")
+    }
+}
+
+fn load(policy: LoadPolicy<All>, save: &str) -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Weapon>()
+    );
+    app.world.insert_resource(policy);
+    app.world.load_from::<All, _>(&save.to_owned());
+    app
+}
+
+fn deserialized(app: &mut App) -> Vec<(String, usize)> {
+    app.world.resource_mut::<Events<SaloEvent<All>>>().drain().filter_map(|e| match e.kind {
+        SaloEventKind::TypeDeserialized { name, count } => Some((name.into_owned(), count)),
+        _ => None,
+    }).collect()
+}
+
+fn count<T: Component>(app: &mut App) -> usize {
+    app.world.run_system_once(|q: Query<&T>| q.iter().count())
+}
+
+const VALID: &str = r#"{"unit":[{"value":{"name":"John"}}],"weapon":[{"value":{"damage":1}}]}"#;
+const NO_WEAPON: &str = r#"{"unit":[{"value":{"name":"John"}}]}"#;
+const CORRUPT_WEAPON: &str = r#"{"unit":[{"value":{"name":"John"}}],"weapon":[{"value":{"damage":"x"}}]}"#;
+
+#[test]
+pub fn optional() {
+    let mut app = load(LoadPolicy::new(), NO_WEAPON);
+    assert_eq!(count::<Unit>(&mut app), 1);
+    assert!(app.world.resource::<SaloErrors<All>>().is_empty());
+
+    // Corrupt records are skipped.
+    let mut app = load(LoadPolicy::new(), CORRUPT_WEAPON);
+    assert_eq!(count::<Unit>(&mut app), 1);
+    assert_eq!(count::<Weapon>(&mut app), 0);
+    assert!(app.world.resource::<SaloErrors<All>>().is_empty());
+    let mut events = deserialized(&mut app);
+    events.sort();
+    assert_eq!(events, [("unit".to_owned(), 1), ("weapon".to_owned(), 0)]);
+}
+
+#[test]
+pub fn required() {
+    let policy = || LoadPolicy::new().with("weapon", TypePolicy::Required);
+    let mut app = load(policy(), VALID);
+    assert_eq!(count::<Unit>(&mut app), 1);
+    assert_eq!(count::<Weapon>(&mut app), 1);
+
+    let mut app = load(policy(), NO_WEAPON);
+    assert_eq!(count::<Unit>(&mut app), 0);
+    assert!(matches!(
+        app.world.resource::<SaloErrors<All>>().iter().next(),
+        Some(SaloError::MissingRequired(name)) if name == "weapon"
+    ));
+
+    let mut app = load(policy(), CORRUPT_WEAPON);
+    assert_eq!(count::<Unit>(&mut app), 0);
+    assert!(matches!(
+        app.world.resource::<SaloErrors<All>>().iter().next(),
+        Some(SaloError::Deserialization(_))
+    ));
+}
+
+#[test]
+pub fn ignore() {
+    let mut app = load(LoadPolicy::new().with("weapon", TypePolicy::Ignore), VALID);
+    assert_eq!(count::<Unit>(&mut app), 1);
+    assert_eq!(count::<Weapon>(&mut app), 0);
+    assert_eq!(deserialized(&mut app), [("unit".to_owned(), 1)]);
+}
+
+fn damages(app: &mut App) -> Vec<i32> {
+    let mut damages = app.world.run_system_once(|q: Query<&Weapon>| q.iter().map(|w| w.damage).collect::<Vec<_>>());
+    damages.sort();
+    damages
+}
+
+#[test]
+pub fn resolver() {
+    const SAVE: &str = r#"{"weapon":[{"path":"John","value":{"damage":3}},{"path":"Jane","value":{"damage":4}}]}"#;
+    let resolve = |resolver: Option<LoadResolver<All, Weapon>>| {
+        let mut app = load(LoadPolicy::new(), "{}");
+        app.world.run_system_once(|mut commands: Commands| {
+            commands.spawn((PathName::new("John"), Weapon { damage: 5 }));
+        });
+        if let Some(resolver) = resolver {
+            app.world.insert_resource(resolver);
+        }
+        app.world.load_from::<All, _>(&SAVE.to_owned());
+        damages(&mut app)
+    };
+    assert_eq!(resolve(None), vec![3, 4]);
+    assert_eq!(resolve(Some(LoadResolver::new(|_, _| Resolution::KeepExisting))), vec![4, 5]);
+    assert_eq!(resolve(Some(LoadResolver::new(|_, _| Resolution::UseIncoming))), vec![3, 4]);
+    // Only called for existing components.
+    assert_eq!(resolve(Some(LoadResolver::new(|existing, incoming: &Weapon| Resolution::Merge(Weapon {
+        damage: existing.damage + incoming.damage
+    })))), vec![4, 8]);
+}
+
+#[test]
+pub fn entity_budget() {
+    use bevy_salo::{SaloConfig, PendingLoad};
+    let save = r#"{
+        "unit":[{"path":"A","value":{"name":"A"}},{"path":"B","value":{"name":"B"}},{"path":"C","value":{"name":"C"}},
+            {"path":"D","value":{"name":"D"}},{"path":"E","value":{"name":"E"}}],
+        "weapon":[{"path":"A","value":{"damage":1}},{"path":"B","value":{"damage":2}},{"path":"C","value":{"damage":3}},
+            {"path":"D","value":{"damage":4}},{"path":"E","value":{"damage":5}}]
+    }"#;
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Weapon>()
+    );
+    app.world.insert_resource(SaloConfig::<All>::new().with_entity_budget(2));
+    app.world.insert_resource(LoadPolicy::<All>::new().with("weapon", TypePolicy::Required));
+    app.world.load_from::<All, _>(&save.to_owned());
+    assert!(app.world.resource::<SaloErrors<All>>().is_empty());
+    assert_eq!(count::<Unit>(&mut app), 2);
+    assert_eq!(count::<Weapon>(&mut app), 2);
+    assert_eq!(app.world.resource::<PendingLoad<All>>().len(), 6);
+
+    app.update();
+    assert!(app.world.resource::<SaloErrors<All>>().is_empty());
+    assert_eq!(count::<Unit>(&mut app), 4);
+    assert_eq!(count::<Weapon>(&mut app), 4);
+
+    app.update();
+    assert_eq!(count::<Unit>(&mut app), 5);
+    assert_eq!(count::<Weapon>(&mut app), 5);
+    assert!(!app.world.contains_resource::<PendingLoad<All>>());
+    assert!(!app.world.load_pending::<All>().unwrap());
+}
+
+#[test]
+pub fn spawn_disabled() {
+    use bevy_salo::{SaloConfig, SaloDisabled};
+    let save = r#"{
+        "unit":[{"path":"A","value":{"name":"A"}},{"path":"B","value":{"name":"B"}},{"path":"C","value":{"name":"C"}}],
+        "(disabled)":[{"path":"C","value":null}]
+    }"#;
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<SaloDisabled>()
+    );
+    app.world.insert_resource(SaloConfig::<All>::new().with_entity_budget(2).with_spawn_disabled(true));
+    app.world.load_from::<All, _>(&save.to_owned());
+    assert!(app.world.resource::<SaloErrors<All>>().is_empty());
+    assert_eq!(count::<Unit>(&mut app), 2);
+    assert_eq!(count::<SaloDisabled>(&mut app), 2);
+
+    app.update();
+    assert_eq!(count::<Unit>(&mut app), 3);
+    let disabled = app.world.run_system_once(|q: Query<&Unit, bevy_ecs::query::With<SaloDisabled>>| {
+        q.iter().map(|u| u.name.clone()).collect::<Vec<_>>()
+    });
+    assert_eq!(disabled, ["C"]);
+
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains(r#""(disabled)":[{"#));
+}
+
+#[test]
+pub fn transactional() {
+    let mut app = load(LoadPolicy::new(), NO_WEAPON);
+    app.world.insert_resource(LoadPolicy::<All>::new().with("weapon", TypePolicy::Required));
+    let entities = app.world.entities().len();
+    let john = app.world.run_system_once(|q: Query<Entity, With<Unit>>| q.single());
+    assert!(app.world.load_transactional::<All>(CORRUPT_WEAPON.as_bytes()).is_err());
+    assert_eq!(app.world.entities().len(), entities);
+    assert_eq!(count::<Unit>(&mut app), 1);
+    // Unnamed entities keep their data after a rollback.
+    assert!(app.world.get::<Unit>(john).is_some());
+
+    // Uses the load policy of the world.
+    app.world.insert_resource(LoadPolicy::<All>::new().with("weapon", TypePolicy::Ignore));
+    app.world.load_transactional::<All>(CORRUPT_WEAPON.as_bytes()).unwrap();
+    assert_eq!(count::<Unit>(&mut app), 2);
+    assert!(app.world.get_resource::<LoadPolicy<All>>().is_some());
+
+    app.world.insert_resource(LoadPolicy::<All>::new());
+    app.world.load_transactional::<All>(VALID.as_bytes()).unwrap();
+    assert_eq!(count::<Unit>(&mut app), 3);
+    assert_eq!(count::<Weapon>(&mut app), 1);
+}
+
+#[derive(Debug, Resource)]
+struct Scale(i32);
+
+#[derive(Debug, Component)]
+struct Scaled(i32);
+
+impl bevy_salo::SaveLoad for Scaled {
+    type Ser<'ser> = i32;
+    type De = i32;
+    type Context<'w, 's> = Res<'w, Scale>;
+    type ContextMut<'w, 's> = Res<'w, Scale>;
+
+    fn to_serializable<'t>(&'t self, _: Entity, _: impl Fn(Entity) -> bevy_salo::EntityPath, scale: &'t Res<Scale>) -> i32 {
+        self.0 / scale.0
+    }
+
+    fn from_deserialize(de: i32, _: &mut Commands, _: Entity, _: impl FnMut(&mut Commands, &bevy_salo::EntityPath) -> Entity, scale: &mut Res<Scale>) -> Self {
+        Scaled(de * scale.0)
+    }
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("scaled")
+    }
+}
+
+#[test]
+pub fn transactional_context() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Scaled>()
+    );
+    app.world.insert_resource(Scale(10));
+    app.world.insert_resource(LoadPolicy::<All>::new().with("weapon", TypePolicy::Required));
+    app.world.spawn(Scaled(20));
+    // Resources of types are only in the world, a failed load is rolled back with them.
+    assert!(app.world.load_transactional::<All>(r#"{"scaled":[{"value":3}]}"#.as_bytes()).is_err());
+    let values = app.world.run_system_once(|q: Query<&Scaled>| q.iter().map(|s| s.0).collect::<Vec<_>>());
+    assert_eq!(values, [20]);
+
+    app.world.insert_resource(LoadPolicy::<All>::new());
+    app.world.load_transactional::<All>(r#"{"scaled":[{"value":3}]}"#.as_bytes()).unwrap();
+    let mut values = app.world.run_system_once(|q: Query<&Scaled>| q.iter().map(|s| s.0).collect::<Vec<_>>());
+    values.sort();
+    assert_eq!(values, [20, 30]);
+}
+
+#[derive(Debug, Clone, Component, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Armor(i32);
+
+impl bevy_salo::SaveLoadCore for Armor {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("armor")
+    }
+
+    fn on_missing() -> Option<Self> {
+        Some(Armor(10))
+    }
+
+    fn fills() -> &'static [&'static str] {
+        &["unit"]
+    }
+}
+
+#[derive(Debug, Clone, bevy_ecs::system::Resource, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Difficulty(u32);
+
+impl bevy_salo::SaveLoadResCore for Difficulty {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("difficulty")
+    }
+
+    fn on_missing() -> Option<Self> {
+        Some(Difficulty(1))
+    }
+}
+
+#[test]
+pub fn fill_defaults() {
+    use bevy_salo::SaloConfig;
+    let old = r#"{"unit":[{"path":"A","value":{"name":"A"}},{"path":"B","value":{"name":"B"}},{"value":{"name":"C"}}],
+        "weapon":[{"path":"W","value":{"damage":1}}]}"#;
+    let new = r#"{"unit":[{"path":"A","value":{"name":"A"}}],"armor":[{"path":"A","value":3}],"difficulty":[{"value":2}]}"#;
+    let build = |fill: bool| {
+        let mut app = App::new();
+        app.add_plugins(SaveLoadPlugin::new::<All>()
+            .register::<Unit>()
+            .register::<Weapon>()
+            .register::<Armor>()
+            .register_resource::<Difficulty>()
+        );
+        app.world.insert_resource(SaloConfig::<All>::new().with_fill_defaults(fill));
+        app
+    };
+
+    let mut app = build(true);
+    app.world.load_from::<All, _>(&old.to_owned());
+    assert!(app.world.resource::<SaloErrors<All>>().is_empty());
+    assert_eq!(count::<Unit>(&mut app), 3);
+    // Unnamed records cannot be matched and are not filled, nor are entities without units.
+    assert_eq!(app.world.run_system_once(|q: Query<&Armor>| q.iter().cloned().collect::<Vec<_>>()), [Armor(10), Armor(10)]);
+    assert_eq!(app.world.resource::<Difficulty>(), &Difficulty(1));
+
+    let mut app = build(true);
+    app.world.load_from::<All, _>(&new.to_owned());
+    assert_eq!(app.world.run_system_once(|q: Query<&Armor>| q.iter().cloned().collect::<Vec<_>>()), [Armor(3)]);
+    assert_eq!(app.world.resource::<Difficulty>(), &Difficulty(2));
+
+    let mut app = build(false);
+    app.world.load_from::<All, _>(&old.to_owned());
+    assert_eq!(count::<Armor>(&mut app), 0);
+    assert!(!app.world.contains_resource::<Difficulty>());
+}
+
+#[derive(Debug, Default, Resource)]
+struct Seen(Vec<bool>);
+
+#[derive(Debug, Component)]
+struct Checked(i32);
+
+impl bevy_salo::SaveLoad for Checked {
+    type Ser<'ser> = i32;
+    type De = i32;
+    type Context<'w, 's> = ();
+    type ContextMut<'w, 's> = ();
+
+    fn to_serializable<'t>(&'t self, _: Entity, _: impl Fn(Entity) -> bevy_salo::EntityPath, _: &'t ()) -> i32 {
+        self.0
+    }
+
+    fn from_deserialize(de: i32, _: &mut Commands, _: Entity, _: impl FnMut(&mut Commands, &bevy_salo::EntityPath) -> Entity, _: &mut ()) -> Self {
+        Checked(de)
+    }
+
+    fn try_from_deserialize(de: i32, commands: &mut Commands, entity: Entity, _: impl FnMut(&mut Commands, &bevy_salo::EntityPath) -> Entity, _: &mut ()) -> anyhow::Result<Self> {
+        commands.add(move |world: &mut bevy_ecs::world::World| {
+            let inserted = world.get::<Checked>(entity).is_some();
+            world.resource_mut::<Seen>().0.push(inserted);
+        });
+        anyhow::ensure!(de >= 0, "negative");
+        Ok(Checked(de))
+    }
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("checked")
+    }
+}
+
+#[test]
+pub fn rejected_records() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Checked>()
+    );
+    app.world.init_resource::<Seen>();
+    app.world.load::<All>(bevy_salo::LoadSource::String(r#"{"checked":[{"path":"A","value":1},{"path":"B","value":-1}]}"#)).unwrap();
+    // Commands of accepted records run after the component is inserted,
+    // rejected records spawn no entity and their commands are dropped.
+    assert_eq!(app.world.resource::<Seen>().0, [true]);
+    assert_eq!(count::<Checked>(&mut app), 1);
+    assert_eq!(app.world.entities().len(), 1);
+}
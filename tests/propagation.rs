@@ -0,0 +1,156 @@
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_hierarchy::{BuildChildren, Parent};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaveTarget, LoadSource, MarkerComponent, MarkerPropagation, SaloConfig, SaloRegistry, UnknownComponents, LoadPolicy, TypePolicy};
+use std::borrow::Cow;
+
+#[derive(Debug, Default, Component)]
+struct Prefab;
+
+impl MarkerComponent for Prefab {
+    type Method = SerdeJson<false>;
+
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("prefab")
+    }
+}
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Unit {
+    name: String,
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+
+    fn path_name(&self) -> Option<Cow<'static, str>> {
+        (!self.name.is_empty()).then(|| self.name.clone().into())
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<Prefab>()
+        .register::<Unit>()
+    );
+    app
+}
+
+fn save(app: &mut App) -> String {
+    app.world.save::<Prefab>(SaveTarget::String).unwrap().into_string().unwrap()
+}
+
+#[test]
+pub fn marker_propagation() {
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Prefab, Unit { name: "Ship".to_owned() })).with_children(|b| {
+            b.spawn(Unit { name: "Cabin".to_owned() }).with_children(|b| {
+                b.spawn(Unit::default());
+            });
+        });
+        commands.spawn(Unit { name: "Stray".to_owned() });
+    });
+
+    let save = self::save(&mut app);
+    assert!(save.contains("Ship"));
+    assert!(!save.contains("Cabin"));
+
+    app.world.resource_mut::<SaloConfig<Prefab>>().set_marker_propagation(MarkerPropagation::Descendants);
+    let save = self::save(&mut app);
+    assert!(save.contains(r#""path":"Ship::Cabin""#));
+    assert!(save.contains(r#""parent":"Ship::Cabin""#));
+    assert!(!save.contains("Stray"));
+
+    let mut app2 = self::app();
+    app2.world.load::<Prefab>(LoadSource::String(&save)).unwrap();
+    let mut units = app2.world.run_system_once(|q: Query<(&Unit, Option<&Parent>)>, units: Query<&Unit>| {
+        q.iter().map(|(u, p)| (u.name.clone(), p.map(|p| units.get(p.get()).unwrap().name.clone()))).collect::<Vec<_>>()
+    });
+    units.sort();
+    assert_eq!(units, vec![
+        (String::new(), Some("Cabin".to_owned())),
+        ("Cabin".to_owned(), Some("Ship".to_owned())),
+        ("Ship".to_owned(), None),
+    ]);
+}
+
+#[test]
+pub fn orphan_policy() {
+    use bevy_ecs::event::Events;
+    use bevy_salo::{OrphanPolicy, SaloEvent, SaloEventKind};
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit::default()).with_children(|b| {
+            b.spawn((Prefab, Unit { name: "Orphan".to_owned() }));
+        });
+        commands.spawn((Prefab, Unit { name: "Root".to_owned() }));
+    });
+
+    app.world.resource_mut::<SaloConfig<Prefab>>().set_orphan_policy(OrphanPolicy::TreatAsRoot);
+    let save = self::save(&mut app);
+    assert!(save.contains(r#""path":"Orphan""#));
+    assert!(save.contains(r#""path":"Root""#));
+    assert!(!save.contains("parent"));
+
+    app.world.resource_mut::<SaloConfig<Prefab>>().set_orphan_policy(OrphanPolicy::Skip);
+    let save = self::save(&mut app);
+    assert!(!save.contains("Orphan"));
+    assert!(save.contains(r#""path":"Root""#));
+
+    let orphans = app.world.resource_mut::<Events<SaloEvent<Prefab>>>().drain()
+        .filter(|e| matches!(&e.kind, SaloEventKind::Orphan { name, .. } if name == "unit"))
+        .count();
+    assert_eq!(orphans, 2);
+}
+
+#[test]
+pub fn persist_marker() {
+    use bevy_ecs::query::With;
+    let mut app = app();
+    app.world.spawn((Prefab, Unit { name: "Ship".to_owned() }));
+    let save = self::save(&mut app);
+    assert!(!save.contains("prefab"));
+
+    let mut app2 = self::app();
+    app2.world.load::<Prefab>(LoadSource::String(&save)).unwrap();
+    assert_eq!(app2.world.run_system_once(|q: Query<(), With<Prefab>>| q.iter().count()), 0);
+
+    app.world.resource_mut::<SaloConfig<Prefab>>().set_persist_marker(true);
+    let save = self::save(&mut app);
+    assert!(save.contains(r#""prefab":[{"path":"Ship"}]"#));
+
+    let mut app2 = self::app();
+    app2.world.resource_mut::<SaloConfig<Prefab>>().set_persist_marker(true);
+    app2.world.load::<Prefab>(LoadSource::String(&save)).unwrap();
+    let marked = app2.world.run_system_once(|q: Query<&Unit, With<Prefab>>| {
+        q.iter().map(|u| u.name.clone()).collect::<Vec<_>>()
+    });
+    assert_eq!(marked, ["Ship"]);
+
+    // The marker is registered, its records are dropped if it is not persisted.
+    let mut app2 = self::app();
+    assert!(app2.world.resource::<SaloRegistry<Prefab>>().contains("prefab"));
+    app2.world.insert_resource(SaloConfig::<Prefab>::new().with_preserve_unknown(true));
+    app2.world.load::<Prefab>(LoadSource::String(&save)).unwrap();
+    assert_eq!(app2.world.run_system_once(|q: Query<(), With<Prefab>>| q.iter().count()), 0);
+    assert!(app2.world.resource::<UnknownComponents<Prefab>>().is_empty());
+
+    let mut app2 = self::app();
+    app2.world.insert_resource(SaloConfig::<Prefab>::new().with_persist_marker(true));
+    app2.world.insert_resource(LoadPolicy::<Prefab>::new().with("prefab", TypePolicy::Ignore));
+    app2.world.load::<Prefab>(LoadSource::String(&save)).unwrap();
+    assert_eq!(app2.world.run_system_once(|q: Query<(), With<Prefab>>| q.iter().count()), 0);
+}
+
+#[test]
+pub fn marker_type_name() {
+    #[derive(Debug, Default, Component)]
+    struct Level;
+    impl MarkerComponent for Level {
+        type Method = SerdeJson<false>;
+    }
+    assert_eq!(<Level as MarkerComponent>::type_name(), "Level");
+}
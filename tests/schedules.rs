@@ -0,0 +1,341 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::schedule::{ExecutorKind, Schedules};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension};
+use bevy_salo::schedules::{SaveSchedule, LoadSchedule};
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[test]
+pub fn schedule_labels() {
+    assert_eq!(format!("{:?}", SaveSchedule::with_marker::<All>()), "SaveSchedule<All<SerdeJson<false>>>");
+}
+
+#[test]
+pub fn configure_schedule() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .configure_schedule(|schedule| {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        })
+    );
+    let schedules = app.world.resource::<Schedules>();
+    for schedule in [
+        schedules.get(SaveSchedule::with_marker::<All>()).unwrap(),
+        schedules.get(LoadSchedule::with_marker::<All>()).unwrap(),
+    ] {
+        assert_eq!(schedule.get_executor_kind(), ExecutorKind::SingleThreaded);
+    }
+    assert!(app.world.save_to::<All, String>().is_some());
+}
+
+#[test]
+pub fn teardown() {
+    use bevy_salo::{SerializeContext, DeserializeContext, PathNames, BytesInput, SaloConfig};
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>());
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(!app.world.contains_resource::<SerializeContext<All>>());
+    assert!(!app.world.contains_resource::<PathNames<All>>());
+    app.world.load_from::<All, _>(&save);
+    assert!(!app.world.contains_resource::<DeserializeContext<All>>());
+    assert!(!app.world.contains_resource::<BytesInput<All>>());
+
+    app.world.resource_mut::<SaloConfig<All>>().set_retain_buffers(true);
+    app.world.save_to::<All, String>().unwrap();
+    assert!(app.world.contains_resource::<SerializeContext<All>>());
+    app.world.load_from::<All, _>(&save);
+    assert!(app.world.contains_resource::<DeserializeContext<All>>());
+    assert!(app.world.contains_resource::<BytesInput<All>>());
+}
+
+#[test]
+pub fn single_threaded() {
+    use bevy_salo::schedules::ResetSchedule;
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>().single_threaded());
+    let schedules = app.world.resource::<Schedules>();
+    assert_eq!(schedules.get(SaveSchedule::with_marker::<All>()).unwrap().get_executor_kind(), ExecutorKind::SingleThreaded);
+    assert_eq!(schedules.get(ResetSchedule::with_marker::<All>()).unwrap().get_executor_kind(), ExecutorKind::SingleThreaded);
+}
+
+mod registry {
+    use super::*;
+    use bevy_ecs::component::Component;
+    use bevy_ecs::system::Resource;
+    use bevy_salo::SaloRegistry;
+    use std::borrow::Cow;
+
+    #[derive(Debug, Component, serde::Serialize, serde::Deserialize)]
+    struct A;
+
+    #[derive(Debug, Component, serde::Serialize, serde::Deserialize)]
+    struct B;
+
+    #[derive(Debug, Component, serde::Serialize, serde::Deserialize)]
+    struct C;
+
+    #[derive(Debug, Resource, serde::Serialize, serde::Deserialize)]
+    struct R;
+
+    impl bevy_salo::SaveLoadCore for A {
+        fn type_name() -> Cow<'static, str> {
+            Cow::Borrowed("a")
+        }
+    }
+
+    impl bevy_salo::SaveLoadCore for B {
+        fn type_name() -> Cow<'static, str> {
+            Cow::Borrowed("a")
+        }
+    }
+
+    impl bevy_salo::SaveLoadCore for C {
+        fn type_name() -> Cow<'static, str> {
+            Cow::Borrowed("c")
+        }
+    }
+
+    impl bevy_salo::SaveLoadResCore for R {
+        fn type_name() -> Cow<'static, str> {
+            Cow::Borrowed("r")
+        }
+    }
+
+    #[test]
+    pub fn registered_types() {
+        let mut app = App::new();
+        app.add_plugins(SaveLoadPlugin::new::<All>()
+            .register::<A>()
+            .register_resource::<R>()
+        );
+        let registry = app.world.resource::<SaloRegistry<All>>();
+        let names: Vec<_> = registry.registered_types().map(|t| t.name.as_ref()).collect();
+        assert_eq!(names, ["a", "r"]);
+        assert!(registry.get("r").unwrap().is_resource);
+        assert!(!registry.contains("b"));
+    }
+
+    #[test]
+    pub fn register_tuple() {
+        let mut app = App::new();
+        app.add_plugins(SaveLoadPlugin::new::<All>()
+            .register_tuple::<(A, C)>()
+            .register_resource::<R>()
+        );
+        let registry = app.world.resource::<SaloRegistry<All>>();
+        let names: Vec<_> = registry.registered_types().map(|t| t.name.as_ref()).collect();
+        assert_eq!(names, ["a", "c", "r"]);
+        assert!(app.world.save_to::<All, String>().is_some());
+    }
+
+    #[test]
+    pub fn register_shared() {
+        use bevy_salo::SharedTypes;
+        type Fork = bevy_salo::All<SerdeJson<false>, 'f'>;
+        let shared = SharedTypes::new().register::<A>().register_resource::<R>();
+        let mut app = App::new();
+        app.add_plugins(SaveLoadPlugin::new::<All>().register_shared(shared));
+        app.add_plugins(SaveLoadPlugin::new::<Fork>().register_shared(shared).register::<C>());
+        let names: Vec<_> = app.world.resource::<SaloRegistry<All>>().registered_types().map(|t| t.name.as_ref()).collect();
+        assert_eq!(names, ["a", "r"]);
+        let names: Vec<_> = app.world.resource::<SaloRegistry<Fork>>().registered_types().map(|t| t.name.as_ref()).collect();
+        assert_eq!(names, ["a", "r", "c"]);
+    }
+
+    #[test]
+    pub fn register_if() {
+        use bevy_ecs::system::{Commands, Query, RunSystemOnce};
+        let mut app = App::new();
+        app.add_plugins(SaveLoadPlugin::new::<All>()
+            .register_if::<A>(true)
+            .register_if::<C>(false)
+        );
+        let registry = app.world.resource::<SaloRegistry<All>>();
+        assert!(registry.is_active("a"));
+        assert!(registry.contains("c"));
+        assert!(!registry.is_active("c"));
+
+        app.world.run_system_once(|mut commands: Commands| {
+            commands.spawn((A, C));
+        });
+        let save = app.world.save_to::<All, String>().unwrap();
+        assert!(save.contains(r#""a""#));
+        assert!(!save.contains(r#""c""#));
+
+        let mut app2 = App::new();
+        app2.add_plugins(SaveLoadPlugin::new::<All>()
+            .register_if::<C>(false)
+        );
+        app2.world.load_from::<All, _>(&r#"{"c":[{"path":"John","value":null}]}"#.to_owned());
+        assert_eq!(app2.world.run_system_once(|q: Query<&C>| q.iter().count()), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate type name \"a\"")]
+    pub fn duplicate_type_name() {
+        let mut app = App::new();
+        app.add_plugins(SaveLoadPlugin::new::<All>()
+            .register::<A>()
+            .register::<B>()
+        );
+    }
+}
+
+mod post_load {
+    use super::*;
+    use bevy_ecs::component::Component;
+    use bevy_ecs::system::{Commands, Query, ResMut, Resource, RunSystemOnce};
+    use bevy_salo::schedules::PostLoadSchedule;
+    use std::borrow::Cow;
+
+    #[derive(Debug, Component, serde::Serialize, serde::Deserialize)]
+    struct Unit;
+
+    impl bevy_salo::SaveLoadCore for Unit {
+        fn type_name() -> Cow<'static, str> {
+            Cow::Borrowed("unit")
+        }
+    }
+
+    #[derive(Debug, Resource, Default)]
+    struct Index(usize, usize);
+
+    fn rebuild(mut index: ResMut<Index>, units: Query<&Unit>) {
+        index.0 = units.iter().count();
+    }
+
+    fn count_runs(mut index: ResMut<Index>) {
+        index.1 += 1;
+    }
+
+    #[test]
+    pub fn post_load_system() {
+        let mut app = App::new();
+        app.init_resource::<Index>();
+        app.add_plugins(SaveLoadPlugin::new::<All>()
+            .register::<Unit>()
+            .add_post_load_system(rebuild)
+        );
+        app.add_systems(PostLoadSchedule::with_marker::<All>(), count_runs);
+        app.world.run_system_once(|mut commands: Commands| {
+            commands.spawn(Unit);
+            commands.spawn(Unit);
+        });
+        let save = app.world.save_to::<All, String>().unwrap();
+        app.world.load_from::<All, _>(&save);
+        // Rebuilt with loaded entities visible.
+        assert_eq!(app.world.resource::<Index>().0, 4);
+        assert_eq!(app.world.resource::<Index>().1, 1);
+        // Not run if the load failed.
+        app.world.load_from::<All, _>(&"{".to_owned());
+        assert_eq!(app.world.resource::<Index>().1, 1);
+    }
+}
+
+mod pre_save {
+    use super::*;
+    use bevy_ecs::component::Component;
+    use bevy_ecs::system::{Commands, Query, Resource, Res, RunSystemOnce};
+    use bevy_salo::{AbortSave, SaloErrors, SaloError};
+    use std::borrow::Cow;
+
+    #[derive(Debug, Component, serde::Serialize, serde::Deserialize)]
+    struct Unit(u32);
+
+    impl bevy_salo::SaveLoadCore for Unit {
+        fn type_name() -> Cow<'static, str> {
+            Cow::Borrowed("unit")
+        }
+    }
+
+    #[derive(Debug, Resource)]
+    struct Animating;
+
+    fn normalize(mut commands: Commands, units: Query<&Unit>, animating: Option<Res<Animating>>) {
+        if animating.is_some() {
+            commands.insert_resource(AbortSave::<All>::new("animating"));
+        } else if units.iter().count() < 2 {
+            commands.spawn(Unit(99));
+        }
+    }
+
+    #[test]
+    pub fn pre_save_system() {
+        let mut app = App::new();
+        app.add_plugins(SaveLoadPlugin::new::<All>()
+            .register::<Unit>()
+            .add_pre_save_system(normalize)
+        );
+        app.world.run_system_once(|mut commands: Commands| {
+            commands.spawn(Unit(1));
+        });
+        // Commands from pre-save are applied before serialization.
+        let save = app.world.save_to::<All, String>().unwrap();
+        assert!(save.contains("99"));
+
+        app.world.insert_resource(Animating);
+        assert!(app.world.save_to::<All, String>().is_none());
+        assert!(app.world.resource::<SaloErrors<All>>().iter().any(|e| matches!(e, SaloError::Aborted(r) if r == "animating")));
+        assert!(app.world.get_resource::<AbortSave<All>>().is_none());
+
+        app.world.remove_resource::<Animating>();
+        assert!(app.world.save_to::<All, String>().is_some());
+    }
+}
+
+mod reentrant {
+    use super::*;
+    use bevy_ecs::component::Component;
+    use bevy_ecs::system::{Commands, Resource, RunSystemOnce};
+    use bevy_ecs::world::World;
+    use bevy_salo::{SaloHistory, SaloOperation, SaloError, SaveTarget, LoadSource, SaloStorage, MemoryStorage};
+    use std::borrow::Cow;
+
+    #[derive(Debug, Component, serde::Serialize, serde::Deserialize)]
+    struct Unit;
+
+    impl bevy_salo::SaveLoadCore for Unit {
+        fn type_name() -> Cow<'static, str> {
+            Cow::Borrowed("unit")
+        }
+    }
+
+    #[derive(Debug, Resource, Default)]
+    struct Results(Vec<bool>);
+
+    fn autosave(world: &mut World) {
+        // In-memory saves cannot return their output after the run and are rejected.
+        let rejected = world.save_to::<All, String>().is_none();
+        let error = matches!(world.save::<All>(SaveTarget::String), Err(SaloError::Reentrant));
+        let writer = matches!(world.save_to_writer::<All, _>(Vec::new()), Err(SaloError::Reentrant));
+        let queued = matches!(world.save::<All>(SaveTarget::Storage("autosave")), Err(SaloError::Queued));
+        world.resource_mut::<Results>().0.extend([rejected, error, writer, queued]);
+    }
+
+    #[test]
+    pub fn save_in_post_load() {
+        let mut app = App::new();
+        app.init_resource::<Results>();
+        app.add_plugins(SaveLoadPlugin::new::<All>()
+            .register::<Unit>()
+            .add_post_load_system(autosave)
+        );
+        app.insert_resource(SaloStorage::<All>::new(MemoryStorage::new()));
+        app.world.run_system_once(|mut commands: Commands| {
+            commands.spawn(Unit);
+        });
+        let save = app.world.save_to::<All, String>().unwrap();
+        app.world.load_from::<All, _>(&save);
+        assert_eq!(app.world.resource::<Results>().0, [true, true, true, true]);
+        // Only the save to storage ran after the load.
+        let operations: Vec<_> = app.world.resource::<SaloHistory<All>>().iter()
+            .map(|entry| entry.operation)
+            .collect();
+        assert_eq!(operations, [SaloOperation::Save, SaloOperation::Load, SaloOperation::Save]);
+        app.world.load::<All>(LoadSource::Storage("autosave")).unwrap();
+        // In-memory saves run as usual when nothing is running.
+        assert!(app.world.save_to::<All, String>().is_some());
+    }
+}
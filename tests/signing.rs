@@ -0,0 +1,119 @@
+use bevy_app::App;
+use bevy_ecs::{component::Component, entity::Entity, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaveTarget, LoadSource, SectionSigning, SaloError, SaloRegistry, SaveMetadata, PathName};
+use std::borrow::Cow;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Gold(u32);
+
+impl bevy_salo::SaveLoadCore for Gold {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("base::gold")
+    }
+}
+
+#[derive(Debug, Clone, Component, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Sword(u32);
+
+impl bevy_salo::SaveLoadCore for Sword {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("swords::sword")
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Gold>()
+        .register::<Sword>()
+    );
+    app.world.insert_resource(SectionSigning::<All>::new([7; 32]).with_section("base"));
+    app
+}
+
+fn load(save: &str) -> Result<App, SaloError> {
+    let mut app = app();
+    app.world.load::<All>(LoadSource::String(save))?;
+    Ok(app)
+}
+
+#[test]
+pub fn section_signing() {
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Gold(100), Sword(1)));
+    });
+    let save = app.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+    assert!(save.contains("$signatures"));
+
+    let mut app2 = load(&save).unwrap();
+    assert_eq!(app2.world.run_system_once(|q: Query<&Gold>| q.single().clone()), Gold(100));
+
+    // Mod sections are not checked.
+    let mut app2 = load(&save.replace(r#""value":1}"#, r#""value":9000}"#)).unwrap();
+    assert_eq!(app2.world.run_system_once(|q: Query<&Sword>| q.single().clone()), Sword(9000));
+
+    let tampered = save.replace(r#""value":100}"#, r#""value":9000}"#);
+    assert!(matches!(load(&tampered), Err(SaloError::InvalidSignature(s)) if s == "base"));
+
+    let mut value: serde_json::Value = serde_json::from_str(&save).unwrap();
+    value.as_object_mut().unwrap().remove("$signatures");
+    assert!(matches!(load(&value.to_string()), Err(SaloError::InvalidSignature(_))));
+
+    // Sections can be reassigned in the registry.
+    app.world.resource_mut::<SaloRegistry<All>>().set_section("swords::sword", "base");
+    let save = app.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+    let mut app2 = self::app();
+    app2.world.resource_mut::<SaloRegistry<All>>().set_section("swords::sword", "base");
+    app2.world.load::<All>(LoadSource::String(&save)).unwrap();
+    let tampered = save.replace(r#""value":1}"#, r#""value":9000}"#);
+    assert!(matches!(app2.world.load::<All>(LoadSource::String(&tampered)), Err(SaloError::InvalidSignature(_))));
+}
+
+#[test]
+pub fn signing_covers_header() {
+    let mut app = app();
+    app.world.insert_resource(SaveMetadata::<All>::new(&"Jimothy").unwrap());
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((PathName::new("Hoard"), Gold(100)));
+        commands.spawn((PathName::new("Stash"), Gold(5)));
+    });
+    let save = app.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+    assert!(load(&save).is_ok());
+    assert!(matches!(load(&save.replace("Jimothy", "John")), Err(SaloError::InvalidSignature(_))));
+
+    let stash = app.world.run_system_once(|q: Query<(Entity, &PathName)>| {
+        q.iter().find(|(_, n)| n.get() == "Stash").unwrap().0
+    });
+    app.world.despawn(stash);
+    let delta = app.world.save_delta::<All, String>("base").unwrap();
+    assert!(delta.contains(r#"["Stash"]"#));
+    assert!(load(&save).unwrap().world.load_delta::<All>(delta.as_bytes(), "base").is_ok());
+    let tampered = delta.replace(r#"["Stash"]"#, "[]");
+    assert!(matches!(
+        load(&save).unwrap().world.load_delta::<All>(tampered.as_bytes(), "base"),
+        Err(SaloError::InvalidSignature(_))
+    ));
+}
+
+#[test]
+pub fn signing_with_transform() {
+    use bevy_salo::DocumentTransform;
+    let mut app = app();
+    app.world.insert_resource(DocumentTransform::<All>::new().on_save(|document| {
+        document.set_metadata(Some(serde_json::json!("Jimothy")));
+        document.retain("base::gold", |_, value| value.as_u64() != Some(0));
+        Ok(())
+    }));
+    app.world.spawn(Gold(100));
+    app.world.spawn(Gold(0));
+    let save = app.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+    assert!(save.contains("Jimothy"));
+
+    // Signatures cover the written document.
+    let mut app2 = load(&save).unwrap();
+    assert_eq!(app2.world.run_system_once(|q: Query<&Gold>| q.single().clone()), Gold(100));
+    assert!(matches!(load(&save.replace("Jimothy", "John")), Err(SaloError::InvalidSignature(_))));
+}
@@ -0,0 +1,111 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}, entity::Entity};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaveState};
+use std::borrow::Cow;
+
+mod common;
+use common::Unit;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app
+}
+
+#[test]
+pub fn dirty_flag() {
+    let mut app = app();
+    assert!(!app.world.has_unsaved_changes::<All>());
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+    });
+    assert!(app.world.has_unsaved_changes::<All>());
+
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(!app.world.has_unsaved_changes::<All>());
+    assert!(app.world.resource::<SaveState<All>>().last_saved().is_some());
+    app.update();
+    assert!(!app.world.has_unsaved_changes::<All>());
+
+    // Modification.
+    app.world.run_system_once(|mut q: Query<&mut Unit>| {
+        q.single_mut().name = "Jane".to_owned();
+    });
+    // Tracked each frame until dirty.
+    app.update();
+    assert!(app.world.resource::<SaveState<All>>().is_dirty());
+    assert!(app.world.has_unsaved_changes::<All>());
+    app.world.save_to::<All, String>().unwrap();
+    assert!(!app.world.has_unsaved_changes::<All>());
+
+    // Removal.
+    app.world.run_system_once(|mut commands: Commands, q: Query<Entity>| {
+        for e in q.iter() {
+            commands.entity(e).despawn();
+        }
+    });
+    assert!(app.world.has_unsaved_changes::<All>());
+
+    // Loading is clean.
+    app.world.load_from::<All, _>(&save);
+    assert!(!app.world.has_unsaved_changes::<All>());
+    app.update();
+    assert!(!app.world.has_unsaved_changes::<All>());
+}
+
+#[test]
+pub fn partial_save_is_dirty() {
+    use bevy_salo::SaveFilter;
+    let mut app = app();
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+    });
+    app.world.insert_resource(SaveFilter::<All>::all().once());
+    app.world.save_to::<All, String>().unwrap();
+    assert!(app.world.has_unsaved_changes::<All>());
+}
+
+#[test]
+pub fn type_filter() {
+    use bevy_salo::TypeFilter;
+
+    #[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+    struct Particle(f32);
+
+    impl bevy_salo::SaveLoadCore for Particle {
+        fn type_name() -> Cow<'static, str> {
+            Cow::Borrowed("particle")
+        }
+    }
+
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Particle>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Unit { name: "John".to_owned() }, Particle(1.0)));
+    });
+    let dir = std::env::temp_dir().join("bevy_salo_type_filter");
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("quick.json").to_str().unwrap().to_owned();
+    app.world.save_to_file_filtered::<All>(&file, TypeFilter::exclude(["particle"])).unwrap();
+    let save = std::fs::read_to_string(&file).unwrap();
+    assert!(save.contains("\"unit\""));
+    assert!(!save.contains("\"particle\""));
+    // A quick save is not a complete snapshot.
+    assert!(app.world.has_unsaved_changes::<All>());
+    assert!(!app.world.contains_resource::<TypeFilter<All>>());
+
+    app.world.insert_resource(TypeFilter::<All>::include(["particle"]));
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(!save.contains("\"unit\""));
+    assert!(save.contains("\"particle\""));
+    std::fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,180 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaloError};
+use bevy_salo::{SaveTarget, LoadSource, Saved, SaloStorage, MemoryStorage};
+use std::borrow::Cow;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Unit {
+    name: String,
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+    });
+    app
+}
+
+fn units(app: &mut App) -> usize {
+    app.world.run_system_once(|e: Query<&Unit>| e.iter().count())
+}
+
+#[test]
+pub fn save_targets() {
+    let mut app = app();
+    let string = app.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+    let bytes = app.world.save::<All>(SaveTarget::Bytes).unwrap().into_bytes().unwrap();
+    assert_eq!(string.as_bytes(), bytes);
+
+    app.world.load::<All>(LoadSource::String(&string)).unwrap();
+    assert_eq!(units(&mut app), 2);
+    app.world.load::<All>(LoadSource::Bytes(&bytes)).unwrap();
+    assert_eq!(units(&mut app), 3);
+    assert!(matches!(app.world.load::<All>(LoadSource::Bytes(b"{")), Err(SaloError::Deserialization(_))));
+}
+
+#[test]
+pub fn storage() {
+    let mut app = app();
+    assert!(matches!(app.world.save::<All>(SaveTarget::Storage("slot1")), Err(SaloError::NoStorage)));
+    assert!(matches!(app.world.load::<All>(LoadSource::Storage("slot1")), Err(SaloError::NoStorage)));
+
+    app.world.insert_resource(SaloStorage::<All>::new(MemoryStorage::new()));
+    assert_eq!(app.world.save::<All>(SaveTarget::Storage("slot1")).unwrap(), Saved::Storage);
+    app.world.load::<All>(LoadSource::Storage("slot1")).unwrap();
+    assert_eq!(units(&mut app), 2);
+    assert!(matches!(app.world.load::<All>(LoadSource::Storage("slot2")), Err(SaloError::Deserialization(_))));
+}
+
+#[test]
+pub fn writer_and_reader() {
+    use bevy_salo::SaloHistory;
+    use std::io::Cursor;
+    let mut app = app();
+    let string = app.world.save::<All>(SaveTarget::String).unwrap().into_string().unwrap();
+    let writer = app.world.save_to_writer::<All, _>(Cursor::new(Vec::new())).unwrap();
+    assert_eq!(writer.get_ref(), string.as_bytes());
+    let size = app.world.resource::<SaloHistory<All>>().last().and_then(|e| e.size);
+    assert_eq!(size, Some(string.len()));
+
+    app.world.load_from_reader::<All>(Cursor::new(writer.into_inner())).unwrap();
+    assert_eq!(units(&mut app), 2);
+    assert!(matches!(app.world.load_from_reader::<All>(&b"{"[..]), Err(SaloError::Deserialization(_))));
+    assert_eq!(units(&mut app), 2);
+}
+
+#[test]
+pub fn commands() {
+    use bevy_ecs::system::Resource;
+    use bevy_salo::SaveLoadCommandsExtension;
+
+    #[derive(Resource)]
+    struct Output(Vec<u8>);
+
+    let mut app = app();
+    app.insert_resource(SaloStorage::<All>::new(MemoryStorage::new()));
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.salo_save_to_storage::<All>("slot1");
+        commands.salo_save_to_bytes::<All>(|world, bytes| world.insert_resource(Output(bytes)));
+    });
+    let bytes = app.world.resource::<Output>().0.clone();
+    assert!(std::str::from_utf8(&bytes).unwrap().contains("John"));
+
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.salo_load_from_storage::<All>("slot1");
+    });
+    assert_eq!(units(&mut app), 2);
+    app.world.run_system_once(move |mut commands: Commands| {
+        commands.salo_load_from_bytes::<All>(bytes.clone());
+        // Errors are logged.
+        commands.salo_load_from_storage::<All>("missing");
+    });
+    assert_eq!(units(&mut app), 3);
+}
+
+#[test]
+pub fn requests() {
+    use bevy_salo::{SaveRequest, LoadRequest};
+    let mut app = app();
+    app.insert_resource(SaloStorage::<All>::new(MemoryStorage::new()));
+    app.world.send_event(SaveRequest::<All>::storage("slot1"));
+    app.update();
+    assert_eq!(units(&mut app), 1);
+
+    // Saves of a frame run before loads.
+    app.world.send_event(LoadRequest::<All>::storage("slot1"));
+    app.world.send_event(SaveRequest::<All>::storage("slot2"));
+    app.update();
+    assert_eq!(units(&mut app), 2);
+    let storage = app.world.resource::<SaloStorage<All>>();
+    assert_eq!(storage.get().read("slot1").unwrap(), storage.get().read("slot2").unwrap());
+
+    app.update();
+    assert_eq!(units(&mut app), 2);
+}
+
+#[test]
+pub fn multiple_outputs() {
+    use bevy_salo::{OutputSpec, SaloHistory};
+    let mut app = app();
+    app.insert_resource(SaloStorage::<All>::new(MemoryStorage::new()));
+    let saves = app.world.resource::<SaloHistory<All>>().len();
+    let spec = OutputSpec::bytes().and_string().and_storage("slot1").and_storage("slot2");
+    #[cfg(feature="fs")]
+    let file = std::env::temp_dir().join("bevy_salo_multiple_outputs.json");
+    #[cfg(feature="fs")]
+    let spec = spec.and_file(file.to_str().unwrap());
+    let saved = app.world.save_outputs::<All>(spec).unwrap();
+    assert_eq!(app.world.resource::<SaloHistory<All>>().len(), saves + 1);
+
+    let bytes = saved.bytes.unwrap();
+    assert_eq!(saved.string.unwrap().as_bytes(), bytes);
+    let storage = app.world.resource::<SaloStorage<All>>().get();
+    assert_eq!(storage.read("slot1").unwrap(), bytes);
+    assert_eq!(storage.read("slot2").unwrap(), bytes);
+    #[cfg(feature="fs")]
+    assert_eq!(std::fs::read(&file).unwrap(), bytes);
+
+    // Storage only does not return bytes.
+    let saved = app.world.save_outputs::<All>(OutputSpec::storage("slot3")).unwrap();
+    assert_eq!(saved.bytes, None);
+    app.world.remove_resource::<SaloStorage<All>>();
+    assert!(matches!(app.world.save_outputs::<All>(OutputSpec::storage("slot3")), Err(SaloError::NoStorage)));
+}
+
+#[test]
+pub fn load_from_str() {
+    let mut app = app();
+    let save = app.world.save_to::<All, String>().unwrap();
+    app.world.load_from_str::<All>(&save.replace("John", "Jane"));
+    assert_eq!(units(&mut app), 2);
+    assert!(app.world.resource::<bevy_salo::SaloErrors<All>>().is_empty());
+
+    app.world.load_from_str::<All>("{");
+    assert!(!app.world.resource::<bevy_salo::SaloErrors<All>>().is_empty());
+}
+
+#[test]
+pub fn save_to_sinks() {
+    let mut app = app();
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert_eq!(app.world.save_to::<All, std::sync::Arc<str>>().unwrap().as_ref(), save);
+    assert_eq!(app.world.save_to::<All, std::io::Cursor<Vec<u8>>>().unwrap().get_ref(), save.as_bytes());
+    app.world.load_from_shared::<All>(std::sync::Arc::<[u8]>::from(save.as_bytes()));
+    assert_eq!(units(&mut app), 2);
+}
@@ -0,0 +1,34 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::event::Events;
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaloConfig, SaveThrottled, SaloError};
+use std::time::Duration;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[test]
+pub fn min_save_interval() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>());
+    app.world.insert_resource(SaloConfig::<All>::new().with_min_save_interval(Duration::from_millis(200)));
+    assert!(app.world.save_to::<All, String>().is_some());
+    assert!(app.world.save_to::<All, String>().is_none());
+    let dir = std::env::temp_dir().join("bevy_salo_throttle");
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("save.json");
+    assert!(matches!(app.world.save_to_file::<All>(file.to_str().unwrap()), Err(SaloError::Throttled)));
+    assert_eq!(app.world.resource::<Events<SaveThrottled<All>>>().len(), 2);
+
+    std::thread::sleep(Duration::from_millis(250));
+    // Failed saves do not delay the next one.
+    let blocker = dir.join("blocker");
+    std::fs::write(&blocker, "").unwrap();
+    let invalid = blocker.join("save.json");
+    assert!(app.world.save_to_file::<All>(invalid.to_str().unwrap()).is_err());
+    assert!(app.world.save_to_file::<All>(file.to_str().unwrap()).is_ok());
+    assert!(app.world.save_to::<All, String>().is_none());
+
+    app.world.resource_mut::<SaloConfig<All>>().set_min_save_interval(None);
+    assert!(app.world.save_to::<All, String>().is_some());
+}
@@ -0,0 +1,300 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, entity::Entity, query::With, system::{RunSystemOnce, Commands, Query}};
+use bevy_hierarchy::{BuildChildren, Parent};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, PathName, TransferOptions, PathRemap, SaveLoadExtension, LoadSource, PathAliases, PathRoot, transfer};
+use std::borrow::Cow;
+
+mod common;
+use common::Unit;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Item {
+    name: String,
+}
+
+impl bevy_salo::SaveLoadCore for Item {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("item")
+    }
+}
+
+#[derive(Debug, Component)]
+struct Leaving;
+
+fn app(level: &'static str) -> App {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Item>()
+    );
+    app.world.run_system_once(move |mut commands: Commands| {
+        commands.spawn(PathName::new(level));
+    });
+    app
+}
+
+fn units(app: &mut App) -> Vec<(String, Option<String>)> {
+    let mut units = app.world.run_system_once(|q: Query<(&Unit, Option<&Parent>)>, names: Query<&PathName>| {
+        q.iter().map(|(unit, parent)| (
+            unit.name.clone(),
+            parent.and_then(|p| names.get(p.get()).ok()).map(|n| n.get().into_owned()),
+        )).collect::<Vec<_>>()
+    });
+    units.sort();
+    units
+}
+
+#[test]
+pub fn transfer_between_worlds() {
+    let mut level1 = app("level1");
+    level1.world.run_system_once(|mut commands: Commands, q: Query<Entity, With<PathName>>| {
+        commands.entity(q.single()).with_children(|b| {
+            b.spawn((Unit { name: "John".to_owned() }, Leaving));
+            b.spawn(Unit { name: "Jane".to_owned() });
+        });
+    });
+    let mut level2 = app("level2");
+
+    transfer::<All, With<Leaving>>(&mut level1.world, &mut level2.world, TransferOptions::new()
+        .with_remap("level1", "level2")
+        .with_despawn(true)
+    ).unwrap();
+    assert_eq!(units(&mut level1), [("Jane".to_owned(), Some("level1".to_owned()))]);
+    assert_eq!(units(&mut level2), [("John".to_owned(), Some("level2".to_owned()))]);
+    assert!(level2.world.get_resource::<PathRemap<All>>().is_none());
+
+    // Copy without despawning, matched by path when transferred again.
+    transfer::<All, With<Unit>>(&mut level1.world, &mut level2.world, TransferOptions::new()
+        .with_remap("level1", "level2")
+    ).unwrap();
+    transfer::<All, With<Unit>>(&mut level1.world, &mut level2.world, TransferOptions::new()
+        .with_remap("level1", "level2")
+    ).unwrap();
+    assert_eq!(units(&mut level1).len(), 1);
+    assert_eq!(units(&mut level2), [
+        ("Jane".to_owned(), Some("level2".to_owned())),
+        ("John".to_owned(), Some("level2".to_owned())),
+    ]);
+}
+
+#[test]
+pub fn path_remap() {
+    let mut level1 = app("level1");
+    level1.world.run_system_once(|mut commands: Commands, q: Query<Entity, With<PathName>>| {
+        commands.entity(q.single()).with_children(|b| {
+            b.spawn(Unit { name: "John".to_owned() });
+        });
+    });
+    let save = level1.world.save_to::<All, String>().unwrap();
+    let mut level2 = app("level2");
+    level2.world.insert_resource(PathRemap::<All>::new().with("level1", "level2"));
+    level2.world.load_from::<All, _>(&save);
+    assert_eq!(units(&mut level2), [("John".to_owned(), Some("level2".to_owned()))]);
+
+    let remap = PathRemap::<All>::new().with("", "root");
+    assert_eq!(remap.apply(&bevy_salo::EntityPath::parse("a::b")), Some(bevy_salo::EntityPath::parse("root::a::b")));
+    assert_eq!(remap.apply(&bevy_salo::EntityPath::Unique), None);
+}
+
+#[test]
+pub fn load_type_records() {
+    let mut source = app("level1");
+    source.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+        commands.spawn(Item { name: "Sword".to_owned() });
+    });
+    let save = source.world.save_to::<All, String>().unwrap();
+    let mut level2 = app("level2");
+    level2.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Item { name: "Shield".to_owned() });
+    });
+    let parent = level2.world.run_system_once(|q: Query<Entity, With<PathName>>| q.single());
+    level2.world.load_type_records::<All, Unit>(LoadSource::String(&save), Some(parent)).unwrap();
+    assert_eq!(units(&mut level2), [("John".to_owned(), Some("level2".to_owned()))]);
+    let items = level2.world.run_system_once(|q: Query<&Item>| q.iter().map(|i| i.name.clone()).collect::<Vec<_>>());
+    assert_eq!(items, ["Shield"]);
+}
+
+#[test]
+pub fn load_type_records_chunks() {
+    use bevy_salo::SaloConfig;
+
+    let mut source = app("level1");
+    source.world.run_system_once(|mut commands: Commands| {
+        for name in ["A", "B", "C"] {
+            commands.spawn(Unit { name: name.to_owned() });
+        }
+        commands.spawn(Item { name: "Sword".to_owned() });
+    });
+    let save = source.world.save_to::<All, String>().unwrap();
+    let mut level2 = app("level2");
+    level2.world.insert_resource(SaloConfig::<All>::new().with_entity_budget(1));
+    let parent = level2.world.run_system_once(|q: Query<Entity, With<PathName>>| q.single());
+    level2.world.load_type_records::<All, Unit>(LoadSource::String(&save), Some(parent)).unwrap();
+    while level2.world.load_pending::<All>().unwrap() {}
+    // Later chunks keep the filter and attach their roots to the parent.
+    let level2_name = || Some("level2".to_owned());
+    assert_eq!(units(&mut level2), [
+        ("A".to_owned(), level2_name()),
+        ("B".to_owned(), level2_name()),
+        ("C".to_owned(), level2_name()),
+    ]);
+    let items = level2.world.run_system_once(|q: Query<&Item>| q.iter().count());
+    assert_eq!(items, 0);
+}
+
+#[test]
+pub fn path_aliases() {
+    let mut level1 = app("level1");
+    level1.world.run_system_once(|mut commands: Commands, q: Query<Entity, With<PathName>>| {
+        commands.entity(q.single()).with_children(|b| {
+            b.spawn(Unit { name: "John".to_owned() });
+        });
+    });
+    let save = level1.world.save_to::<All, String>().unwrap();
+    assert!(!save.contains("$aliases"));
+    let mut level2 = app("level2");
+    level2.world.insert_resource(PathAliases::<All>::new().with("level1", "level2"));
+    level2.world.load_from::<All, _>(&save);
+    assert_eq!(units(&mut level2), [("John".to_owned(), Some("level2".to_owned()))]);
+
+    // Aliases in the header are applied without the resource.
+    level1.world.insert_resource(PathAliases::<All>::new().with("level1", "level3"));
+    let save = level1.world.save_to::<All, String>().unwrap();
+    assert!(save.contains("$aliases"));
+    let mut level3 = app("level3");
+    level3.world.load_from::<All, _>(&save);
+    assert_eq!(units(&mut level3), [("John".to_owned(), Some("level3".to_owned()))]);
+
+    // Chains are resolved and unused aliases are not written.
+    level1.world.insert_resource(PathAliases::<All>::new()
+        .with("level1", "level3")
+        .with("level3", "level4")
+        .with("dungeon", "level5")
+    );
+    let save = level1.world.save_to::<All, String>().unwrap();
+    assert!(save.contains(r#""$aliases":{"level1":"level4"}"#), "{}", save);
+    let mut level4 = app("level4");
+    level4.world.load_from::<All, _>(&save);
+    assert_eq!(units(&mut level4), [("John".to_owned(), Some("level4".to_owned()))]);
+}
+
+#[test]
+pub fn path_root() {
+    let mut chunk = app("level1");
+    chunk.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(PathRoot::<All>::new("chunk")).with_children(|b| {
+            b.spawn(Unit { name: "John".to_owned() });
+        });
+    });
+    chunk.world.insert_resource(PathRoot::<All>::new("chunk"));
+    let save = chunk.world.save_to::<All, String>().unwrap();
+    assert!(save.contains(r#"{"path":"John","value":{"name":"John"}}"#));
+
+    let mut world = app("level2");
+    world.world.spawn(PathRoot::<All>::new("chunk_a"));
+    for root in ["chunk_a", "chunk_b"] {
+        world.world.insert_resource(PathRoot::<All>::new(root));
+        world.world.load_from::<All, _>(&save);
+    }
+    world.world.remove_resource::<PathRoot<All>>();
+    let mut units = world.world.run_system_once(|q: Query<(&Unit, &Parent)>, roots: Query<&PathRoot<All>>| {
+        q.iter().map(|(unit, parent)| (
+            unit.name.clone(),
+            roots.get(parent.get()).ok().map(|r| r.get().into_owned()),
+        )).collect::<Vec<_>>()
+    });
+    units.sort();
+    // `chunk_b` is spawned without a `PathRoot`.
+    assert_eq!(units, [("John".to_owned(), None), ("John".to_owned(), Some("chunk_a".to_owned()))]);
+}
+
+#[test]
+pub fn spawn_under_label() {
+    use bevy_salo::{SpawnAnchor, LoadOptions, SaloError};
+    let mut source = app("level1");
+    source.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+        commands.spawn(Unit { name: "Jane".to_owned() });
+        commands.spawn(Item { name: "Sword".to_owned() });
+    });
+    let save = source.world.save_to::<All, String>().unwrap();
+    let mut target = app("ui");
+    target.world.run_system_once(|mut commands: Commands, q: Query<Entity, With<PathName>>| {
+        commands.entity(q.single()).insert(SpawnAnchor::new("UI"));
+        commands.spawn((PathName::new("level2"), SpawnAnchor::new("Level")));
+    });
+    let result = target.world.load_with::<All>(LoadSource::String(&save), LoadOptions::new().spawn_under_label("Persistent"));
+    assert!(matches!(result, Err(SaloError::MissingAnchor(_))));
+    assert!(units(&mut target).is_empty());
+
+    target.world.load_with::<All>(LoadSource::String(&save), LoadOptions::new().spawn_under_label("Level")).unwrap();
+    assert_eq!(units(&mut target), [
+        ("Jane".to_owned(), Some("level2".to_owned())),
+        ("John".to_owned(), Some("level2".to_owned())),
+    ]);
+
+    // Records without a path are attached as well.
+    let unnamed = r#"{"item": [{"value": {"name": "Shield"}}]}"#;
+    target.world.load_with::<All>(LoadSource::String(unnamed), LoadOptions::new().spawn_under_label("UI")).unwrap();
+    let mut items = target.world.run_system_once(|q: Query<(&Item, Option<&Parent>)>, names: Query<&PathName>| {
+        q.iter().map(|(item, parent)| (
+            item.name.clone(),
+            parent.and_then(|p| names.get(p.get()).ok()).map(|n| n.get().into_owned()),
+        )).collect::<Vec<_>>()
+    });
+    items.sort();
+    assert_eq!(items, [
+        ("Shield".to_owned(), Some("ui".to_owned())),
+        ("Sword".to_owned(), Some("level2".to_owned())),
+    ]);
+}
+
+#[test]
+pub fn path_root_chunks() {
+    use bevy_salo::{SaloConfig, RecordAttributes};
+    use bevy_ecs::system::{ResMut, Resource};
+
+    #[derive(Debug, Default, Resource)]
+    struct PostLoads(usize);
+
+    let save = r#"{
+        "$attributes": {"unit": [{"path": "A", "attributes": {"k": "a"}}, {"path": "D", "attributes": {"k": "d"}}]},
+        "unit": [{"path": "A", "value": {"name": "A"}}, {"path": "B", "value": {"name": "B"}},
+            {"path": "C", "value": {"name": "C"}}, {"path": "D", "value": {"name": "D"}}]
+    }"#;
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .add_post_load_system(|mut n: ResMut<PostLoads>| n.0 += 1)
+    );
+    app.world.init_resource::<PostLoads>();
+    app.world.spawn(PathRoot::<All>::new("chunk"));
+    app.world.insert_resource(PathRoot::<All>::new("chunk"));
+    app.world.insert_resource(SaloConfig::<All>::new().with_entity_budget(1));
+    app.world.load::<All>(LoadSource::String(save)).unwrap();
+    while app.world.load_pending::<All>().unwrap() {}
+
+    let units = app.world.run_system_once(|q: Query<(&Unit, &Parent, Option<&RecordAttributes>)>, roots: Query<&PathRoot<All>>| {
+        let mut units = q.iter().map(|(unit, parent, attributes)| (
+            unit.name.clone(),
+            roots.get(parent.get()).ok().map(|r| r.get().into_owned()),
+            attributes.and_then(|a| a.attribute("unit", "k").map(str::to_owned)),
+        )).collect::<Vec<_>>();
+        units.sort();
+        units
+    });
+    let chunk = || Some("chunk".to_owned());
+    assert_eq!(units, [
+        ("A".to_owned(), chunk(), Some("a".to_owned())),
+        ("B".to_owned(), chunk(), None),
+        ("C".to_owned(), chunk(), None),
+        ("D".to_owned(), chunk(), Some("d".to_owned())),
+    ]);
+    // Hooks run once per load, not per chunk.
+    assert_eq!(app.world.resource::<PostLoads>().0, 1);
+}
@@ -0,0 +1,159 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, SaloConfig, UnknownComponents, RegistryChanges, SaloRegistry};
+use std::borrow::Cow;
+
+mod common;
+use common::Unit;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Weapon {
+    damage: i32,
+}
+
+impl bevy_salo::SaveLoadCore for Weapon {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("weapon")
+    }
+}
+
+#[derive(Debug, Clone, bevy_ecs::system::Resource, Default, serde::Serialize, serde::Deserialize)]
+struct Gold(u32);
+
+impl bevy_salo::SaveLoadResCore for Gold {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("gold")
+    }
+}
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Tag(u32);
+
+impl bevy_salo::SaveLoadCore for Tag {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("tag")
+    }
+}
+
+fn foreign_save() -> String {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Weapon>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Unit { name: "John".to_owned() }, Weapon { damage: 42 }));
+    });
+    app.world.save_to::<All, String>().unwrap()
+}
+
+fn resave(preserve: bool) -> (App, String) {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app.world.resource_mut::<SaloConfig<All>>().set_preserve_unknown(preserve);
+    app.world.load_from::<All, _>(&foreign_save());
+    let save = app.world.save_to::<All, String>().unwrap();
+    (app, save)
+}
+
+#[test]
+pub fn preserve_unknown() {
+    let (app, save) = resave(true);
+    let unknown = app.world.resource::<UnknownComponents<All>>();
+    assert!(unknown.contains("weapon"));
+    assert_eq!(unknown.iter().count(), 1);
+    assert_eq!(unknown.iter().next().unwrap().1.to_string(), "John");
+    assert!(save.contains("\"weapon\""));
+    assert!(save.contains("42"));
+}
+
+#[test]
+pub fn preserve_unknown_unnamed() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Tag>()
+        .register::<Weapon>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn((Tag(1), Weapon { damage: 42 }));
+        commands.spawn(Weapon { damage: 7 });
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Tag>()
+    );
+    app.world.resource_mut::<SaloConfig<All>>().set_preserve_unknown(true);
+    for _ in 0..5 {
+        app.world.spawn_empty();
+    }
+    app.world.load_from::<All, _>(&save);
+    let save = app.world.save_to::<All, String>().unwrap();
+    let json: serde_json::Value = serde_json::from_str(&save).unwrap();
+    let tag = &json["tag"][0]["path"];
+    let weapons = json["weapon"].as_array().unwrap();
+    assert_eq!(weapons.len(), 2);
+    let paired = weapons.iter().find(|w| w["value"]["damage"] == 42).unwrap();
+    let alone = weapons.iter().find(|w| w["value"]["damage"] == 7).unwrap();
+    assert_eq!(&paired["path"], tag);
+    assert_ne!(&alone["path"], tag);
+
+    // Ids do not collide with entities spawned after the load.
+    let entity = app.world.spawn(Tag(2)).id();
+    let save = app.world.save_to::<All, String>().unwrap();
+    let json: serde_json::Value = serde_json::from_str(&save).unwrap();
+    assert_ne!(alone["path"], serde_json::json!(entity.to_bits()));
+    assert_eq!(json["weapon"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+pub fn drop_unknown() {
+    let (app, save) = resave(false);
+    assert!(app.world.get_resource::<UnknownComponents<All>>().is_none());
+    assert!(!save.contains("\"weapon\""));
+}
+
+#[test]
+pub fn fingerprint() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Weapon>()
+    );
+    app.world.resource_mut::<SaloConfig<All>>().set_fingerprint(true);
+    let save = app.world.save_to::<All, String>().unwrap();
+    assert!(save.contains("$fingerprint"));
+    app.world.load_from::<All, _>(&save);
+    assert!(app.world.resource::<RegistryChanges<All>>().is_empty());
+
+    let save = r#"{
+        "$fingerprint": {"unit": 0, "blade": 0, "armor": 0, "gold": 1},
+        "blade": [{"value": {"damage": 1}}]
+    }"#.to_owned();
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+        .register::<Weapon>()
+        .register_resource::<Gold>()
+        .register::<Tag>()
+    );
+    app.world.resource_mut::<SaloRegistry<All>>().set_renamed("blade", "weapon");
+    app.world.load_from::<All, _>(&save);
+    let changes = app.world.resource::<RegistryChanges<All>>();
+    assert_eq!(changes.added, vec!["tag".to_owned()]);
+    assert_eq!(changes.removed, vec!["armor".to_owned()]);
+    assert_eq!(changes.renamed, vec![("blade".to_owned(), "weapon".to_owned())]);
+    assert_eq!(changes.versions, vec![("gold".to_owned(), 1, 0)]);
+    // Records of renamed types are loaded under the new name.
+    assert_eq!(app.world.run_system_once(|q: Query<&Weapon>| q.iter().count()), 1);
+
+    app.world.load_from::<All, _>(&"{}".to_owned());
+    assert!(!app.world.contains_resource::<RegistryChanges<All>>());
+}
@@ -0,0 +1,42 @@
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_salo::{methods::SerdeJson, SaveLoadExtension};
+
+mod common;
+use common::units;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+fn app() -> App {
+    common::players::<All>()
+}
+
+#[test]
+pub fn validate_save() {
+    let mut app = app();
+    let save = app.world.save_to::<All, String>().unwrap();
+    let entities = app.world.entities().len();
+    let report = app.world.validate_save::<All>(save.as_bytes()).unwrap();
+    assert!(report.is_compatible());
+    assert_eq!(report.records.get("unit"), Some(&1));
+    assert!(report.unresolved_parents.is_empty());
+
+    let broken = r#"{
+        "unit": [
+            {"parent": "Players", "path": "Players::Ann", "value": {"name": 4}},
+            {"parent": "Enemies", "path": "Enemies::Bob", "value": {"name": "Bob"}}
+        ],
+        "missing": [{"path": "Players::Ann", "value": 1}]
+    }"#;
+    let report = app.world.validate_save::<All>(broken.as_bytes()).unwrap();
+    assert!(!report.is_compatible());
+    assert_eq!(report.unknown_types, vec!["missing".to_owned()]);
+    assert_eq!(report.errors.len(), 1);
+    assert!(report.errors[0].contains("Players::Ann"));
+    assert_eq!(report.unresolved_parents, vec!["Enemies".to_owned()]);
+
+    assert!(app.world.validate_save::<All>(b"not a save").is_err());
+    assert_eq!(app.world.entities().len(), entities);
+    assert_eq!(units(&mut app), 1);
+}
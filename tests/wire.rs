@@ -0,0 +1,116 @@
+//! Golden encodings of the wire contract, these must not change without bumping `WIRE_VERSION`.
+#![allow(deprecated)]
+
+use bevy_app::App;
+use bevy_ecs::{component::Component, system::{RunSystemOnce, Commands, Query}};
+use bevy_salo::{SaveLoadPlugin, methods::SerdeJson, SaveLoadExtension, EntityPath, wire::{PathEncoding, WIRE_VERSION}};
+use std::borrow::Cow;
+
+type All = bevy_salo::All<SerdeJson<false>>;
+
+#[derive(Debug, Clone, Component, Default, serde::Serialize, serde::Deserialize)]
+struct Unit {
+    name: String,
+}
+
+impl bevy_salo::SaveLoadCore for Unit {
+    fn type_name() -> Cow<'static, str> {
+        Cow::Borrowed("unit")
+    }
+
+    fn path_name(&self) -> Option<Cow<'static, str>> {
+        Some(self.name.clone().into())
+    }
+}
+
+#[cfg(not(feature="legacy-paths"))]
+fn paths() -> [(EntityPath, &'static str); 4] {
+    [
+        (EntityPath::Unique, "null"),
+        (EntityPath::Entity(4), "4"),
+        (EntityPath::parse("Players::John"), r#""Players::John""#),
+        (EntityPath::Path(vec!["Sir::Lancelot".into()]), r#""Sir\\::Lancelot""#),
+    ]
+}
+
+#[cfg(not(feature="legacy-paths"))]
+#[test]
+pub fn json_paths() {
+    assert_eq!(WIRE_VERSION, 1);
+    for (path, json) in paths() {
+        assert_eq!(serde_json::to_string(&path).unwrap(), json);
+        assert_eq!(serde_json::from_str::<EntityPath>(json).unwrap(), path);
+    }
+}
+
+#[cfg(all(feature="postcard", not(feature="legacy-paths")))]
+#[test]
+pub fn postcard_paths() {
+    let golden: [&[u8]; 4] = [
+        &[0],
+        &[1, 4],
+        &[2, 13, b'P', b'l', b'a', b'y', b'e', b'r', b's', b':', b':', b'J', b'o', b'h', b'n'],
+        &[2, 14, b'S', b'i', b'r', b'\\', b':', b':', b'L', b'a', b'n', b'c', b'e', b'l', b'o', b't'],
+    ];
+    for ((path, _), bytes) in paths().into_iter().zip(golden) {
+        assert_eq!(postcard::to_allocvec(&path).unwrap(), bytes);
+        assert_eq!(postcard::from_bytes::<EntityPath>(bytes).unwrap(), path);
+    }
+}
+
+#[test]
+pub fn json_records() {
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app.world.run_system_once(|mut commands: Commands| {
+        commands.spawn(Unit { name: "John".to_owned() });
+    });
+    let save = app.world.save_to::<All, String>().unwrap();
+    let version = PathEncoding::current().version();
+    assert_eq!(save, format!(r#"{{"$wire":{},"unit":[{{"path":"John","value":{{"name":"John"}}}}]}}"#, version));
+
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    app.world.load_from::<All, _>(&save);
+    assert_eq!(app.world.run_system_once(|q: Query<&Unit>| q.iter().count()), 1);
+}
+
+#[test]
+pub fn path_encodings() {
+    assert_eq!(PathEncoding::from_version(0), Some(PathEncoding::Unescaped));
+    assert_eq!(PathEncoding::from_version(WIRE_VERSION), Some(PathEncoding::Escaped));
+    assert_eq!(PathEncoding::from_version(WIRE_VERSION + 1), None);
+
+    let old = r"C:\Saves::John";
+    let path = PathEncoding::Unescaped.parse(old);
+    assert_eq!(path, EntityPath::Path(vec![r"C:\Saves".into(), "John".into()]));
+    let upgraded = PathEncoding::Unescaped.convert(old, PathEncoding::Escaped);
+    assert_eq!(upgraded, r"C:\\Saves::John");
+    assert_eq!(PathEncoding::Escaped.parse(&upgraded), path);
+    assert_eq!(PathEncoding::Escaped.format(&EntityPath::Entity(1)), None);
+}
+
+#[test]
+pub fn wire_version_header() {
+    use bevy_salo::{LoadSource, PathName};
+    // A path written by the other encoding, loaded onto the entity with that name.
+    #[cfg(not(feature="legacy-paths"))]
+    let (save, name) = (r#"{"$wire":0,"unit":[{"path":"C:\\Saves","value":{"name":"C:\\Saves"}}]}"#, r"C:\Saves");
+    #[cfg(feature="legacy-paths")]
+    let (save, name) = (r#"{"$wire":1,"unit":[{"path":"Sir\\::Lancelot","value":{"name":"Sir::Lancelot"}}]}"#, "Sir::Lancelot");
+    let mut app = App::new();
+    app.add_plugins(SaveLoadPlugin::new::<All>()
+        .register::<Unit>()
+    );
+    let entity = app.world.spawn(PathName::new(name)).id();
+    app.world.load::<All>(LoadSource::String(save)).unwrap();
+    assert_eq!(app.world.run_system_once(|q: Query<&Unit>| q.iter().count()), 1);
+    assert!(app.world.get::<Unit>(entity).is_some());
+
+    let newer = format!(r#"{{"$wire":{},"unit":[]}}"#, WIRE_VERSION + 1);
+    assert!(app.world.load::<All>(LoadSource::String(&newer)).is_err());
+}